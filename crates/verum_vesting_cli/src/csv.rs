@@ -0,0 +1,151 @@
+//! CSV de criacao em lote.
+//!
+//! Cabecalho obrigatorio, colunas separadas por virgula e sem aspas:
+//!
+//! ```text
+//! contract_id,beneficiary,mint,total_amount,start_time,end_time,vesting_type,cliff_time,cliff_percentage
+//! 1,<pubkey>,<mint>,1000000,2025-01-01,2026-01-01,linear,,
+//! 2,<pubkey>,<mint>,500000,1735689600,1767225600,cliff,2025-07-01,25
+//! ```
+//!
+//! Instantes aceitam unix timestamp ou data ISO 8601 em UTC (`2025-01-01` ou
+//! `2025-01-01T12:00:00Z`). `cliff_time` e `cliff_percentage` so valem para `cliff`.
+
+use anyhow::{anyhow, bail, Context, Result};
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
+use verum_vesting_client::VestingType;
+
+const COLUMNS: [&str; 9] = [
+    "contract_id",
+    "beneficiary",
+    "mint",
+    "total_amount",
+    "start_time",
+    "end_time",
+    "vesting_type",
+    "cliff_time",
+    "cliff_percentage",
+];
+
+#[derive(Clone)]
+pub struct ContractRow {
+    pub contract_id: u64,
+    pub beneficiary: Pubkey,
+    pub mint: Pubkey,
+    pub total_amount: u64,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub vesting_type: VestingType,
+}
+
+pub fn parse_contracts(input: &str) -> Result<Vec<ContractRow>> {
+    let mut lines = input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty());
+
+    let (_, header) = lines.next().ok_or_else(|| anyhow!("CSV vazio"))?;
+    let header: Vec<&str> = header.split(',').map(str::trim).collect();
+    let index = |column: &str| header.iter().position(|name| *name == column);
+    for column in &COLUMNS[..7] {
+        if index(column).is_none() {
+            bail!("coluna obrigatoria ausente: {column}");
+        }
+    }
+    let positions: Vec<Option<usize>> = COLUMNS.iter().map(|column| index(column)).collect();
+
+    lines
+        .map(|(number, line)| {
+            let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+            let field = |column: usize| {
+                positions[column]
+                    .and_then(|position| fields.get(position).copied())
+                    .unwrap_or("")
+            };
+            parse_row(&field).with_context(|| format!("linha {}", number + 1))
+        })
+        .collect()
+}
+
+fn parse_row<'a>(field: &impl Fn(usize) -> &'a str) -> Result<ContractRow> {
+    let start_time = parse_time(field(4)).context("start_time")?;
+    let end_time = parse_time(field(5)).context("end_time")?;
+    if end_time <= start_time {
+        bail!("end_time deve ser posterior a start_time");
+    }
+
+    let vesting_type = match field(6).to_ascii_lowercase().as_str() {
+        "linear" => VestingType::Linear,
+        "cliff" => {
+            let cliff_time = parse_time(field(7)).context("cliff_time")?;
+            let percentage: u64 = field(8).parse().context("cliff_percentage")?;
+            if percentage > 100 {
+                bail!("cliff_percentage deve estar entre 0 e 100");
+            }
+            VestingType::Cliff(cliff_time, percentage)
+        }
+        other => bail!("vesting_type nao suportado no CSV: {other:?} (use linear ou cliff)"),
+    };
+
+    Ok(ContractRow {
+        contract_id: field(0).parse().context("contract_id")?,
+        beneficiary: Pubkey::from_str(field(1)).context("beneficiary")?,
+        mint: Pubkey::from_str(field(2)).context("mint")?,
+        total_amount: field(3).parse().context("total_amount")?,
+        start_time,
+        end_time,
+        vesting_type,
+    })
+}
+
+/// Unix timestamp ou data/hora ISO 8601 em UTC.
+pub fn parse_time(value: &str) -> Result<i64> {
+    if let Ok(timestamp) = value.parse::<i64>() {
+        return Ok(timestamp);
+    }
+
+    let (date, time) = match value.split_once('T') {
+        Some((date, time)) => (date, Some(time.trim_end_matches('Z'))),
+        None => (value, None),
+    };
+    let date: Vec<i64> = date
+        .split('-')
+        .map(str::parse)
+        .collect::<Result<_, _>>()
+        .map_err(|_| anyhow!("instante invalido: {value:?}"))?;
+    let [year, month, day] = date[..] else {
+        bail!("instante invalido: {value:?}");
+    };
+    let seconds = match time {
+        Some(time) => {
+            let parts: Vec<i64> = time
+                .split(':')
+                .map(str::parse)
+                .collect::<Result<_, _>>()
+                .map_err(|_| anyhow!("instante invalido: {value:?}"))?;
+            match parts[..] {
+                [hour, minute] => hour * 3_600 + minute * 60,
+                [hour, minute, second] => hour * 3_600 + minute * 60 + second,
+                _ => bail!("instante invalido: {value:?}"),
+            }
+        }
+        None => 0,
+    };
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        bail!("instante invalido: {value:?}");
+    }
+
+    Ok(days_from_civil(year, month, day) * 86_400 + seconds)
+}
+
+/// Dias desde 1970-01-01 no calendario gregoriano proleptico.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = (month + 9) % 12;
+    let day_of_year = (153 * month + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
@@ -0,0 +1,6 @@
+//! Partes do `verum-vesting` independentes de RPC: leitura do CSV de criacao,
+//! filtros de `getProgramAccounts` e o relatorio de status de um contrato.
+
+pub mod csv;
+pub mod query;
+pub mod status;
@@ -0,0 +1,454 @@
+//! `verum-vesting`: operacao dos contratos de vesting via RPC.
+//!
+//! Padrao apontado para um `solana-test-validator` local com a keypair da CLI do
+//! Solana; `--json` troca a saida legivel por JSON e `--dry-run` simula as
+//! transacoes sem envia-las.
+
+use anchor_lang::AccountDeserialize;
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use anyhow::{anyhow, bail, Context, Result};
+use clap::{Arg, ArgMatches, Command};
+use serde_json::{json, Value};
+use solana_account_decoder::UiAccountEncoding;
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_rpc_client_api::config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use solana_sdk::account::from_account;
+use solana_sdk::clock::Clock;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{read_keypair_file, Keypair, Signer};
+use solana_sdk::sysvar;
+use solana_sdk::transaction::Transaction;
+use std::str::FromStr;
+use verum_vesting_cli::csv::parse_contracts;
+use verum_vesting_cli::query::ContractQuery;
+use verum_vesting_cli::status::contract_status;
+use verum_vesting_client::verum_vesting::{PriceTrigger, VestingContract};
+use verum_vesting_client::*;
+
+const DEFAULT_URL: &str = "http://127.0.0.1:8899";
+
+fn main() {
+    let matches = app().get_matches();
+    let json_output = matches.is_present("json");
+    if let Err(err) = run(&matches) {
+        if json_output {
+            println!("{}", json!({ "error": format!("{err:#}") }));
+        } else {
+            eprintln!("erro: {err:#}");
+        }
+        std::process::exit(1);
+    }
+}
+
+fn app() -> Command<'static> {
+    let contract = || {
+        Arg::new("contract")
+            .required(true)
+            .help("Endereco do VestingContract")
+    };
+
+    Command::new("verum-vesting")
+        .about("Opera contratos do verum_vesting")
+        .subcommand_required(true)
+        .arg(
+            Arg::new("url")
+                .long("url")
+                .short('u')
+                .global(true)
+                .takes_value(true)
+                .default_value(DEFAULT_URL)
+                .help("Endpoint RPC"),
+        )
+        .arg(
+            Arg::new("keypair")
+                .long("keypair")
+                .short('k')
+                .global(true)
+                .takes_value(true)
+                .help("Keypair que assina e paga (padrao: ~/.config/solana/id.json)"),
+        )
+        .arg(
+            Arg::new("json")
+                .long("json")
+                .global(true)
+                .help("Saida em JSON"),
+        )
+        .arg(
+            Arg::new("dry-run")
+                .long("dry-run")
+                .global(true)
+                .help("Simula as transacoes sem envia-las"),
+        )
+        .subcommand(
+            Command::new("create")
+                .about("Cria contratos a partir de um CSV")
+                .arg(Arg::new("csv").long("csv").required(true).takes_value(true))
+                .arg(
+                    Arg::new("audit-log")
+                        .long("audit-log")
+                        .help("Cria o AuditLog de cada contrato"),
+                )
+                .arg(
+                    Arg::new("allowed-mint-flags")
+                        .long("allowed-mint-flags")
+                        .takes_value(true)
+                        .default_value("0")
+                        .help("Mascara de mint_flags aceita pelo criador"),
+                ),
+        )
+        .subcommand(
+            Command::new("list")
+                .about("Lista contratos por criador, beneficiario ou mint")
+                .arg(Arg::new("creator").long("creator").takes_value(true))
+                .arg(
+                    Arg::new("beneficiary")
+                        .long("beneficiary")
+                        .takes_value(true),
+                )
+                .arg(Arg::new("mint").long("mint").takes_value(true)),
+        )
+        .subcommand(
+            Command::new("status")
+                .about("Mostra valores liberados e resgataveis")
+                .arg(contract()),
+        )
+        .subcommand(
+            Command::new("claim")
+                .about("Resgata o valor liberado para o beneficiario")
+                .arg(contract()),
+        )
+        .subcommand(
+            Command::new("cancel")
+                .about("Cancela o contrato e devolve o saldo ao criador")
+                .arg(contract()),
+        )
+        .subcommand(
+            Command::new("set-beneficiary")
+                .about("Troca o beneficiario do contrato")
+                .arg(contract())
+                .arg(Arg::new("new-beneficiary").required(true)),
+        )
+}
+
+struct Config {
+    client: RpcClient,
+    keypair_path: String,
+    json: bool,
+    dry_run: bool,
+}
+
+impl Config {
+    fn signer(&self) -> Result<Keypair> {
+        read_keypair_file(&self.keypair_path)
+            .map_err(|err| anyhow!("falha ao ler a keypair {}: {err}", self.keypair_path))
+    }
+
+    fn now(&self) -> Result<i64> {
+        let account = self.client.get_account(&sysvar::clock::ID)?;
+        let clock: Clock =
+            from_account(&account).ok_or_else(|| anyhow!("sysvar Clock invalido"))?;
+        Ok(clock.unix_timestamp)
+    }
+
+    fn vesting_contract(&self, address: &Pubkey) -> Result<VestingContract> {
+        let data = self
+            .client
+            .get_account_data(address)
+            .with_context(|| format!("conta {address}"))?;
+        decode_vesting_contract(&data)
+            .with_context(|| format!("{address} nao e um VestingContract"))
+    }
+
+    /// Envia (ou simula, com `--dry-run`) uma transacao paga por `payer`.
+    fn execute(&self, payer: &Keypair, instructions: &[Instruction]) -> Result<Value> {
+        let blockhash = self.client.get_latest_blockhash()?;
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&payer.pubkey()),
+            &[payer],
+            blockhash,
+        );
+
+        if self.dry_run {
+            let simulation = self.client.simulate_transaction(&transaction)?.value;
+            return Ok(json!({
+                "dry_run": true,
+                "success": simulation.err.is_none(),
+                "error": simulation.err.map(|err| err.to_string()),
+                "units_consumed": simulation.units_consumed,
+                "logs": simulation.logs.unwrap_or_default(),
+            }));
+        }
+
+        let signature = self.client.send_and_confirm_transaction(&transaction)?;
+        Ok(json!({ "signature": signature.to_string() }))
+    }
+
+    fn print(&self, value: &Value) {
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(value).unwrap());
+        } else {
+            print_human(value, "");
+        }
+    }
+}
+
+fn print_human(value: &Value, indent: &str) {
+    match value {
+        Value::Array(items) => {
+            for (index, item) in items.iter().enumerate() {
+                if index > 0 {
+                    println!();
+                }
+                print_human(item, indent);
+            }
+        }
+        Value::Object(fields) => {
+            for (key, field) in fields {
+                match field {
+                    Value::Array(items) if items.iter().all(Value::is_string) => {
+                        println!("{indent}{key}:");
+                        for item in items {
+                            println!("{indent}  {}", item.as_str().unwrap());
+                        }
+                    }
+                    Value::Array(_) | Value::Object(_) => {
+                        println!("{indent}{key}:");
+                        print_human(field, &format!("{indent}  "));
+                    }
+                    Value::String(text) => println!("{indent}{key}: {text}"),
+                    other => println!("{indent}{key}: {other}"),
+                }
+            }
+        }
+        Value::String(text) => println!("{indent}{text}"),
+        other => println!("{indent}{other}"),
+    }
+}
+
+fn pubkey_arg(matches: &ArgMatches, name: &str) -> Result<Option<Pubkey>> {
+    matches
+        .value_of(name)
+        .map(|value| Pubkey::from_str(value).with_context(|| format!("{name} invalido")))
+        .transpose()
+}
+
+fn required_pubkey(matches: &ArgMatches, name: &str) -> Result<Pubkey> {
+    pubkey_arg(matches, name)?.ok_or_else(|| anyhow!("{name} obrigatorio"))
+}
+
+fn run(matches: &ArgMatches) -> Result<()> {
+    let keypair_path = match matches.value_of("keypair") {
+        Some(path) => path.to_string(),
+        None => {
+            let home = std::env::var("HOME").context("HOME nao definido; use --keypair")?;
+            format!("{home}/.config/solana/id.json")
+        }
+    };
+    let config = Config {
+        client: RpcClient::new_with_commitment(
+            matches.value_of("url").unwrap().to_string(),
+            CommitmentConfig::confirmed(),
+        ),
+        keypair_path,
+        json: matches.is_present("json"),
+        dry_run: matches.is_present("dry-run"),
+    };
+
+    let output = match matches.subcommand() {
+        Some(("create", sub)) => create(&config, sub)?,
+        Some(("list", sub)) => list(&config, sub)?,
+        Some(("status", sub)) => {
+            let address = required_pubkey(sub, "contract")?;
+            contract_status(&address, &config.vesting_contract(&address)?, config.now()?)
+        }
+        Some(("claim", sub)) => claim(&config, &required_pubkey(sub, "contract")?)?,
+        Some(("cancel", sub)) => cancel(&config, &required_pubkey(sub, "contract")?)?,
+        Some(("set-beneficiary", sub)) => set_beneficiary(
+            &config,
+            &required_pubkey(sub, "contract")?,
+            &required_pubkey(sub, "new-beneficiary")?,
+        )?,
+        _ => unreachable!("subcommand_required"),
+    };
+    config.print(&output);
+    Ok(())
+}
+
+/// Uma transacao por linha; as linhas seguintes continuam mesmo se uma falhar.
+fn create(config: &Config, matches: &ArgMatches) -> Result<Value> {
+    let path = matches.value_of("csv").unwrap();
+    let input = std::fs::read_to_string(path).with_context(|| format!("falha ao ler {path}"))?;
+    let rows = parse_contracts(&input).with_context(|| format!("CSV {path}"))?;
+    let allowed_mint_flags: u16 = matches
+        .value_of("allowed-mint-flags")
+        .unwrap()
+        .parse()
+        .context("allowed-mint-flags invalido")?;
+    let creator = config.signer()?;
+
+    let mut results = Vec::with_capacity(rows.len());
+    for row in rows {
+        let vesting_contract = vesting_address(&creator.pubkey(), &row.mint, row.contract_id);
+        let result = (|| {
+            let token_program = config.client.get_account(&row.mint)?.owner;
+            let instruction = create_vesting(
+                &CreateVestingArgs {
+                    creator: creator.pubkey(),
+                    beneficiary: row.beneficiary,
+                    mint: row.mint,
+                    token_program,
+                    sender_token_account: get_associated_token_address_with_program_id(
+                        &creator.pubkey(),
+                        &row.mint,
+                        &token_program,
+                    ),
+                    contract_id: row.contract_id,
+                    total_amount: row.total_amount,
+                    start_time: row.start_time,
+                    end_time: row.end_time,
+                    vesting_type: row.vesting_type,
+                    allowed_mint_flags,
+                    with_audit_log: matches.is_present("audit-log"),
+                },
+                &[],
+            );
+            config.execute(&creator, &[instruction])
+        })();
+
+        let mut entry = json!({
+            "contract_id": row.contract_id,
+            "vesting_contract": vesting_contract.to_string(),
+        });
+        match result {
+            Ok(Value::Object(fields)) => entry.as_object_mut().unwrap().extend(fields),
+            Ok(_) => unreachable!(),
+            Err(err) => {
+                entry["error"] = json!(format!("{err:#}"));
+            }
+        }
+        results.push(entry);
+    }
+    Ok(Value::Array(results))
+}
+
+fn list(config: &Config, matches: &ArgMatches) -> Result<Value> {
+    let query = ContractQuery {
+        creator: pubkey_arg(matches, "creator")?,
+        beneficiary: pubkey_arg(matches, "beneficiary")?,
+        mint: pubkey_arg(matches, "mint")?,
+    };
+    let accounts = config.client.get_program_accounts_with_config(
+        &PROGRAM_ID,
+        RpcProgramAccountsConfig {
+            filters: Some(query.filters()),
+            account_config: RpcAccountInfoConfig {
+                encoding: Some(UiAccountEncoding::Base64),
+                ..RpcAccountInfoConfig::default()
+            },
+            ..RpcProgramAccountsConfig::default()
+        },
+    )?;
+    let now = config.now()?;
+
+    let mut contracts: Vec<Value> = accounts
+        .iter()
+        .filter_map(|(address, account)| {
+            let state = VestingContract::try_deserialize(&mut account.data.as_slice()).ok()?;
+            Some(contract_status(address, &state, now))
+        })
+        .collect();
+    contracts.sort_by_key(|contract| contract["contract_id"].as_u64());
+    Ok(Value::Array(contracts))
+}
+
+/// `PriceTriggered` exige o feed registrado no `PriceTrigger` do contrato.
+fn price_feed_for(
+    config: &Config,
+    address: &Pubkey,
+    state: &VestingContract,
+) -> Result<Option<Pubkey>> {
+    if state.vesting_type != VestingType::PriceTriggered {
+        return Ok(None);
+    }
+    let data = config
+        .client
+        .get_account_data(&price_trigger_address(address))?;
+    let trigger: PriceTrigger = decode_account(&data)?;
+    Ok(Some(trigger.price_feed))
+}
+
+/// Resgates sao permissionless: a keypair apenas paga a transacao.
+fn claim(config: &Config, address: &Pubkey) -> Result<Value> {
+    let state = config.vesting_contract(address)?;
+    let claimable = releasable_amount(&state, config.now()?);
+    if claimable == 0 && !config.dry_run {
+        bail!("nada a resgatar em {address}");
+    }
+
+    let payer = config.signer()?;
+    let mut args = ClaimTokensArgs::for_contract(
+        *address,
+        &state,
+        get_associated_token_address_with_program_id(
+            &state.beneficiary,
+            &state.mint,
+            &token_program_for(&state),
+        ),
+    );
+    args.price_feed = price_feed_for(config, address, &state)?;
+
+    let mut output = config.execute(&payer, &[claim_tokens(&args, &[])])?;
+    output["vesting_contract"] = json!(address.to_string());
+    output["claimable_amount"] = json!(claimable);
+    Ok(output)
+}
+
+fn cancel(config: &Config, address: &Pubkey) -> Result<Value> {
+    let state = config.vesting_contract(address)?;
+    let creator = config.signer()?;
+    if creator.pubkey() != state.creator {
+        bail!(
+            "a keypair {} nao e o criador de {address}",
+            creator.pubkey()
+        );
+    }
+
+    let args = CancelVestingArgs::for_contract(
+        *address,
+        &state,
+        get_associated_token_address_with_program_id(
+            &state.creator,
+            &state.mint,
+            &token_program_for(&state),
+        ),
+    );
+    let mut output = config.execute(&creator, &[cancel_vesting(&args, &[])])?;
+    output["vesting_contract"] = json!(address.to_string());
+    Ok(output)
+}
+
+fn set_beneficiary(config: &Config, address: &Pubkey, new_beneficiary: &Pubkey) -> Result<Value> {
+    let state = config.vesting_contract(address)?;
+    let creator = config.signer()?;
+    if creator.pubkey() != state.creator {
+        bail!(
+            "a keypair {} nao e o criador de {address}",
+            creator.pubkey()
+        );
+    }
+
+    let instruction = update_beneficiary(
+        address,
+        &creator.pubkey(),
+        new_beneficiary,
+        state.has_audit_log,
+    );
+    let mut output = config.execute(&creator, &[instruction])?;
+    output["vesting_contract"] = json!(address.to_string());
+    output["previous_beneficiary"] = json!(state.beneficiary.to_string());
+    output["new_beneficiary"] = json!(new_beneficiary.to_string());
+    Ok(output)
+}
@@ -0,0 +1,43 @@
+//! Filtros de `getProgramAccounts` sobre `VestingContract`.
+
+use anchor_lang::Discriminator;
+use solana_rpc_client_api::filter::{Memcmp, RpcFilterType};
+use solana_sdk::pubkey::Pubkey;
+use verum_vesting_client::verum_vesting::VestingContract;
+
+/// Offsets dos campos no layout da conta (apos o discriminador de 8 bytes).
+const CREATOR_OFFSET: usize = 8;
+const BENEFICIARY_OFFSET: usize = CREATOR_OFFSET + 32;
+const MINT_OFFSET: usize = BENEFICIARY_OFFSET + 32;
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ContractQuery {
+    pub creator: Option<Pubkey>,
+    pub beneficiary: Option<Pubkey>,
+    pub mint: Option<Pubkey>,
+}
+
+impl ContractQuery {
+    /// Sempre filtra pelo discriminador de `VestingContract`; os demais campos so
+    /// quando informados.
+    pub fn filters(&self) -> Vec<RpcFilterType> {
+        let mut filters = vec![RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+            0,
+            VestingContract::DISCRIMINATOR.to_vec(),
+        ))];
+        let fields = [
+            (CREATOR_OFFSET, self.creator),
+            (BENEFICIARY_OFFSET, self.beneficiary),
+            (MINT_OFFSET, self.mint),
+        ];
+        for (offset, value) in fields {
+            if let Some(value) = value {
+                filters.push(RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+                    offset,
+                    value.to_bytes().to_vec(),
+                )));
+            }
+        }
+        filters
+    }
+}
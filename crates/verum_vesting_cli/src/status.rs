@@ -0,0 +1,59 @@
+//! Relatorio de status de um contrato.
+
+use serde_json::{json, Value};
+use solana_sdk::pubkey::Pubkey;
+use verum_vesting_client::releasable_amount;
+use verum_vesting_client::verum_vesting::{VestingContract, VestingType};
+
+pub fn vesting_type_label(vesting_type: &VestingType) -> String {
+    match vesting_type {
+        VestingType::Linear => "linear".to_string(),
+        VestingType::Cliff(cliff_time, percentage) => format!("cliff({cliff_time}, {percentage}%)"),
+        VestingType::Milestones => "milestones".to_string(),
+        VestingType::PriceTriggered => "price_triggered".to_string(),
+    }
+}
+
+pub fn state_label(vesting_contract: &VestingContract, now: i64) -> &'static str {
+    if vesting_contract.is_cancelled {
+        "cancelled"
+    } else if vesting_contract.is_paused() {
+        "paused"
+    } else if vesting_contract.released_amount >= vesting_contract.total_amount {
+        "completed"
+    } else if now < vesting_contract.start_time {
+        "pending"
+    } else {
+        "active"
+    }
+}
+
+/// Situacao do contrato em `now`. Valores em unidades base do mint.
+pub fn contract_status(address: &Pubkey, vesting_contract: &VestingContract, now: i64) -> Value {
+    let vested = if vesting_contract.is_cancelled {
+        vesting_contract.released_amount
+    } else {
+        vesting_contract.vested_amount(now)
+    };
+
+    json!({
+        "address": address.to_string(),
+        "contract_id": vesting_contract.contract_id,
+        "state": state_label(vesting_contract, now),
+        "creator": vesting_contract.creator.to_string(),
+        "beneficiary": vesting_contract.beneficiary.to_string(),
+        "mint": vesting_contract.mint.to_string(),
+        "is_native": vesting_contract.is_native,
+        "vesting_type": vesting_type_label(&vesting_contract.vesting_type),
+        "start_time": vesting_contract.start_time,
+        "end_time": vesting_contract.end_time,
+        "total_amount": vesting_contract.total_amount,
+        "vested_amount": vested,
+        "released_amount": vesting_contract.released_amount,
+        "claimable_amount": releasable_amount(vesting_contract, now),
+        "remaining_amount": vesting_contract
+            .total_amount
+            .saturating_sub(vesting_contract.released_amount),
+        "as_of": now,
+    })
+}
@@ -0,0 +1,137 @@
+use anchor_lang::Discriminator;
+use solana_rpc_client_api::filter::RpcFilterType;
+use solana_sdk::pubkey::Pubkey;
+use verum_vesting_cli::csv::{parse_contracts, parse_time};
+use verum_vesting_cli::query::ContractQuery;
+use verum_vesting_cli::status::contract_status;
+use verum_vesting_client::verum_vesting::VestingContract;
+use verum_vesting_client::VestingType;
+
+#[test]
+fn parses_contract_csv() {
+    let beneficiary = Pubkey::new_unique();
+    let mint = Pubkey::new_unique();
+    let input = format!(
+        "contract_id,beneficiary,mint,total_amount,start_time,end_time,vesting_type,cliff_time,cliff_percentage\r\n\
+         1,{beneficiary},{mint},1000000,2025-01-01,2026-01-01T00:00:00Z,linear,,\r\n\
+         \r\n\
+         2,{beneficiary},{mint},500,1735689600,1767225600,cliff,2025-07-01,25\r\n"
+    );
+
+    let rows = parse_contracts(&input).unwrap();
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0].contract_id, 1);
+    assert_eq!(rows[0].beneficiary, beneficiary);
+    assert_eq!(rows[0].mint, mint);
+    assert_eq!(rows[0].total_amount, 1_000_000);
+    assert_eq!(rows[0].start_time, 1_735_689_600);
+    assert_eq!(rows[0].end_time, 1_767_225_600);
+    assert!(rows[0].vesting_type == VestingType::Linear);
+    assert!(rows[1].vesting_type == VestingType::Cliff(1_751_328_000, 25));
+
+    assert_eq!(parse_time("1970-01-01T00:01:05Z").unwrap(), 65);
+    assert_eq!(parse_time("2024-02-29T12:30").unwrap(), 1_709_209_800);
+
+    let missing_column = "contract_id,beneficiary\n1,x\n";
+    assert!(parse_contracts(missing_column).is_err());
+
+    let bad_row = format!(
+        "contract_id,beneficiary,mint,total_amount,start_time,end_time,vesting_type\n\
+         7,{beneficiary},{mint},10,2026-01-01,2025-01-01,linear\n"
+    );
+    let err = parse_contracts(&bad_row).err().unwrap();
+    assert!(format!("{err:#}").contains("linha 2"));
+}
+
+#[test]
+fn builds_program_account_filters() {
+    let creator = Pubkey::new_unique();
+    let beneficiary = Pubkey::new_unique();
+    let mint = Pubkey::new_unique();
+    let mut data = VestingContract::DISCRIMINATOR.to_vec();
+    data.extend_from_slice(creator.as_ref());
+    data.extend_from_slice(beneficiary.as_ref());
+    data.extend_from_slice(mint.as_ref());
+    data.resize(VestingContract::LEN, 0);
+    let matches = |query: ContractQuery, data: &[u8]| {
+        query.filters().iter().all(|filter| match filter {
+            RpcFilterType::Memcmp(memcmp) => memcmp.bytes_match(data),
+            _ => panic!("filtro inesperado"),
+        })
+    };
+
+    assert_eq!(ContractQuery::default().filters().len(), 1);
+    assert!(matches(ContractQuery::default(), &data));
+
+    let query = ContractQuery {
+        creator: Some(creator),
+        beneficiary: None,
+        mint: Some(mint),
+    };
+    assert_eq!(query.filters().len(), 3);
+    assert!(matches(query, &data));
+    assert!(!matches(
+        ContractQuery {
+            beneficiary: Some(creator),
+            ..ContractQuery::default()
+        },
+        &data
+    ));
+
+    data[0] ^= 1;
+    assert!(!matches(ContractQuery::default(), &data));
+}
+
+#[test]
+fn reports_contract_status() {
+    let address = Pubkey::new_unique();
+    let mut vesting_contract = VestingContract {
+        creator: Pubkey::new_unique(),
+        beneficiary: Pubkey::new_unique(),
+        mint: Pubkey::new_unique(),
+        total_amount: 1_000,
+        released_amount: 200,
+        start_time: 1_000,
+        end_time: 2_000,
+        contract_id: 9,
+        vesting_type: VestingType::Linear,
+        bump: 255,
+        is_cancelled: false,
+        is_token_2022: false,
+        mint_flags: 0,
+        is_native: false,
+        pause_policy: 0,
+        paused_at: 0,
+        paused_duration: 0,
+        pause_count: 0,
+        unlocked_amount: 0,
+        staking_program: Pubkey::default(),
+        staked_amount: 0,
+        compliance_authority: Pubkey::default(),
+        clawed_back_amount: 0,
+        timelock_delay: 0,
+        guardian: Pubkey::default(),
+        has_audit_log: false,
+        claim_count: 1,
+        beneficiary_updated_slot: 0,
+    };
+
+    let status = contract_status(&address, &vesting_contract, 1_500);
+    assert_eq!(status["address"], address.to_string());
+    assert_eq!(status["state"], "active");
+    assert_eq!(status["vesting_type"], "linear");
+    assert_eq!(status["vested_amount"], 500);
+    assert_eq!(status["claimable_amount"], 300);
+    assert_eq!(status["remaining_amount"], 800);
+
+    assert_eq!(
+        contract_status(&address, &vesting_contract, 500)["state"],
+        "pending"
+    );
+
+    vesting_contract.is_cancelled = true;
+    let status = contract_status(&address, &vesting_contract, 1_500);
+    assert_eq!(status["state"], "cancelled");
+    assert_eq!(status["vested_amount"], 200);
+    assert_eq!(status["claimable_amount"], 0);
+}
@@ -0,0 +1,31 @@
+//! Decodificacao das contas do programa.
+
+use anchor_lang::{AccountDeserialize, Result};
+use verum_vesting::{AuditLog, ReleaseReceipt, VestingContract};
+
+/// Decodifica uma conta Anchor, validando o discriminador.
+pub fn decode_account<T: AccountDeserialize>(data: &[u8]) -> Result<T> {
+    T::try_deserialize(&mut &data[..])
+}
+
+pub fn decode_vesting_contract(data: &[u8]) -> Result<VestingContract> {
+    decode_account(data)
+}
+
+pub fn decode_audit_log(data: &[u8]) -> Result<AuditLog> {
+    decode_account(data)
+}
+
+pub fn decode_release_receipt(data: &[u8]) -> Result<ReleaseReceipt> {
+    decode_account(data)
+}
+
+/// Valor que `claim_tokens` pagaria em `now`.
+pub fn releasable_amount(vesting_contract: &VestingContract, now: i64) -> u64 {
+    if vesting_contract.is_cancelled {
+        return 0;
+    }
+    vesting_contract
+        .vested_amount(now)
+        .saturating_sub(vesting_contract.released_amount)
+}
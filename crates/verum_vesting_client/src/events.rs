@@ -0,0 +1,83 @@
+//! Decodificacao dos eventos do programa.
+//!
+//! Os eventos do ciclo de vida sao emitidos via `emit_cpi!` e chegam aos indexadores
+//! como instrucoes internas do proprio programa (`decode_cpi_event`); os demais
+//! saem nos logs como "Program data:" (`decode_log_events`).
+
+use anchor_lang::event::EVENT_IX_TAG_LE;
+use anchor_lang::{AnchorDeserialize, Discriminator};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use verum_vesting::{BeneficiaryUpdated, TokensClaimed, VestingCancelled, VestingCreated};
+
+const PROGRAM_DATA: &str = "Program data: ";
+
+pub enum VestingEvent {
+    Created(VestingCreated),
+    Claimed(TokensClaimed),
+    BeneficiaryUpdated(BeneficiaryUpdated),
+    Cancelled(VestingCancelled),
+}
+
+impl VestingEvent {
+    pub fn name(&self) -> &'static str {
+        match self {
+            VestingEvent::Created(_) => "VestingCreated",
+            VestingEvent::Claimed(_) => "TokensClaimed",
+            VestingEvent::BeneficiaryUpdated(_) => "BeneficiaryUpdated",
+            VestingEvent::Cancelled(_) => "VestingCancelled",
+        }
+    }
+
+    pub fn vesting_contract(&self) -> anchor_lang::prelude::Pubkey {
+        match self {
+            VestingEvent::Created(event) => event.vesting_contract,
+            VestingEvent::Claimed(event) => event.vesting_contract,
+            VestingEvent::BeneficiaryUpdated(event) => event.vesting_contract,
+            VestingEvent::Cancelled(event) => event.vesting_contract,
+        }
+    }
+
+    pub fn slot(&self) -> u64 {
+        match self {
+            VestingEvent::Created(event) => event.slot,
+            VestingEvent::Claimed(event) => event.slot,
+            VestingEvent::BeneficiaryUpdated(event) => event.slot,
+            VestingEvent::Cancelled(event) => event.slot,
+        }
+    }
+}
+
+/// Decodifica discriminador + payload Borsh. `None` para eventos desconhecidos ou
+/// payloads invalidos.
+pub fn decode_event(data: &[u8]) -> Option<VestingEvent> {
+    let (discriminator, mut payload) = data.split_at_checked(8)?;
+    match <[u8; 8]>::try_from(discriminator).ok()? {
+        VestingCreated::DISCRIMINATOR => VestingCreated::deserialize(&mut payload)
+            .ok()
+            .map(VestingEvent::Created),
+        TokensClaimed::DISCRIMINATOR => TokensClaimed::deserialize(&mut payload)
+            .ok()
+            .map(VestingEvent::Claimed),
+        BeneficiaryUpdated::DISCRIMINATOR => BeneficiaryUpdated::deserialize(&mut payload)
+            .ok()
+            .map(VestingEvent::BeneficiaryUpdated),
+        VestingCancelled::DISCRIMINATOR => VestingCancelled::deserialize(&mut payload)
+            .ok()
+            .map(VestingEvent::Cancelled),
+        _ => None,
+    }
+}
+
+/// Decodifica os dados de uma instrucao interna do programa gerada por `emit_cpi!`.
+pub fn decode_cpi_event(instruction_data: &[u8]) -> Option<VestingEvent> {
+    decode_event(instruction_data.strip_prefix(EVENT_IX_TAG_LE.as_slice())?)
+}
+
+/// Eventos presentes nas linhas "Program data:" dos logs de uma transacao.
+pub fn decode_log_events<S: AsRef<str>>(logs: &[S]) -> Vec<VestingEvent> {
+    logs.iter()
+        .filter_map(|log| log.as_ref().strip_prefix(PROGRAM_DATA))
+        .filter_map(|data| STANDARD.decode(data).ok())
+        .filter_map(|data| decode_event(&data))
+        .collect()
+}
@@ -0,0 +1,272 @@
+//! Builders das instrucoes principais.
+//!
+//! As contas opcionais (`audit_log`, recibo, gatilho de preco) sao enviadas conforme
+//! os argumentos; em contratos ja existentes os builders `for_contract` leem essas
+//! escolhas do proprio `VestingContract`.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::system_program;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_spl::{token, token_2022};
+use verum_vesting::{VestingContract, VestingType, ID};
+
+use crate::pda::*;
+
+/// Programa de token do mint do contrato.
+pub fn token_program_for(vesting_contract: &VestingContract) -> Pubkey {
+    if vesting_contract.is_token_2022 {
+        token_2022::ID
+    } else {
+        token::ID
+    }
+}
+
+/// Contas de token usadas em pagamentos; ausentes em contratos nativos.
+#[derive(Clone, Copy, Debug)]
+pub struct TokenAccounts {
+    /// Conta de origem ou destino dos tokens, conforme a instrucao.
+    pub token_account: Pubkey,
+    pub mint: Pubkey,
+    pub token_program: Pubkey,
+}
+
+pub struct CreateVestingArgs {
+    pub creator: Pubkey,
+    pub beneficiary: Pubkey,
+    pub mint: Pubkey,
+    pub token_program: Pubkey,
+    pub sender_token_account: Pubkey,
+    pub contract_id: u64,
+    pub total_amount: u64,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub vesting_type: VestingType,
+    pub allowed_mint_flags: u16,
+    /// Cria o `AuditLog` junto com o contrato.
+    pub with_audit_log: bool,
+}
+
+/// `remaining_accounts` leva as contas do transfer hook em mints Token-2022 com a
+/// extensao.
+pub fn create_vesting(args: &CreateVestingArgs, remaining_accounts: &[AccountMeta]) -> Instruction {
+    let vesting_contract = vesting_address(&args.creator, &args.mint, args.contract_id);
+    let mut accounts = verum_vesting::accounts::CreateVesting {
+        vesting_contract,
+        creator: args.creator,
+        beneficiary: args.beneficiary,
+        mint: args.mint,
+        escrow_wallet: escrow_address(&vesting_contract),
+        sender_token_account: args.sender_token_account,
+        system_program: system_program::ID,
+        token_program: args.token_program,
+        audit_log: args
+            .with_audit_log
+            .then(|| audit_log_address(&vesting_contract)),
+        event_authority: event_authority_address(),
+        program: ID,
+    }
+    .to_account_metas(None);
+    accounts.extend_from_slice(remaining_accounts);
+
+    Instruction {
+        program_id: ID,
+        accounts,
+        data: verum_vesting::instruction::CreateVesting {
+            contract_id: args.contract_id,
+            total_amount: args.total_amount,
+            start_time: args.start_time,
+            end_time: args.end_time,
+            vesting_type: args.vesting_type,
+            allowed_mint_flags: args.allowed_mint_flags,
+        }
+        .data(),
+    }
+}
+
+/// `ReleaseReceipt` emitido pelo resgate.
+#[derive(Clone, Copy, Debug)]
+pub struct ReceiptArgs {
+    /// Deve ser o `claim_count` atual do contrato.
+    pub claim_index: u64,
+    pub payer: Pubkey,
+}
+
+pub struct ClaimTokensArgs {
+    pub vesting_contract: Pubkey,
+    pub beneficiary: Pubkey,
+    /// Conta de token do beneficiario; `None` em contratos nativos.
+    pub token_accounts: Option<TokenAccounts>,
+    /// Feed do oraculo, apenas em contratos `PriceTriggered`.
+    pub price_feed: Option<Pubkey>,
+    pub has_audit_log: bool,
+    pub receipt: Option<ReceiptArgs>,
+}
+
+impl ClaimTokensArgs {
+    /// Argumentos para resgatar de um contrato existente, sem recibo.
+    /// `beneficiary_token_account` e ignorada em contratos nativos.
+    pub fn for_contract(
+        vesting_contract: Pubkey,
+        state: &VestingContract,
+        beneficiary_token_account: Pubkey,
+    ) -> Self {
+        Self {
+            vesting_contract,
+            beneficiary: state.beneficiary,
+            token_accounts: (!state.is_native).then(|| TokenAccounts {
+                token_account: beneficiary_token_account,
+                mint: state.mint,
+                token_program: token_program_for(state),
+            }),
+            price_feed: None,
+            has_audit_log: state.has_audit_log,
+            receipt: None,
+        }
+    }
+}
+
+pub fn claim_tokens(args: &ClaimTokensArgs, remaining_accounts: &[AccountMeta]) -> Instruction {
+    let vesting_contract = &args.vesting_contract;
+    let token_accounts = args.token_accounts.as_ref();
+    let mut accounts = verum_vesting::accounts::ClaimTokens {
+        vesting_contract: *vesting_contract,
+        escrow_wallet: token_accounts.map(|_| escrow_address(vesting_contract)),
+        beneficiary_token_account: token_accounts.map(|accounts| accounts.token_account),
+        beneficiary: args.beneficiary,
+        mint: token_accounts.map(|accounts| accounts.mint),
+        token_program: token_accounts.map(|accounts| accounts.token_program),
+        price_trigger: args
+            .price_feed
+            .map(|_| price_trigger_address(vesting_contract)),
+        price_feed: args.price_feed,
+        audit_log: args
+            .has_audit_log
+            .then(|| audit_log_address(vesting_contract)),
+        release_receipt: args
+            .receipt
+            .map(|receipt| release_receipt_address(vesting_contract, receipt.claim_index)),
+        receipt_payer: args.receipt.map(|receipt| receipt.payer),
+        system_program: args.receipt.map(|_| system_program::ID),
+        event_authority: event_authority_address(),
+        program: ID,
+    }
+    .to_account_metas(None);
+
+    // Contratos nativos pagam em lamports direto ao beneficiario
+    if token_accounts.is_none() {
+        for meta in accounts.iter_mut() {
+            if meta.pubkey == args.beneficiary {
+                meta.is_writable = true;
+            }
+        }
+    }
+    accounts.extend_from_slice(remaining_accounts);
+
+    Instruction {
+        program_id: ID,
+        accounts,
+        data: verum_vesting::instruction::ClaimTokens {}.data(),
+    }
+}
+
+pub fn update_beneficiary(
+    vesting_contract: &Pubkey,
+    creator: &Pubkey,
+    new_beneficiary: &Pubkey,
+    has_audit_log: bool,
+) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: verum_vesting::accounts::UpdateBeneficiary {
+            vesting_contract: *vesting_contract,
+            creator: *creator,
+            new_beneficiary: *new_beneficiary,
+            audit_log: has_audit_log.then(|| audit_log_address(vesting_contract)),
+            event_authority: event_authority_address(),
+            program: ID,
+        }
+        .to_account_metas(None),
+        data: verum_vesting::instruction::UpdateBeneficiary {}.data(),
+    }
+}
+
+pub struct CancelVestingArgs {
+    pub vesting_contract: Pubkey,
+    pub creator: Pubkey,
+    /// Conta de token do criador; `None` em contratos nativos.
+    pub token_accounts: Option<TokenAccounts>,
+    pub has_audit_log: bool,
+}
+
+impl CancelVestingArgs {
+    /// `creator_token_account` e ignorada em contratos nativos.
+    pub fn for_contract(
+        vesting_contract: Pubkey,
+        state: &VestingContract,
+        creator_token_account: Pubkey,
+    ) -> Self {
+        Self {
+            vesting_contract,
+            creator: state.creator,
+            token_accounts: (!state.is_native).then(|| TokenAccounts {
+                token_account: creator_token_account,
+                mint: state.mint,
+                token_program: token_program_for(state),
+            }),
+            has_audit_log: state.has_audit_log,
+        }
+    }
+}
+
+pub fn cancel_vesting(args: &CancelVestingArgs, remaining_accounts: &[AccountMeta]) -> Instruction {
+    let vesting_contract = &args.vesting_contract;
+    let token_accounts = args.token_accounts.as_ref();
+    let mut accounts = verum_vesting::accounts::CancelVesting {
+        vesting_contract: *vesting_contract,
+        creator: args.creator,
+        escrow_wallet: token_accounts.map(|_| escrow_address(vesting_contract)),
+        creator_token_account: token_accounts.map(|accounts| accounts.token_account),
+        mint: token_accounts.map(|accounts| accounts.mint),
+        token_program: token_accounts.map(|accounts| accounts.token_program),
+        audit_log: args
+            .has_audit_log
+            .then(|| audit_log_address(vesting_contract)),
+        event_authority: event_authority_address(),
+        program: ID,
+    }
+    .to_account_metas(None);
+    accounts.extend_from_slice(remaining_accounts);
+
+    Instruction {
+        program_id: ID,
+        accounts,
+        data: verum_vesting::instruction::CancelVesting {}.data(),
+    }
+}
+
+/// View para simulacao; o resultado volta como `ScheduleProjection` no return data.
+pub fn project_schedule(vesting_contract: &Pubkey, points: u8) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: verum_vesting::accounts::ProjectSchedule {
+            vesting_contract: *vesting_contract,
+        }
+        .to_account_metas(None),
+        data: verum_vesting::instruction::ProjectSchedule { points }.data(),
+    }
+}
+
+/// Estende um contrato criado com um layout anterior do `VestingContract`.
+pub fn migrate_contract(vesting_contract: &Pubkey, payer: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: verum_vesting::accounts::MigrateContract {
+            vesting_contract: *vesting_contract,
+            payer: *payer,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: verum_vesting::instruction::MigrateContract {}.data(),
+    }
+}
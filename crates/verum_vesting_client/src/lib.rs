@@ -0,0 +1,19 @@
+//! Cliente Rust do programa `verum_vesting`.
+//!
+//! Reune o que os servicos off-chain precisavam reimplementar: derivacao dos PDAs,
+//! builders tipados das instrucoes principais, decodificacao de contas e dos
+//! eventos emitidos via `emit_cpi!`. Os tipos vem do proprio programa (feature
+//! `cpi`), entao mudancas de layout quebram a compilacao em vez de quebrar em
+//! producao.
+
+pub mod accounts;
+pub mod events;
+pub mod instructions;
+pub mod pda;
+
+pub use accounts::*;
+pub use events::*;
+pub use instructions::*;
+pub use pda::*;
+
+pub use verum_vesting::{self, VestingType, ID as PROGRAM_ID};
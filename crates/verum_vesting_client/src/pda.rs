@@ -0,0 +1,62 @@
+//! Enderecos derivados do programa.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_spl::token::spl_token::native_mint;
+use verum_vesting::{ID, RELEASE_RECEIPT_SEED};
+
+/// `[b"vesting", creator, mint, contract_id]`
+pub fn vesting_address(creator: &Pubkey, mint: &Pubkey, contract_id: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            b"vesting",
+            creator.as_ref(),
+            mint.as_ref(),
+            &contract_id.to_le_bytes(),
+        ],
+        &ID,
+    )
+    .0
+}
+
+/// Contrato criado por `create_native_vesting`.
+pub fn native_vesting_address(creator: &Pubkey, contract_id: u64) -> Pubkey {
+    vesting_address(creator, &native_mint::ID, contract_id)
+}
+
+/// `[b"escrow", vesting_contract]`
+pub fn escrow_address(vesting_contract: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"escrow", vesting_contract.as_ref()], &ID).0
+}
+
+/// `[b"audit_log", vesting_contract]`
+pub fn audit_log_address(vesting_contract: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"audit_log", vesting_contract.as_ref()], &ID).0
+}
+
+/// `[b"release_receipt", vesting_contract, claim_index]`
+pub fn release_receipt_address(vesting_contract: &Pubkey, claim_index: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            RELEASE_RECEIPT_SEED,
+            vesting_contract.as_ref(),
+            &claim_index.to_le_bytes(),
+        ],
+        &ID,
+    )
+    .0
+}
+
+/// `[b"price_trigger", vesting_contract]`
+pub fn price_trigger_address(vesting_contract: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"price_trigger", vesting_contract.as_ref()], &ID).0
+}
+
+/// `[b"pending_action", vesting_contract]`
+pub fn pending_action_address(vesting_contract: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"pending_action", vesting_contract.as_ref()], &ID).0
+}
+
+/// PDA que assina os eventos emitidos via `emit_cpi!`.
+pub fn event_authority_address() -> Pubkey {
+    Pubkey::find_program_address(&[b"__event_authority"], &ID).0
+}
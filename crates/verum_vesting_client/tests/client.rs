@@ -0,0 +1,223 @@
+//! Exercita os builders do cliente contra o programa rodando no program-test.
+
+use anchor_lang::event::EVENT_IX_TAG_LE;
+use anchor_lang::Event;
+use anchor_spl::token::spl_token;
+use solana_program_test::{processor, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    account_info::AccountInfo,
+    clock::Clock,
+    entrypoint::ProgramResult,
+    instruction::Instruction,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_instruction,
+    transaction::Transaction,
+};
+use verum_vesting_client::*;
+
+const TOTAL: u64 = 1_000_000;
+
+// O `entry` gerado pelo Anchor exige `&'info [AccountInfo<'info>]`.
+fn process_vesting_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> ProgramResult {
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    verum_vesting::entry(program_id, accounts, data)
+}
+
+async fn process(
+    context: &mut ProgramTestContext,
+    instructions: &[Instruction],
+    signers: &[&Keypair],
+) {
+    let payer = context.payer.insecure_clone();
+    let mut all_signers = vec![&payer];
+    all_signers.extend_from_slice(signers);
+    let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+    let transaction = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&payer.pubkey()),
+        &all_signers,
+        blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap();
+}
+
+async fn create_token_account(
+    context: &mut ProgramTestContext,
+    mint: &Pubkey,
+    owner: &Pubkey,
+) -> Pubkey {
+    let payer = context.payer.pubkey();
+    let account = Keypair::new();
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let instructions = [
+        system_instruction::create_account(
+            &payer,
+            &account.pubkey(),
+            rent.minimum_balance(spl_token::state::Account::LEN),
+            spl_token::state::Account::LEN as u64,
+            &spl_token::ID,
+        ),
+        spl_token::instruction::initialize_account3(&spl_token::ID, &account.pubkey(), mint, owner)
+            .unwrap(),
+    ];
+    process(context, &instructions, &[&account]).await;
+    account.pubkey()
+}
+
+async fn account_data(context: &mut ProgramTestContext, address: &Pubkey) -> Vec<u8> {
+    context
+        .banks_client
+        .get_account(*address)
+        .await
+        .unwrap()
+        .unwrap()
+        .data
+}
+
+async fn set_time(context: &mut ProgramTestContext, unix_timestamp: i64) {
+    let mut clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+    clock.unix_timestamp = unix_timestamp;
+    context.set_sysvar(&clock);
+}
+
+#[tokio::test]
+async fn builders_drive_a_full_contract_lifecycle() {
+    let mut context = ProgramTest::new(
+        "verum_vesting",
+        PROGRAM_ID,
+        processor!(process_vesting_instruction),
+    )
+    .start_with_context()
+    .await;
+    let creator = context.payer.pubkey();
+    let beneficiary = Pubkey::new_unique();
+
+    let mint = Keypair::new();
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let instructions = [
+        system_instruction::create_account(
+            &creator,
+            &mint.pubkey(),
+            rent.minimum_balance(spl_token::state::Mint::LEN),
+            spl_token::state::Mint::LEN as u64,
+            &spl_token::ID,
+        ),
+        spl_token::instruction::initialize_mint2(&spl_token::ID, &mint.pubkey(), &creator, None, 6)
+            .unwrap(),
+    ];
+    process(&mut context, &instructions, &[&mint]).await;
+    let mint = mint.pubkey();
+    let creator_token_account = create_token_account(&mut context, &mint, &creator).await;
+    let beneficiary_token_account = create_token_account(&mut context, &mint, &beneficiary).await;
+    let mint_to = spl_token::instruction::mint_to(
+        &spl_token::ID,
+        &mint,
+        &creator_token_account,
+        &creator,
+        &[],
+        TOTAL,
+    )
+    .unwrap();
+    process(&mut context, &[mint_to], &[]).await;
+
+    let clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+    let start_time = clock.unix_timestamp;
+    let args = CreateVestingArgs {
+        creator,
+        beneficiary,
+        mint,
+        token_program: spl_token::ID,
+        sender_token_account: creator_token_account,
+        contract_id: 9,
+        total_amount: TOTAL,
+        start_time,
+        end_time: start_time + 1_000,
+        vesting_type: VestingType::Linear,
+        allowed_mint_flags: 0,
+        with_audit_log: true,
+    };
+    process(&mut context, &[create_vesting(&args, &[])], &[]).await;
+
+    let vesting_contract = vesting_address(&creator, &mint, 9);
+    let data = account_data(&mut context, &vesting_contract).await;
+    let state = decode_vesting_contract(&data).unwrap();
+    assert_eq!(state.beneficiary, beneficiary);
+    assert!(state.has_audit_log);
+    assert_eq!(releasable_amount(&state, start_time + 250), TOTAL / 4);
+    assert!(decode_audit_log(&data).is_err());
+
+    set_time(&mut context, start_time + 250).await;
+    let mut claim =
+        ClaimTokensArgs::for_contract(vesting_contract, &state, beneficiary_token_account);
+    claim.receipt = Some(ReceiptArgs {
+        claim_index: state.claim_count,
+        payer: creator,
+    });
+    process(&mut context, &[claim_tokens(&claim, &[])], &[]).await;
+
+    let receipt_address = release_receipt_address(&vesting_contract, 0);
+    let data = account_data(&mut context, &receipt_address).await;
+    let receipt = decode_release_receipt(&data).unwrap();
+    assert_eq!(receipt.amount, TOTAL / 4);
+    assert_eq!(receipt.destination, beneficiary_token_account);
+
+    let data = account_data(&mut context, &vesting_contract).await;
+    let state = decode_vesting_contract(&data).unwrap();
+    let cancel = CancelVestingArgs::for_contract(vesting_contract, &state, creator_token_account);
+    process(&mut context, &[cancel_vesting(&cancel, &[])], &[]).await;
+
+    let data = account_data(&mut context, &audit_log_address(&vesting_contract)).await;
+    let audit_log = decode_audit_log(&data).unwrap();
+    assert_eq!(audit_log.total_entries, 3);
+    let data = account_data(&mut context, &vesting_contract).await;
+    assert!(decode_vesting_contract(&data).unwrap().is_cancelled);
+}
+
+#[test]
+fn events_decode_from_cpi_data_and_logs() {
+    let vesting_contract = Pubkey::new_unique();
+    let event = verum_vesting::TokensClaimed {
+        contract_id: 1,
+        beneficiary: Pubkey::new_unique(),
+        amount: 10,
+        timestamp: 20,
+        vesting_contract,
+        released_amount: 10,
+        remaining_amount: 90,
+        start_time: 0,
+        end_time: 100,
+        slot: 42,
+    };
+
+    let cpi_data = [EVENT_IX_TAG_LE.as_slice(), &event.data()].concat();
+    let decoded = decode_cpi_event(&cpi_data).unwrap();
+    assert_eq!(decoded.name(), "TokensClaimed");
+    assert_eq!(decoded.vesting_contract(), vesting_contract);
+    assert_eq!(decoded.slot(), 42);
+    assert!(decode_cpi_event(&event.data()).is_none());
+
+    use base64::Engine;
+    let logs = vec![
+        "Program log: Instruction: ClaimTokens".to_string(),
+        format!(
+            "Program data: {}",
+            base64::engine::general_purpose::STANDARD.encode(event.data())
+        ),
+    ];
+    let events = decode_log_events(&logs);
+    assert_eq!(events.len(), 1);
+    match &events[0] {
+        VestingEvent::Claimed(claimed) => assert_eq!(claimed.remaining_amount, 90),
+        other => panic!("evento inesperado: {}", other.name()),
+    }
+}
@@ -0,0 +1,101 @@
+//! API de consulta em HTTP/JSON.
+//!
+//! `GET /events?contract=<pubkey>&wallet=<pubkey>&kind=<Evento>&since_slot=<n>&limit=<n>`
+//! devolve os registros que atendem a todos os filtros informados.
+
+use serde_json::json;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::{Arc, RwLock};
+use std::thread::{self, JoinHandle};
+
+use crate::store::{EventFilter, EventStore};
+
+/// Filtro a partir do caminho da requisicao; `None` para rotas desconhecidas ou
+/// parametros invalidos. Os valores aceitos (base58, nomes de eventos, numeros)
+/// nao precisam de percent-decoding.
+pub fn parse_events_query(path: &str) -> Option<EventFilter> {
+    let (route, query) = path.split_once('?').unwrap_or((path, ""));
+    if route != "/events" {
+        return None;
+    }
+
+    let mut filter = EventFilter::default();
+    for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+        let (key, value) = pair.split_once('=')?;
+        match key {
+            "contract" => filter.vesting_contract = Some(value.to_string()),
+            "wallet" => filter.wallet = Some(value.to_string()),
+            "kind" => filter.kind = Some(value.to_string()),
+            "since_slot" => filter.since_slot = Some(value.parse().ok()?),
+            "limit" => filter.limit = Some(value.parse().ok()?),
+            _ => return None,
+        }
+    }
+    Some(filter)
+}
+
+fn respond(stream: &mut TcpStream, status: &str, body: &str) {
+    let _ = write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nAccess-Control-Allow-Origin: *\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+}
+
+fn handle<S: EventStore>(mut stream: TcpStream, store: &RwLock<S>) {
+    let mut request_line = String::new();
+    if BufReader::new(&stream)
+        .read_line(&mut request_line)
+        .is_err()
+    {
+        return;
+    }
+    let mut parts = request_line.split_whitespace();
+    let (Some("GET"), Some(path)) = (parts.next(), parts.next()) else {
+        respond(
+            &mut stream,
+            "405 Method Not Allowed",
+            r#"{"error":"use GET"}"#,
+        );
+        return;
+    };
+
+    if path == "/health" {
+        respond(&mut stream, "200 OK", r#"{"ok":true}"#);
+        return;
+    }
+    let Some(filter) = parse_events_query(path) else {
+        respond(
+            &mut stream,
+            "400 Bad Request",
+            r#"{"error":"consulta invalida"}"#,
+        );
+        return;
+    };
+
+    match store.read().unwrap().query(&filter) {
+        Ok(records) => respond(
+            &mut stream,
+            "200 OK",
+            &serde_json::to_string(&records).unwrap(),
+        ),
+        Err(err) => respond(
+            &mut stream,
+            "500 Internal Server Error",
+            &json!({ "error": format!("{err:#}") }).to_string(),
+        ),
+    }
+}
+
+pub fn serve<S: EventStore + Send + Sync + 'static>(
+    store: Arc<RwLock<S>>,
+    addr: SocketAddr,
+) -> std::io::Result<JoinHandle<()>> {
+    let listener = TcpListener::bind(addr)?;
+    Ok(thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            handle(stream, &store);
+        }
+    }))
+}
@@ -0,0 +1,161 @@
+//! Leitura das transacoes do programa e extracao dos eventos.
+
+use anyhow::{Context, Result};
+use solana_rpc_client::rpc_client::{GetConfirmedSignaturesForAddress2Config, RpcClient};
+use solana_rpc_client_api::config::RpcTransactionConfig;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_transaction_status::option_serializer::OptionSerializer;
+use solana_transaction_status::{
+    EncodedConfirmedTransactionWithStatusMeta, EncodedTransaction, UiInnerInstructions,
+    UiInstruction, UiMessage, UiTransactionEncoding,
+};
+use std::str::FromStr;
+use std::sync::{Arc, RwLock};
+use verum_vesting_client::{decode_cpi_event, decode_log_events, VestingEvent, PROGRAM_ID};
+
+use crate::record::EventRecord;
+use crate::store::EventStore;
+
+/// Limite de `getSignaturesForAddress` por pagina.
+const SIGNATURES_PAGE: usize = 1_000;
+
+/// Eventos de uma transacao, na ordem de execucao.
+///
+/// `account_keys` deve incluir os enderecos carregados por lookup table, na ordem
+/// do runtime. Os eventos de `emit_cpi!` vem das instrucoes internas do programa;
+/// eventos de `emit!` vem das linhas "Program data:" dos logs.
+pub fn decode_transaction_events(
+    account_keys: &[Pubkey],
+    inner_instructions: &[UiInnerInstructions],
+    logs: &[String],
+) -> Vec<VestingEvent> {
+    let mut events: Vec<VestingEvent> = inner_instructions
+        .iter()
+        .flat_map(|inner| inner.instructions.iter())
+        .filter_map(|instruction| match instruction {
+            UiInstruction::Compiled(instruction) => Some(instruction),
+            UiInstruction::Parsed(_) => None,
+        })
+        .filter(|instruction| {
+            account_keys.get(instruction.program_id_index as usize) == Some(&PROGRAM_ID)
+        })
+        .filter_map(|instruction| bs58::decode(&instruction.data).into_vec().ok())
+        .filter_map(|data| decode_cpi_event(&data))
+        .collect();
+    events.extend(decode_log_events(logs));
+    events
+}
+
+/// Registros de uma transacao obtida com `UiTransactionEncoding::Json`; vazio para
+/// transacoes com erro.
+pub fn transaction_records(
+    signature: &str,
+    transaction: &EncodedConfirmedTransactionWithStatusMeta,
+) -> Vec<EventRecord> {
+    let Some(meta) = &transaction.transaction.meta else {
+        return Vec::new();
+    };
+    if meta.err.is_some() {
+        return Vec::new();
+    }
+    let EncodedTransaction::Json(ui_transaction) = &transaction.transaction.transaction else {
+        return Vec::new();
+    };
+    let UiMessage::Raw(message) = &ui_transaction.message else {
+        return Vec::new();
+    };
+
+    let mut keys = message.account_keys.clone();
+    if let OptionSerializer::Some(loaded) = &meta.loaded_addresses {
+        keys.extend(loaded.writable.iter().cloned());
+        keys.extend(loaded.readonly.iter().cloned());
+    }
+    let account_keys: Vec<Pubkey> = keys
+        .iter()
+        .map(|key| Pubkey::from_str(key).unwrap_or_default())
+        .collect();
+    let inner_instructions = match &meta.inner_instructions {
+        OptionSerializer::Some(inner) => inner.as_slice(),
+        _ => &[],
+    };
+    let logs = match &meta.log_messages {
+        OptionSerializer::Some(logs) => logs.as_slice(),
+        _ => &[],
+    };
+
+    decode_transaction_events(&account_keys, inner_instructions, logs)
+        .iter()
+        .enumerate()
+        .map(|(index, event)| {
+            EventRecord::new(
+                signature,
+                index as u32,
+                transaction.slot,
+                transaction.block_time,
+                event,
+            )
+        })
+        .collect()
+}
+
+pub struct Indexer<S> {
+    pub client: RpcClient,
+    pub store: Arc<RwLock<S>>,
+}
+
+impl<S: EventStore> Indexer<S> {
+    /// Processa as transacoes posteriores ao cursor, da mais antiga para a mais
+    /// recente, avancando o cursor a cada uma. Devolve quantos registros gravou.
+    pub fn poll(&self) -> Result<usize> {
+        let cursor = self.store.read().unwrap().cursor()?;
+        let until = cursor
+            .as_deref()
+            .map(Signature::from_str)
+            .transpose()
+            .context("cursor invalido")?;
+
+        // A RPC devolve da mais recente para a mais antiga
+        let mut signatures = Vec::new();
+        let mut before = None;
+        loop {
+            let page = self.client.get_signatures_for_address_with_config(
+                &PROGRAM_ID,
+                GetConfirmedSignaturesForAddress2Config {
+                    before,
+                    until,
+                    limit: Some(SIGNATURES_PAGE),
+                    commitment: Some(CommitmentConfig::confirmed()),
+                },
+            )?;
+            let done = page.len() < SIGNATURES_PAGE;
+            before = page
+                .last()
+                .map(|status| Signature::from_str(&status.signature))
+                .transpose()?;
+            signatures.extend(page);
+            if done {
+                break;
+            }
+        }
+
+        let mut inserted = 0;
+        for status in signatures.iter().rev() {
+            if status.err.is_none() {
+                let transaction = self.client.get_transaction_with_config(
+                    &Signature::from_str(&status.signature)?,
+                    RpcTransactionConfig {
+                        encoding: Some(UiTransactionEncoding::Json),
+                        commitment: Some(CommitmentConfig::confirmed()),
+                        max_supported_transaction_version: Some(0),
+                    },
+                )?;
+                let records = transaction_records(&status.signature, &transaction);
+                inserted += self.store.write().unwrap().insert(&records)?;
+            }
+            self.store.write().unwrap().set_cursor(&status.signature)?;
+        }
+        Ok(inserted)
+    }
+}
@@ -0,0 +1,12 @@
+//! Indexador dos eventos do ciclo de vida (`VestingCreated`, `TokensClaimed`,
+//! `BeneficiaryUpdated`, `VestingCancelled`).
+//!
+//! `ingest` le as transacoes do programa via RPC e decodifica os eventos das
+//! instrucoes internas de `emit_cpi!` e dos logs; `store` persiste os registros
+//! atras de `EventStore`; `api` serve as consultas em HTTP/JSON para o dashboard,
+//! a auditoria e as notificacoes.
+
+pub mod api;
+pub mod ingest;
+pub mod record;
+pub mod store;
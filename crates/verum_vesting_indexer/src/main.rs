@@ -0,0 +1,90 @@
+//! `verum-vesting-indexer`: ingestao continua e API de consulta.
+
+use anyhow::{Context, Result};
+use clap::{Arg, Command};
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use std::net::SocketAddr;
+use std::path::Path;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use verum_vesting_indexer::api;
+use verum_vesting_indexer::ingest::Indexer;
+use verum_vesting_indexer::store::FileStore;
+
+fn main() -> Result<()> {
+    let matches = Command::new("verum-vesting-indexer")
+        .about("Indexa os eventos do verum_vesting e serve consultas em HTTP")
+        .arg(
+            Arg::new("url")
+                .long("url")
+                .short('u')
+                .takes_value(true)
+                .default_value("http://127.0.0.1:8899"),
+        )
+        .arg(
+            Arg::new("data-dir")
+                .long("data-dir")
+                .takes_value(true)
+                .default_value("indexer-data"),
+        )
+        .arg(
+            Arg::new("listen")
+                .long("listen")
+                .takes_value(true)
+                .default_value("127.0.0.1:8787"),
+        )
+        .arg(
+            Arg::new("interval")
+                .long("interval")
+                .takes_value(true)
+                .default_value("10")
+                .help("Segundos entre consultas a RPC"),
+        )
+        .arg(
+            Arg::new("once")
+                .long("once")
+                .help("Processa as transacoes pendentes e sai, sem servir a API"),
+        )
+        .get_matches();
+
+    let store = Arc::new(RwLock::new(FileStore::open(Path::new(
+        matches.value_of("data-dir").unwrap(),
+    ))?));
+    let indexer = Indexer {
+        client: RpcClient::new_with_commitment(
+            matches.value_of("url").unwrap().to_string(),
+            CommitmentConfig::confirmed(),
+        ),
+        store: store.clone(),
+    };
+
+    if !matches.is_present("once") {
+        let addr: SocketAddr = matches
+            .value_of("listen")
+            .unwrap()
+            .parse()
+            .context("listen invalido")?;
+        api::serve(store, addr)?;
+        println!("[indexer] API em http://{addr}/events");
+    }
+
+    let interval = Duration::from_secs(
+        matches
+            .value_of("interval")
+            .unwrap()
+            .parse()
+            .context("interval invalido")?,
+    );
+    loop {
+        match indexer.poll() {
+            Ok(0) => {}
+            Ok(inserted) => println!("[indexer] {inserted} eventos novos"),
+            Err(err) => eprintln!("[indexer] falha na ingestao: {err:#}"),
+        }
+        if matches.is_present("once") {
+            return Ok(());
+        }
+        std::thread::sleep(interval);
+    }
+}
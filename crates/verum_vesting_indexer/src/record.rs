@@ -0,0 +1,112 @@
+//! Forma persistida de um evento.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use verum_vesting_client::{VestingEvent, VestingType};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EventRecord {
+    pub signature: String,
+    /// Posicao do evento dentro da transacao; com `signature` identifica o registro.
+    pub index: u32,
+    pub slot: u64,
+    pub block_time: Option<i64>,
+    /// Nome do evento no programa, ex.: `TokensClaimed`.
+    pub kind: String,
+    pub vesting_contract: String,
+    pub contract_id: u64,
+    /// Carteiras envolvidas (criador, beneficiarios antigo e novo), para consultas
+    /// por usuario.
+    pub wallets: Vec<String>,
+    /// Valor movimentado pelo evento, quando houver.
+    pub amount: Option<u64>,
+    /// Campos do evento, com chaves publicas em base58.
+    pub payload: Value,
+}
+
+fn vesting_type_label(vesting_type: &VestingType) -> Value {
+    match vesting_type {
+        VestingType::Linear => json!("linear"),
+        VestingType::Cliff(cliff_time, percentage) => {
+            json!({ "cliff": { "cliff_time": cliff_time, "percentage": percentage } })
+        }
+        VestingType::Milestones => json!("milestones"),
+        VestingType::PriceTriggered => json!("price_triggered"),
+    }
+}
+
+impl EventRecord {
+    pub fn new(
+        signature: &str,
+        index: u32,
+        slot: u64,
+        block_time: Option<i64>,
+        event: &VestingEvent,
+    ) -> Self {
+        let (contract_id, wallets, amount, payload) = match event {
+            VestingEvent::Created(event) => (
+                event.contract_id,
+                vec![event.creator, event.beneficiary],
+                Some(event.total_amount),
+                json!({
+                    "creator": event.creator.to_string(),
+                    "beneficiary": event.beneficiary.to_string(),
+                    "mint": event.mint.to_string(),
+                    "total_amount": event.total_amount,
+                    "start_time": event.start_time,
+                    "end_time": event.end_time,
+                    "mint_flags": event.mint_flags,
+                    "vesting_type": vesting_type_label(&event.vesting_type),
+                }),
+            ),
+            VestingEvent::Claimed(event) => (
+                event.contract_id,
+                vec![event.beneficiary],
+                Some(event.amount),
+                json!({
+                    "beneficiary": event.beneficiary.to_string(),
+                    "amount": event.amount,
+                    "timestamp": event.timestamp,
+                    "released_amount": event.released_amount,
+                    "remaining_amount": event.remaining_amount,
+                    "start_time": event.start_time,
+                    "end_time": event.end_time,
+                }),
+            ),
+            VestingEvent::BeneficiaryUpdated(event) => (
+                event.contract_id,
+                vec![event.old_beneficiary, event.new_beneficiary],
+                None,
+                json!({
+                    "old_beneficiary": event.old_beneficiary.to_string(),
+                    "new_beneficiary": event.new_beneficiary.to_string(),
+                    "timestamp": event.timestamp,
+                }),
+            ),
+            VestingEvent::Cancelled(event) => (
+                event.contract_id,
+                vec![event.creator],
+                Some(event.remaining_amount),
+                json!({
+                    "creator": event.creator.to_string(),
+                    "remaining_amount": event.remaining_amount,
+                    "released_amount": event.released_amount,
+                    "timestamp": event.timestamp,
+                }),
+            ),
+        };
+
+        Self {
+            signature: signature.to_string(),
+            index,
+            slot,
+            block_time,
+            kind: event.name().to_string(),
+            vesting_contract: event.vesting_contract().to_string(),
+            contract_id,
+            wallets: wallets.iter().map(ToString::to_string).collect(),
+            amount,
+            payload,
+        }
+    }
+}
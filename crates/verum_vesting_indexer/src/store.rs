@@ -0,0 +1,147 @@
+//! Persistencia dos registros.
+//!
+//! `EventStore` e o contrato usado pela ingestao e pela API; `FileStore` e o backend
+//! local (JSON Lines com indice em memoria). Um backend SQL implementa o mesmo trait
+//! com uma tabela por registro e chave unica em `(signature, index)`.
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+use crate::record::EventRecord;
+
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct EventFilter {
+    pub vesting_contract: Option<String>,
+    /// Eventos em que a carteira aparece como criador ou beneficiario.
+    pub wallet: Option<String>,
+    pub kind: Option<String>,
+    pub since_slot: Option<u64>,
+    pub limit: Option<usize>,
+}
+
+impl EventFilter {
+    pub fn matches(&self, record: &EventRecord) -> bool {
+        self.vesting_contract
+            .as_ref()
+            .is_none_or(|contract| record.vesting_contract == *contract)
+            && self
+                .wallet
+                .as_ref()
+                .is_none_or(|wallet| record.wallets.contains(wallet))
+            && self.kind.as_ref().is_none_or(|kind| record.kind == *kind)
+            && self.since_slot.is_none_or(|slot| record.slot >= slot)
+    }
+}
+
+pub trait EventStore {
+    /// Insere os registros ainda inexistentes; devolve quantos foram gravados.
+    fn insert(&mut self, records: &[EventRecord]) -> Result<usize>;
+
+    /// Registros em ordem cronologica.
+    fn query(&self, filter: &EventFilter) -> Result<Vec<EventRecord>>;
+
+    /// Assinatura da transacao mais recente ja processada.
+    fn cursor(&self) -> Result<Option<String>>;
+
+    fn set_cursor(&mut self, signature: &str) -> Result<()>;
+}
+
+pub struct FileStore {
+    events_path: PathBuf,
+    cursor_path: PathBuf,
+    records: Vec<EventRecord>,
+    keys: HashSet<(String, u32)>,
+}
+
+impl FileStore {
+    /// Abre (ou cria) o armazenamento em `dir`.
+    pub fn open(dir: &Path) -> Result<Self> {
+        fs::create_dir_all(dir).with_context(|| format!("falha ao criar {}", dir.display()))?;
+        let events_path = dir.join("events.jsonl");
+
+        let mut records = Vec::new();
+        if events_path.exists() {
+            for (number, line) in BufReader::new(File::open(&events_path)?)
+                .lines()
+                .enumerate()
+            {
+                let line = line?;
+                if line.trim().is_empty() {
+                    continue;
+                }
+                records.push(
+                    serde_json::from_str::<EventRecord>(&line)
+                        .with_context(|| format!("{}:{}", events_path.display(), number + 1))?,
+                );
+            }
+        }
+        let keys = records
+            .iter()
+            .map(|record| (record.signature.clone(), record.index))
+            .collect();
+
+        Ok(Self {
+            events_path,
+            cursor_path: dir.join("cursor"),
+            records,
+            keys,
+        })
+    }
+}
+
+impl EventStore for FileStore {
+    fn insert(&mut self, records: &[EventRecord]) -> Result<usize> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.events_path)?;
+
+        let mut inserted = 0;
+        for record in records {
+            if !self.keys.insert((record.signature.clone(), record.index)) {
+                continue;
+            }
+            writeln!(file, "{}", serde_json::to_string(record)?)?;
+            self.records.push(record.clone());
+            inserted += 1;
+        }
+        file.sync_data()?;
+        Ok(inserted)
+    }
+
+    fn query(&self, filter: &EventFilter) -> Result<Vec<EventRecord>> {
+        let mut records: Vec<EventRecord> = self
+            .records
+            .iter()
+            .filter(|record| filter.matches(record))
+            .cloned()
+            .collect();
+        // Ordenacao estavel: no mesmo slot vale a ordem de ingestao
+        records.sort_by_key(|record| record.slot);
+        if let Some(limit) = filter.limit {
+            // Mantem os mais recentes
+            records.drain(..records.len().saturating_sub(limit));
+        }
+        Ok(records)
+    }
+
+    fn cursor(&self) -> Result<Option<String>> {
+        match fs::read_to_string(&self.cursor_path) {
+            Ok(cursor) => Ok(Some(cursor.trim().to_string()).filter(|cursor| !cursor.is_empty())),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Grava em arquivo temporario e renomeia, para nunca deixar um cursor truncado.
+    fn set_cursor(&mut self, signature: &str) -> Result<()> {
+        let temporary = self.cursor_path.with_extension("tmp");
+        fs::write(&temporary, signature)?;
+        fs::rename(&temporary, &self.cursor_path)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,215 @@
+use anchor_lang::event::EVENT_IX_TAG_LE;
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::Event;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde_json::json;
+use solana_transaction_status::EncodedConfirmedTransactionWithStatusMeta;
+use verum_vesting_client::verum_vesting::{TokensClaimed, VestingCancelled};
+use verum_vesting_client::PROGRAM_ID;
+use verum_vesting_indexer::api::parse_events_query;
+use verum_vesting_indexer::ingest::transaction_records;
+use verum_vesting_indexer::record::EventRecord;
+use verum_vesting_indexer::store::{EventFilter, EventStore, FileStore};
+
+fn rpc_transaction(
+    account_keys: &[Pubkey],
+    inner: serde_json::Value,
+    logs: &[String],
+    err: Option<serde_json::Value>,
+) -> EncodedConfirmedTransactionWithStatusMeta {
+    serde_json::from_value(json!({
+        "slot": 42,
+        "blockTime": 1_700_000_000,
+        "transaction": {
+            "signatures": ["1111111111111111111111111111111111111111111111111111111111111111"],
+            "message": {
+                "header": {
+                    "numRequiredSignatures": 1,
+                    "numReadonlySignedAccounts": 0,
+                    "numReadonlyUnsignedAccounts": 1
+                },
+                "accountKeys": account_keys.iter().map(ToString::to_string).collect::<Vec<_>>(),
+                "recentBlockhash": "11111111111111111111111111111111",
+                "instructions": []
+            }
+        },
+        "meta": {
+            "err": err,
+            "status": { "Ok": null },
+            "fee": 5000,
+            "preBalances": [],
+            "postBalances": [],
+            "innerInstructions": inner,
+            "logMessages": logs
+        }
+    }))
+    .unwrap()
+}
+
+fn claimed(vesting_contract: Pubkey, beneficiary: Pubkey, amount: u64) -> TokensClaimed {
+    TokensClaimed {
+        contract_id: 3,
+        beneficiary,
+        amount,
+        timestamp: 1_700_000_000,
+        vesting_contract,
+        released_amount: amount,
+        remaining_amount: 1_000 - amount,
+        start_time: 0,
+        end_time: 10,
+        slot: 42,
+    }
+}
+
+#[test]
+fn decodes_events_from_rpc_transactions() {
+    let payer = Pubkey::new_unique();
+    let other_program = Pubkey::new_unique();
+    let vesting_contract = Pubkey::new_unique();
+    let beneficiary = Pubkey::new_unique();
+    let creator = Pubkey::new_unique();
+
+    let cpi_data = [
+        EVENT_IX_TAG_LE.as_slice(),
+        &claimed(vesting_contract, beneficiary, 250).data(),
+    ]
+    .concat();
+    let cpi_data = bs58::encode(cpi_data).into_string();
+    let inner = json!([{
+        "index": 0,
+        "instructions": [
+            { "programIdIndex": 2, "accounts": [], "data": cpi_data, "stackHeight": 2 },
+            // Mesmo payload vindo de outro programa nao e evento do vesting
+            { "programIdIndex": 1, "accounts": [], "data": cpi_data, "stackHeight": 2 }
+        ]
+    }]);
+    let cancelled = VestingCancelled {
+        contract_id: 3,
+        creator,
+        remaining_amount: 750,
+        timestamp: 1_700_000_000,
+        vesting_contract,
+        released_amount: 250,
+        slot: 42,
+    };
+    let logs = vec![
+        format!("Program {PROGRAM_ID} invoke [1]"),
+        format!("Program data: {}", STANDARD.encode(cancelled.data())),
+    ];
+    let keys = [payer, other_program, PROGRAM_ID];
+
+    let records = transaction_records("sig", &rpc_transaction(&keys, inner.clone(), &logs, None));
+    assert_eq!(records.len(), 2);
+
+    assert_eq!(records[0].kind, "TokensClaimed");
+    assert_eq!(records[0].index, 0);
+    assert_eq!(records[0].slot, 42);
+    assert_eq!(records[0].block_time, Some(1_700_000_000));
+    assert_eq!(records[0].vesting_contract, vesting_contract.to_string());
+    assert_eq!(records[0].wallets, vec![beneficiary.to_string()]);
+    assert_eq!(records[0].amount, Some(250));
+    assert_eq!(records[0].payload["remaining_amount"], 750);
+
+    assert_eq!(records[1].kind, "VestingCancelled");
+    assert_eq!(records[1].index, 1);
+    assert_eq!(records[1].wallets, vec![creator.to_string()]);
+    assert_eq!(records[1].amount, Some(750));
+
+    // Transacoes com erro nao geram registros
+    let failed = rpc_transaction(
+        &keys,
+        inner,
+        &logs,
+        Some(json!({ "InstructionError": [0, { "Custom": 1 }] })),
+    );
+    assert!(transaction_records("sig", &failed).is_empty());
+}
+
+fn record(
+    signature: &str,
+    index: u32,
+    slot: u64,
+    kind: &str,
+    contract: &str,
+    wallet: &str,
+) -> EventRecord {
+    EventRecord {
+        signature: signature.to_string(),
+        index,
+        slot,
+        block_time: None,
+        kind: kind.to_string(),
+        vesting_contract: contract.to_string(),
+        contract_id: 1,
+        wallets: vec![wallet.to_string()],
+        amount: None,
+        payload: json!({}),
+    }
+}
+
+#[test]
+fn file_store_deduplicates_persists_and_filters() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut store = FileStore::open(dir.path()).unwrap();
+    assert_eq!(store.cursor().unwrap(), None);
+
+    let records = vec![
+        record("b", 0, 20, "TokensClaimed", "c1", "w1"),
+        record("a", 0, 10, "VestingCreated", "c1", "w1"),
+        record("a", 1, 10, "VestingCreated", "c2", "w2"),
+    ];
+    assert_eq!(store.insert(&records).unwrap(), 3);
+    assert_eq!(store.insert(&records[..1]).unwrap(), 0);
+    store.set_cursor("b").unwrap();
+
+    let store = FileStore::open(dir.path()).unwrap();
+    assert_eq!(store.cursor().unwrap().as_deref(), Some("b"));
+
+    let all = store.query(&EventFilter::default()).unwrap();
+    let order: Vec<(&str, u32)> = all
+        .iter()
+        .map(|r| (r.signature.as_str(), r.index))
+        .collect();
+    assert_eq!(order, vec![("a", 0), ("a", 1), ("b", 0)]);
+
+    let by_contract = EventFilter {
+        vesting_contract: Some("c1".to_string()),
+        ..EventFilter::default()
+    };
+    assert_eq!(store.query(&by_contract).unwrap().len(), 2);
+
+    let by_wallet_and_kind = EventFilter {
+        wallet: Some("w1".to_string()),
+        kind: Some("TokensClaimed".to_string()),
+        ..EventFilter::default()
+    };
+    assert_eq!(
+        store.query(&by_wallet_and_kind).unwrap(),
+        vec![records[0].clone()]
+    );
+
+    let latest = EventFilter {
+        since_slot: Some(10),
+        limit: Some(1),
+        ..EventFilter::default()
+    };
+    assert_eq!(store.query(&latest).unwrap()[0].signature, "b");
+}
+
+#[test]
+fn parses_event_queries() {
+    let filter = parse_events_query(
+        "/events?contract=c1&wallet=w1&kind=TokensClaimed&since_slot=5&limit=10",
+    )
+    .unwrap();
+    assert_eq!(filter.vesting_contract.as_deref(), Some("c1"));
+    assert_eq!(filter.wallet.as_deref(), Some("w1"));
+    assert_eq!(filter.kind.as_deref(), Some("TokensClaimed"));
+    assert_eq!(filter.since_slot, Some(5));
+    assert_eq!(filter.limit, Some(10));
+
+    assert!(parse_events_query("/events").is_some());
+    assert!(parse_events_query("/events?limit=abc").is_none());
+    assert!(parse_events_query("/events?unknown=1").is_none());
+    assert!(parse_events_query("/contracts").is_none());
+}
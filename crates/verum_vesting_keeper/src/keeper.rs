@@ -0,0 +1,343 @@
+//! Varredura, montagem dos lotes e envio com novas tentativas.
+
+use anchor_lang::{AccountDeserialize, Discriminator};
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use anyhow::{anyhow, Result};
+use solana_account_decoder::UiAccountEncoding;
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_rpc_client_api::config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use solana_rpc_client_api::filter::{Memcmp, RpcFilterType};
+use solana_sdk::account::from_account;
+use solana_sdk::clock::Clock;
+use solana_sdk::compute_budget::ComputeBudgetInstruction;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signature, Signer};
+use solana_sdk::sysvar;
+use solana_sdk::transaction::{Transaction, TransactionError};
+use spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+use std::sync::Arc;
+use std::time::Duration;
+use verum_vesting_client::verum_vesting::{PriceTrigger, VestingContract};
+use verum_vesting_client::*;
+
+use crate::metrics::Metrics;
+use crate::plan::{plan_releases, still_pending, PlanConfig, Release};
+
+#[derive(Clone, Copy, Debug)]
+pub struct SubmitConfig {
+    /// Preco por unidade de computacao, em micro-lamports (0 desativa).
+    pub priority_fee_micro_lamports: u64,
+    /// Limite de CU reservado por resgate no lote.
+    pub compute_units_per_claim: u32,
+    pub max_retries: u32,
+    /// Espera antes da primeira nova tentativa; dobra a cada tentativa.
+    pub retry_backoff: Duration,
+}
+
+impl Default for SubmitConfig {
+    fn default() -> Self {
+        Self {
+            priority_fee_micro_lamports: 0,
+            compute_units_per_claim: 80_000,
+            max_retries: 3,
+            retry_backoff: Duration::from_secs(2),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct RunSummary {
+    pub scanned: usize,
+    pub planned: usize,
+    pub succeeded: usize,
+    pub failed: usize,
+    pub skipped: usize,
+    pub signatures: Vec<Signature>,
+}
+
+pub struct Keeper {
+    pub client: RpcClient,
+    /// Paga taxas e as contas de token criadas para os beneficiarios.
+    pub payer: Keypair,
+    pub plan: PlanConfig,
+    pub submit: SubmitConfig,
+    pub metrics: Arc<Metrics>,
+}
+
+/// Instrucoes de uma transacao e o resgate dono de cada uma (`None` para as de
+/// compute budget), para atribuir um `InstructionError` ao contrato certo.
+struct Batch {
+    instructions: Vec<Instruction>,
+    owners: Vec<Option<usize>>,
+}
+
+impl Keeper {
+    pub fn now(&self) -> Result<i64> {
+        let account = self.client.get_account(&sysvar::clock::ID)?;
+        let clock: Clock =
+            from_account(&account).ok_or_else(|| anyhow!("sysvar Clock invalido"))?;
+        Ok(clock.unix_timestamp)
+    }
+
+    pub fn scan(&self) -> Result<Vec<(Pubkey, VestingContract)>> {
+        let accounts = self.client.get_program_accounts_with_config(
+            &PROGRAM_ID,
+            RpcProgramAccountsConfig {
+                filters: Some(vec![RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+                    0,
+                    VestingContract::DISCRIMINATOR.to_vec(),
+                ))]),
+                account_config: RpcAccountInfoConfig {
+                    encoding: Some(UiAccountEncoding::Base64),
+                    ..RpcAccountInfoConfig::default()
+                },
+                ..RpcProgramAccountsConfig::default()
+            },
+        )?;
+
+        Ok(accounts
+            .into_iter()
+            .filter_map(|(address, account)| {
+                VestingContract::try_deserialize(&mut account.data.as_slice())
+                    .ok()
+                    .map(|state| (address, state))
+            })
+            .collect())
+    }
+
+    /// Uma rodada completa: varre, planeja e envia todos os lotes. Erros de um lote
+    /// nao interrompem os demais.
+    pub fn run_once(&self) -> Result<RunSummary> {
+        let scanned = self
+            .scan()
+            .inspect_err(|_| Metrics::inc(&self.metrics.scan_failures))?;
+        let now = self.now()?;
+        let releases = plan_releases(&scanned, now, &self.plan);
+
+        Metrics::inc(&self.metrics.scans);
+        Metrics::set(&self.metrics.contracts_scanned, scanned.len() as u64);
+        Metrics::set(&self.metrics.releases_planned, releases.len() as u64);
+        Metrics::set(&self.metrics.last_scan_timestamp, now.max(0) as u64);
+
+        let mut summary = RunSummary {
+            scanned: scanned.len(),
+            planned: releases.len(),
+            ..RunSummary::default()
+        };
+        for batch in releases.chunks(self.plan.max_claims_per_tx.max(1)) {
+            self.submit_batch(batch.to_vec(), &mut summary);
+        }
+        Ok(summary)
+    }
+
+    fn claim_instructions(&self, release: &Release) -> Result<Vec<Instruction>> {
+        let state = &release.state;
+        let token_program = token_program_for(state);
+        let beneficiary_token_account = get_associated_token_address_with_program_id(
+            &state.beneficiary,
+            &state.mint,
+            &token_program,
+        );
+
+        let mut args =
+            ClaimTokensArgs::for_contract(release.address, state, beneficiary_token_account);
+        if state.vesting_type == VestingType::PriceTriggered {
+            let data = self
+                .client
+                .get_account_data(&price_trigger_address(&release.address))?;
+            args.price_feed = Some(decode_account::<PriceTrigger>(&data)?.price_feed);
+        }
+
+        let mut instructions = Vec::with_capacity(2);
+        if !state.is_native {
+            instructions.push(create_associated_token_account_idempotent(
+                &self.payer.pubkey(),
+                &state.beneficiary,
+                &state.mint,
+                &token_program,
+            ));
+        }
+        instructions.push(claim_tokens(&args, &[]));
+        Ok(instructions)
+    }
+
+    /// Monta o lote; resgates cujas contas nao puderam ser resolvidas voltam em
+    /// `failed`.
+    fn build_batch(&self, releases: &[Release]) -> (Batch, Vec<usize>) {
+        let mut instructions = vec![ComputeBudgetInstruction::set_compute_unit_limit(
+            self.submit
+                .compute_units_per_claim
+                .saturating_mul(releases.len() as u32),
+        )];
+        if self.submit.priority_fee_micro_lamports > 0 {
+            instructions.push(ComputeBudgetInstruction::set_compute_unit_price(
+                self.submit.priority_fee_micro_lamports,
+            ));
+        }
+        let mut owners = vec![None; instructions.len()];
+        let mut failed = Vec::new();
+
+        for (index, release) in releases.iter().enumerate() {
+            match self.claim_instructions(release) {
+                Ok(claim) => {
+                    owners.extend(std::iter::repeat_n(Some(index), claim.len()));
+                    instructions.extend(claim);
+                }
+                Err(err) => {
+                    eprintln!("[keeper] {}: {err:#}", release.address);
+                    failed.push(index);
+                }
+            }
+        }
+        (
+            Batch {
+                instructions,
+                owners,
+            },
+            failed,
+        )
+    }
+
+    /// Descarta resgates ja feitos (por outro keeper, pelo beneficiario ou por uma
+    /// tentativa anterior que confirmou apos o timeout).
+    fn refresh(&self, releases: &[Release], summary: &mut RunSummary) -> Result<Vec<Release>> {
+        let addresses: Vec<Pubkey> = releases.iter().map(|release| release.address).collect();
+        let accounts = self.client.get_multiple_accounts(&addresses)?;
+        let now = self.now()?;
+
+        Ok(releases
+            .iter()
+            .zip(accounts)
+            .filter_map(|(release, account)| {
+                let current = account.and_then(|account| {
+                    VestingContract::try_deserialize(&mut account.data.as_slice()).ok()
+                });
+                match current {
+                    Some(current) if still_pending(release, &current, now, &self.plan) => {
+                        Some(release.clone())
+                    }
+                    _ => {
+                        Metrics::inc(&self.metrics.claims_skipped);
+                        summary.skipped += 1;
+                        None
+                    }
+                }
+            })
+            .collect())
+    }
+
+    fn landed(&self, signatures: &[Signature]) -> bool {
+        !signatures.is_empty()
+            && self
+                .client
+                .get_signature_statuses(signatures)
+                .map(|statuses| {
+                    statuses
+                        .value
+                        .iter()
+                        .flatten()
+                        .any(|status| status.err.is_none())
+                })
+                .unwrap_or(false)
+    }
+
+    fn submit_batch(&self, mut releases: Vec<Release>, summary: &mut RunSummary) {
+        let mut attempts: Vec<Signature> = Vec::new();
+
+        for attempt in 0..=self.submit.max_retries {
+            if attempt > 0 {
+                Metrics::inc(&self.metrics.retries);
+                std::thread::sleep(self.submit.retry_backoff * 2u32.pow(attempt - 1));
+
+                // Idempotencia: uma tentativa anterior pode ter confirmado depois do erro
+                if self.landed(&attempts) {
+                    self.record_success(releases.len(), summary);
+                    return;
+                }
+                releases = match self.refresh(&releases, summary) {
+                    Ok(pending) => pending,
+                    Err(err) => {
+                        eprintln!("[keeper] falha ao reler contratos: {err:#}");
+                        continue;
+                    }
+                };
+            }
+            if releases.is_empty() {
+                return;
+            }
+
+            let (batch, unresolved) = self.build_batch(&releases);
+            if !unresolved.is_empty() {
+                releases = self.drop_releases(releases, &unresolved, summary);
+                continue;
+            }
+
+            let transaction = match self.client.get_latest_blockhash() {
+                Ok(blockhash) => Transaction::new_signed_with_payer(
+                    &batch.instructions,
+                    Some(&self.payer.pubkey()),
+                    &[&self.payer],
+                    blockhash,
+                ),
+                Err(err) => {
+                    eprintln!("[keeper] falha ao obter blockhash: {err:#}");
+                    continue;
+                }
+            };
+            attempts.push(transaction.signatures[0]);
+            Metrics::inc(&self.metrics.transactions_sent);
+
+            match self.client.send_and_confirm_transaction(&transaction) {
+                Ok(signature) => {
+                    summary.signatures.push(signature);
+                    self.record_success(releases.len(), summary);
+                    return;
+                }
+                Err(err) => {
+                    Metrics::inc(&self.metrics.transactions_failed);
+                    eprintln!("[keeper] lote de {} resgates falhou: {err}", releases.len());
+
+                    // Erro deterministico de uma instrucao: retira o contrato do lote
+                    if let Some(TransactionError::InstructionError(index, _)) =
+                        err.get_transaction_error()
+                    {
+                        if let Some(Some(owner)) = batch.owners.get(index as usize) {
+                            releases = self.drop_releases(releases, &[*owner], summary);
+                        }
+                    }
+                }
+            }
+        }
+
+        Metrics::add(&self.metrics.claims_failed, releases.len() as u64);
+        summary.failed += releases.len();
+    }
+
+    fn drop_releases(
+        &self,
+        releases: Vec<Release>,
+        indexes: &[usize],
+        summary: &mut RunSummary,
+    ) -> Vec<Release> {
+        Metrics::add(&self.metrics.claims_failed, indexes.len() as u64);
+        summary.failed += indexes.len();
+        releases
+            .into_iter()
+            .enumerate()
+            .filter(|(index, release)| {
+                let dropped = indexes.contains(index);
+                if dropped {
+                    eprintln!("[keeper] {} removido do lote", release.address);
+                }
+                !dropped
+            })
+            .map(|(_, release)| release)
+            .collect()
+    }
+
+    fn record_success(&self, claims: usize, summary: &mut RunSummary) {
+        Metrics::add(&self.metrics.claims_succeeded, claims as u64);
+        summary.succeeded += claims;
+    }
+}
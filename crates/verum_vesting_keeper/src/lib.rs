@@ -0,0 +1,8 @@
+//! Keeper de liberacoes: varre os `VestingContract`, calcula o valor resgatavel com a
+//! mesma matematica do programa e envia `claim_tokens` em lote.
+//!
+//! Substitui o cron `/api/cron/release`, cujo IDL embutido ficou defasado.
+
+pub mod keeper;
+pub mod metrics;
+pub mod plan;
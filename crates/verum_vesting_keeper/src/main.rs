@@ -0,0 +1,139 @@
+//! `verum-vesting-keeper`: roda `Keeper::run_once` em intervalo fixo.
+
+use anyhow::{anyhow, Context, Result};
+use clap::{Arg, ArgMatches, Command};
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::signature::{read_keypair_file, Signer};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+use verum_vesting_keeper::keeper::{Keeper, SubmitConfig};
+use verum_vesting_keeper::metrics::Metrics;
+use verum_vesting_keeper::plan::PlanConfig;
+
+fn main() -> Result<()> {
+    let matches = Command::new("verum-vesting-keeper")
+        .about("Executa os resgates liberados dos contratos do verum_vesting")
+        .arg(
+            Arg::new("url")
+                .long("url")
+                .short('u')
+                .takes_value(true)
+                .default_value("http://127.0.0.1:8899"),
+        )
+        .arg(
+            Arg::new("keypair")
+                .long("keypair")
+                .short('k')
+                .takes_value(true)
+                .required(true)
+                .help("Keypair que paga as transacoes"),
+        )
+        .arg(
+            Arg::new("interval")
+                .long("interval")
+                .takes_value(true)
+                .default_value("60")
+                .help("Segundos entre varreduras"),
+        )
+        .arg(Arg::new("once").long("once").help("Uma unica varredura"))
+        .arg(
+            Arg::new("batch-size")
+                .long("batch-size")
+                .takes_value(true)
+                .default_value("4")
+                .help("Resgates por transacao"),
+        )
+        .arg(
+            Arg::new("min-claimable")
+                .long("min-claimable")
+                .takes_value(true)
+                .default_value("1")
+                .help("Valor minimo, em unidades base, para resgatar"),
+        )
+        .arg(
+            Arg::new("priority-fee")
+                .long("priority-fee")
+                .takes_value(true)
+                .default_value("0")
+                .help("Preco por CU em micro-lamports"),
+        )
+        .arg(
+            Arg::new("compute-units-per-claim")
+                .long("compute-units-per-claim")
+                .takes_value(true)
+                .default_value("80000"),
+        )
+        .arg(
+            Arg::new("max-retries")
+                .long("max-retries")
+                .takes_value(true)
+                .default_value("3"),
+        )
+        .arg(
+            Arg::new("metrics-addr")
+                .long("metrics-addr")
+                .takes_value(true)
+                .help("Endereco do endpoint de metricas, ex.: 0.0.0.0:9464"),
+        )
+        .get_matches();
+
+    let keypair_path = matches.value_of("keypair").unwrap();
+    let keeper = Keeper {
+        client: RpcClient::new_with_commitment(
+            matches.value_of("url").unwrap().to_string(),
+            CommitmentConfig::confirmed(),
+        ),
+        payer: read_keypair_file(keypair_path)
+            .map_err(|err| anyhow!("falha ao ler a keypair {keypair_path}: {err}"))?,
+        plan: PlanConfig {
+            min_claimable: parse(&matches, "min-claimable")?,
+            max_claims_per_tx: parse(&matches, "batch-size")?,
+        },
+        submit: SubmitConfig {
+            priority_fee_micro_lamports: parse(&matches, "priority-fee")?,
+            compute_units_per_claim: parse(&matches, "compute-units-per-claim")?,
+            max_retries: parse(&matches, "max-retries")?,
+            ..SubmitConfig::default()
+        },
+        metrics: Arc::new(Metrics::default()),
+    };
+
+    if let Some(addr) = matches.value_of("metrics-addr") {
+        let addr: SocketAddr = addr.parse().context("metrics-addr invalido")?;
+        keeper.metrics.clone().serve(addr)?;
+        println!("[keeper] metricas em http://{addr}/metrics");
+    }
+
+    let interval = Duration::from_secs(parse(&matches, "interval")?);
+    println!("[keeper] pagador {}", keeper.payer.pubkey());
+    loop {
+        match keeper.run_once() {
+            Ok(summary) => println!(
+                "[keeper] {} contratos, {} planejados, {} resgatados, {} ignorados, {} falhas",
+                summary.scanned,
+                summary.planned,
+                summary.succeeded,
+                summary.skipped,
+                summary.failed
+            ),
+            Err(err) => eprintln!("[keeper] varredura falhou: {err:#}"),
+        }
+        if matches.is_present("once") {
+            return Ok(());
+        }
+        std::thread::sleep(interval);
+    }
+}
+
+fn parse<T: std::str::FromStr>(matches: &ArgMatches, name: &str) -> Result<T>
+where
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    matches
+        .value_of(name)
+        .unwrap()
+        .parse()
+        .with_context(|| format!("{name} invalido"))
+}
@@ -0,0 +1,135 @@
+//! Metricas no formato texto do Prometheus, servidas por um HTTP minimo.
+
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpListener};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+
+#[derive(Default)]
+pub struct Metrics {
+    pub scans: AtomicU64,
+    pub scan_failures: AtomicU64,
+    pub contracts_scanned: AtomicU64,
+    pub releases_planned: AtomicU64,
+    pub claims_succeeded: AtomicU64,
+    pub claims_failed: AtomicU64,
+    /// Resgates descartados antes do envio porque outro agente ja resgatou.
+    pub claims_skipped: AtomicU64,
+    pub transactions_sent: AtomicU64,
+    pub transactions_failed: AtomicU64,
+    pub retries: AtomicU64,
+    pub last_scan_timestamp: AtomicU64,
+}
+
+impl Metrics {
+    pub fn inc(counter: &AtomicU64) {
+        Self::add(counter, 1);
+    }
+
+    pub fn add(counter: &AtomicU64, value: u64) {
+        counter.fetch_add(value, Ordering::Relaxed);
+    }
+
+    pub fn set(gauge: &AtomicU64, value: u64) {
+        gauge.store(value, Ordering::Relaxed);
+    }
+
+    pub fn render(&self) -> String {
+        let series: [(&str, &str, &str, &AtomicU64); 11] = [
+            (
+                "verum_keeper_scans_total",
+                "counter",
+                "Varreduras concluidas",
+                &self.scans,
+            ),
+            (
+                "verum_keeper_scan_failures_total",
+                "counter",
+                "Varreduras com erro",
+                &self.scan_failures,
+            ),
+            (
+                "verum_keeper_contracts_scanned",
+                "gauge",
+                "Contratos na ultima varredura",
+                &self.contracts_scanned,
+            ),
+            (
+                "verum_keeper_releases_planned",
+                "gauge",
+                "Resgates planejados na ultima varredura",
+                &self.releases_planned,
+            ),
+            (
+                "verum_keeper_claims_succeeded_total",
+                "counter",
+                "Resgates confirmados",
+                &self.claims_succeeded,
+            ),
+            (
+                "verum_keeper_claims_failed_total",
+                "counter",
+                "Resgates abandonados apos erro",
+                &self.claims_failed,
+            ),
+            (
+                "verum_keeper_claims_skipped_total",
+                "counter",
+                "Resgates ja feitos por outro agente",
+                &self.claims_skipped,
+            ),
+            (
+                "verum_keeper_transactions_sent_total",
+                "counter",
+                "Transacoes enviadas",
+                &self.transactions_sent,
+            ),
+            (
+                "verum_keeper_transactions_failed_total",
+                "counter",
+                "Transacoes com erro",
+                &self.transactions_failed,
+            ),
+            (
+                "verum_keeper_retries_total",
+                "counter",
+                "Novas tentativas de lotes",
+                &self.retries,
+            ),
+            (
+                "verum_keeper_last_scan_timestamp_seconds",
+                "gauge",
+                "Unix timestamp da ultima varredura",
+                &self.last_scan_timestamp,
+            ),
+        ];
+
+        let mut output = String::new();
+        for (name, kind, help, value) in series {
+            output.push_str(&format!(
+                "# HELP {name} {help}\n# TYPE {name} {kind}\n{name} {}\n",
+                value.load(Ordering::Relaxed)
+            ));
+        }
+        output
+    }
+
+    /// Responde qualquer requisicao com `render()`; roda ate o fim do processo.
+    pub fn serve(self: Arc<Self>, addr: SocketAddr) -> std::io::Result<JoinHandle<()>> {
+        let listener = TcpListener::bind(addr)?;
+        Ok(thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else { continue };
+                let mut request = [0u8; 1024];
+                let _ = stream.read(&mut request);
+                let body = self.render();
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+            }
+        }))
+    }
+}
@@ -0,0 +1,70 @@
+//! Selecao dos contratos a resgatar.
+
+use anchor_lang::prelude::Pubkey;
+use verum_vesting_client::releasable_amount;
+use verum_vesting_client::verum_vesting::VestingContract;
+
+#[derive(Clone, Copy, Debug)]
+pub struct PlanConfig {
+    /// Valor minimo (unidades base) para justificar uma transacao.
+    pub min_claimable: u64,
+    pub max_claims_per_tx: usize,
+}
+
+impl Default for PlanConfig {
+    fn default() -> Self {
+        Self {
+            min_claimable: 1,
+            max_claims_per_tx: 4,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Release {
+    pub address: Pubkey,
+    pub state: VestingContract,
+    pub claimable: u64,
+}
+
+/// Contratos com resgate pendente em `now`, na ordem dos enderecos para que
+/// execucoes repetidas montem os mesmos lotes.
+///
+/// Contratos pausados ficam de fora: o valor liberado esta congelado e o
+/// programa recusa o resgate ate a retomada.
+pub fn plan_releases(
+    contracts: &[(Pubkey, VestingContract)],
+    now: i64,
+    config: &PlanConfig,
+) -> Vec<Release> {
+    let mut releases: Vec<Release> = contracts
+        .iter()
+        .filter(|(_, state)| !state.is_cancelled && !state.is_paused())
+        .filter_map(|(address, state)| {
+            let claimable = releasable_amount(state, now);
+            (claimable > 0 && claimable >= config.min_claimable).then(|| Release {
+                address: *address,
+                state: state.clone(),
+                claimable,
+            })
+        })
+        .collect();
+    releases.sort_by_key(|release| release.address);
+    releases
+}
+
+/// Um resgate planejado continua valido se ninguem resgatou desde a varredura
+/// (`claim_count` inalterado) e ainda ha valor acima do minimo.
+pub fn still_pending(
+    release: &Release,
+    current: &VestingContract,
+    now: i64,
+    config: &PlanConfig,
+) -> bool {
+    let claimable = releasable_amount(current, now);
+    current.claim_count == release.state.claim_count
+        && !current.is_cancelled
+        && !current.is_paused()
+        && claimable > 0
+        && claimable >= config.min_claimable
+}
@@ -0,0 +1,107 @@
+use anchor_lang::prelude::Pubkey;
+use verum_vesting_client::verum_vesting::VestingContract;
+use verum_vesting_client::VestingType;
+use verum_vesting_keeper::metrics::Metrics;
+use verum_vesting_keeper::plan::{plan_releases, still_pending, PlanConfig};
+
+fn linear_contract(total_amount: u64, released_amount: u64) -> VestingContract {
+    VestingContract {
+        creator: Pubkey::new_unique(),
+        beneficiary: Pubkey::new_unique(),
+        mint: Pubkey::new_unique(),
+        total_amount,
+        released_amount,
+        start_time: 1_000,
+        end_time: 2_000,
+        contract_id: 1,
+        vesting_type: VestingType::Linear,
+        bump: 255,
+        is_cancelled: false,
+        is_token_2022: false,
+        mint_flags: 0,
+        is_native: false,
+        pause_policy: 0,
+        paused_at: 0,
+        paused_duration: 0,
+        pause_count: 0,
+        unlocked_amount: 0,
+        staking_program: Pubkey::default(),
+        staked_amount: 0,
+        compliance_authority: Pubkey::default(),
+        clawed_back_amount: 0,
+        timelock_delay: 0,
+        guardian: Pubkey::default(),
+        has_audit_log: false,
+        claim_count: 0,
+        beneficiary_updated_slot: 0,
+    }
+}
+
+#[test]
+fn plans_only_pending_releases() {
+    let mut cancelled = linear_contract(1_000, 0);
+    cancelled.is_cancelled = true;
+    let mut paused = linear_contract(1_000, 0);
+    paused.paused_at = 1_200;
+    let mut milestones = linear_contract(1_000, 100);
+    milestones.vesting_type = VestingType::Milestones;
+    milestones.unlocked_amount = 400;
+
+    let contracts = vec![
+        (Pubkey::new_unique(), linear_contract(1_000, 0)),
+        (Pubkey::new_unique(), linear_contract(1_000, 500)),
+        (Pubkey::new_unique(), linear_contract(1_000, 495)),
+        (Pubkey::new_unique(), cancelled),
+        (Pubkey::new_unique(), paused),
+        (Pubkey::new_unique(), milestones),
+    ];
+    let config = PlanConfig {
+        min_claimable: 10,
+        max_claims_per_tx: 4,
+    };
+
+    let releases = plan_releases(&contracts, 1_500, &config);
+    let mut expected = vec![(contracts[0].0, 500), (contracts[5].0, 300)];
+    expected.sort();
+    let planned: Vec<(Pubkey, u64)> = releases
+        .iter()
+        .map(|release| (release.address, release.claimable))
+        .collect();
+    assert_eq!(planned, expected);
+
+    // Ninguem resgatou ainda: continua pendente, inclusive mais tarde
+    let release = releases
+        .iter()
+        .find(|release| release.address == contracts[0].0)
+        .unwrap();
+    assert!(still_pending(release, &contracts[0].1, 1_800, &config));
+
+    // Resgatado por outro agente entre a varredura e o envio
+    let mut claimed = contracts[0].1.clone();
+    claimed.claim_count += 1;
+    claimed.released_amount = 500;
+    assert!(!still_pending(release, &claimed, 1_500, &config));
+
+    let mut cancelled = contracts[0].1.clone();
+    cancelled.is_cancelled = true;
+    assert!(!still_pending(release, &cancelled, 1_500, &config));
+}
+
+#[test]
+fn renders_prometheus_metrics() {
+    let metrics = Metrics::default();
+    Metrics::inc(&metrics.scans);
+    Metrics::add(&metrics.claims_succeeded, 3);
+    Metrics::set(&metrics.contracts_scanned, 12);
+    Metrics::set(&metrics.contracts_scanned, 7);
+
+    let output = metrics.render();
+    assert!(
+        output.contains("# TYPE verum_keeper_scans_total counter\nverum_keeper_scans_total 1\n")
+    );
+    assert!(output.contains("verum_keeper_claims_succeeded_total 3\n"));
+    assert!(output.contains(
+        "# TYPE verum_keeper_contracts_scanned gauge\nverum_keeper_contracts_scanned 7\n"
+    ));
+    assert!(output.contains("verum_keeper_retries_total 0\n"));
+}
@@ -0,0 +1,156 @@
+//! Matematica dos cronogramas de vesting.
+//!
+//! Fonte unica do calculo usado pelo programa principal, pelo programa legado
+//! (`programs/verum_vesting`), pelos clientes Rust e, via `verum_vesting_math_wasm`,
+//! pelo frontend. Sem dependencias e `no_std`; a aritmetica e saturada em `u128`
+//! para que nenhum cronograma provoque overflow ou panic.
+
+#![no_std]
+
+/// Forma do cronograma, independente da representacao on-chain.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Schedule {
+    /// Liberacao linear de `start` a `end`.
+    Linear,
+    /// `percentage`% de uma vez em `cliff_time`, restante linear ate `end`.
+    Cliff { cliff_time: i64, percentage: u64 },
+    /// Liberacao por evento (marcos, gatilhos de preco): o tempo nao libera nada.
+    EventDriven,
+}
+
+/// `total * elapsed / duration`, limitado a `total`.
+fn pro_rata(total: u64, elapsed: i64, duration: i64) -> u64 {
+    if duration <= 0 || elapsed >= duration {
+        return total;
+    }
+    let elapsed = elapsed.max(0) as u128;
+    ((total as u128).saturating_mul(elapsed) / duration as u128) as u64
+}
+
+/// Valor liberado em `current_time` pelo cronograma do programa principal.
+pub fn vested_amount(
+    current_time: i64,
+    start: i64,
+    end: i64,
+    total: u64,
+    schedule: Schedule,
+) -> u64 {
+    if current_time < start || schedule == Schedule::EventDriven {
+        return 0;
+    }
+    if current_time >= end {
+        return total;
+    }
+
+    match schedule {
+        Schedule::Linear => pro_rata(
+            total,
+            current_time.saturating_sub(start),
+            end.saturating_sub(start),
+        ),
+        Schedule::Cliff {
+            cliff_time,
+            percentage,
+        } => {
+            if current_time < cliff_time {
+                return 0;
+            }
+            let cliff_amount = cliff_amount(total, percentage);
+            let remaining = total.saturating_sub(cliff_amount);
+            cliff_amount.saturating_add(pro_rata(
+                remaining,
+                current_time.saturating_sub(cliff_time),
+                end.saturating_sub(cliff_time),
+            ))
+        }
+        Schedule::EventDriven => 0,
+    }
+}
+
+/// Parcela liberada de uma vez no cliff (`percentage` acima de 100 libera tudo).
+pub fn cliff_amount(total: u64, percentage: u64) -> u64 {
+    ((total as u128).saturating_mul(percentage.min(100) as u128) / 100) as u64
+}
+
+/// Valor liberado pelo cronograma do programa legado: nada antes do cliff e linear
+/// desde `start` ate `start + duration`. Contratos revogados contam como totalmente
+/// liberados.
+pub fn legacy_vested_amount(
+    current_time: i64,
+    start: i64,
+    cliff: i64,
+    duration: u64,
+    total: u64,
+    revoked: bool,
+) -> u64 {
+    let duration = duration.min(i64::MAX as u64) as i64;
+    if revoked || current_time >= start.saturating_add(duration) {
+        return total;
+    }
+    if current_time < cliff {
+        return 0;
+    }
+    pro_rata(total, current_time.saturating_sub(start), duration)
+}
+
+/// Ponto de inflexao do cronograma.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UnlockKind {
+    Start,
+    Cliff,
+    End,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UnlockEvent {
+    pub kind: UnlockKind,
+    pub time: i64,
+    /// Valor acumulado liberado em `time`, ja incluindo o degrau do cliff.
+    pub vested: u64,
+}
+
+/// Calendario completo de liberacoes. Entre eventos consecutivos o valor cresce
+/// linearmente, exceto de `Start` ate `Cliff`, intervalo em que nada e liberado.
+/// Cronogramas por evento nao tem calendario.
+pub fn unlock_events(
+    start: i64,
+    end: i64,
+    total: u64,
+    schedule: Schedule,
+) -> impl Iterator<Item = UnlockEvent> {
+    let event = |kind, time| UnlockEvent {
+        kind,
+        time,
+        vested: vested_amount(time, start, end, total, schedule),
+    };
+    let timed = schedule != Schedule::EventDriven;
+    let first = (timed && start < end).then(|| event(UnlockKind::Start, start));
+    let cliff = match schedule {
+        Schedule::Cliff { cliff_time, .. } if start < cliff_time && cliff_time < end => {
+            Some(event(UnlockKind::Cliff, cliff_time))
+        }
+        _ => None,
+    };
+    let last = timed.then(|| event(UnlockKind::End, end));
+    [first, cliff, last].into_iter().flatten()
+}
+
+/// `points` instantes igualmente espacados entre `start` e `end`, inclusive.
+pub fn sample_times(start: i64, end: i64, points: usize) -> impl Iterator<Item = i64> {
+    let span = end.saturating_sub(start).max(0) as i128;
+    let intervals = points.saturating_sub(1).max(1) as i128;
+    (0..points).map(move |index| start.saturating_add((span * index as i128 / intervals) as i64))
+}
+
+/// `points` amostras `(instante, valor liberado)` igualmente espacadas entre `start`
+/// e `end`, inclusive. Para graficos; os degraus de cliff podem cair entre amostras.
+pub fn sample_curve(
+    start: i64,
+    end: i64,
+    total: u64,
+    schedule: Schedule,
+    points: usize,
+) -> impl Iterator<Item = (i64, u64)> {
+    sample_times(start, end, points)
+        .map(move |time| (time, vested_amount(time, start, end, total, schedule)))
+}
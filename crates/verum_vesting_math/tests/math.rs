@@ -0,0 +1,139 @@
+use verum_vesting_math::{
+    cliff_amount, legacy_vested_amount, sample_curve, unlock_events, vested_amount, Schedule,
+    UnlockEvent, UnlockKind,
+};
+
+#[test]
+fn linear_and_cliff_schedules() {
+    assert_eq!(vested_amount(999, 1_000, 2_000, 1_000, Schedule::Linear), 0);
+    assert_eq!(
+        vested_amount(1_000, 1_000, 2_000, 1_000, Schedule::Linear),
+        0
+    );
+    assert_eq!(
+        vested_amount(1_250, 1_000, 2_000, 1_000, Schedule::Linear),
+        250
+    );
+    assert_eq!(
+        vested_amount(2_000, 1_000, 2_000, 1_000, Schedule::Linear),
+        1_000
+    );
+    assert_eq!(
+        vested_amount(9_999, 1_000, 2_000, 1_000, Schedule::Linear),
+        1_000
+    );
+
+    let cliff = Schedule::Cliff {
+        cliff_time: 1_500,
+        percentage: 20,
+    };
+    assert_eq!(vested_amount(1_499, 1_000, 2_000, 1_000, cliff), 0);
+    assert_eq!(vested_amount(1_500, 1_000, 2_000, 1_000, cliff), 200);
+    assert_eq!(vested_amount(1_750, 1_000, 2_000, 1_000, cliff), 600);
+    assert_eq!(vested_amount(2_000, 1_000, 2_000, 1_000, cliff), 1_000);
+
+    // Cliff no fim libera tudo de uma vez; percentuais acima de 100 nao passam do total
+    let at_end = Schedule::Cliff {
+        cliff_time: 2_000,
+        percentage: 0,
+    };
+    assert_eq!(vested_amount(1_999, 1_000, 2_000, 1_000, at_end), 0);
+    let oversized = Schedule::Cliff {
+        cliff_time: 1_000,
+        percentage: 250,
+    };
+    assert_eq!(vested_amount(1_000, 1_000, 2_000, 1_000, oversized), 1_000);
+    assert_eq!(cliff_amount(1_000, 250), 1_000);
+
+    assert_eq!(
+        vested_amount(1_500, 1_000, 2_000, 1_000, Schedule::EventDriven),
+        0
+    );
+    assert_eq!(
+        vested_amount(5_000, 1_000, 2_000, 1_000, Schedule::EventDriven),
+        0
+    );
+}
+
+#[test]
+fn legacy_schedule_does_not_overflow() {
+    assert_eq!(legacy_vested_amount(50, 0, 10, 100, 1_000, false), 500);
+    assert_eq!(legacy_vested_amount(5, 0, 10, 100, 1_000, false), 0);
+    assert_eq!(legacy_vested_amount(5, 0, 10, 100, 1_000, true), 1_000);
+    assert_eq!(legacy_vested_amount(100, 0, 10, 100, 1_000, false), 1_000);
+
+    // `total * elapsed` estourava u64 na versao original
+    assert_eq!(
+        legacy_vested_amount(500, 0, 0, 1_000, u64::MAX, false),
+        u64::MAX / 2
+    );
+    assert_eq!(legacy_vested_amount(0, 0, 0, 0, 7, false), 7);
+}
+
+#[test]
+fn samples_curve_between_start_and_end() {
+    let points: Vec<(i64, u64)> = sample_curve(1_000, 2_000, 1_000, Schedule::Linear, 5).collect();
+    assert_eq!(
+        points,
+        vec![
+            (1_000, 0),
+            (1_250, 250),
+            (1_500, 500),
+            (1_750, 750),
+            (2_000, 1_000)
+        ]
+    );
+
+    assert_eq!(
+        sample_curve(1_000, 2_000, 1_000, Schedule::Linear, 1).collect::<Vec<_>>(),
+        vec![(1_000, 0)]
+    );
+    assert_eq!(
+        sample_curve(1_000, 2_000, 1_000, Schedule::Linear, 0).count(),
+        0
+    );
+}
+
+#[test]
+fn lists_unlock_events() {
+    let event = |kind, time, vested| UnlockEvent { kind, time, vested };
+
+    assert_eq!(
+        unlock_events(1_000, 2_000, 1_000, Schedule::Linear).collect::<Vec<_>>(),
+        vec![
+            event(UnlockKind::Start, 1_000, 0),
+            event(UnlockKind::End, 2_000, 1_000),
+        ]
+    );
+
+    let cliff = Schedule::Cliff {
+        cliff_time: 1_200,
+        percentage: 10,
+    };
+    assert_eq!(
+        unlock_events(1_000, 2_000, 1_000, cliff).collect::<Vec<_>>(),
+        vec![
+            event(UnlockKind::Start, 1_000, 0),
+            event(UnlockKind::Cliff, 1_200, 100),
+            event(UnlockKind::End, 2_000, 1_000),
+        ]
+    );
+
+    // Cliff no inicio: o degrau ja aparece no evento de inicio
+    let immediate = Schedule::Cliff {
+        cliff_time: 1_000,
+        percentage: 10,
+    };
+    assert_eq!(
+        unlock_events(1_000, 2_000, 1_000, immediate).collect::<Vec<_>>(),
+        vec![
+            event(UnlockKind::Start, 1_000, 100),
+            event(UnlockKind::End, 2_000, 1_000),
+        ]
+    );
+
+    assert_eq!(
+        unlock_events(1_000, 2_000, 1_000, Schedule::EventDriven).count(),
+        0
+    );
+}
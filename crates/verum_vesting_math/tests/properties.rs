@@ -0,0 +1,157 @@
+//! Propriedades dos cronogramas sobre entradas pseudoaleatorias (semente fixa, para
+//! que uma falha seja reproduzivel), incluindo valores extremos de tempo e total.
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use verum_vesting_math::{legacy_vested_amount, vested_amount, Schedule};
+
+const CASES: usize = 2_000;
+const SAMPLES: usize = 64;
+
+fn any_time(rng: &mut StdRng) -> i64 {
+    match rng.gen_range(0..8) {
+        0 => i64::MIN + rng.gen_range(0..1_000),
+        1 => i64::MAX - rng.gen_range(0..1_000),
+        2 => rng.gen(),
+        _ => rng.gen_range(-10_000..10_000),
+    }
+}
+
+fn any_total(rng: &mut StdRng) -> u64 {
+    match rng.gen_range(0..6) {
+        0 => 0,
+        1 => u64::MAX - rng.gen_range(0..1_000),
+        2 => rng.gen(),
+        _ => rng.gen_range(1..1_000_000_000),
+    }
+}
+
+fn any_schedule(rng: &mut StdRng) -> Schedule {
+    match rng.gen_range(0..3) {
+        0 => Schedule::Linear,
+        1 => Schedule::Cliff {
+            cliff_time: any_time(rng),
+            percentage: match rng.gen_range(0..4) {
+                0 => rng.gen(),
+                _ => rng.gen_range(0..=150),
+            },
+        },
+        _ => Schedule::EventDriven,
+    }
+}
+
+/// Instantes ordenados que incluem os pontos de inflexao e seus vizinhos.
+fn sorted_times(rng: &mut StdRng, anchors: &[i64]) -> Vec<i64> {
+    let mut times: Vec<i64> = (0..SAMPLES).map(|_| any_time(rng)).collect();
+    for &anchor in anchors {
+        times.extend([anchor.saturating_sub(1), anchor, anchor.saturating_add(1)]);
+    }
+    times.extend([i64::MIN, i64::MAX]);
+    times.sort_unstable();
+    times
+}
+
+#[test]
+fn vested_amount_is_bounded_and_monotonic() {
+    let mut rng = StdRng::seed_from_u64(0x7665_7374);
+    for _ in 0..CASES {
+        let start = any_time(&mut rng);
+        let end = any_time(&mut rng);
+        let total = any_total(&mut rng);
+        let schedule = any_schedule(&mut rng);
+        let mut anchors = vec![start, end];
+        if let Schedule::Cliff { cliff_time, .. } = schedule {
+            anchors.push(cliff_time);
+        }
+
+        let mut previous = 0;
+        for time in sorted_times(&mut rng, &anchors) {
+            let vested = vested_amount(time, start, end, total, schedule);
+            assert!(
+                vested <= total,
+                "{vested} > {total} em {time} ({start}..{end}, {schedule:?})"
+            );
+            assert!(
+                vested >= previous,
+                "valor caiu de {previous} para {vested} em {time} ({start}..{end}, {schedule:?})"
+            );
+            previous = vested;
+        }
+    }
+}
+
+#[test]
+fn vested_amount_end_states() {
+    let mut rng = StdRng::seed_from_u64(0x656e_6473);
+    for _ in 0..CASES {
+        let start = any_time(&mut rng);
+        let end = any_time(&mut rng);
+        let total = any_total(&mut rng);
+        let schedule = any_schedule(&mut rng);
+        let time = any_time(&mut rng);
+        let vested = vested_amount(time, start, end, total, schedule);
+
+        if schedule == Schedule::EventDriven {
+            assert_eq!(vested, 0);
+        } else if time < start {
+            assert_eq!(vested, 0, "antes do inicio ({start}..{end}, {schedule:?})");
+        } else if time >= end {
+            assert_eq!(vested, total, "apos o fim ({start}..{end}, {schedule:?})");
+        }
+
+        // Antes do cliff nada e liberado; no cliff (dentro do intervalo) o degrau inteiro
+        if let Schedule::Cliff {
+            cliff_time,
+            percentage,
+        } = schedule
+        {
+            if start <= cliff_time && cliff_time < end {
+                let step = verum_vesting_math::cliff_amount(total, percentage);
+                assert!(vested_amount(cliff_time, start, end, total, schedule) >= step);
+                if cliff_time > start {
+                    assert_eq!(
+                        vested_amount(cliff_time - 1, start, end, total, schedule),
+                        0
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn legacy_vested_amount_is_bounded_and_monotonic() {
+    let mut rng = StdRng::seed_from_u64(0x6c65_6761);
+    for _ in 0..CASES {
+        let start = any_time(&mut rng);
+        let cliff = any_time(&mut rng);
+        let duration = match rng.gen_range(0..4) {
+            0 => rng.gen(),
+            1 => 0,
+            _ => rng.gen_range(1..100_000),
+        };
+        let total = any_total(&mut rng);
+        let end = start.saturating_add(duration.min(i64::MAX as u64) as i64);
+
+        let mut previous = 0;
+        for time in sorted_times(&mut rng, &[start, cliff, end]) {
+            let vested = legacy_vested_amount(time, start, cliff, duration, total, false);
+            assert!(vested <= total);
+            assert!(
+                vested >= previous,
+                "valor caiu de {previous} para {vested} em {time} (inicio {start}, cliff {cliff}, duracao {duration})"
+            );
+            if time < cliff && time < end {
+                assert_eq!(vested, 0);
+            }
+            if time >= end {
+                assert_eq!(vested, total);
+            }
+            assert_eq!(
+                legacy_vested_amount(time, start, cliff, duration, total, true),
+                total
+            );
+            previous = vested;
+        }
+    }
+}
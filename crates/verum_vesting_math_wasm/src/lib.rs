@@ -0,0 +1,92 @@
+//! Bindings de `verum_vesting_math` para o frontend; gerar com `npm run build:wasm`
+//! (alvo `web`, carregado por `loadVestingMath` em src/utils/date-utils.ts).
+//! Os tipos de cronograma usam os mesmos nomes do programa: `linear`, `cliff`,
+//! `milestones` e `price_triggered`.
+
+use verum_vesting_math::Schedule;
+use wasm_bindgen::prelude::*;
+
+fn schedule(kind: &str, cliff_time: i64, cliff_percentage: u64) -> Result<Schedule, JsError> {
+    match kind {
+        "linear" => Ok(Schedule::Linear),
+        "cliff" => Ok(Schedule::Cliff {
+            cliff_time,
+            percentage: cliff_percentage,
+        }),
+        "milestones" | "price_triggered" => Ok(Schedule::EventDriven),
+        other => Err(JsError::new(&format!(
+            "tipo de vesting desconhecido: {other}"
+        ))),
+    }
+}
+
+#[wasm_bindgen(js_name = vestedAmount)]
+pub fn vested_amount(
+    current_time: i64,
+    start: i64,
+    end: i64,
+    total: u64,
+    kind: &str,
+    cliff_time: i64,
+    cliff_percentage: u64,
+) -> Result<u64, JsError> {
+    Ok(verum_vesting_math::vested_amount(
+        current_time,
+        start,
+        end,
+        total,
+        schedule(kind, cliff_time, cliff_percentage)?,
+    ))
+}
+
+#[wasm_bindgen(js_name = legacyVestedAmount)]
+pub fn legacy_vested_amount(
+    current_time: i64,
+    start: i64,
+    cliff: i64,
+    duration: u64,
+    total: u64,
+    revoked: bool,
+) -> u64 {
+    verum_vesting_math::legacy_vested_amount(current_time, start, cliff, duration, total, revoked)
+}
+
+#[wasm_bindgen]
+pub struct VestingCurve {
+    times: Vec<i64>,
+    amounts: Vec<u64>,
+}
+
+#[wasm_bindgen]
+impl VestingCurve {
+    #[wasm_bindgen(getter)]
+    pub fn times(&self) -> Vec<i64> {
+        self.times.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn amounts(&self) -> Vec<u64> {
+        self.amounts.clone()
+    }
+}
+
+#[wasm_bindgen(js_name = vestingCurve)]
+pub fn vesting_curve(
+    start: i64,
+    end: i64,
+    total: u64,
+    kind: &str,
+    cliff_time: i64,
+    cliff_percentage: u64,
+    points: usize,
+) -> Result<VestingCurve, JsError> {
+    let (times, amounts) = verum_vesting_math::sample_curve(
+        start,
+        end,
+        total,
+        schedule(kind, cliff_time, cliff_percentage)?,
+        points,
+    )
+    .unzip();
+    Ok(VestingCurve { times, amounts })
+}
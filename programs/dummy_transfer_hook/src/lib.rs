@@ -0,0 +1,183 @@
+//! Transfer hook minimo usado pelos testes de integracao do verum_vesting.
+//!
+//! Exige uma conta extra (PDA `[b"counter", mint]`) declarada na
+//! `ExtraAccountMetaList` do mint e incrementa esse contador a cada
+//! transferencia, permitindo verificar que o hook foi de fato executado.
+
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program::invoke_signed,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_instruction,
+    sysvar::Sysvar,
+};
+use spl_tlv_account_resolution::{account::ExtraAccountMeta, state::ExtraAccountMetaList};
+use spl_transfer_hook_interface::{
+    collect_extra_account_metas_signer_seeds, get_extra_account_metas_address_and_bump_seed,
+    instruction::{ExecuteInstruction, TransferHookInstruction},
+};
+
+solana_program::declare_id!("ADcbb8JXke2XYd9i1AgwN4qkfejAE1kGbzZp1yXd8QbH");
+
+#[cfg(not(feature = "no-entrypoint"))]
+solana_program::entrypoint!(process_instruction);
+
+pub const COUNTER_SEED: &[u8] = b"counter";
+
+/// Endereco do contador de transferencias de um mint.
+pub fn get_counter_address(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[COUNTER_SEED, mint.as_ref()], &ID)
+}
+
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    input: &[u8],
+) -> ProgramResult {
+    match TransferHookInstruction::unpack(input)? {
+        TransferHookInstruction::Execute { amount } => {
+            process_execute(program_id, accounts, amount)
+        }
+        TransferHookInstruction::InitializeExtraAccountMetaList {
+            extra_account_metas,
+        } => process_initialize(program_id, accounts, &extra_account_metas),
+    }
+}
+
+/// Contas esperadas:
+///
+///   0. `[]` Conta de origem
+///   1. `[]` Mint
+///   2. `[]` Conta de destino
+///   3. `[]` Autoridade da origem
+///   4. `[]` Lista de contas extras (validation)
+///   5. `[w]` Contador do mint
+fn process_execute(program_id: &Pubkey, accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let _source_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let _destination_info = next_account_info(account_info_iter)?;
+    let _authority_info = next_account_info(account_info_iter)?;
+    let validation_info = next_account_info(account_info_iter)?;
+    let counter_info = next_account_info(account_info_iter)?;
+
+    let (validation_address, _) =
+        get_extra_account_metas_address_and_bump_seed(mint_info.key, program_id);
+    if *validation_info.key != validation_address {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let instruction_data = TransferHookInstruction::Execute { amount }.pack();
+    ExtraAccountMetaList::check_account_infos::<ExecuteInstruction>(
+        accounts,
+        &instruction_data,
+        program_id,
+        &validation_info.try_borrow_data()?,
+    )?;
+
+    if counter_info.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let mut data = counter_info.try_borrow_mut_data()?;
+    let count = data
+        .get(..8)
+        .and_then(|slice| slice.try_into().ok())
+        .map(u64::from_le_bytes)
+        .ok_or(ProgramError::InvalidAccountData)?;
+    let count = count
+        .checked_add(1)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    data[..8].copy_from_slice(&count.to_le_bytes());
+
+    Ok(())
+}
+
+/// Contas esperadas:
+///
+///   0. `[w]` Lista de contas extras (validation)
+///   1. `[]` Mint
+///   2. `[ws]` Pagador
+///   3. `[]` System program
+///   4. `[w]` Contador do mint
+fn process_initialize(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    extra_account_metas: &[ExtraAccountMeta],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let validation_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let system_program_info = next_account_info(account_info_iter)?;
+    let counter_info = next_account_info(account_info_iter)?;
+
+    if !payer_info.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let (validation_address, validation_bump) =
+        get_extra_account_metas_address_and_bump_seed(mint_info.key, program_id);
+    if *validation_info.key != validation_address {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let validation_bump = [validation_bump];
+    let validation_seeds =
+        collect_extra_account_metas_signer_seeds(mint_info.key, &validation_bump);
+    let validation_len = ExtraAccountMetaList::size_of(extra_account_metas.len())?;
+    create_pda(
+        program_id,
+        payer_info,
+        system_program_info,
+        validation_info,
+        validation_len,
+        &validation_seeds,
+    )?;
+    ExtraAccountMetaList::init::<ExecuteInstruction>(
+        &mut validation_info.try_borrow_mut_data()?,
+        extra_account_metas,
+    )?;
+
+    let (counter_address, counter_bump) = get_counter_address(mint_info.key);
+    if *counter_info.key != counter_address {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    create_pda(
+        program_id,
+        payer_info,
+        system_program_info,
+        counter_info,
+        8,
+        &[COUNTER_SEED, mint_info.key.as_ref(), &[counter_bump]],
+    )?;
+
+    Ok(())
+}
+
+fn create_pda<'a>(
+    program_id: &Pubkey,
+    payer_info: &AccountInfo<'a>,
+    system_program_info: &AccountInfo<'a>,
+    new_account_info: &AccountInfo<'a>,
+    space: usize,
+    seeds: &[&[u8]],
+) -> ProgramResult {
+    let lamports = Rent::get()?.minimum_balance(space);
+    invoke_signed(
+        &system_instruction::create_account(
+            payer_info.key,
+            new_account_info.key,
+            lamports,
+            space as u64,
+            program_id,
+        ),
+        &[
+            payer_info.clone(),
+            new_account_info.clone(),
+            system_program_info.clone(),
+        ],
+        &[seeds],
+    )
+}
@@ -0,0 +1,104 @@
+//! Feed de preco minimo no layout de conta de preco do Pyth (v2), usado pelos
+//! testes de integracao do verum_vesting.
+//!
+//! A unica instrucao grava preco, confianca, expoente e timestamp na conta indicada,
+//! que precisa pertencer a este programa e ter `PRICE_ACCOUNT_LEN` bytes. Nao ha
+//! controle de acesso: o programa existe apenas para testes.
+
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+solana_program::declare_id!("96dShjFYJydp8SPDzbtJuw5Nq1ABRbFo1oCG3mutyaRb");
+
+#[cfg(not(feature = "no-entrypoint"))]
+solana_program::entrypoint!(process_instruction);
+
+/// Tamanho de uma conta de preco do Pyth v2.
+pub const PRICE_ACCOUNT_LEN: usize = 3312;
+
+pub const MAGIC: u32 = 0xa1b2_c3d4;
+pub const VERSION: u32 = 2;
+pub const ACCOUNT_TYPE_PRICE: u32 = 3;
+pub const STATUS_TRADING: u32 = 1;
+
+// Offsets do layout `PriceAccount` do Pyth v2
+const EXPO_OFFSET: usize = 20;
+const TIMESTAMP_OFFSET: usize = 96;
+const AGG_PRICE_OFFSET: usize = 208;
+const AGG_CONF_OFFSET: usize = 216;
+const AGG_STATUS_OFFSET: usize = 224;
+
+/// Dados da instrucao `SetPrice`.
+pub struct SetPrice {
+    pub price: i64,
+    pub conf: u64,
+    pub expo: i32,
+    pub publish_time: i64,
+    pub status: u32,
+}
+
+impl SetPrice {
+    pub const LEN: usize = 8 + 8 + 4 + 8 + 4;
+
+    pub fn pack(&self) -> Vec<u8> {
+        let mut data = Vec::with_capacity(Self::LEN);
+        data.extend_from_slice(&self.price.to_le_bytes());
+        data.extend_from_slice(&self.conf.to_le_bytes());
+        data.extend_from_slice(&self.expo.to_le_bytes());
+        data.extend_from_slice(&self.publish_time.to_le_bytes());
+        data.extend_from_slice(&self.status.to_le_bytes());
+        data
+    }
+
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        if input.len() != Self::LEN {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let field = |start: usize, len: usize| &input[start..start + len];
+        Ok(Self {
+            price: i64::from_le_bytes(field(0, 8).try_into().unwrap()),
+            conf: u64::from_le_bytes(field(8, 8).try_into().unwrap()),
+            expo: i32::from_le_bytes(field(16, 4).try_into().unwrap()),
+            publish_time: i64::from_le_bytes(field(20, 8).try_into().unwrap()),
+            status: u32::from_le_bytes(field(28, 4).try_into().unwrap()),
+        })
+    }
+}
+
+/// Contas esperadas:
+///
+///   0. `[w]` Conta de preco (dono: este programa)
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    input: &[u8],
+) -> ProgramResult {
+    let set_price = SetPrice::unpack(input)?;
+    let account_info_iter = &mut accounts.iter();
+    let price_info = next_account_info(account_info_iter)?;
+
+    if price_info.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let mut data = price_info.try_borrow_mut_data()?;
+    if data.len() != PRICE_ACCOUNT_LEN {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    data[0..4].copy_from_slice(&MAGIC.to_le_bytes());
+    data[4..8].copy_from_slice(&VERSION.to_le_bytes());
+    data[8..12].copy_from_slice(&ACCOUNT_TYPE_PRICE.to_le_bytes());
+    data[12..16].copy_from_slice(&(PRICE_ACCOUNT_LEN as u32).to_le_bytes());
+    data[EXPO_OFFSET..EXPO_OFFSET + 4].copy_from_slice(&set_price.expo.to_le_bytes());
+    data[TIMESTAMP_OFFSET..TIMESTAMP_OFFSET + 8]
+        .copy_from_slice(&set_price.publish_time.to_le_bytes());
+    data[AGG_PRICE_OFFSET..AGG_PRICE_OFFSET + 8].copy_from_slice(&set_price.price.to_le_bytes());
+    data[AGG_CONF_OFFSET..AGG_CONF_OFFSET + 8].copy_from_slice(&set_price.conf.to_le_bytes());
+    data[AGG_STATUS_OFFSET..AGG_STATUS_OFFSET + 4].copy_from_slice(&set_price.status.to_le_bytes());
+
+    Ok(())
+}
@@ -0,0 +1,446 @@
+//! Programa de staking minimo usado pelos testes de integracao do verum_vesting.
+//!
+//! Implementa a interface de staking que o verum_vesting chama via CPI (ver
+//! `staking.rs` no programa de vesting): cada dono tem um registro
+//! `[b"stake", owner, mint]` com o saldo depositado e as recompensas pendentes, e
+//! os tokens ficam em um cofre `[b"vault", mint]` controlado por
+//! `[b"vault_authority"]`. `FundRewards` credita recompensas a um registro sem
+//! controle de acesso: o programa existe apenas para testes.
+
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program::{invoke, invoke_signed},
+    program_error::ProgramError,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_instruction,
+    sysvar::Sysvar,
+};
+use spl_token_2022::{
+    extension::{BaseStateWithExtensions, ExtensionType, StateWithExtensions},
+    state::{Account, Mint},
+};
+
+solana_program::declare_id!("F5de7MZEmGG9eNSFuLHhb5fq6gGQwwznvB75JmwRgVRW");
+
+#[cfg(not(feature = "no-entrypoint"))]
+solana_program::entrypoint!(process_instruction);
+
+pub const STAKE_SEED: &[u8] = b"stake";
+pub const VAULT_SEED: &[u8] = b"vault";
+pub const VAULT_AUTHORITY_SEED: &[u8] = b"vault_authority";
+
+/// Tamanho do registro de stake: owner, mint, staked e pending_rewards.
+pub const STAKE_RECORD_LEN: usize = 32 + 32 + 8 + 8;
+
+pub fn get_stake_record_address(owner: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[STAKE_SEED, owner.as_ref(), mint.as_ref()], &ID)
+}
+
+pub fn get_vault_address(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[VAULT_SEED, mint.as_ref()], &ID)
+}
+
+pub fn get_vault_authority_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[VAULT_AUTHORITY_SEED], &ID)
+}
+
+/// Instrucoes do programa. O primeiro byte e a tag; valores seguem em little-endian.
+pub enum StakingInstruction {
+    InitializeVault,
+    Stake { amount: u64 },
+    Unstake { amount: u64 },
+    ClaimRewards,
+    FundRewards { amount: u64 },
+}
+
+impl StakingInstruction {
+    pub fn pack(&self) -> Vec<u8> {
+        match self {
+            Self::InitializeVault => vec![0],
+            Self::Stake { amount } => [&[1][..], &amount.to_le_bytes()].concat(),
+            Self::Unstake { amount } => [&[2][..], &amount.to_le_bytes()].concat(),
+            Self::ClaimRewards => vec![3],
+            Self::FundRewards { amount } => [&[4][..], &amount.to_le_bytes()].concat(),
+        }
+    }
+
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (&tag, rest) = input
+            .split_first()
+            .ok_or(ProgramError::InvalidInstructionData)?;
+        let amount = || {
+            rest.try_into()
+                .map(u64::from_le_bytes)
+                .map_err(|_| ProgramError::InvalidInstructionData)
+        };
+        Ok(match tag {
+            0 => Self::InitializeVault,
+            1 => Self::Stake { amount: amount()? },
+            2 => Self::Unstake { amount: amount()? },
+            3 => Self::ClaimRewards,
+            4 => Self::FundRewards { amount: amount()? },
+            _ => return Err(ProgramError::InvalidInstructionData),
+        })
+    }
+}
+
+/// Registro de stake de um dono para um mint.
+pub struct StakeRecord {
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub staked: u64,
+    pub pending_rewards: u64,
+}
+
+impl StakeRecord {
+    pub fn unpack(data: &[u8]) -> Result<Self, ProgramError> {
+        if data.len() != STAKE_RECORD_LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let pubkey =
+            |start: usize| Pubkey::new_from_array(data[start..start + 32].try_into().unwrap());
+        let number = |start: usize| u64::from_le_bytes(data[start..start + 8].try_into().unwrap());
+        Ok(Self {
+            owner: pubkey(0),
+            mint: pubkey(32),
+            staked: number(64),
+            pending_rewards: number(72),
+        })
+    }
+
+    fn pack(&self, data: &mut [u8]) {
+        data[0..32].copy_from_slice(self.owner.as_ref());
+        data[32..64].copy_from_slice(self.mint.as_ref());
+        data[64..72].copy_from_slice(&self.staked.to_le_bytes());
+        data[72..80].copy_from_slice(&self.pending_rewards.to_le_bytes());
+    }
+}
+
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    input: &[u8],
+) -> ProgramResult {
+    match StakingInstruction::unpack(input)? {
+        StakingInstruction::InitializeVault => process_initialize_vault(accounts),
+        StakingInstruction::Stake { amount } => process_stake(program_id, accounts, amount),
+        StakingInstruction::Unstake { amount } => {
+            process_withdraw(program_id, accounts, Withdrawal::Unstake(amount))
+        }
+        StakingInstruction::ClaimRewards => {
+            process_withdraw(program_id, accounts, Withdrawal::Rewards)
+        }
+        StakingInstruction::FundRewards { amount } => {
+            process_fund_rewards(program_id, accounts, amount)
+        }
+    }
+}
+
+/// Contas esperadas:
+///
+///   0. `[ws]` Pagador
+///   1. `[w]` Cofre do mint
+///   2. `[]` Autoridade do cofre
+///   3. `[]` Mint
+///   4. `[]` Token program
+///   5. `[]` System program
+fn process_initialize_vault(accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let payer_info = next_account_info(account_info_iter)?;
+    let vault_info = next_account_info(account_info_iter)?;
+    let vault_authority_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let token_program_info = next_account_info(account_info_iter)?;
+    let system_program_info = next_account_info(account_info_iter)?;
+
+    let (vault_address, vault_bump) = get_vault_address(mint_info.key);
+    if *vault_info.key != vault_address
+        || *vault_authority_info.key != get_vault_authority_address().0
+    {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let vault_len = {
+        let mint_data = mint_info.try_borrow_data()?;
+        let mint = StateWithExtensions::<Mint>::unpack(&mint_data)?;
+        let extensions =
+            ExtensionType::get_required_init_account_extensions(&mint.get_extension_types()?);
+        ExtensionType::try_calculate_account_len::<Account>(&extensions)?
+    };
+    let lamports = Rent::get()?.minimum_balance(vault_len);
+    invoke_signed(
+        &system_instruction::create_account(
+            payer_info.key,
+            vault_info.key,
+            lamports,
+            vault_len as u64,
+            token_program_info.key,
+        ),
+        &[
+            payer_info.clone(),
+            vault_info.clone(),
+            system_program_info.clone(),
+        ],
+        &[&[VAULT_SEED, mint_info.key.as_ref(), &[vault_bump]]],
+    )?;
+    invoke(
+        &spl_token_2022::instruction::initialize_account3(
+            token_program_info.key,
+            vault_info.key,
+            mint_info.key,
+            vault_authority_info.key,
+        )?,
+        &[vault_info.clone(), mint_info.clone()],
+    )?;
+
+    Ok(())
+}
+
+/// Contas esperadas:
+///
+///   0. `[w]` Registro de stake do dono (criado no primeiro deposito)
+///   1. `[s]` Dono
+///   2. `[w]` Conta de token de origem (autoridade: dono)
+///   3. `[w]` Cofre do mint
+///   4. `[]` Mint
+///   5. `[]` Token program
+///   6. `[ws]` Pagador
+///   7. `[]` System program
+fn process_stake(program_id: &Pubkey, accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let record_info = next_account_info(account_info_iter)?;
+    let owner_info = next_account_info(account_info_iter)?;
+    let source_info = next_account_info(account_info_iter)?;
+    let vault_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let token_program_info = next_account_info(account_info_iter)?;
+    let payer_info = next_account_info(account_info_iter)?;
+    let system_program_info = next_account_info(account_info_iter)?;
+
+    if !owner_info.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if *vault_info.key != get_vault_address(mint_info.key).0 {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let (record_address, record_bump) = get_stake_record_address(owner_info.key, mint_info.key);
+    if *record_info.key != record_address {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    if record_info.data_is_empty() {
+        let lamports = Rent::get()?.minimum_balance(STAKE_RECORD_LEN);
+        invoke_signed(
+            &system_instruction::create_account(
+                payer_info.key,
+                record_info.key,
+                lamports,
+                STAKE_RECORD_LEN as u64,
+                program_id,
+            ),
+            &[
+                payer_info.clone(),
+                record_info.clone(),
+                system_program_info.clone(),
+            ],
+            &[&[
+                STAKE_SEED,
+                owner_info.key.as_ref(),
+                mint_info.key.as_ref(),
+                &[record_bump],
+            ]],
+        )?;
+        StakeRecord {
+            owner: *owner_info.key,
+            mint: *mint_info.key,
+            staked: 0,
+            pending_rewards: 0,
+        }
+        .pack(&mut record_info.try_borrow_mut_data()?);
+    }
+
+    transfer(
+        token_program_info,
+        source_info,
+        mint_info,
+        vault_info,
+        owner_info,
+        amount,
+        &[],
+    )?;
+
+    let mut record = StakeRecord::unpack(&record_info.try_borrow_data()?)?;
+    record.staked = record
+        .staked
+        .checked_add(amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    record.pack(&mut record_info.try_borrow_mut_data()?);
+
+    Ok(())
+}
+
+enum Withdrawal {
+    Unstake(u64),
+    Rewards,
+}
+
+/// `Unstake` e `ClaimRewards`. Contas esperadas:
+///
+///   0. `[w]` Registro de stake do dono
+///   1. `[s]` Dono
+///   2. `[w]` Conta de token de destino
+///   3. `[w]` Cofre do mint
+///   4. `[]` Autoridade do cofre
+///   5. `[]` Mint
+///   6. `[]` Token program
+fn process_withdraw(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    withdrawal: Withdrawal,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let record_info = next_account_info(account_info_iter)?;
+    let owner_info = next_account_info(account_info_iter)?;
+    let destination_info = next_account_info(account_info_iter)?;
+    let vault_info = next_account_info(account_info_iter)?;
+    let vault_authority_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let token_program_info = next_account_info(account_info_iter)?;
+
+    if !owner_info.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    let (vault_authority, vault_authority_bump) = get_vault_authority_address();
+    if *vault_info.key != get_vault_address(mint_info.key).0
+        || *vault_authority_info.key != vault_authority
+    {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let mut record = load_record(program_id, record_info)?;
+    if record.owner != *owner_info.key || record.mint != *mint_info.key {
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    let amount = match withdrawal {
+        Withdrawal::Unstake(amount) => {
+            record.staked = record
+                .staked
+                .checked_sub(amount)
+                .ok_or(ProgramError::InsufficientFunds)?;
+            amount
+        }
+        Withdrawal::Rewards => std::mem::take(&mut record.pending_rewards),
+    };
+    record.pack(&mut record_info.try_borrow_mut_data()?);
+
+    if amount > 0 {
+        transfer(
+            token_program_info,
+            vault_info,
+            mint_info,
+            destination_info,
+            vault_authority_info,
+            amount,
+            &[VAULT_AUTHORITY_SEED, &[vault_authority_bump]],
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Contas esperadas:
+///
+///   0. `[w]` Registro de stake beneficiado
+///   1. `[s]` Pagador das recompensas
+///   2. `[w]` Conta de token do pagador
+///   3. `[w]` Cofre do mint
+///   4. `[]` Mint
+///   5. `[]` Token program
+fn process_fund_rewards(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let record_info = next_account_info(account_info_iter)?;
+    let funder_info = next_account_info(account_info_iter)?;
+    let source_info = next_account_info(account_info_iter)?;
+    let vault_info = next_account_info(account_info_iter)?;
+    let mint_info = next_account_info(account_info_iter)?;
+    let token_program_info = next_account_info(account_info_iter)?;
+
+    if *vault_info.key != get_vault_address(mint_info.key).0 {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let mut record = load_record(program_id, record_info)?;
+    if record.mint != *mint_info.key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    transfer(
+        token_program_info,
+        source_info,
+        mint_info,
+        vault_info,
+        funder_info,
+        amount,
+        &[],
+    )?;
+
+    record.pending_rewards = record
+        .pending_rewards
+        .checked_add(amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    record.pack(&mut record_info.try_borrow_mut_data()?);
+
+    Ok(())
+}
+
+fn load_record(
+    program_id: &Pubkey,
+    record_info: &AccountInfo,
+) -> Result<StakeRecord, ProgramError> {
+    if record_info.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    StakeRecord::unpack(&record_info.try_borrow_data()?)
+}
+
+fn transfer<'a>(
+    token_program_info: &AccountInfo<'a>,
+    source_info: &AccountInfo<'a>,
+    mint_info: &AccountInfo<'a>,
+    destination_info: &AccountInfo<'a>,
+    authority_info: &AccountInfo<'a>,
+    amount: u64,
+    authority_seeds: &[&[u8]],
+) -> ProgramResult {
+    let decimals = Mint::unpack_from_slice(&mint_info.try_borrow_data()?[..Mint::LEN])?.decimals;
+    let signer_seeds: &[&[&[u8]]] = if authority_seeds.is_empty() {
+        &[]
+    } else {
+        &[authority_seeds]
+    };
+    invoke_signed(
+        &spl_token_2022::instruction::transfer_checked(
+            token_program_info.key,
+            source_info.key,
+            mint_info.key,
+            destination_info.key,
+            authority_info.key,
+            &[],
+            amount,
+            decimals,
+        )?,
+        &[
+            source_info.clone(),
+            mint_info.clone(),
+            destination_info.clone(),
+            authority_info.clone(),
+        ],
+        signer_seeds,
+    )
+}
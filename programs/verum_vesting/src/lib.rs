@@ -140,14 +140,7 @@ fn calculate_vested_amount(
     total: u64,
     is_revoked: bool,
 ) -> u64 {
-    if is_revoked || current_time >= start + duration as i64 {
-        return total;
-    }
-    if current_time < cliff {
-        return 0;
-    }
-    let elapsed = current_time.checked_sub(start).unwrap() as u64;
-    (total * elapsed) / duration
+    verum_vesting_math::legacy_vested_amount(current_time, start, cliff, duration, total, is_revoked)
 }
 
 #[derive(Accounts)]
@@ -0,0 +1,438 @@
+//! Testes de integracao do programa legado (`vesting_account` + `vault`), rodando
+//! nativamente dentro do `solana-program-test` com o SPL Token embutido.
+
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_spl::token::spl_token;
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    account_info::AccountInfo,
+    clock::Clock,
+    entrypoint::ProgramResult,
+    instruction::{Instruction, InstructionError},
+    program_pack::Pack,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_instruction,
+    transaction::{Transaction, TransactionError},
+};
+use verum_vesting::VestingError;
+
+const TOTAL: u64 = 1_000_000;
+const CLIFF_SECONDS: u64 = 100;
+const DURATION_SECONDS: u64 = 1_000;
+
+// O `entry` gerado pelo Anchor exige `&'info [AccountInfo<'info>]`, mais
+// restritivo do que o `processor!` oferece.
+fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> ProgramResult {
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    verum_vesting::entry(program_id, accounts, data)
+}
+
+fn vault_address(vesting_account: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"vault", vesting_account.as_ref()], &verum_vesting::ID).0
+}
+
+fn assert_custom_error(result: Result<(), BanksClientError>, expected: u32) {
+    match result.unwrap_err().unwrap() {
+        TransactionError::InstructionError(_, InstructionError::Custom(code)) => {
+            assert_eq!(code, expected);
+        }
+        other => panic!("erro inesperado: {other:?}"),
+    }
+}
+
+fn assert_vesting_error(result: Result<(), BanksClientError>, expected: VestingError) {
+    assert_custom_error(result, u32::from(expected));
+}
+
+fn assert_anchor_error(
+    result: Result<(), BanksClientError>,
+    expected: anchor_lang::error::ErrorCode,
+) {
+    assert_custom_error(result, u32::from(expected));
+}
+
+struct Env {
+    context: ProgramTestContext,
+    mint: Pubkey,
+    sender_token_account: Pubkey,
+    start_time: i64,
+}
+
+impl Env {
+    /// Mint SPL com `TOTAL` tokens por contrato na conta do payer, que atua como `sender`.
+    async fn new(contracts: u64) -> Self {
+        let program_test = ProgramTest::new(
+            "verum_vesting",
+            verum_vesting::ID,
+            processor!(process_instruction),
+        );
+        let context = program_test.start_with_context().await;
+        let mut env = Self {
+            context,
+            mint: Pubkey::default(),
+            sender_token_account: Pubkey::default(),
+            start_time: 0,
+        };
+
+        let payer = env.payer();
+        let mint = Keypair::new();
+        let rent = env.context.banks_client.get_rent().await.unwrap();
+        env.process(
+            &[
+                system_instruction::create_account(
+                    &payer.pubkey(),
+                    &mint.pubkey(),
+                    rent.minimum_balance(spl_token::state::Mint::LEN),
+                    spl_token::state::Mint::LEN as u64,
+                    &spl_token::ID,
+                ),
+                spl_token::instruction::initialize_mint2(
+                    &spl_token::ID,
+                    &mint.pubkey(),
+                    &payer.pubkey(),
+                    None,
+                    6,
+                )
+                .unwrap(),
+            ],
+            &[&mint],
+        )
+        .await
+        .unwrap();
+        env.mint = mint.pubkey();
+
+        env.sender_token_account = env.create_token_account(&payer.pubkey()).await;
+        env.process(
+            &[spl_token::instruction::mint_to(
+                &spl_token::ID,
+                &env.mint,
+                &env.sender_token_account,
+                &payer.pubkey(),
+                &[],
+                TOTAL * contracts,
+            )
+            .unwrap()],
+            &[],
+        )
+        .await
+        .unwrap();
+
+        let clock: Clock = env.context.banks_client.get_sysvar().await.unwrap();
+        env.start_time = clock.unix_timestamp;
+        env
+    }
+
+    fn payer(&self) -> Keypair {
+        self.context.payer.insecure_clone()
+    }
+
+    async fn process(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> Result<(), BanksClientError> {
+        let payer = self.payer();
+        let mut all_signers = vec![&payer];
+        all_signers.extend_from_slice(signers);
+        let blockhash = self.context.banks_client.get_latest_blockhash().await?;
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&payer.pubkey()),
+            &all_signers,
+            blockhash,
+        );
+        self.context
+            .banks_client
+            .process_transaction(transaction)
+            .await
+    }
+
+    /// Avanca um slot e fixa o relogio em `unix_timestamp`.
+    async fn set_time(&mut self, unix_timestamp: i64) {
+        let clock: Clock = self.context.banks_client.get_sysvar().await.unwrap();
+        self.context.warp_to_slot(clock.slot + 1).unwrap();
+        let mut clock: Clock = self.context.banks_client.get_sysvar().await.unwrap();
+        clock.unix_timestamp = unix_timestamp;
+        self.context.set_sysvar(&clock);
+    }
+
+    async fn create_token_account(&mut self, owner: &Pubkey) -> Pubkey {
+        let payer = self.payer();
+        let account = Keypair::new();
+        let rent = self.context.banks_client.get_rent().await.unwrap();
+        self.process(
+            &[
+                system_instruction::create_account(
+                    &payer.pubkey(),
+                    &account.pubkey(),
+                    rent.minimum_balance(spl_token::state::Account::LEN),
+                    spl_token::state::Account::LEN as u64,
+                    &spl_token::ID,
+                ),
+                spl_token::instruction::initialize_account3(
+                    &spl_token::ID,
+                    &account.pubkey(),
+                    &self.mint,
+                    owner,
+                )
+                .unwrap(),
+            ],
+            &[&account],
+        )
+        .await
+        .unwrap();
+        account.pubkey()
+    }
+
+    async fn token_balance(&mut self, token_account: &Pubkey) -> u64 {
+        let account = self
+            .context
+            .banks_client
+            .get_account(*token_account)
+            .await
+            .unwrap()
+            .unwrap();
+        spl_token::state::Account::unpack(&account.data)
+            .unwrap()
+            .amount
+    }
+
+    async fn vesting_account(&mut self, address: &Pubkey) -> verum_vesting::VestingAccount {
+        let account = self
+            .context
+            .banks_client
+            .get_account(*address)
+            .await
+            .unwrap()
+            .unwrap();
+        anchor_lang::AccountDeserialize::try_deserialize(&mut account.data.as_slice()).unwrap()
+    }
+
+    /// Cria um contrato com cliff de `CLIFF_SECONDS` e duracao de `DURATION_SECONDS`.
+    async fn create(&mut self, beneficiary: &Pubkey, revocable: bool) -> Pubkey {
+        let payer = self.payer();
+        let vesting_account = Keypair::new();
+        let instruction = Instruction {
+            program_id: verum_vesting::ID,
+            accounts: verum_vesting::accounts::CreateVesting {
+                sender: payer.pubkey(),
+                sender_token_account: self.sender_token_account,
+                beneficiary: *beneficiary,
+                mint: self.mint,
+                vesting_account: vesting_account.pubkey(),
+                vault: vault_address(&vesting_account.pubkey()),
+                system_program: solana_sdk::system_program::ID,
+                token_program: spl_token::ID,
+                rent: solana_sdk::sysvar::rent::ID,
+            }
+            .to_account_metas(None),
+            data: verum_vesting::instruction::CreateVesting {
+                start_time: self.start_time,
+                cliff_seconds: CLIFF_SECONDS,
+                duration_seconds: DURATION_SECONDS,
+                total_amount: TOTAL,
+                revocable,
+            }
+            .data(),
+        };
+        self.process(&[instruction], &[&vesting_account])
+            .await
+            .unwrap();
+        vesting_account.pubkey()
+    }
+
+    async fn release(
+        &mut self,
+        vesting_account: &Pubkey,
+        vault: &Pubkey,
+        beneficiary: &Pubkey,
+        beneficiary_token_account: &Pubkey,
+    ) -> Result<(), BanksClientError> {
+        let instruction = Instruction {
+            program_id: verum_vesting::ID,
+            accounts: verum_vesting::accounts::Release {
+                beneficiary: *beneficiary,
+                beneficiary_token_account: *beneficiary_token_account,
+                vesting_account: *vesting_account,
+                vault: *vault,
+                token_program: spl_token::ID,
+            }
+            .to_account_metas(None),
+            data: verum_vesting::instruction::Release {}.data(),
+        };
+        self.process(&[instruction], &[]).await
+    }
+
+    async fn revoke(
+        &mut self,
+        vesting_account: &Pubkey,
+        authority: &Keypair,
+        refund_token_account: &Pubkey,
+    ) -> Result<(), BanksClientError> {
+        let instruction = Instruction {
+            program_id: verum_vesting::ID,
+            accounts: verum_vesting::accounts::Revoke {
+                authority: authority.pubkey(),
+                vesting_account: *vesting_account,
+                vault: vault_address(vesting_account),
+                refund_token_account: *refund_token_account,
+                token_program: spl_token::ID,
+            }
+            .to_account_metas(None),
+            data: verum_vesting::instruction::Revoke {}.data(),
+        };
+        self.process(&[instruction], &[authority]).await
+    }
+}
+
+#[tokio::test]
+async fn releases_across_cliff_and_end() {
+    let mut env = Env::new(1).await;
+    let beneficiary = Pubkey::new_unique();
+    let beneficiary_token_account = env.create_token_account(&beneficiary).await;
+    let vesting_account = env.create(&beneficiary, true).await;
+    let vault = vault_address(&vesting_account);
+    assert_eq!(env.token_balance(&vault).await, TOTAL);
+
+    let start_time = env.start_time;
+    env.set_time(start_time + CLIFF_SECONDS as i64 - 1).await;
+    assert_vesting_error(
+        env.release(
+            &vesting_account,
+            &vault,
+            &beneficiary,
+            &beneficiary_token_account,
+        )
+        .await,
+        VestingError::NothingToRelease,
+    );
+
+    // Apos o cliff o valor e linear desde `start_time`
+    env.set_time(start_time + 500).await;
+    env.release(
+        &vesting_account,
+        &vault,
+        &beneficiary,
+        &beneficiary_token_account,
+    )
+    .await
+    .unwrap();
+    assert_eq!(env.token_balance(&beneficiary_token_account).await, 500_000);
+
+    env.set_time(start_time + 5_000).await;
+    env.release(
+        &vesting_account,
+        &vault,
+        &beneficiary,
+        &beneficiary_token_account,
+    )
+    .await
+    .unwrap();
+    assert_eq!(env.token_balance(&beneficiary_token_account).await, TOTAL);
+    assert_eq!(env.token_balance(&vault).await, 0);
+    assert_eq!(
+        env.vesting_account(&vesting_account).await.released_amount,
+        TOTAL
+    );
+
+    env.set_time(start_time + 5_001).await;
+    assert_vesting_error(
+        env.release(
+            &vesting_account,
+            &vault,
+            &beneficiary,
+            &beneficiary_token_account,
+        )
+        .await,
+        VestingError::NothingToRelease,
+    );
+}
+
+#[tokio::test]
+async fn revoke_refunds_unvested_once() {
+    let mut env = Env::new(2).await;
+    let beneficiary = Pubkey::new_unique();
+    let vesting_account = env.create(&beneficiary, true).await;
+    let sender = env.payer();
+    let sender_token_account = env.sender_token_account;
+
+    let intruder = Keypair::new();
+    let intruder_token_account = env.create_token_account(&intruder.pubkey()).await;
+    assert_vesting_error(
+        env.revoke(&vesting_account, &intruder, &intruder_token_account)
+            .await,
+        VestingError::Unauthorized,
+    );
+
+    env.set_time(env.start_time + 250).await;
+    env.revoke(&vesting_account, &sender, &sender_token_account)
+        .await
+        .unwrap();
+    assert_eq!(
+        env.token_balance(&sender_token_account).await,
+        TOTAL + 750_000
+    );
+    assert_eq!(
+        env.token_balance(&vault_address(&vesting_account)).await,
+        250_000
+    );
+    assert!(env.vesting_account(&vesting_account).await.revoked);
+
+    env.set_time(env.start_time + 251).await;
+    assert_vesting_error(
+        env.revoke(&vesting_account, &sender, &sender_token_account)
+            .await,
+        VestingError::AlreadyRevoked,
+    );
+
+    let irrevocable = env.create(&beneficiary, false).await;
+    assert_vesting_error(
+        env.revoke(&irrevocable, &sender, &sender_token_account)
+            .await,
+        VestingError::NotRevocable,
+    );
+}
+
+#[tokio::test]
+async fn rejects_vault_of_another_contract() {
+    let mut env = Env::new(2).await;
+    let beneficiary = Pubkey::new_unique();
+    let beneficiary_token_account = env.create_token_account(&beneficiary).await;
+    let first = env.create(&beneficiary, true).await;
+    let second = env.create(&beneficiary, true).await;
+    let foreign_vault = vault_address(&second);
+
+    env.set_time(env.start_time + 500).await;
+    assert_anchor_error(
+        env.release(
+            &first,
+            &foreign_vault,
+            &beneficiary,
+            &beneficiary_token_account,
+        )
+        .await,
+        anchor_lang::error::ErrorCode::ConstraintHasOne,
+    );
+
+    // Beneficiario diferente do registrado no contrato
+    let stranger = Pubkey::new_unique();
+    let stranger_token_account = env.create_token_account(&stranger).await;
+    assert_anchor_error(
+        env.release(
+            &first,
+            &vault_address(&first),
+            &stranger,
+            &stranger_token_account,
+        )
+        .await,
+        anchor_lang::error::ErrorCode::ConstraintHasOne,
+    );
+
+    assert_eq!(env.token_balance(&vault_address(&first)).await, TOTAL);
+    assert_eq!(env.token_balance(&foreign_vault).await, TOTAL);
+}
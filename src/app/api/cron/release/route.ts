@@ -2,6 +2,10 @@ export const runtime = "nodejs";
 
 import { NextRequest, NextResponse } from 'next/server';
 
+// DEPRECATED: substituido pelo keeper em Rust (crates/verum_vesting_keeper), que
+// usa os tipos do proprio programa. Este IDL embutido esta defasado (a conta
+// `token2022Program` nao existe em claim_tokens); nao agendar este cron em novos ambientes.
+
 // O IDL agora usa 'writable' em vez de 'isMut' para compatibilidade com versões novas do Anchor
 const IDL: any = {
     "version": "0.1.0",
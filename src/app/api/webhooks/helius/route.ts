@@ -1,5 +1,7 @@
 
 import { NextRequest, NextResponse } from 'next/server';
+import { extractVestingEvents } from '@/utils/vesting-events';
+import { DEFAULT_NETWORK, getProgramId } from '@/utils/solana-config';
 
 /**
  * Helius Webhook Receiver
@@ -23,9 +25,17 @@ export async function POST(req: NextRequest) {
             return NextResponse.json({ message: 'Invalid body' }, { status: 400 });
         }
 
+        const programId = getProgramId(DEFAULT_NETWORK);
+
         for (const tx of body) {
             console.log(`[Helius Webhook] Nova transação detectada: ${tx.signature}`);
 
+            // 0. Eventos do programa (emit_cpi!): decodificados das instruções internas
+            for (const event of extractVestingEvents(tx, programId)) {
+                const slot = 'slot' in event ? ` (slot ${event.slot})` : '';
+                console.log(`[Helius Webhook] ${event.name} em ${event.vestingContract}${slot}`, event);
+            }
+
             // 1. Identificar o tipo de transação
             // No caso de criação de Vesting ou transferência de tokens
             const type = tx.type; // ex: "TRANSFER", "UNKNOWN" (se for instrução customizada)
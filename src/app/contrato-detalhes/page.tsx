@@ -10,56 +10,18 @@ import { isAdmin, getWalletCookie } from "@/utils/rbac";
 import { useWallet } from "@/contexts/WalletContext";
 import { useNetwork } from "@/contexts/NetworkContext";
 import NetworkSelector from "@/components/NetworkSelector";
-import { parseVestingDate, calculateVestingProgress } from "@/utils/date-utils";
+import { parseVestingDate, calculateVestingProgress, calculateUnlockedAmount } from "@/utils/date-utils";
+import { useVestingMath } from "@/hooks/useVestingMath";
 import { Transaction, PublicKey, SystemProgram } from '@solana/web3.js';
 import { getAssociatedTokenAddress, TOKEN_PROGRAM_ID, createAssociatedTokenAccountIdempotentInstruction } from '@solana/spl-token';
-import { Program, AnchorProvider, Idl } from '@project-serum/anchor';
+import { Program, AnchorProvider } from '@project-serum/anchor';
 import { detectTokenProgram } from "@/utils/tokenProgram";
 import { PROGRAM_IDS } from "@/utils/solana-config";
+import { VESTING_IDL, eventCpiAccounts, resolveAuditLog } from "@/utils/vesting-idl";
 import { fetchContractById } from "@/utils/blockchain-scanner";
 
 const TOKEN_2022_PROGRAM_ID = new PublicKey("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
 
-const IDL: Idl = {
-    "version": "0.1.0",
-    "name": "verum_vesting",
-    "instructions": [
-        {
-            "name": "claimTokens",
-            "accounts": [
-                { "name": "vestingContract", "isMut": true, "isSigner": false },
-                { "name": "escrowWallet", "isMut": true, "isSigner": false },
-                { "name": "beneficiaryTokenAccount", "isMut": true, "isSigner": false },
-                { "name": "beneficiary", "isMut": false, "isSigner": false },
-                { "name": "mint", "isMut": false, "isSigner": false },
-                { "name": "tokenProgram", "isMut": false, "isSigner": false }
-            ],
-            "args": []
-        },
-        {
-            "name": "cancelVesting",
-            "accounts": [
-                { "name": "vestingContract", "isMut": true, "isSigner": false },
-                { "name": "creator", "isMut": true, "isSigner": true },
-                { "name": "escrowWallet", "isMut": true, "isSigner": false },
-                { "name": "creatorTokenAccount", "isMut": true, "isSigner": false },
-                { "name": "mint", "isMut": false, "isSigner": false },
-                { "name": "tokenProgram", "isMut": false, "isSigner": false }
-            ],
-            "args": []
-        },
-        {
-            "name": "updateBeneficiary",
-            "accounts": [
-                { "name": "vestingContract", "isMut": true, "isSigner": false },
-                { "name": "creator", "isMut": true, "isSigner": true },
-                { "name": "newBeneficiary", "isMut": false, "isSigner": false }
-            ],
-            "args": []
-        }
-    ]
-};
-
 
 export default function VestingContractDetailsPage() {
     const router = useRouter();
@@ -78,6 +40,7 @@ export default function VestingContractDetailsPage() {
     const [isSendingEmail, setIsSendingEmail] = useState(false);
     const [isCanceling, setIsCanceling] = useState(false);
     const [isUpdatingRecipient, setIsUpdatingRecipient] = useState(false);
+    const vestingMathReady = useVestingMath();
     const dropdownRef = React.useRef<HTMLDivElement>(null);
 
     // Fechar dropdown ao clicar fora
@@ -102,7 +65,7 @@ export default function VestingContractDetailsPage() {
     // --- Dynamic Engine for Details ---
     const getDynamicVesting = () => {
         const startDate = parseVestingDate(contractData?.vestingStartDate);
-        if (!startDate || !contractData?.vestingDuration) {
+        if (!startDate || !contractData?.vestingDuration || !vestingMathReady) {
             return { unlocked: 0, progress: 0, locked: contractData?.totalAmount || 0 };
         }
         try {
@@ -113,7 +76,7 @@ export default function VestingContractDetailsPage() {
 
             const total = contractData.totalAmount || 0;
             const progress = calculateVestingProgress(contractData, now);
-            const unlocked = total * progress;
+            const unlocked = calculateUnlockedAmount(contractData, total, now);
             return {
                 unlocked,
                 progress: Math.round(progress * 100),
@@ -368,7 +331,7 @@ export default function VestingContractDetailsPage() {
             );
 
             const provider = new AnchorProvider(connection, wallet as any, { commitment: 'confirmed' });
-            const program = new Program(IDL, activeProgramId, provider);
+            const program = new Program(VESTING_IDL, activeProgramId, provider);
 
             const tx = new Transaction();
 
@@ -390,6 +353,8 @@ export default function VestingContractDetailsPage() {
                     creatorTokenAccount: creatorTokenAccount,
                     mint: mintPubkey,
                     tokenProgram: tokenProgramId,
+                    auditLog: await resolveAuditLog(connection, vestingContractPubkey, activeProgramId),
+                    ...eventCpiAccounts(activeProgramId),
                 })
                 .instruction();
 
@@ -483,7 +448,7 @@ export default function VestingContractDetailsPage() {
             const activeProgramId = new PublicKey(PROGRAM_IDS[currentNetwork] || PROGRAM_IDS['devnet']);
 
             const provider = new AnchorProvider(connection, wallet as any, { commitment: 'confirmed' });
-            const program = new Program(IDL, activeProgramId, provider);
+            const program = new Program(VESTING_IDL, activeProgramId, provider);
 
             const tx = new Transaction();
             const updateIx = await program.methods.updateBeneficiary()
@@ -491,6 +456,8 @@ export default function VestingContractDetailsPage() {
                     vestingContract: vestingContractPubkey,
                     creator: creatorPubkey,
                     newBeneficiary: newBeneficiaryPubkey,
+                    auditLog: await resolveAuditLog(connection, vestingContractPubkey, activeProgramId),
+                    ...eventCpiAccounts(activeProgramId),
                 })
                 .instruction();
 
@@ -671,7 +638,7 @@ export default function VestingContractDetailsPage() {
 
             // Provider e Program do Anchor
             const provider = new AnchorProvider(connection, wallet as any, { commitment: 'confirmed' });
-            const program = new Program(IDL, activeProgramId, provider);
+            const program = new Program(VESTING_IDL, activeProgramId, provider);
 
             console.log("Executando claim_tokens on-chain...", {
                 vestingContract: vestingContractPubkey.toBase58(),
@@ -703,6 +670,14 @@ export default function VestingContractDetailsPage() {
                     beneficiary: beneficiaryPubkey,
                     mint: mintPubkey,          // ← obrigatório: contrato valida owner do mint
                     tokenProgram: tokenProgramId, // detectado dinamicamente (SPL ou Token-2022)
+                    // Contas opcionais ausentes (None): observação de preço e recibo
+                    priceTrigger: activeProgramId,
+                    priceFeed: activeProgramId,
+                    auditLog: await resolveAuditLog(connection, vestingContractPubkey, activeProgramId),
+                    releaseReceipt: activeProgramId,
+                    receiptPayer: activeProgramId,
+                    systemProgram: activeProgramId,
+                    ...eventCpiAccounts(activeProgramId),
                 })
                 .instruction();
 
@@ -9,7 +9,8 @@ import { useTokenMonitor } from "@/hooks/useTokenMonitor";
 import { useNetwork } from "@/contexts/NetworkContext";
 import ConnectWalletModal from "@/components/ConnectWalletModal";
 import NetworkSelector from "@/components/NetworkSelector";
-import { parseVestingDate, calculateVestingProgress } from "@/utils/date-utils";
+import { parseVestingDate, calculateVestingProgress, calculateUnlockedAmount } from "@/utils/date-utils";
+import { useVestingMath } from "@/hooks/useVestingMath";
 import { scanBlockchainContracts } from "@/utils/blockchain-scanner";
 
 export default function HomeClientePage() {
@@ -24,6 +25,7 @@ export default function HomeClientePage() {
     const [searchQuery, setSearchQuery] = useState("");
     const [tick, setTick] = useState(0);
     const [isSyncingBlockchain, setIsSyncingBlockchain] = useState(false);
+    const vestingMathReady = useVestingMath();
 
     // Ticker para atualizações em tempo real
     useEffect(() => {
@@ -144,6 +146,8 @@ export default function HomeClientePage() {
 
     // Automação de Status e Auto-reivindicação
     useEffect(() => {
+        if (!vestingMathReady) return;
+
         const processAutomation = () => {
             const saved = localStorage.getItem("created_contracts");
             if (!saved) return;
@@ -163,7 +167,6 @@ export default function HomeClientePage() {
                     // 1. Cálculo de progresso atual
                     const startDate = parseVestingDate(c.vestingStartDate);
                     if (startDate && c.vestingDuration) {
-                        const start = startDate.getTime();
                         const now = Date.now();
                         // Progress Calculation (0 to 1) using unified logic
                         const progress = calculateVestingProgress(c, now);
@@ -197,7 +200,7 @@ export default function HomeClientePage() {
                         // Automação: Auto-reivindicação
                         if (c.autoClaim) {
                             const total = c.totalAmount || 0;
-                            const unlocked = total * progress;
+                            const unlocked = calculateUnlockedAmount(c, total, now);
                             // Update claimed amount silently (simulation)
                             if (c.claimedAmount !== unlocked) {
                                 c.claimedAmount = unlocked;
@@ -219,7 +222,7 @@ export default function HomeClientePage() {
         const timer = setInterval(processAutomation, 5000); // Check every 5s
         processAutomation(); // Run once on mount
         return () => clearInterval(timer);
-    }, []);
+    }, [vestingMathReady]);
 
     const formatAddress = (addr: string) => {
         if (!addr) return "...";
@@ -296,13 +299,9 @@ export default function HomeClientePage() {
 
             // --- Engine de Cáculo Dinâmico (Real-time Simulation) ---
             const calculateSimulatedUnlocked = () => {
-                const startDate = parseVestingDate(contract.vestingStartDate);
-                if (!startDate || !contract.vestingDuration) return 0;
+                if (!vestingMathReady || !contract.vestingDuration) return 0;
                 try {
-                    const start = startDate.getTime();
-                    const now = Date.now();
-                    const progress = calculateVestingProgress(contract, now);
-                    return total * progress;
+                    return calculateUnlockedAmount(contract, total, Date.now());
                 } catch (e) { return 0; }
             };
 
@@ -366,7 +365,7 @@ export default function HomeClientePage() {
             // Se empatar no USD (ex: ambos $0), ordena pelo total absoluto (bloqueado + líquido)
             return (b.absoluteTotal || 0) - (a.absoluteTotal || 0);
         });
-    }, [walletTokens, contracts, connected, publicKey, currentNetwork, tick]);
+    }, [walletTokens, contracts, connected, publicKey, currentNetwork, tick, vestingMathReady]);
 
     // Filter Logic - Normalized for active category selection
     const relevantContracts = React.useMemo(() => {
@@ -454,6 +453,7 @@ export default function HomeClientePage() {
         if (rawStatus === "alterado") return false;
 
         // Calculate unified progress
+        if (!vestingMathReady) return false;
         const progress = calculateVestingProgress(contract, Date.now());
 
         // 3. Em Andamento (Strict: Progress > 0% AND < 100%)
@@ -715,7 +715,7 @@ export default function HomeClientePage() {
                                             {(() => {
                                                 const total = contract.totalAmount || 1;
                                                 const startDate = parseVestingDate(contract.vestingStartDate);
-                                                if (!startDate) return <span className="text-[10px] text-zinc-600 font-bold">--%</span>;
+                                                if (!startDate || !vestingMathReady) return <span className="text-[10px] text-zinc-600 font-bold">--%</span>;
 
                                                 const progress = calculateVestingProgress(contract, Date.now());
                                                 const percentage = Math.round(progress * 100);
@@ -729,10 +729,9 @@ export default function HomeClientePage() {
                                                 {(() => {
                                                     const total = contract.totalAmount || 0;
                                                     const startDate = parseVestingDate(contract.vestingStartDate);
-                                                    if (!startDate) return "0.00";
+                                                    if (!startDate || !vestingMathReady) return "0.00";
 
-                                                    const progress = calculateVestingProgress(contract, Date.now());
-                                                    const unlocked = total * progress;
+                                                    const unlocked = calculateUnlockedAmount(contract, total, Date.now());
                                                     return unlocked.toLocaleString(undefined, { maximumFractionDigits: 2 });
                                                 })()} {contract.selectedToken?.symbol}
                                             </span>
@@ -12,52 +12,21 @@ import NetworkSelector from "@/components/NetworkSelector";
 import { Transaction, SystemProgram, PublicKey, SYSVAR_RENT_PUBKEY } from '@solana/web3.js';
 import { parseVestingDate } from "@/utils/date-utils";
 import { getAssociatedTokenAddress, TOKEN_PROGRAM_ID } from '@solana/spl-token';
-import { Program, AnchorProvider, Idl, BN } from '@project-serum/anchor';
+import { Program, AnchorProvider, BN } from '@project-serum/anchor';
 import { detectTokenProgram } from "@/utils/tokenProgram";
 import { createAssociatedTokenAccountIdempotentInstruction } from '@solana/spl-token';
 import { PROGRAM_IDS } from "@/utils/solana-config";
+import {
+    DANGEROUS_MINT_FLAGS,
+    DEFAULT_ALLOWED_MINT_FLAGS,
+    MINT_FLAG_LABELS,
+    detectMintFlags,
+    listMintFlags,
+} from "@/utils/mint-flags";
+import { VESTING_IDL, eventCpiAccounts } from "@/utils/vesting-idl";
 
 const TOKEN_2022_PROGRAM_ID = new PublicKey("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
 
-const IDL: Idl = {
-    "version": "0.1.0",
-    "name": "verum_vesting",
-    "instructions": [
-        {
-            "name": "createVesting",
-            "accounts": [
-                { "name": "vestingContract", "isMut": true, "isSigner": false },
-                { "name": "creator", "isMut": true, "isSigner": true },
-                { "name": "beneficiary", "isMut": false, "isSigner": false },
-                { "name": "mint", "isMut": false, "isSigner": false },
-                { "name": "escrowWallet", "isMut": true, "isSigner": false },
-                { "name": "senderTokenAccount", "isMut": true, "isSigner": false },
-                { "name": "systemProgram", "isMut": false, "isSigner": false },
-                { "name": "tokenProgram", "isMut": false, "isSigner": false }
-            ],
-            "args": [
-                { "name": "contractId", "type": "u64" },
-                { "name": "totalAmount", "type": "u64" },
-                { "name": "startTime", "type": "i64" },
-                { "name": "endTime", "type": "i64" },
-                { "name": "vestingType", "type": { "defined": "VestingType" } }
-            ]
-        }
-    ],
-    "types": [
-        {
-            "name": "VestingType",
-            "type": {
-                "kind": "enum",
-                "variants": [
-                    { "name": "Linear" },
-                    { "name": "Cliff", "fields": ["i64", "u64"] }
-                ]
-            }
-        }
-    ]
-};
-
 
 export default function ReviewPage() {
     const router = useRouter();
@@ -74,6 +43,10 @@ export default function ReviewPage() {
     const [recipients, setRecipients] = useState<any[]>([]);
     const [walletAddress, setWalletAddress] = useState<string>("");
 
+    // Caracteristicas do mint detectadas e as de risco aceitas pelo criador
+    const [detectedMintFlags, setDetectedMintFlags] = useState<number | null>(null);
+    const [acceptedMintFlags, setAcceptedMintFlags] = useState<number>(DEFAULT_ALLOWED_MINT_FLAGS);
+
     // Load data on mount
     React.useEffect(() => {
         const savedConfig = localStorage.getItem("contract_draft");
@@ -92,6 +65,27 @@ export default function ReviewPage() {
         if (savedRecipients) setRecipients(JSON.parse(savedRecipients));
     }, []);
 
+    React.useEffect(() => {
+        const mintAddress = config?.selectedToken?.address || config?.selectedToken?.mint;
+        if (!mintAddress) return;
+
+        let cancelled = false;
+        (async () => {
+            try {
+                const tokenProgramId = await detectTokenProgram(connection, mintAddress);
+                const flags = await detectMintFlags(connection, new PublicKey(mintAddress), tokenProgramId);
+                if (!cancelled) setDetectedMintFlags(flags);
+            } catch (error) {
+                console.error("[revisar] Falha ao inspecionar o mint:", error);
+            }
+        })();
+        return () => { cancelled = true; };
+    }, [config, connection]);
+
+    const toggleAcceptedMintFlag = (flag: number) => {
+        setAcceptedMintFlags((flags) => flags ^ flag);
+    };
+
     const handleCreateContract = async () => {
         if (!publicKey || !wallet) {
             alert("Por favor, conecte sua carteira primeiro.");
@@ -109,6 +103,13 @@ export default function ReviewPage() {
             // Detectar qual programa de token usar (SPL ou Token-2022)
             const tokenProgramId = await detectTokenProgram(connection, selectedToken.address || selectedToken.mint);
 
+            // O programa rejeita caracteristicas de risco fora de `allowed_mint_flags`
+            const mintFlags = await detectMintFlags(connection, mintPubkey, tokenProgramId);
+            const allowedMintFlags = acceptedMintFlags & DANGEROUS_MINT_FLAGS;
+            const rejectedFlags = listMintFlags(mintFlags & DANGEROUS_MINT_FLAGS & ~allowedMintFlags);
+            if (rejectedFlags.length > 0) {
+                throw new Error(`Aceite as características de risco do token antes de criar: ${rejectedFlags.map((flag) => MINT_FLAG_LABELS[flag]).join('; ')}.`);
+            }
 
             // BUSCAR DECIMAIS REAIS DIRETO DA SOLANA (Production-ready)
             const mintInfo = await connection.getParsedAccountInfo(mintPubkey);
@@ -131,7 +132,7 @@ export default function ReviewPage() {
 
             // Configurar Anchor Provider e Program
             const provider = new AnchorProvider(connection, wallet as any, { commitment: 'confirmed' });
-            const program = new Program(IDL, PROGRAM_ID, provider);
+            const program = new Program(VESTING_IDL, PROGRAM_ID, provider);
 
             // Cria uma nova transação com todas as instruções de múltiplos destinatários
             const transaction = new Transaction();
@@ -205,7 +206,8 @@ export default function ReviewPage() {
                         totalAmountOnChain,
                         new BN(startTimestamp), // Use startTimestamp from above
                         new BN(endTimestamp), // Use endTimestamp from above
-                        vestingType as any
+                        vestingType as any,
+                        allowedMintFlags
                     )
                     .accounts({
                         vestingContract,
@@ -216,6 +218,8 @@ export default function ReviewPage() {
                         senderTokenAccount,
                         systemProgram: SystemProgram.programId,
                         tokenProgram: tokenProgramId, // detectado dinamicamente — Interface<TokenInterface>
+                        auditLog: PROGRAM_ID, // None: contrato criado sem AuditLog
+                        ...eventCpiAccounts(PROGRAM_ID),
                     })
                     .instruction();
 
@@ -542,6 +546,36 @@ export default function ReviewPage() {
                     </div>
                 </section>
 
+                {/* Mint Flags Section */}
+                {detectedMintFlags !== null && detectedMintFlags !== 0 && (
+                    <section className="mt-12">
+                        <h2 className="text-lg font-bold mb-4">Características do Token</h2>
+                        <div className="space-y-3">
+                            {listMintFlags(detectedMintFlags).map((flag) => {
+                                const isDangerous = (flag & DANGEROUS_MINT_FLAGS) !== 0;
+                                return (
+                                    <label key={flag} className="bg-[#1C1C1E] rounded-2xl p-4 flex items-center justify-between border border-white/5">
+                                        <div className="flex items-center space-x-3">
+                                            <span className={`material-icons-round ${isDangerous ? "text-yellow-500" : "text-zinc-500"}`}>
+                                                {isDangerous ? "warning" : "info"}
+                                            </span>
+                                            <span className="text-[13px] leading-snug text-zinc-200">{MINT_FLAG_LABELS[flag]}</span>
+                                        </div>
+                                        {isDangerous && (
+                                            <input
+                                                type="checkbox"
+                                                checked={(acceptedMintFlags & flag) !== 0}
+                                                onChange={() => toggleAcceptedMintFlag(flag)}
+                                                className="w-5 h-5 accent-[#C6963D] cursor-pointer"
+                                            />
+                                        )}
+                                    </label>
+                                );
+                            })}
+                        </div>
+                    </section>
+                )}
+
                 {/* Recipients Section */}
                 <section className="mt-12">
                     <h2 className="text-lg font-bold mb-4">Destinatários</h2>
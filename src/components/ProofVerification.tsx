@@ -3,6 +3,9 @@
 import React, { useState } from 'react';
 import { Connection, PublicKey } from '@solana/web3.js';
 import { observeBlockchainRecord } from '@/utils/validation-observer';
+import { fetchClawbackEvents, ClawbackEvent } from '@/utils/clawback-events';
+import { fetchAuditLog, AuditLogAccount } from '@/utils/audit-log';
+import { fetchReleaseReceipt, ReleaseReceipt } from '@/utils/release-receipt';
 import { useNetwork } from '@/contexts/NetworkContext';
 
 interface ValidationRecord {
@@ -23,6 +26,9 @@ export default function ProofVerification() {
     const [result, setResult] = useState<ValidationRecord | null>(null);
     const [auditLog, setAuditLog] = useState<string[]>([]);
     const [error, setError] = useState<string | null>(null);
+    const [clawbacks, setClawbacks] = useState<ClawbackEvent[]>([]);
+    const [history, setHistory] = useState<AuditLogAccount | null>(null);
+    const [receipt, setReceipt] = useState<ReleaseReceipt | null>(null);
 
     const logStep = (msg: string) => setAuditLog(prev => [...prev, `[${new Date().toLocaleTimeString()}] ${msg}`]);
 
@@ -31,6 +37,9 @@ export default function ProofVerification() {
         setLoading(true);
         setError(null);
         setResult(null);
+        setClawbacks([]);
+        setHistory(null);
+        setReceipt(null);
         setAuditLog([]);
 
         try {
@@ -62,6 +71,33 @@ export default function ProofVerification() {
                     logStep("Classificação: Carteira Nativa (System Program)");
                 } else {
                     logStep(`Classificação: Contrato/PDA (Owner: ${observation.record.owner})`);
+
+                    // 3. Recibo de liberação: prova o pagamento pelo próprio endereço
+                    const releaseReceipt = await fetchReleaseReceipt(connection, pubkey);
+                    if (releaseReceipt) {
+                        setReceipt(releaseReceipt);
+                        logStep(`Recibo de liberação #${releaseReceipt.claimIndex}: ${releaseReceipt.amount} enviado(s) para ${releaseReceipt.destination}.`);
+                    } else {
+                        // 4. Clawbacks de compliance registrados para o contrato
+                        logStep("Buscando eventos de clawback...");
+                        const events = await fetchClawbackEvents(connection, pubkey, new PublicKey(observation.record.owner));
+                        setClawbacks(events);
+                        if (events.length > 0) {
+                            logStep(`AVISO: ${events.length} clawback(s) de compliance encontrado(s).`);
+                        } else {
+                            logStep("Nenhum clawback registrado.");
+                        }
+
+                        // 5. Histórico on-chain (AuditLog) do contrato
+                        logStep("Lendo histórico on-chain do contrato...");
+                        const onChainLog = await fetchAuditLog(connection, pubkey, new PublicKey(observation.record.owner));
+                        setHistory(onChainLog);
+                        if (onChainLog) {
+                            logStep(`Histórico on-chain: ${onChainLog.totalEntries} operação(ões) registrada(s).`);
+                        } else {
+                            logStep("Contrato sem histórico on-chain.");
+                        }
+                    }
                 }
 
                 logStep("Auditoria concluída com SUCESSO.");
@@ -218,6 +254,72 @@ export default function ProofVerification() {
                 </div>
 
             </div>
+
+            {/* Release Receipt */}
+            {receipt && (
+                <div className="bg-zinc-900/50 border border-zinc-800 p-6 rounded-3xl space-y-4">
+                    <div className="flex items-center gap-2">
+                        <span className="material-symbols-outlined text-green-500">receipt_long</span>
+                        <h3 className="text-lg font-bold text-white">Recibo de Liberação #{receipt.claimIndex}</h3>
+                    </div>
+                    <div className="p-4 bg-black/40 rounded-xl border border-zinc-800/50 space-y-2 font-mono text-xs">
+                        <div className="flex justify-between items-center">
+                            <span className="text-zinc-300">Valor: <span className="text-[#EAB308]">{receipt.amount}</span></span>
+                            <span className="text-zinc-500">{new Date(receipt.timestamp * 1000).toLocaleString()}</span>
+                        </div>
+                        <div className="text-zinc-400 break-all">Destino: {receipt.destination}</div>
+                        <div className="text-zinc-400 break-all">Contrato: {receipt.vestingContract}</div>
+                        <div className="text-zinc-600">Slot: {receipt.slot}</div>
+                    </div>
+                </div>
+            )}
+
+            {/* On-chain History */}
+            {history && history.entries.length > 0 && (
+                <div className="bg-zinc-900/50 border border-zinc-800 p-6 rounded-3xl space-y-4">
+                    <div className="flex items-center gap-2">
+                        <span className="material-symbols-outlined text-[#EAB308]">history</span>
+                        <h3 className="text-lg font-bold text-white">Histórico On-chain</h3>
+                    </div>
+                    <div className="space-y-2">
+                        {history.entries.map((entry) => (
+                            <div key={entry.sequence} className="p-3 bg-black/40 rounded-xl border border-zinc-800/50 font-mono text-xs space-y-1">
+                                <div className="flex justify-between items-center">
+                                    <span className="text-zinc-200 font-bold">#{entry.sequence} {entry.action}</span>
+                                    <span className="text-zinc-500">{new Date(entry.timestamp * 1000).toLocaleString()}</span>
+                                </div>
+                                {entry.amount !== '0' && <div className="text-zinc-300">Valor: <span className="text-[#EAB308]">{entry.amount}</span></div>}
+                                <div className="text-zinc-400 break-all">Autor: {entry.actor}</div>
+                            </div>
+                        ))}
+                    </div>
+                </div>
+            )}
+
+            {/* Compliance Clawbacks */}
+            {clawbacks.length > 0 && (
+                <div className="bg-zinc-900/50 border border-zinc-800 p-6 rounded-3xl space-y-4">
+                    <div className="flex items-center gap-2">
+                        <span className="material-symbols-outlined text-red-500">gavel</span>
+                        <h3 className="text-lg font-bold text-white">Clawbacks de Compliance</h3>
+                    </div>
+                    <div className="space-y-3">
+                        {clawbacks.map((event) => (
+                            <div key={event.signature} className="p-4 bg-black/40 rounded-xl border border-zinc-800/50 space-y-2 font-mono text-xs">
+                                <div className="flex justify-between items-center">
+                                    <span className="text-red-400 font-bold uppercase tracking-wider">{event.reason}</span>
+                                    <span className="text-zinc-500">{new Date(event.timestamp * 1000).toLocaleString()}</span>
+                                </div>
+                                <div className="text-zinc-300">Valor: <span className="text-[#EAB308]">{event.amount}</span></div>
+                                <div className="text-zinc-400 break-all">Destino: {event.destination}</div>
+                                <div className="text-zinc-400 break-all">Autoridade: {event.complianceAuthority}</div>
+                                {event.memo && <div className="text-zinc-300">Memo: {event.memo}</div>}
+                                <div className="text-zinc-600 break-all">Tx: {event.signature}</div>
+                            </div>
+                        ))}
+                    </div>
+                </div>
+            )}
         </div>
     );
 }
@@ -0,0 +1,217 @@
+//! Historico on-chain de cada contrato.
+//!
+//! O `AuditLog` (`[b"audit_log", vesting_contract]`) e um buffer circular com as
+//! ultimas `AUDIT_LOG_CAPACITY` operacoes (autor, acao, valor e instante). E criado
+//! junto com o contrato quando o cliente envia a conta, ou depois por
+//! `create_audit_log`; a partir dai `VestingContract::has_audit_log` torna a conta
+//! obrigatoria nas instrucoes que o alimentam. `read_audit_log` devolve uma pagina
+//! de entradas via return data.
+
+use super::*;
+
+/// Entradas mantidas no buffer; as mais antigas sao sobrescritas.
+pub const AUDIT_LOG_CAPACITY: usize = 32;
+
+/// Maximo de entradas por pagina de `read_audit_log` (cabe no limite de return data).
+pub const MAX_AUDIT_PAGE: u8 = 16;
+
+/// Codigos de `AuditEntry::action`.
+pub mod audit_action {
+    pub const CREATE: u8 = 0;
+    pub const CLAIM: u8 = 1;
+    pub const UPDATE_BENEFICIARY: u8 = 2;
+    pub const CANCEL: u8 = 3;
+    pub const CLAWBACK: u8 = 4;
+    pub const AMEND_SCHEDULE: u8 = 5;
+    pub const MILESTONE_REFUND: u8 = 6;
+}
+
+pub(crate) fn create(ctx: Context<CreateAuditLog>) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp;
+    require!(
+        !ctx.accounts.vesting_contract.has_audit_log,
+        VestingError::InvalidAuditLog
+    );
+
+    initialize(
+        &mut ctx.accounts.vesting_contract,
+        &mut ctx.accounts.audit_log,
+        ctx.bumps.audit_log,
+    );
+
+    emit!(AuditLogCreated {
+        contract_id: ctx.accounts.vesting_contract.contract_id,
+        audit_log: ctx.accounts.audit_log.key(),
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
+
+pub(crate) fn read(ctx: Context<ReadAuditLog>, start_sequence: u64, limit: u8) -> Result<AuditLogPage> {
+    let audit_log = &ctx.accounts.audit_log;
+    let oldest_sequence = audit_log.oldest_sequence();
+    let limit = limit.min(MAX_AUDIT_PAGE) as u64;
+
+    let first = start_sequence.max(oldest_sequence);
+    let last = first.saturating_add(limit).min(audit_log.total_entries);
+    let entries = (first..last)
+        .map(|sequence| audit_log.entries[AuditLog::slot(sequence)])
+        .collect();
+
+    Ok(AuditLogPage {
+        total_entries: audit_log.total_entries,
+        oldest_sequence,
+        entries,
+    })
+}
+
+/// Prepara um log recem-criado e marca o contrato como auditado.
+pub(crate) fn initialize(
+    vesting_contract: &mut Account<VestingContract>,
+    audit_log: &mut AuditLog,
+    bump: u8,
+) {
+    audit_log.vesting_contract = vesting_contract.key();
+    audit_log.bump = bump;
+    audit_log.total_entries = 0;
+    audit_log.entries = Vec::new();
+    vesting_contract.has_audit_log = true;
+}
+
+/// Acrescenta uma entrada ao log do contrato. Contratos sem log ignoram a chamada;
+/// com log, a conta e obrigatoria.
+pub(crate) fn record(
+    vesting_contract: &VestingContract,
+    audit_log: Option<&mut Account<AuditLog>>,
+    actor: Pubkey,
+    action: u8,
+    amount: u64,
+) -> Result<()> {
+    let Some(audit_log) = audit_log else {
+        require!(!vesting_contract.has_audit_log, VestingError::MissingAuditLog);
+        return Ok(());
+    };
+
+    let sequence = audit_log.total_entries;
+    let entry = AuditEntry {
+        sequence,
+        actor,
+        action,
+        amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    };
+    if audit_log.entries.len() < AUDIT_LOG_CAPACITY {
+        audit_log.entries.push(entry);
+    } else {
+        audit_log.entries[AuditLog::slot(sequence)] = entry;
+    }
+    audit_log.total_entries = sequence
+        .checked_add(1)
+        .ok_or(VestingError::MathOverflow)?;
+
+    Ok(())
+}
+
+// -------------------------------------------------------------------------
+// DATA STRUCTURES
+// -------------------------------------------------------------------------
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AuditEntry {
+    /// Posicao da entrada no historico completo (comeca em 0).
+    pub sequence: u64,
+    pub actor: Pubkey,
+    /// Um dos codigos de `audit_action`.
+    pub action: u8,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+impl AuditEntry {
+    pub const LEN: usize = 8 + 32 + 1 + 8 + 8;
+}
+
+#[account]
+pub struct AuditLog {
+    pub vesting_contract: Pubkey,
+    pub bump: u8,
+    /// Total de entradas ja registradas, inclusive as sobrescritas.
+    pub total_entries: u64,
+    pub entries: Vec<AuditEntry>,
+}
+
+impl AuditLog {
+    pub const LEN: usize =
+        8 + // discriminator
+        32 + // vesting_contract
+        1 + // bump
+        8 + // total_entries
+        4 + AUDIT_LOG_CAPACITY * AuditEntry::LEN; // entries
+
+    /// Posicao no buffer da entrada `sequence`.
+    pub fn slot(sequence: u64) -> usize {
+        (sequence % AUDIT_LOG_CAPACITY as u64) as usize
+    }
+
+    /// Sequencia da entrada mais antiga ainda no buffer.
+    pub fn oldest_sequence(&self) -> u64 {
+        self.total_entries.saturating_sub(self.entries.len() as u64)
+    }
+}
+
+/// Resultado de `read_audit_log`, em ordem cronologica.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct AuditLogPage {
+    pub total_entries: u64,
+    pub oldest_sequence: u64,
+    pub entries: Vec<AuditEntry>,
+}
+
+// -------------------------------------------------------------------------
+// EVENTS
+// -------------------------------------------------------------------------
+
+#[event]
+pub struct AuditLogCreated {
+    pub contract_id: u64,
+    pub audit_log: Pubkey,
+    pub timestamp: i64,
+}
+
+// -------------------------------------------------------------------------
+// CONTEXTS
+// -------------------------------------------------------------------------
+
+/// Cria o log de um contrato existente; as operacoes anteriores nao sao registradas.
+#[derive(Accounts)]
+pub struct CreateAuditLog<'info> {
+    #[account(
+        init,
+        payer = creator,
+        seeds = [b"audit_log", vesting_contract.key().as_ref()],
+        bump,
+        space = AuditLog::LEN
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+
+    #[account(
+        mut,
+        has_one = creator @ VestingError::Unauthorized
+    )]
+    pub vesting_contract: Account<'info, VestingContract>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ReadAuditLog<'info> {
+    #[account(
+        seeds = [b"audit_log", audit_log.vesting_contract.as_ref()],
+        bump = audit_log.bump
+    )]
+    pub audit_log: Account<'info, AuditLog>,
+}
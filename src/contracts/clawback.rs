@@ -0,0 +1,211 @@
+//! Clawback por motivos de compliance.
+//!
+//! Criador e beneficiario definem juntos a `compliance_authority` do contrato. Ela
+//! pode retirar qualquer parte do saldo ainda nao pago ao beneficiario, inclusive o
+//! ja liberado e nao resgatado, para uma conta indicada. O valor sai de
+//! `total_amount` (o cronograma passa a liberar o saldo reduzido) e e somado em
+//! `clawed_back_amount`; `released_amount` continua sendo apenas o que o
+//! beneficiario recebeu. Cada retirada emite um `Clawback` via `emit_cpi!` com o
+//! codigo do motivo e o memo para auditoria.
+
+use super::*;
+
+/// Tamanho maximo do memo do clawback, em bytes.
+pub const MAX_CLAWBACK_MEMO_LEN: usize = 200;
+
+/// Codigos de motivo aceitos por `clawback`.
+pub mod clawback_reason {
+    pub const SANCTIONS: u8 = 1;
+    pub const COURT_ORDER: u8 = 2;
+    pub const FRAUD: u8 = 3;
+    pub const REGULATORY_REQUEST: u8 = 4;
+    pub const TERMINATION_FOR_CAUSE: u8 = 5;
+    pub const OTHER: u8 = 255;
+
+    pub fn is_valid(reason_code: u8) -> bool {
+        matches!(
+            reason_code,
+            SANCTIONS | COURT_ORDER | FRAUD | REGULATORY_REQUEST | TERMINATION_FOR_CAUSE | OTHER
+        )
+    }
+}
+
+pub(crate) fn set_authority(
+    ctx: Context<SetComplianceAuthority>,
+    compliance_authority: Pubkey,
+) -> Result<()> {
+    let vesting_contract = &mut ctx.accounts.vesting_contract;
+    vesting_contract.compliance_authority = compliance_authority;
+
+    emit!(ComplianceAuthorityUpdated {
+        contract_id: vesting_contract.contract_id,
+        compliance_authority,
+    });
+
+    Ok(())
+}
+
+pub(crate) fn clawback<'info>(
+    ctx: Context<'_, '_, '_, 'info, ClawbackTokens<'info>>,
+    amount: u64,
+    reason_code: u8,
+    memo: String,
+) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp;
+
+    require!(
+        clawback_reason::is_valid(reason_code),
+        VestingError::InvalidClawbackReason
+    );
+    require!(
+        memo.len() <= MAX_CLAWBACK_MEMO_LEN,
+        VestingError::ClawbackMemoTooLong
+    );
+    require!(amount > 0, VestingError::InvalidAmount);
+
+    {
+        let vesting_contract = &mut ctx.accounts.vesting_contract;
+        let outstanding = vesting_contract
+            .total_amount
+            .saturating_sub(vesting_contract.released_amount);
+        require!(
+            amount <= outstanding,
+            VestingError::InsufficientEscrowBalance
+        );
+
+        vesting_contract.total_amount -= amount;
+        vesting_contract.clawed_back_amount = vesting_contract
+            .clawed_back_amount
+            .checked_add(amount)
+            .ok_or(VestingError::MathOverflow)?;
+    }
+
+    if ctx.accounts.vesting_contract.is_native {
+        require!(
+            ctx.accounts.destination.is_writable,
+            VestingError::RecipientNotWritable
+        );
+    }
+
+    ContractPayout {
+        vesting_contract: &ctx.accounts.vesting_contract,
+        lamports_recipient: ctx.accounts.destination.to_account_info(),
+        escrow_wallet: ctx.accounts.escrow_wallet.as_ref(),
+        destination: ctx.accounts.destination_token_account.as_ref(),
+        mint: ctx.accounts.mint.as_ref(),
+        token_program: ctx.accounts.token_program.as_ref(),
+        remaining_accounts: ctx.remaining_accounts,
+    }
+    .pay(amount)?;
+
+    audit_log::record(
+        &ctx.accounts.vesting_contract,
+        ctx.accounts.audit_log.as_mut(),
+        ctx.accounts.compliance_authority.key(),
+        audit_action::CLAWBACK,
+        amount,
+    )?;
+
+    emit_cpi!(Clawback {
+        contract_id: ctx.accounts.vesting_contract.contract_id,
+        vesting_contract: ctx.accounts.vesting_contract.key(),
+        compliance_authority: ctx.accounts.compliance_authority.key(),
+        destination: ctx.accounts.destination.key(),
+        amount,
+        reason_code,
+        memo,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
+
+// -------------------------------------------------------------------------
+// EVENTS
+// -------------------------------------------------------------------------
+
+#[event]
+pub struct ComplianceAuthorityUpdated {
+    pub contract_id: u64,
+    pub compliance_authority: Pubkey,
+}
+
+#[event]
+pub struct Clawback {
+    pub contract_id: u64,
+    pub vesting_contract: Pubkey,
+    pub compliance_authority: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub reason_code: u8,
+    pub memo: String,
+    pub timestamp: i64,
+}
+
+// -------------------------------------------------------------------------
+// CONTEXTS
+// -------------------------------------------------------------------------
+
+/// Exige a assinatura do criador e do beneficiario: o clawback alcanca valores ja
+/// liberados.
+#[derive(Accounts)]
+pub struct SetComplianceAuthority<'info> {
+    #[account(
+        mut,
+        has_one = creator @ VestingError::Unauthorized,
+        has_one = beneficiary @ VestingError::Unauthorized,
+        constraint = !vesting_contract.is_cancelled @ VestingError::ContractCancelled
+    )]
+    pub vesting_contract: Account<'info, VestingContract>,
+
+    pub creator: Signer<'info>,
+    pub beneficiary: Signer<'info>,
+}
+
+/// As contas de token sao opcionais para permitir contratos nativos (`is_native`),
+/// em que `destination` recebe os lamports.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClawbackTokens<'info> {
+    #[account(
+        mut,
+        has_one = compliance_authority @ VestingError::Unauthorized,
+        constraint = !vesting_contract.is_cancelled @ VestingError::ContractCancelled
+    )]
+    pub vesting_contract: Account<'info, VestingContract>,
+
+    pub compliance_authority: Signer<'info>,
+
+    /// CHECK: Dono da conta de destino (ou destino dos lamports em contratos nativos)
+    pub destination: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", vesting_contract.key().as_ref()],
+        bump,
+        constraint = escrow_wallet.owner == vesting_contract.key() @ VestingError::Unauthorized,
+        constraint = escrow_wallet.mint == vesting_contract.mint @ VestingError::InvalidAmount
+    )]
+    pub escrow_wallet: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = destination_token_account.mint == vesting_contract.mint @ VestingError::InvalidAmount,
+        constraint = destination_token_account.owner == destination.key() @ VestingError::Unauthorized
+    )]
+    pub destination_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        constraint = mint.key() == vesting_contract.mint @ VestingError::InvalidAmount
+    )]
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
+    /// Presente se o contrato tiver `AuditLog` (obrigatorio quando `has_audit_log`).
+    #[account(
+        mut,
+        seeds = [b"audit_log", vesting_contract.key().as_ref()],
+        bump = audit_log.bump
+    )]
+    pub audit_log: Option<Account<'info, AuditLog>>,
+}
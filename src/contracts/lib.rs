@@ -1,8 +1,49 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::Token;
-use anchor_spl::token_2022::Token2022;
-use anchor_spl::associated_token::AssociatedToken;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_lang::system_program;
+use anchor_spl::token::spl_token::native_mint;
+use anchor_spl::token_2022::spl_token_2022;
 use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface};
+use spl_tlv_account_resolution::state::ExtraAccountMetaList;
+use spl_token_2022::extension::{
+    default_account_state::DefaultAccountState, mint_close_authority::MintCloseAuthority,
+    permanent_delegate::PermanentDelegate, transfer_hook, BaseStateWithExtensions, ExtensionType,
+    StateWithExtensions,
+};
+use spl_token_2022::state::AccountState;
+use spl_transfer_hook_interface::get_extra_account_metas_address;
+use spl_transfer_hook_interface::instruction::ExecuteInstruction;
+
+pub mod audit_log;
+pub mod clawback;
+pub mod migration;
+pub mod milestones;
+pub mod multisig;
+pub mod oracle;
+pub mod pool;
+pub mod price_trigger;
+pub mod projection;
+pub mod receipt;
+pub mod staking;
+pub mod stream;
+pub mod timelock;
+pub mod voter_weight;
+
+pub use audit_log::*;
+pub use clawback::*;
+pub use migration::*;
+pub use milestones::*;
+pub use multisig::*;
+pub use oracle::*;
+pub use pool::*;
+pub use price_trigger::*;
+pub use projection::*;
+pub use receipt::*;
+pub use staking::*;
+pub use stream::*;
+pub use timelock::*;
+pub use voter_weight::*;
 
 declare_id!("DE9UHAY6UhxYfMTGBwzCoDRHphV6Xrcee8z1L8xJqydy");
 
@@ -10,13 +51,20 @@ declare_id!("DE9UHAY6UhxYfMTGBwzCoDRHphV6Xrcee8z1L8xJqydy");
 pub mod verum_vesting {
     use super::*;
 
-    pub fn create_vesting(
-        ctx: Context<CreateVesting>,
+    /// Mints Token-2022 com a extensao TransferHook exigem que o cliente envie, em
+    /// `remaining_accounts`, a `ExtraAccountMetaList` do hook, o programa do hook e
+    /// as contas extras nela declaradas. O mesmo vale para `claim_tokens` e `cancel_vesting`.
+    ///
+    /// `allowed_mint_flags` e a politica do criador: qualquer caracteristica de
+    /// `mint_flags::DANGEROUS` detectada no mint e ausente dessa mascara rejeita a criacao.
+    pub fn create_vesting<'info>(
+        ctx: Context<'_, '_, '_, 'info, CreateVesting<'info>>,
         contract_id: u64,
         total_amount: u64,
         start_time: i64,
         end_time: i64,
         vesting_type: VestingType,
+        allowed_mint_flags: u16,
     ) -> Result<()> {
         require!(end_time > start_time, VestingError::InvalidTimeRange);
         require!(total_amount > 0, VestingError::InvalidAmount);
@@ -28,6 +76,12 @@ pub mod verum_vesting {
         // Detecta se é Token-2022 ou SPL Token via owner do mint
         let is_token_2022 = mint_info.owner == &anchor_spl::token_2022::ID;
 
+        let detected_mint_flags = inspect_mint_flags(&mint_info)?;
+        require!(
+            detected_mint_flags & mint_flags::DANGEROUS & !allowed_mint_flags == 0,
+            VestingError::DangerousMintExtension
+        );
+
         vesting_contract.creator = ctx.accounts.creator.key();
         vesting_contract.beneficiary = ctx.accounts.beneficiary.key();
         vesting_contract.mint = mint_key;
@@ -39,6 +93,22 @@ pub mod verum_vesting {
         vesting_contract.contract_id = contract_id;
         vesting_contract.is_cancelled = false;
         vesting_contract.is_token_2022 = is_token_2022;
+        vesting_contract.mint_flags = detected_mint_flags;
+        vesting_contract.is_native = false;
+        vesting_contract.pause_policy = 0;
+        vesting_contract.paused_at = 0;
+        vesting_contract.paused_duration = 0;
+        vesting_contract.pause_count = 0;
+        vesting_contract.unlocked_amount = 0;
+        vesting_contract.staking_program = Pubkey::default();
+        vesting_contract.staked_amount = 0;
+        vesting_contract.compliance_authority = Pubkey::default();
+        vesting_contract.clawed_back_amount = 0;
+        vesting_contract.timelock_delay = 0;
+        vesting_contract.guardian = Pubkey::default();
+        vesting_contract.has_audit_log = false;
+        vesting_contract.claim_count = 0;
+        vesting_contract.beneficiary_updated_slot = 0;
 
         // Transferência compatível via Interface
         let decimals = ctx.accounts.mint.decimals;
@@ -51,46 +121,153 @@ pub mod verum_vesting {
         };
 
         let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts)
+            .with_remaining_accounts(ctx.remaining_accounts.to_vec());
 
-        token_interface::transfer_checked(cpi_ctx, total_amount, decimals)?;
+        transfer_checked_with_hook(cpi_ctx, total_amount, decimals)?;
 
         // Captura o valor real recebido (Essencial para tokens com Transfer Fee / Extensões)
         ctx.accounts.escrow_wallet.reload()?;
         vesting_contract.total_amount = ctx.accounts.escrow_wallet.amount;
 
-        emit!(VestingCreated {
+        if let Some(audit_log) = ctx.accounts.audit_log.as_mut() {
+            audit_log::initialize(vesting_contract, audit_log, ctx.bumps.audit_log);
+        }
+        audit_log::record(
+            vesting_contract,
+            ctx.accounts.audit_log.as_mut(),
+            ctx.accounts.creator.key(),
+            audit_action::CREATE,
+            vesting_contract.total_amount,
+        )?;
+
+        let total_amount = vesting_contract.total_amount;
+        let vesting_contract = vesting_contract.key();
+        emit_cpi!(VestingCreated {
             contract_id,
             creator: ctx.accounts.creator.key(),
             beneficiary: ctx.accounts.beneficiary.key(),
             mint: mint_key,
-            total_amount: vesting_contract.total_amount,
+            total_amount,
+            start_time,
+            end_time,
+            mint_flags: detected_mint_flags,
+            vesting_contract,
+            vesting_type,
+            slot: Clock::get()?.slot,
+        });
+
+        Ok(())
+    }
+
+    /// Vesting em SOL nativo: os lamports ficam no proprio PDA do contrato, acima do
+    /// minimo de isencao de aluguel. O mint registrado e o `native_mint` (apenas como
+    /// seed), e `claim_tokens`/`cancel_vesting` pagam em lamports sem contas de token.
+    pub fn create_native_vesting(
+        ctx: Context<CreateNativeVesting>,
+        contract_id: u64,
+        total_amount: u64,
+        start_time: i64,
+        end_time: i64,
+        vesting_type: VestingType,
+    ) -> Result<()> {
+        require!(end_time > start_time, VestingError::InvalidTimeRange);
+        require!(total_amount > 0, VestingError::InvalidAmount);
+
+        let vesting_contract = &mut ctx.accounts.vesting_contract;
+
+        vesting_contract.creator = ctx.accounts.creator.key();
+        vesting_contract.beneficiary = ctx.accounts.beneficiary.key();
+        vesting_contract.mint = native_mint::ID;
+        vesting_contract.total_amount = total_amount;
+        vesting_contract.released_amount = 0;
+        vesting_contract.start_time = start_time;
+        vesting_contract.end_time = end_time;
+        vesting_contract.vesting_type = vesting_type;
+        vesting_contract.bump = ctx.bumps.vesting_contract;
+        vesting_contract.contract_id = contract_id;
+        vesting_contract.is_cancelled = false;
+        vesting_contract.is_token_2022 = false;
+        vesting_contract.mint_flags = 0;
+        vesting_contract.is_native = true;
+        vesting_contract.pause_policy = 0;
+        vesting_contract.paused_at = 0;
+        vesting_contract.paused_duration = 0;
+        vesting_contract.pause_count = 0;
+        vesting_contract.unlocked_amount = 0;
+        vesting_contract.staking_program = Pubkey::default();
+        vesting_contract.staked_amount = 0;
+        vesting_contract.compliance_authority = Pubkey::default();
+        vesting_contract.clawed_back_amount = 0;
+        vesting_contract.timelock_delay = 0;
+        vesting_contract.guardian = Pubkey::default();
+        vesting_contract.has_audit_log = false;
+        vesting_contract.claim_count = 0;
+        vesting_contract.beneficiary_updated_slot = 0;
+
+        let cpi_accounts = system_program::Transfer {
+            from: ctx.accounts.creator.to_account_info(),
+            to: vesting_contract.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+        system_program::transfer(cpi_ctx, total_amount)?;
+
+        if let Some(audit_log) = ctx.accounts.audit_log.as_mut() {
+            audit_log::initialize(vesting_contract, audit_log, ctx.bumps.audit_log);
+        }
+        audit_log::record(
+            vesting_contract,
+            ctx.accounts.audit_log.as_mut(),
+            ctx.accounts.creator.key(),
+            audit_action::CREATE,
+            total_amount,
+        )?;
+
+        let vesting_contract = vesting_contract.key();
+        emit_cpi!(VestingCreated {
+            contract_id,
+            creator: ctx.accounts.creator.key(),
+            beneficiary: ctx.accounts.beneficiary.key(),
+            mint: native_mint::ID,
+            total_amount,
             start_time,
             end_time,
+            mint_flags: 0,
+            vesting_contract,
+            vesting_type,
+            slot: Clock::get()?.slot,
         });
 
         Ok(())
     }
 
-    pub fn claim_tokens(ctx: Context<ClaimTokens>) -> Result<()> {
+    /// Em contratos nativos (`is_native`) as contas de token podem ser omitidas e o
+    /// `beneficiary` deve vir como writable para receber os lamports. Em contratos
+    /// `PriceTriggered`, `price_trigger` e `price_feed` sao opcionais: quando enviados,
+    /// o preco e observado antes do calculo se estiver valido; as parcelas ja
+    /// liberadas sao pagas mesmo com o oraculo desatualizado ou indisponivel.
+    pub fn claim_tokens<'info>(ctx: Context<'_, '_, '_, 'info, ClaimTokens<'info>>) -> Result<()> {
         let current_time = Clock::get()?.unix_timestamp;
 
-        let (releasable, signer_seeds_data) = {
+        let releasable = {
             let vesting_contract = &mut ctx.accounts.vesting_contract;
 
             require!(!vesting_contract.is_cancelled, VestingError::ContractCancelled);
 
-            let vested_amount = calculate_vested_amount(
-                current_time,
-                vesting_contract.start_time,
-                vesting_contract.end_time,
-                vesting_contract.total_amount,
-                &vesting_contract.vesting_type,
-            );
+            if vesting_contract.vesting_type == VestingType::PriceTriggered {
+                if let (Some(price_trigger), Some(price_feed)) = (
+                    ctx.accounts.price_trigger.as_mut(),
+                    ctx.accounts.price_feed.as_ref(),
+                ) {
+                    price_trigger::try_observe_price(vesting_contract, price_trigger, price_feed)?;
+                }
+            }
 
-            let releasable = vested_amount
-                .checked_sub(vesting_contract.released_amount)
-                .ok_or(VestingError::MathOverflow)?;
+            let vested_amount = vesting_contract.vested_amount(current_time);
+
+            // Um clawback reduz `total_amount`, e o valor liberado pode ficar abaixo
+            // de `released_amount` ate o cronograma alcanca-lo
+            let releasable = vested_amount.saturating_sub(vesting_contract.released_amount);
 
             require!(releasable > 0, VestingError::NothingToRelease);
 
@@ -99,205 +276,910 @@ pub mod verum_vesting {
                 .checked_add(releasable)
                 .ok_or(VestingError::MathOverflow)?;
 
-            (
-                releasable,
-                (
-                    vesting_contract.creator,
-                    vesting_contract.mint,
-                    vesting_contract.contract_id,
-                    vesting_contract.bump,
-                )
-            )
+            releasable
         };
 
-        let (creator_key, mint_key, contract_id, bump) = signer_seeds_data;
-        let id_bytes = contract_id.to_le_bytes();
-
-        let seeds = &[
-            b"vesting",
-            creator_key.as_ref(),
-            mint_key.as_ref(),
-            id_bytes.as_ref(),
-            &[bump],
-        ];
-        let signer = &[&seeds[..]];
+        let claim_index = ctx.accounts.vesting_contract.claim_count;
+        ctx.accounts.vesting_contract.claim_count = claim_index
+            .checked_add(1)
+            .ok_or(VestingError::MathOverflow)?;
 
-        let decimals = ctx.accounts.mint.decimals;
+        let contract_id = ctx.accounts.vesting_contract.contract_id;
 
-        let cpi_accounts = token_interface::TransferChecked {
-            from: ctx.accounts.escrow_wallet.to_account_info(),
-            mint: ctx.accounts.mint.to_account_info(),
-            to: ctx.accounts.beneficiary_token_account.to_account_info(),
-            authority: ctx.accounts.vesting_contract.to_account_info(),
-        };
-
-        let cpi_ctx = CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            cpi_accounts,
-            signer,
-        );
+        if ctx.accounts.vesting_contract.is_native {
+            require!(
+                ctx.accounts.beneficiary.is_writable,
+                VestingError::RecipientNotWritable
+            );
+        }
 
-        token_interface::transfer_checked(cpi_ctx, releasable, decimals)?;
+        ContractPayout {
+            vesting_contract: &ctx.accounts.vesting_contract,
+            lamports_recipient: ctx.accounts.beneficiary.to_account_info(),
+            escrow_wallet: ctx.accounts.escrow_wallet.as_ref(),
+            destination: ctx.accounts.beneficiary_token_account.as_ref(),
+            mint: ctx.accounts.mint.as_ref(),
+            token_program: ctx.accounts.token_program.as_ref(),
+            remaining_accounts: ctx.remaining_accounts,
+        }
+        .pay(releasable)?;
+
+        audit_log::record(
+            &ctx.accounts.vesting_contract,
+            ctx.accounts.audit_log.as_mut(),
+            ctx.accounts.beneficiary.key(),
+            audit_action::CLAIM,
+            releasable,
+        )?;
+
+        if let Some(release_receipt) = ctx.accounts.release_receipt.as_mut() {
+            let destination = match ctx.accounts.beneficiary_token_account.as_ref() {
+                Some(token_account) => token_account.key(),
+                None => ctx.accounts.beneficiary.key(),
+            };
+            receipt::issue(
+                release_receipt,
+                ctx.accounts.vesting_contract.key(),
+                claim_index,
+                destination,
+                releasable,
+                ctx.bumps.release_receipt,
+            )?;
+        }
 
-        emit!(TokensClaimed {
+        let vesting_contract = &ctx.accounts.vesting_contract;
+        let event = TokensClaimed {
             contract_id,
             beneficiary: ctx.accounts.beneficiary.key(),
             amount: releasable,
             timestamp: current_time,
-        });
+            vesting_contract: vesting_contract.key(),
+            released_amount: vesting_contract.released_amount,
+            remaining_amount: vesting_contract
+                .total_amount
+                .saturating_sub(vesting_contract.released_amount),
+            start_time: vesting_contract.start_time,
+            end_time: vesting_contract.end_time,
+            slot: Clock::get()?.slot,
+        };
+        emit_cpi!(event);
 
         Ok(())
     }
 
+    /// Com timelock ativo (`timelock_delay > 0`) a troca deve passar por
+    /// `propose_action`.
     pub fn update_beneficiary(ctx: Context<UpdateBeneficiary>) -> Result<()> {
         let vesting_contract = &mut ctx.accounts.vesting_contract;
 
         require!(!vesting_contract.is_cancelled, VestingError::ContractCancelled);
-        require!(vesting_contract.released_amount == 0, VestingError::AlreadyClaimed);
+        require!(vesting_contract.timelock_delay == 0, VestingError::TimelockRequired);
+
+        let event = replace_beneficiary(vesting_contract, ctx.accounts.new_beneficiary.key())?;
+
+        audit_log::record(
+            &ctx.accounts.vesting_contract,
+            ctx.accounts.audit_log.as_mut(),
+            ctx.accounts.creator.key(),
+            audit_action::UPDATE_BENEFICIARY,
+            0,
+        )?;
+
+        emit_cpi!(event);
+
+        Ok(())
+    }
+
+    /// Define quem pode pausar o contrato (`pause_policy`). Exige a assinatura do
+    /// criador e do beneficiario, ja que a pausa afeta os dois lados.
+    pub fn set_pause_policy(ctx: Context<SetPausePolicy>, pause_policy: u8) -> Result<()> {
+        require!(
+            pause_policy & !pause_policy::ALL == 0,
+            VestingError::PauseNotAllowed
+        );
 
-        let old_beneficiary = vesting_contract.beneficiary;
-        vesting_contract.beneficiary = ctx.accounts.new_beneficiary.key();
+        let vesting_contract = &mut ctx.accounts.vesting_contract;
+        vesting_contract.pause_policy = pause_policy;
 
-        emit!(BeneficiaryUpdated {
+        emit!(PausePolicyUpdated {
             contract_id: vesting_contract.contract_id,
-            old_beneficiary,
-            new_beneficiary: ctx.accounts.new_beneficiary.key(),
+            pause_policy,
         });
 
         Ok(())
     }
 
-    pub fn cancel_vesting(ctx: Context<CancelVesting>) -> Result<()> {
+    /// Congela o acumulo de um contrato liberado por tempo. Inicio, cliff e fim
+    /// gravados nao mudam: o tempo pausado dentro do cronograma entra em
+    /// `paused_duration` e e descontado do instante usado no calculo (`VestingContract::effective_time`). Contratos
+    /// liberados por evento (`Milestones`, `PriceTriggered`) nao podem ser pausados.
+    pub fn pause_vesting(ctx: Context<PauseVesting>) -> Result<()> {
         let current_time = Clock::get()?.unix_timestamp;
+        let authority = ctx.accounts.authority.key();
+        let vesting_contract = &mut ctx.accounts.vesting_contract;
 
-        let (escrow_balance, signer_seeds_data) = {
-            let vesting_contract = &mut ctx.accounts.vesting_contract;
+        require!(!vesting_contract.is_cancelled, VestingError::ContractCancelled);
+        require!(
+            !vesting_contract.vesting_type.is_event_driven(),
+            VestingError::UnsupportedVestingType
+        );
+        require!(
+            vesting_contract.can_pause(&authority),
+            VestingError::PauseNotAllowed
+        );
+        require!(!vesting_contract.is_paused(), VestingError::AlreadyPaused);
 
-            require!(!vesting_contract.is_cancelled, VestingError::ContractCancelled);
+        vesting_contract.paused_at = current_time;
+        vesting_contract.pause_count = vesting_contract
+            .pause_count
+            .checked_add(1)
+            .ok_or(VestingError::MathOverflow)?;
 
-            let balance = ctx.accounts.escrow_wallet.amount;
-            vesting_contract.is_cancelled = true;
-            vesting_contract.released_amount = vesting_contract.total_amount;
-
-            (
-                balance,
-                (
-                    vesting_contract.creator,
-                    vesting_contract.mint,
-                    vesting_contract.contract_id,
-                    vesting_contract.bump,
-                )
-            )
-        };
+        emit!(VestingPaused {
+            contract_id: vesting_contract.contract_id,
+            authority,
+            timestamp: current_time,
+        });
 
-        if escrow_balance > 0 {
-            let (creator_key, mint_key, contract_id, bump) = signer_seeds_data;
-            let id_bytes = contract_id.to_le_bytes();
-
-            let seeds = &[
-                b"vesting",
-                creator_key.as_ref(),
-                mint_key.as_ref(),
-                id_bytes.as_ref(),
-                &[bump],
-            ];
-            let signer = &[&seeds[..]];
-
-            let decimals = ctx.accounts.mint.decimals;
-
-            let cpi_accounts = token_interface::TransferChecked {
-                from: ctx.accounts.escrow_wallet.to_account_info(),
-                mint: ctx.accounts.mint.to_account_info(),
-                to: ctx.accounts.creator_token_account.to_account_info(),
-                authority: ctx.accounts.vesting_contract.to_account_info(),
-            };
+        Ok(())
+    }
 
-            let cpi_program = ctx.accounts.token_program.to_account_info();
-            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
-            
-            token_interface::transfer_checked(cpi_ctx, escrow_balance, decimals)?;
-        }
+    pub fn resume_vesting(ctx: Context<PauseVesting>) -> Result<()> {
+        let current_time = Clock::get()?.unix_timestamp;
+        let authority = ctx.accounts.authority.key();
+        let vesting_contract = &mut ctx.accounts.vesting_contract;
 
-        emit!(VestingCancelled {
-            contract_id: ctx.accounts.vesting_contract.contract_id,
-            creator: ctx.accounts.creator.key(),
-            remaining_amount: escrow_balance,
+        require!(!vesting_contract.is_cancelled, VestingError::ContractCancelled);
+        require!(
+            vesting_contract.can_pause(&authority),
+            VestingError::PauseNotAllowed
+        );
+        require!(vesting_contract.is_paused(), VestingError::NotPaused);
+
+        let paused_at = vesting_contract.paused_at;
+        let interval = vesting_contract.ongoing_pause(current_time);
+        vesting_contract.paused_duration = vesting_contract
+            .paused_duration
+            .checked_add(interval)
+            .ok_or(VestingError::MathOverflow)?;
+        vesting_contract.paused_at = 0;
+
+        emit!(VestingResumed {
+            contract_id: vesting_contract.contract_id,
+            authority,
+            paused_at,
             timestamp: current_time,
+            paused_duration: vesting_contract.paused_duration,
         });
 
         Ok(())
     }
+
+    /// Em contratos nativos o saldo acima da isencao de aluguel volta em lamports ao
+    /// `creator`, e a conta do contrato permanece aberta como registro. Tokens em
+    /// staking precisam voltar ao escrow (`unstake_locked`) antes. Com timelock ativo
+    /// o cancelamento deve passar por `propose_action`.
+    pub fn cancel_vesting<'info>(ctx: Context<'_, '_, '_, 'info, CancelVesting<'info>>) -> Result<()> {
+        require!(
+            ctx.accounts.vesting_contract.timelock_delay == 0,
+            VestingError::TimelockRequired
+        );
+
+        let event = cancel_contract(
+            &mut ctx.accounts.vesting_contract,
+            ctx.accounts.creator.to_account_info(),
+            ctx.accounts.escrow_wallet.as_ref(),
+            ctx.accounts.creator_token_account.as_ref(),
+            ctx.accounts.mint.as_ref(),
+            ctx.accounts.token_program.as_ref(),
+            ctx.remaining_accounts,
+        )?;
+
+        audit_log::record(
+            &ctx.accounts.vesting_contract,
+            ctx.accounts.audit_log.as_mut(),
+            ctx.accounts.creator.key(),
+            audit_action::CANCEL,
+            event.remaining_amount,
+        )?;
+
+        emit_cpi!(event);
+
+        Ok(())
+    }
+
+    /// Pool com varios beneficiarios sobre um unico escrow; `members` define as
+    /// participacoes em basis points, que devem somar 10000.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_vesting_pool<'info>(
+        ctx: Context<'_, '_, '_, 'info, CreateVestingPool<'info>>,
+        pool_id: u64,
+        total_amount: u64,
+        start_time: i64,
+        end_time: i64,
+        vesting_type: VestingType,
+        allowed_mint_flags: u16,
+        members: Vec<PoolMemberInit>,
+    ) -> Result<()> {
+        pool::create(
+            ctx,
+            pool_id,
+            total_amount,
+            start_time,
+            end_time,
+            vesting_type,
+            allowed_mint_flags,
+            members,
+        )
+    }
+
+    pub fn claim_pool_share<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClaimPoolShare<'info>>,
+    ) -> Result<()> {
+        pool::claim_share(ctx)
+    }
+
+    pub fn reassign_pool_share(
+        ctx: Context<ReassignPoolShare>,
+        departed: Pubkey,
+        new_beneficiary: Pubkey,
+    ) -> Result<()> {
+        pool::reassign_share(ctx, departed, new_beneficiary)
+    }
+
+    /// Devolve ao criador o residuo de arredondamento do escrow do pool, depois que o
+    /// cronograma terminou e todas as participacoes foram resgatadas.
+    pub fn sweep_pool_dust<'info>(
+        ctx: Context<'_, '_, '_, 'info, SweepPoolDust<'info>>,
+    ) -> Result<()> {
+        pool::sweep_dust(ctx)
+    }
+
+    /// Stream com taxa por segundo e sem data final; pausa sozinho quando o saldo
+    /// depositado termina e retoma no proximo `top_up_stream`.
+    pub fn create_stream<'info>(
+        ctx: Context<'_, '_, '_, 'info, CreateStream<'info>>,
+        stream_id: u64,
+        rate_per_second: u64,
+        start_time: i64,
+        initial_deposit: u64,
+        allowed_mint_flags: u16,
+    ) -> Result<()> {
+        stream::create(
+            ctx,
+            stream_id,
+            rate_per_second,
+            start_time,
+            initial_deposit,
+            allowed_mint_flags,
+        )
+    }
+
+    pub fn top_up_stream<'info>(
+        ctx: Context<'_, '_, '_, 'info, TopUpStream<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        stream::top_up(ctx, amount)
+    }
+
+    pub fn withdraw_from_stream<'info>(
+        ctx: Context<'_, '_, '_, 'info, WithdrawFromStream<'info>>,
+    ) -> Result<()> {
+        stream::withdraw(ctx)
+    }
+
+    /// Encerra o stream: paga ao destinatario o acumulado, devolve o restante ao
+    /// pagador e fecha as contas.
+    pub fn cancel_stream<'info>(
+        ctx: Context<'_, '_, '_, 'info, CancelStream<'info>>,
+    ) -> Result<()> {
+        stream::cancel(ctx)
+    }
+
+    /// Define os marcos de um contrato `VestingType::Milestones`. A soma dos valores
+    /// deve ser igual a `total_amount`; `deadline` 0 desativa a devolucao por prazo.
+    pub fn create_milestone_schedule(
+        ctx: Context<CreateMilestoneSchedule>,
+        milestones: Vec<MilestoneInit>,
+        deadline: i64,
+    ) -> Result<()> {
+        milestones::create_schedule(ctx, milestones, deadline)
+    }
+
+    /// Assinada pelo attestor do marco; o valor aprovado passa a ser resgatavel via
+    /// `claim_tokens`.
+    pub fn approve_milestone(ctx: Context<ApproveMilestone>, index: u8) -> Result<()> {
+        milestones::approve(ctx, index)
+    }
+
+    pub fn refund_expired_milestones<'info>(
+        ctx: Context<'_, '_, '_, 'info, RefundExpiredMilestones<'info>>,
+    ) -> Result<()> {
+        milestones::refund_expired(ctx)
+    }
+
+    /// Define as parcelas de um contrato `VestingType::PriceTriggered` e o feed de
+    /// preco (formato Pyth) que as libera. A soma das parcelas deve ser `total_amount`.
+    pub fn create_price_trigger<'info>(
+        ctx: Context<'_, '_, '_, 'info, CreatePriceTrigger<'info>>,
+        config: PriceTriggerConfig,
+        tranches: Vec<PriceTrancheInit>,
+    ) -> Result<()> {
+        price_trigger::create(ctx, config, tranches)
+    }
+
+    /// Registra uma observacao do preco sem resgatar; usada por keepers para manter
+    /// a contagem de permanencia acima do limite.
+    pub fn refresh_price_trigger(ctx: Context<RefreshPriceTrigger>) -> Result<()> {
+        price_trigger::refresh(ctx)
+    }
+
+    /// Registra o plugin de peso de voto para um realm do SPL Governance; exige a
+    /// assinatura da autoridade do realm.
+    pub fn create_registrar(
+        ctx: Context<CreateRegistrar>,
+        lockup_bonus_bps: u16,
+        lockup_saturation_secs: i64,
+    ) -> Result<()> {
+        voter_weight::create_registrar(ctx, lockup_bonus_bps, lockup_saturation_secs)
+    }
+
+    pub fn create_voter_weight_record(ctx: Context<CreateVoterWeightRecord>) -> Result<()> {
+        voter_weight::create_record(ctx)
+    }
+
+    /// Recalcula o peso a partir dos `VestingContract`s em `remaining_accounts`. Deve
+    /// ser chamada na mesma transacao da acao de governanca (expira no slot atual).
+    pub fn update_voter_weight_record<'info>(
+        ctx: Context<'_, '_, 'info, 'info, UpdateVoterWeightRecord<'info>>,
+    ) -> Result<()> {
+        voter_weight::update_record(ctx)
+    }
+
+    /// Autoriza (ou, com `Pubkey::default()`, desativa) o programa de staking do
+    /// contrato. So pode mudar sem tokens em staking.
+    pub fn set_staking_program(ctx: Context<SetStakingProgram>, staking_program: Pubkey) -> Result<()> {
+        staking::set_program(ctx, staking_program)
+    }
+
+    /// Delega ao programa de staking ate o saldo ainda nao liberado do escrow.
+    pub fn stake_locked(ctx: Context<StakeLocked>, amount: u64) -> Result<()> {
+        staking::stake(ctx, amount)
+    }
+
+    pub fn unstake_locked(ctx: Context<UnstakeLocked>, amount: u64) -> Result<()> {
+        staking::unstake(ctx, amount)
+    }
+
+    pub fn claim_staking_rewards(ctx: Context<ClaimStakingRewards>) -> Result<()> {
+        staking::claim_rewards(ctx)
+    }
+
+    /// Define a autoridade de compliance que pode executar `clawback`
+    /// (`Pubkey::default()` desativa). Exige criador e beneficiario.
+    pub fn set_compliance_authority(
+        ctx: Context<SetComplianceAuthority>,
+        compliance_authority: Pubkey,
+    ) -> Result<()> {
+        clawback::set_authority(ctx, compliance_authority)
+    }
+
+    /// Retira `amount` do saldo ainda nao pago ao beneficiario (liberado ou nao)
+    /// para a conta indicada, registrando o motivo (`clawback_reason`) e o memo. O
+    /// valor sai de `total_amount`; o cronograma segue liberando o saldo reduzido.
+    pub fn clawback<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClawbackTokens<'info>>,
+        amount: u64,
+        reason_code: u8,
+        memo: String,
+    ) -> Result<()> {
+        clawback::clawback(ctx, amount, reason_code, memo)
+    }
+
+    /// Define o atraso das acoes administrativas e o `guardian` que pode veta-las
+    /// (`Pubkey::default()` desativa). Exige criador e beneficiario.
+    pub fn set_timelock(ctx: Context<SetTimelock>, delay: i64, guardian: Pubkey) -> Result<()> {
+        timelock::set_timelock(ctx, delay, guardian)
+    }
+
+    /// Registra uma acao do criador executavel apos `timelock_delay`, que deve ser
+    /// maior que zero.
+    pub fn propose_action(ctx: Context<ProposeAction>, action: TimelockedAction) -> Result<()> {
+        timelock::propose(ctx, action)
+    }
+
+    /// Aplica a acao pendente depois do atraso. Para `TimelockedAction::Cancel` as
+    /// contas seguem as de `cancel_vesting`.
+    pub fn execute_action<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteAction<'info>>,
+    ) -> Result<()> {
+        timelock::execute(ctx)
+    }
+
+    /// Descarta a acao pendente; assinada pelo beneficiario ou pelo `guardian`.
+    pub fn veto_action(ctx: Context<VetoAction>) -> Result<()> {
+        timelock::veto(ctx)
+    }
+
+    /// Descarta a acao pendente a pedido do criador, que a propos.
+    pub fn withdraw_action(ctx: Context<WithdrawAction>) -> Result<()> {
+        timelock::withdraw(ctx)
+    }
+
+    /// Cria uma autoridade M-de-N; o PDA `[b"multisig_signer", multisig]` passa a
+    /// poder atuar como `creator` via propostas.
+    pub fn create_multisig(
+        ctx: Context<CreateMultisig>,
+        multisig_id: u64,
+        members: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        multisig::create(ctx, multisig_id, members, threshold)
+    }
+
+    pub fn create_multisig_proposal(
+        ctx: Context<CreateMultisigProposal>,
+        action: MultisigAction,
+    ) -> Result<()> {
+        multisig::propose(ctx, action)
+    }
+
+    pub fn approve_multisig_proposal(ctx: Context<ApproveMultisigProposal>) -> Result<()> {
+        multisig::approve(ctx)
+    }
+
+    /// Executa a proposta aprovada; `remaining_accounts` sao as contas da instrucao
+    /// correspondente, com o PDA do multisig na posicao do `creator`.
+    pub fn execute_multisig_proposal<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteMultisigProposal<'info>>,
+    ) -> Result<()> {
+        multisig::execute(ctx)
+    }
+
+    /// Cria o `AuditLog` de um contrato ja existente.
+    pub fn create_audit_log(ctx: Context<CreateAuditLog>) -> Result<()> {
+        audit_log::create(ctx)
+    }
+
+    /// Leitura paginada do `AuditLog` (para simulacao): ate `limit` entradas a partir
+    /// da sequencia `start_sequence`, limitadas as ainda presentes no buffer.
+    pub fn read_audit_log(
+        ctx: Context<ReadAuditLog>,
+        start_sequence: u64,
+        limit: u8,
+    ) -> Result<AuditLogPage> {
+        audit_log::read(ctx, start_sequence, limit)
+    }
+
+    /// Calendario de liberacoes e curva com ate `points` amostras entre o inicio e o
+    /// fim do contrato (para simulacao).
+    pub fn project_schedule(
+        ctx: Context<ProjectSchedule>,
+        points: u8,
+    ) -> Result<ScheduleProjection> {
+        projection::read(ctx, points)
+    }
+
+    /// Realoca um contrato criado com um layout anterior do `VestingContract` para o
+    /// tamanho atual; o `payer` cobre o aluguel adicional.
+    pub fn migrate_contract(ctx: Context<MigrateContract>) -> Result<()> {
+        migration::migrate(ctx)
+    }
 }
 
 // -------------------------------------------------------------------------
 // LOGIC HELPERS
 // -------------------------------------------------------------------------
 
-fn calculate_vested_amount(
-    current_time: i64,
-    start: i64,
-    end: i64,
-    total: u64,
-    vesting_type: &VestingType,
-) -> u64 {
-    if current_time < start {
-        return 0;
+/// Equivalente a `token_interface::transfer_checked`, mas repassando as
+/// `remaining_accounts` do contexto. Se o mint tiver a extensao TransferHook, a
+/// `ExtraAccountMetaList` do hook e resolvida a partir dessas contas e anexada a CPI.
+fn transfer_checked_with_hook<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, token_interface::TransferChecked<'info>>,
+    amount: u64,
+    decimals: u8,
+) -> Result<()> {
+    let mut cpi_instruction = spl_token_2022::instruction::transfer_checked(
+        ctx.program.key,
+        ctx.accounts.from.key,
+        ctx.accounts.mint.key,
+        ctx.accounts.to.key,
+        ctx.accounts.authority.key,
+        &[],
+        amount,
+        decimals,
+    )?;
+
+    let hook_program_id = {
+        let mint_data = ctx.accounts.mint.try_borrow_data()?;
+        let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
+        transfer_hook::get_program_id(&mint)
+    };
+
+    let mut cpi_account_infos = vec![
+        ctx.accounts.from,
+        ctx.accounts.mint,
+        ctx.accounts.to,
+        ctx.accounts.authority,
+    ];
+
+    if let Some(hook_program_id) = hook_program_id {
+        add_transfer_hook_accounts(
+            &mut cpi_instruction,
+            &mut cpi_account_infos,
+            &hook_program_id,
+            amount,
+            &ctx.remaining_accounts,
+        )?;
     }
-    if current_time >= end {
-        return total;
+
+    invoke_signed(&cpi_instruction, &cpi_account_infos, ctx.signer_seeds).map_err(Into::into)
+}
+
+/// Anexa a CPI de transferencia as contas exigidas pelo hook. As contas extras sao
+/// resolvidas sobre uma instrucao `Execute` do proprio hook, para que PDAs declaradas
+/// na `ExtraAccountMetaList` sejam derivadas com o program id do hook.
+fn add_transfer_hook_accounts<'info>(
+    cpi_instruction: &mut Instruction,
+    cpi_account_infos: &mut Vec<AccountInfo<'info>>,
+    hook_program_id: &Pubkey,
+    amount: u64,
+    remaining_accounts: &[AccountInfo<'info>],
+) -> Result<()> {
+    let mint_key = *cpi_account_infos[1].key;
+    let validation_key = get_extra_account_metas_address(&mint_key, hook_program_id);
+
+    let validation_info = remaining_accounts
+        .iter()
+        .find(|info| *info.key == validation_key)
+        .ok_or(VestingError::MissingTransferHookAccounts)?;
+    let hook_program_info = remaining_accounts
+        .iter()
+        .find(|info| info.key == hook_program_id)
+        .ok_or(VestingError::MissingTransferHookAccounts)?;
+
+    let mut execute_instruction = spl_transfer_hook_interface::instruction::execute(
+        hook_program_id,
+        cpi_account_infos[0].key,
+        &mint_key,
+        cpi_account_infos[2].key,
+        cpi_account_infos[3].key,
+        &validation_key,
+        amount,
+    );
+    let mut execute_account_infos = cpi_account_infos.clone();
+    execute_account_infos.push(validation_info.clone());
+    let base_len = execute_account_infos.len();
+
+    ExtraAccountMetaList::add_to_cpi_instruction::<ExecuteInstruction>(
+        &mut execute_instruction,
+        &mut execute_account_infos,
+        &validation_info.try_borrow_data()?,
+        remaining_accounts,
+    )
+    .map_err(|_| VestingError::MissingTransferHookAccounts)?;
+
+    cpi_instruction
+        .accounts
+        .extend(execute_instruction.accounts.drain(base_len..));
+    cpi_account_infos.extend(execute_account_infos.drain(base_len..));
+
+    cpi_instruction
+        .accounts
+        .push(AccountMeta::new_readonly(validation_key, false));
+    cpi_account_infos.push(validation_info.clone());
+    cpi_instruction
+        .accounts
+        .push(AccountMeta::new_readonly(*hook_program_id, false));
+    cpi_account_infos.push(hook_program_info.clone());
+
+    Ok(())
+}
+
+/// Troca o beneficiario enquanto nada foi resgatado. Usada por `update_beneficiary`
+/// e por `TimelockedAction::UpdateBeneficiary`, que emitem o evento retornado via
+/// `emit_cpi!`.
+fn replace_beneficiary(
+    vesting_contract: &mut Account<VestingContract>,
+    new_beneficiary: Pubkey,
+) -> Result<BeneficiaryUpdated> {
+    require!(vesting_contract.released_amount == 0, VestingError::AlreadyClaimed);
+
+    let clock = Clock::get()?;
+    let old_beneficiary = vesting_contract.beneficiary;
+    vesting_contract.beneficiary = new_beneficiary;
+    vesting_contract.beneficiary_updated_slot = clock.slot;
+
+    Ok(BeneficiaryUpdated {
+        contract_id: vesting_contract.contract_id,
+        old_beneficiary,
+        new_beneficiary,
+        vesting_contract: vesting_contract.key(),
+        timestamp: clock.unix_timestamp,
+        slot: clock.slot,
+    })
+}
+
+/// Encerra o contrato e devolve ao criador o saldo do escrow. Usada por
+/// `cancel_vesting` e por `TimelockedAction::Cancel`, que emitem o evento retornado
+/// (com o valor devolvido em `remaining_amount`) via `emit_cpi!`.
+fn cancel_contract<'a, 'info>(
+    vesting_contract: &'a mut Account<'info, VestingContract>,
+    creator: AccountInfo<'info>,
+    escrow_wallet: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+    creator_token_account: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+    mint: Option<&'a InterfaceAccount<'info, Mint>>,
+    token_program: Option<&'a Interface<'info, TokenInterface>>,
+    remaining_accounts: &'a [AccountInfo<'info>],
+) -> Result<VestingCancelled> {
+    let clock = Clock::get()?;
+
+    require!(!vesting_contract.is_cancelled, VestingError::ContractCancelled);
+    require!(vesting_contract.staked_amount == 0, VestingError::TokensStaked);
+
+    let escrow_balance = if vesting_contract.is_native {
+        native_escrow_balance(&vesting_contract.to_account_info())?
+    } else {
+        escrow_wallet
+            .ok_or(VestingError::MissingTokenAccounts)?
+            .amount
+    };
+    let released_amount = vesting_contract.released_amount;
+    vesting_contract.is_cancelled = true;
+    vesting_contract.released_amount = vesting_contract.total_amount;
+
+    if escrow_balance > 0 {
+        ContractPayout {
+            vesting_contract,
+            lamports_recipient: creator.clone(),
+            escrow_wallet,
+            destination: creator_token_account,
+            mint,
+            token_program,
+            remaining_accounts,
+        }
+        .pay(escrow_balance)?;
     }
 
-    match vesting_type {
-        VestingType::Linear => {
-            let duration = end.saturating_sub(start) as u128;
-            let elapsed = current_time.saturating_sub(start) as u128;
-            ((total as u128)
-                .saturating_mul(elapsed)
-                .saturating_div(duration)) as u64
+    Ok(VestingCancelled {
+        contract_id: vesting_contract.contract_id,
+        creator: creator.key(),
+        remaining_amount: escrow_balance,
+        timestamp: clock.unix_timestamp,
+        vesting_contract: vesting_contract.key(),
+        released_amount,
+        slot: clock.slot,
+    })
+}
+
+/// Contas usadas para pagar a partir de um `VestingContract`: em contratos nativos os
+/// lamports saem do proprio PDA para `lamports_recipient`; nos demais as contas de
+/// token sao obrigatorias e a transferencia e assinada pelo PDA.
+struct ContractPayout<'a, 'info> {
+    vesting_contract: &'a Account<'info, VestingContract>,
+    lamports_recipient: AccountInfo<'info>,
+    escrow_wallet: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+    destination: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+    mint: Option<&'a InterfaceAccount<'info, Mint>>,
+    token_program: Option<&'a Interface<'info, TokenInterface>>,
+    remaining_accounts: &'a [AccountInfo<'info>],
+}
+
+impl<'a, 'info> ContractPayout<'a, 'info> {
+    fn pay(self, amount: u64) -> Result<()> {
+        let vesting_contract = self.vesting_contract;
+
+        if vesting_contract.is_native {
+            return transfer_lamports_from_contract(
+                &vesting_contract.to_account_info(),
+                &self.lamports_recipient,
+                amount,
+            );
         }
-        VestingType::Cliff(cliff_time, percentage) => {
-            if current_time < *cliff_time {
-                return 0;
-            }
-            
-            // O valor inicial liberado (0 a 100%)
-            let cliff_amount = ((total as u128)
-                .saturating_mul(*percentage as u128)
-                .saturating_div(100)) as u64;
-
-            let remaining_total = total.saturating_sub(cliff_amount);
-            let post_cliff_duration = end.saturating_sub(*cliff_time) as u128;
-            let post_cliff_elapsed = current_time.saturating_sub(*cliff_time) as u128;
-
-            let linear_vested = if post_cliff_duration > 0 {
-                ((remaining_total as u128)
-                    .saturating_mul(post_cliff_elapsed)
-                    .saturating_div(post_cliff_duration)) as u64
-            } else {
-                remaining_total
-            };
 
-            cliff_amount.saturating_add(linear_vested)
+        let (Some(escrow_wallet), Some(destination), Some(mint), Some(token_program)) = (
+            self.escrow_wallet,
+            self.destination,
+            self.mint,
+            self.token_program,
+        ) else {
+            return err!(VestingError::MissingTokenAccounts);
+        };
+        require!(
+            vesting_contract.staked_amount == 0 || escrow_wallet.amount >= amount,
+            VestingError::TokensStaked
+        );
+
+        let id_bytes = vesting_contract.contract_id.to_le_bytes();
+
+        let seeds = &[
+            b"vesting",
+            vesting_contract.creator.as_ref(),
+            vesting_contract.mint.as_ref(),
+            id_bytes.as_ref(),
+            &[vesting_contract.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        let decimals = mint.decimals;
+
+        let cpi_accounts = token_interface::TransferChecked {
+            from: escrow_wallet.to_account_info(),
+            mint: mint.to_account_info(),
+            to: destination.to_account_info(),
+            authority: vesting_contract.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            cpi_accounts,
+            signer,
+        )
+        .with_remaining_accounts(self.remaining_accounts.to_vec());
+
+        transfer_checked_with_hook(cpi_ctx, amount, decimals)
+    }
+}
+
+/// Saldo de um contrato nativo disponivel para pagamento: tudo o que excede o minimo
+/// de isencao de aluguel da propria conta.
+fn native_escrow_balance(contract_info: &AccountInfo) -> Result<u64> {
+    let rent_exempt_minimum = Rent::get()?.minimum_balance(contract_info.data_len());
+    Ok(contract_info.lamports().saturating_sub(rent_exempt_minimum))
+}
+
+/// Debita lamports diretamente do PDA do contrato (dono: este programa), sem nunca
+/// deixa-lo abaixo da isencao de aluguel.
+fn transfer_lamports_from_contract(
+    contract_info: &AccountInfo,
+    recipient_info: &AccountInfo,
+    amount: u64,
+) -> Result<()> {
+    require!(
+        amount <= native_escrow_balance(contract_info)?,
+        VestingError::InsufficientEscrowBalance
+    );
+
+    let contract_lamports = contract_info
+        .lamports()
+        .checked_sub(amount)
+        .ok_or(VestingError::MathOverflow)?;
+    let recipient_lamports = recipient_info
+        .lamports()
+        .checked_add(amount)
+        .ok_or(VestingError::MathOverflow)?;
+
+    **contract_info.try_borrow_mut_lamports()? = contract_lamports;
+    **recipient_info.try_borrow_mut_lamports()? = recipient_lamports;
+
+    Ok(())
+}
+
+/// Inspeciona o mint (SPL Token ou Token-2022) e devolve a mascara de `mint_flags`
+/// com as caracteristicas relevantes para a seguranca do escrow.
+fn inspect_mint_flags(mint_info: &AccountInfo) -> Result<u16> {
+    let mint_data = mint_info.try_borrow_data()?;
+    let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
+
+    let mut flags = 0;
+    if mint.base.freeze_authority.is_some() {
+        flags |= mint_flags::FREEZE_AUTHORITY;
+    }
+
+    for extension in mint.get_extension_types()? {
+        match extension {
+            ExtensionType::PermanentDelegate => {
+                let delegate = mint.get_extension::<PermanentDelegate>()?.delegate;
+                if Option::<Pubkey>::from(delegate).is_some() {
+                    flags |= mint_flags::PERMANENT_DELEGATE;
+                }
+            }
+            ExtensionType::NonTransferable => flags |= mint_flags::NON_TRANSFERABLE,
+            ExtensionType::DefaultAccountState => {
+                let state = mint.get_extension::<DefaultAccountState>()?.state;
+                if state == AccountState::Frozen as u8 {
+                    flags |= mint_flags::DEFAULT_FROZEN;
+                }
+            }
+            ExtensionType::TransferFeeConfig => flags |= mint_flags::TRANSFER_FEE,
+            ExtensionType::TransferHook if transfer_hook::get_program_id(&mint).is_some() => {
+                flags |= mint_flags::TRANSFER_HOOK;
+            }
+            ExtensionType::MintCloseAuthority => {
+                let authority = mint.get_extension::<MintCloseAuthority>()?.close_authority;
+                if Option::<Pubkey>::from(authority).is_some() {
+                    flags |= mint_flags::MINT_CLOSE_AUTHORITY;
+                }
+            }
+            ExtensionType::ConfidentialTransferMint => {
+                flags |= mint_flags::CONFIDENTIAL_TRANSFER;
+            }
+            _ => {}
         }
     }
+
+    Ok(flags)
+}
+
+fn calculate_vested_amount(
+    current_time: i64,
+    start: i64,
+    end: i64,
+    total: u64,
+    vesting_type: &VestingType,
+) -> u64 {
+    // Liberacoes por evento nao dependem do tempo; ver `VestingContract::vested_amount`
+    verum_vesting_math::vested_amount(current_time, start, end, total, vesting_type.schedule())
 }
 
 // -------------------------------------------------------------------------
 // DATA STRUCTURES
 // -------------------------------------------------------------------------
 
+/// Caracteristicas do mint registradas em `VestingContract::mint_flags`.
+pub mod mint_flags {
+    /// PermanentDelegate definido: o delegado pode mover ou queimar o saldo do escrow.
+    pub const PERMANENT_DELEGATE: u16 = 1 << 0;
+    /// Freeze authority definida: o escrow pode ser congelado.
+    pub const FREEZE_AUTHORITY: u16 = 1 << 1;
+    /// NonTransferable: os tokens nao podem sair do escrow.
+    pub const NON_TRANSFERABLE: u16 = 1 << 2;
+    /// DefaultAccountState como Frozen: contas novas nascem congeladas.
+    pub const DEFAULT_FROZEN: u16 = 1 << 3;
+    /// TransferFeeConfig: cada liberacao paga taxa.
+    pub const TRANSFER_FEE: u16 = 1 << 4;
+    /// TransferHook: um programa externo autoriza cada transferencia.
+    pub const TRANSFER_HOOK: u16 = 1 << 5;
+    /// MintCloseAuthority definida.
+    pub const MINT_CLOSE_AUTHORITY: u16 = 1 << 6;
+    /// ConfidentialTransferMint habilitado.
+    pub const CONFIDENTIAL_TRANSFER: u16 = 1 << 7;
+
+    /// Caracteristicas que so sao aceitas se o criador as permitir explicitamente.
+    pub const DANGEROUS: u16 =
+        PERMANENT_DELEGATE | FREEZE_AUTHORITY | NON_TRANSFERABLE | DEFAULT_FROZEN;
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
 pub enum VestingType {
     Linear,
     Cliff(i64, u64),
+    /// Liberacao por marcos aprovados (ver `MilestoneSchedule`).
+    Milestones,
+    /// Liberacao por preco do oraculo (ver `PriceTrigger`).
+    PriceTriggered,
 }
 
+impl VestingType {
+    /// Tipos liberados por evento, e nao pelo tempo: o valor liberado fica em
+    /// `VestingContract::unlocked_amount`.
+    pub fn is_event_driven(&self) -> bool {
+        matches!(self, VestingType::Milestones | VestingType::PriceTriggered)
+    }
+
+    pub fn schedule(&self) -> verum_vesting_math::Schedule {
+        match *self {
+            VestingType::Linear => verum_vesting_math::Schedule::Linear,
+            VestingType::Cliff(cliff_time, percentage) => verum_vesting_math::Schedule::Cliff {
+                cliff_time,
+                percentage,
+            },
+            VestingType::Milestones | VestingType::PriceTriggered => {
+                verum_vesting_math::Schedule::EventDriven
+            }
+        }
+    }
+}
+
+/// Campos novos entram sempre no fim e com valor inicial zero, para que contratos
+/// antigos possam ser estendidos por `migrate_contract`.
 #[account]
 pub struct VestingContract {
     pub creator: Pubkey,
     pub beneficiary: Pubkey,
     pub mint: Pubkey,
     pub total_amount: u64,
+    /// Valor ja pago ao beneficiario.
     pub released_amount: u64,
     pub start_time: i64,
     pub end_time: i64,
@@ -306,6 +1188,37 @@ pub struct VestingContract {
     pub bump: u8,
     pub is_cancelled: bool,
     pub is_token_2022: bool,
+    pub mint_flags: u16,
+    pub is_native: bool,
+    /// Mascara de `pause_policy` com quem pode pausar/retomar.
+    pub pause_policy: u8,
+    /// Inicio da pausa em andamento (0 quando nao pausado).
+    pub paused_at: i64,
+    /// Soma dos intervalos de pausa ja encerrados.
+    pub paused_duration: i64,
+    pub pause_count: u16,
+    /// Soma das parcelas liberadas por evento (marcos aprovados ou gatilhos de preco).
+    pub unlocked_amount: u64,
+    /// Programa de staking autorizado pelo criador (`Pubkey::default()` desativa).
+    pub staking_program: Pubkey,
+    /// Valor do escrow delegado ao `staking_program`.
+    pub staked_amount: u64,
+    /// Autoridade que pode executar `clawback` (`Pubkey::default()` desativa).
+    pub compliance_authority: Pubkey,
+    /// Soma dos valores retirados por clawback (ja descontados de `total_amount`).
+    pub clawed_back_amount: u64,
+    /// Atraso (s) entre `propose_action` e `execute_action`; 0 mantem as acoes
+    /// administrativas imediatas e recusa propostas.
+    pub timelock_delay: i64,
+    /// Pode vetar acoes pendentes junto com o beneficiario (`Pubkey::default()` desativa).
+    pub guardian: Pubkey,
+    /// Ha um `AuditLog` para o contrato, que passa a ser exigido nas operacoes.
+    pub has_audit_log: bool,
+    /// Resgates ja realizados; indice do proximo `ReleaseReceipt`.
+    pub claim_count: u64,
+    /// Slot da ultima troca de beneficiario. Nesse slot o `VoterWeightRecord` do
+    /// beneficiario anterior ainda vale, e o contrato nao conta para o novo.
+    pub beneficiary_updated_slot: u64,
 }
 
 impl VestingContract {
@@ -322,7 +1235,84 @@ impl VestingContract {
         1 + 16 + // vesting_type (enum tag + max variant)
         1 + // bump
         1 + // is_cancelled
-        1;  // is_token_2022
+        1 + // is_token_2022
+        2 + // mint_flags
+        1 + // is_native
+        1 + // pause_policy
+        8 + // paused_at
+        8 + // paused_duration
+        2 + // pause_count
+        8 + // unlocked_amount
+        32 + // staking_program
+        8 + // staked_amount
+        32 + // compliance_authority
+        8 + // clawed_back_amount
+        8 + // timelock_delay
+        32 + // guardian
+        1 + // has_audit_log
+        8 + // claim_count
+        8; // beneficiary_updated_slot
+
+    pub fn is_paused(&self) -> bool {
+        self.paused_at != 0
+    }
+
+    pub fn can_pause(&self, authority: &Pubkey) -> bool {
+        (self.pause_policy & pause_policy::CREATOR != 0 && *authority == self.creator)
+            || (self.pause_policy & pause_policy::BENEFICIARY != 0
+                && *authority == self.beneficiary)
+    }
+
+    /// Quanto a pausa em andamento desloca o cronograma ate `current_time`, em tempo
+    /// efetivo. So conta o trecho em que o cronograma estaria correndo: uma pausa
+    /// iniciada antes de `start_time` conta a partir dele, e uma iniciada depois de
+    /// `end_time` nao conta. Iniciada dentro do cronograma, ela o congela, entao conta
+    /// inteira mesmo que passe do fim.
+    pub fn ongoing_pause(&self, current_time: i64) -> i64 {
+        let paused_at = self.paused_at.saturating_sub(self.paused_duration);
+        if !self.is_paused() || paused_at >= self.end_time {
+            return 0;
+        }
+        current_time
+            .saturating_sub(self.paused_duration)
+            .saturating_sub(paused_at.max(self.start_time))
+            .max(0)
+    }
+
+    /// Instante equivalente no cronograma original: o tempo pausado (encerrado ou em
+    /// andamento) nao conta. Durante a pausa o instante fica parado em `paused_at`,
+    /// o que ja vale para pausas fora de `[start_time, end_time)`: antes do inicio
+    /// nada foi liberado e depois do fim tudo foi.
+    pub fn effective_time(&self, current_time: i64) -> i64 {
+        let reference = if self.is_paused() {
+            self.paused_at.min(current_time)
+        } else {
+            current_time
+        };
+        reference.saturating_sub(self.paused_duration)
+    }
+
+    pub fn vested_amount(&self, current_time: i64) -> u64 {
+        if self.vesting_type.is_event_driven() {
+            // Um clawback pode ter reduzido `total_amount` abaixo do ja desbloqueado
+            return self.unlocked_amount.min(self.total_amount);
+        }
+        calculate_vested_amount(
+            self.effective_time(current_time),
+            self.start_time,
+            self.end_time,
+            self.total_amount,
+            &self.vesting_type,
+        )
+    }
+}
+
+/// Quem pode pausar um `VestingContract` (`VestingContract::pause_policy`).
+pub mod pause_policy {
+    pub const CREATOR: u8 = 1 << 0;
+    pub const BENEFICIARY: u8 = 1 << 1;
+
+    pub const ALL: u8 = CREATOR | BENEFICIARY;
 }
 
 #[error_code]
@@ -343,6 +1333,102 @@ pub enum VestingError {
     AlreadyClaimed,
     #[msg("Conta de token invalida.")]
     InvalidTokenAccount,
+    #[msg("Contas do transfer hook ausentes ou invalidas.")]
+    MissingTransferHookAccounts,
+    #[msg("Mint possui extensoes de risco nao permitidas pelo criador.")]
+    DangerousMintExtension,
+    #[msg("Contas de token ausentes para contrato SPL.")]
+    MissingTokenAccounts,
+    #[msg("Conta de destino dos lamports precisa ser writable.")]
+    RecipientNotWritable,
+    #[msg("Saldo do escrow insuficiente.")]
+    InsufficientEscrowBalance,
+    #[msg("Participacoes do pool invalidas: devem ser positivas, unicas e somar 10000 bps.")]
+    InvalidPoolShares,
+    #[msg("Beneficiario nao pertence ao pool.")]
+    PoolMemberNotFound,
+    #[msg("Pool atingiu o limite de membros.")]
+    PoolFull,
+    #[msg("Pausa nao permitida pela politica do contrato.")]
+    PauseNotAllowed,
+    #[msg("Contrato ja esta pausado.")]
+    AlreadyPaused,
+    #[msg("Contrato nao esta pausado.")]
+    NotPaused,
+    #[msg("Tipo de vesting nao suportado nesta operacao.")]
+    UnsupportedVestingType,
+    #[msg("Marcos invalidos.")]
+    InvalidMilestones,
+    #[msg("Marco ja aprovado ou devolvido.")]
+    MilestoneAlreadySettled,
+    #[msg("Prazo dos marcos encerrado.")]
+    MilestoneDeadlinePassed,
+    #[msg("Prazo dos marcos ainda nao encerrado.")]
+    MilestoneDeadlineNotReached,
+    #[msg("Gatilho de preco invalido.")]
+    InvalidPriceTrigger,
+    #[msg("Contas do gatilho de preco ausentes.")]
+    MissingPriceAccounts,
+    #[msg("Conta de preco invalida.")]
+    InvalidPriceFeed,
+    #[msg("Preco do oraculo desatualizado.")]
+    StalePrice,
+    #[msg("Intervalo de confianca do preco acima do limite.")]
+    PriceConfidenceTooWide,
+    #[msg("Realm invalido ou autoridade incorreta.")]
+    InvalidRealm,
+    #[msg("Configuracao do registrar invalida.")]
+    InvalidRegistrarConfig,
+    #[msg("Contrato de vesting nao pertence ao votante ou ao mint do registrar.")]
+    InvalidVoterWeightContract,
+    #[msg("Programa de staking nao autorizado para este contrato.")]
+    StakingProgramNotAllowed,
+    #[msg("Mint nao suportado em staking (taxa ou transfer hook).")]
+    UnsupportedStakingMint,
+    #[msg("Valor acima do saldo ainda bloqueado.")]
+    StakeExceedsLocked,
+    #[msg("Ha tokens em staking; faca o unstake antes.")]
+    TokensStaked,
+    #[msg("Saldo do escrow nao confere com a operacao de staking.")]
+    StakingBalanceMismatch,
+    #[msg("Codigo de motivo do clawback invalido.")]
+    InvalidClawbackReason,
+    #[msg("Memo do clawback muito longo.")]
+    ClawbackMemoTooLong,
+    #[msg("Atraso do timelock invalido.")]
+    InvalidTimelockDelay,
+    #[msg("Acao com timelock invalida.")]
+    InvalidTimelockAction,
+    #[msg("Atraso do timelock ainda nao decorrido.")]
+    TimelockNotElapsed,
+    #[msg("Contrato com timelock: use propose_action.")]
+    TimelockRequired,
+    #[msg("Configuracao do multisig invalida.")]
+    InvalidMultisigConfig,
+    #[msg("Assinante nao e membro do multisig.")]
+    NotMultisigMember,
+    #[msg("Proposta do multisig ja executada.")]
+    MultisigProposalAlreadyExecuted,
+    #[msg("Aprovacoes insuficientes para executar a proposta.")]
+    MultisigThresholdNotMet,
+    #[msg("Contas da proposta do multisig nao conferem.")]
+    InvalidMultisigAccounts,
+    #[msg("Log de auditoria ja existe ou e invalido.")]
+    InvalidAuditLog,
+    #[msg("Conta do log de auditoria ausente.")]
+    MissingAuditLog,
+    #[msg("Conta nao e um contrato de vesting em layout conhecido.")]
+    InvalidContractLayout,
+    #[msg("Contrato ja esta no layout atual.")]
+    ContractAlreadyMigrated,
+    #[msg("Beneficiario trocado neste slot; o peso de voto so conta a partir do proximo.")]
+    BeneficiaryChangedThisSlot,
+    #[msg("Contrato sem timelock: propostas exigem timelock_delay maior que zero.")]
+    TimelockDisabled,
+    #[msg("O programa de staking alterou o dono, o delegate ou a close authority do escrow.")]
+    EscrowAuthorityChanged,
+    #[msg("Ainda ha participacoes do pool a liberar ou a resgatar.")]
+    PoolSharesOutstanding,
 }
 
 // -------------------------------------------------------------------------
@@ -358,6 +1444,10 @@ pub struct VestingCreated {
     pub total_amount: u64,
     pub start_time: i64,
     pub end_time: i64,
+    pub mint_flags: u16,
+    pub vesting_contract: Pubkey,
+    pub vesting_type: VestingType,
+    pub slot: u64,
 }
 
 #[event]
@@ -366,6 +1456,14 @@ pub struct TokensClaimed {
     pub beneficiary: Pubkey,
     pub amount: u64,
     pub timestamp: i64,
+    pub vesting_contract: Pubkey,
+    /// Total ja pago apos este resgate.
+    pub released_amount: u64,
+    /// Saldo do contrato ainda nao resgatado.
+    pub remaining_amount: u64,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub slot: u64,
 }
 
 #[event]
@@ -373,20 +1471,51 @@ pub struct BeneficiaryUpdated {
     pub contract_id: u64,
     pub old_beneficiary: Pubkey,
     pub new_beneficiary: Pubkey,
+    pub vesting_contract: Pubkey,
+    pub timestamp: i64,
+    pub slot: u64,
+}
+
+#[event]
+pub struct PausePolicyUpdated {
+    pub contract_id: u64,
+    pub pause_policy: u8,
+}
+
+#[event]
+pub struct VestingPaused {
+    pub contract_id: u64,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VestingResumed {
+    pub contract_id: u64,
+    pub authority: Pubkey,
+    pub paused_at: i64,
+    pub timestamp: i64,
+    pub paused_duration: i64,
 }
 
 #[event]
 pub struct VestingCancelled {
     pub contract_id: u64,
     pub creator: Pubkey,
+    /// Saldo do escrow devolvido ao criador.
     pub remaining_amount: u64,
     pub timestamp: i64,
+    pub vesting_contract: Pubkey,
+    /// Valor ja pago ao beneficiario antes do cancelamento.
+    pub released_amount: u64,
+    pub slot: u64,
 }
 
 // -------------------------------------------------------------------------
 // CONTEXTS
 // -------------------------------------------------------------------------
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(contract_id: u64)]
 pub struct CreateVesting<'info> {
@@ -432,8 +1561,57 @@ pub struct CreateVesting<'info> {
 
     pub system_program: Program<'info, System>,
     pub token_program: Interface<'info, TokenInterface>,
+
+    /// Opcional: cria o `AuditLog` do contrato junto com ele.
+    #[account(
+        init,
+        payer = creator,
+        seeds = [b"audit_log", vesting_contract.key().as_ref()],
+        bump,
+        space = AuditLog::LEN
+    )]
+    pub audit_log: Option<Account<'info, AuditLog>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(contract_id: u64)]
+pub struct CreateNativeVesting<'info> {
+    #[account(
+        init,
+        payer = creator,
+        seeds = [
+            b"vesting",
+            creator.key().as_ref(),
+            native_mint::ID.as_ref(),
+            &contract_id.to_le_bytes()
+        ],
+        bump,
+        space = VestingContract::LEN
+    )]
+    pub vesting_contract: Account<'info, VestingContract>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    /// CHECK: Apenas endereco para seed e armazenamento
+    pub beneficiary: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Opcional: cria o `AuditLog` do contrato junto com ele.
+    #[account(
+        init,
+        payer = creator,
+        seeds = [b"audit_log", vesting_contract.key().as_ref()],
+        bump,
+        space = AuditLog::LEN
+    )]
+    pub audit_log: Option<Account<'info, AuditLog>>,
 }
 
+/// As contas de token sao opcionais para permitir contratos nativos (`is_native`).
+#[event_cpi]
 #[derive(Accounts)]
 pub struct ClaimTokens<'info> {
     #[account(
@@ -450,14 +1628,14 @@ pub struct ClaimTokens<'info> {
         constraint = escrow_wallet.owner == vesting_contract.key() @ VestingError::Unauthorized,
         constraint = escrow_wallet.mint == vesting_contract.mint @ VestingError::InvalidAmount
     )]
-    pub escrow_wallet: InterfaceAccount<'info, TokenAccount>,
+    pub escrow_wallet: Option<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(
         mut,
         constraint = beneficiary_token_account.mint == vesting_contract.mint @ VestingError::InvalidAmount,
         constraint = beneficiary_token_account.owner == beneficiary.key() @ VestingError::Unauthorized
     )]
-    pub beneficiary_token_account: InterfaceAccount<'info, TokenAccount>,
+    pub beneficiary_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
 
     /// CHECK: Validado via has_one no vesting_contract
     pub beneficiary: UncheckedAccount<'info>,
@@ -465,10 +1643,44 @@ pub struct ClaimTokens<'info> {
     #[account(
         constraint = mint.key() == vesting_contract.mint @ VestingError::InvalidAmount
     )]
-    pub mint: InterfaceAccount<'info, Mint>,
-    pub token_program: Interface<'info, TokenInterface>,
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
+    /// Apenas contratos `PriceTriggered`; vinculo validado em `observe_price`.
+    #[account(mut)]
+    pub price_trigger: Option<Account<'info, PriceTrigger>>,
+
+    /// CHECK: Validado contra o `price_trigger`
+    pub price_feed: Option<UncheckedAccount<'info>>,
+
+    /// Presente se o contrato tiver `AuditLog` (obrigatorio quando `has_audit_log`).
+    #[account(
+        mut,
+        seeds = [b"audit_log", vesting_contract.key().as_ref()],
+        bump = audit_log.bump
+    )]
+    pub audit_log: Option<Account<'info, AuditLog>>,
+
+    /// Recibo opcional do resgate, pago por `receipt_payer`.
+    #[account(
+        init,
+        payer = receipt_payer,
+        seeds = [
+            RELEASE_RECEIPT_SEED,
+            vesting_contract.key().as_ref(),
+            &vesting_contract.claim_count.to_le_bytes()
+        ],
+        bump,
+        space = ReleaseReceipt::LEN
+    )]
+    pub release_receipt: Option<Account<'info, ReleaseReceipt>>,
+
+    #[account(mut)]
+    pub receipt_payer: Option<Signer<'info>>,
+    pub system_program: Option<Program<'info, System>>,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct UpdateBeneficiary<'info> {
     #[account(
@@ -483,8 +1695,42 @@ pub struct UpdateBeneficiary<'info> {
 
     /// CHECK: Novo beneficiario
     pub new_beneficiary: UncheckedAccount<'info>,
+
+    /// Presente se o contrato tiver `AuditLog` (obrigatorio quando `has_audit_log`).
+    #[account(
+        mut,
+        seeds = [b"audit_log", vesting_contract.key().as_ref()],
+        bump = audit_log.bump
+    )]
+    pub audit_log: Option<Account<'info, AuditLog>>,
 }
 
+#[derive(Accounts)]
+pub struct SetPausePolicy<'info> {
+    #[account(
+        mut,
+        has_one = creator @ VestingError::Unauthorized,
+        has_one = beneficiary @ VestingError::Unauthorized,
+        constraint = !vesting_contract.is_cancelled @ VestingError::ContractCancelled
+    )]
+    pub vesting_contract: Account<'info, VestingContract>,
+
+    pub creator: Signer<'info>,
+    pub beneficiary: Signer<'info>,
+}
+
+/// Usado por `pause_vesting` e `resume_vesting`; a autoridade e validada contra a
+/// `pause_policy` do contrato.
+#[derive(Accounts)]
+pub struct PauseVesting<'info> {
+    #[account(mut)]
+    pub vesting_contract: Account<'info, VestingContract>,
+
+    pub authority: Signer<'info>,
+}
+
+/// As contas de token sao opcionais para permitir contratos nativos (`is_native`).
+#[event_cpi]
 #[derive(Accounts)]
 pub struct CancelVesting<'info> {
     #[account(
@@ -504,18 +1750,26 @@ pub struct CancelVesting<'info> {
         constraint = escrow_wallet.owner == vesting_contract.key() @ VestingError::Unauthorized,
         constraint = escrow_wallet.mint == vesting_contract.mint @ VestingError::InvalidAmount
     )]
-    pub escrow_wallet: InterfaceAccount<'info, TokenAccount>,
+    pub escrow_wallet: Option<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(
         mut,
         constraint = creator_token_account.mint == vesting_contract.mint @ VestingError::InvalidAmount,
         constraint = creator_token_account.owner == creator.key() @ VestingError::Unauthorized
     )]
-    pub creator_token_account: InterfaceAccount<'info, TokenAccount>,
+    pub creator_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(
         constraint = mint.key() == vesting_contract.mint @ VestingError::InvalidAmount
     )]
-    pub mint: InterfaceAccount<'info, Mint>,
-    pub token_program: Interface<'info, TokenInterface>,
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
+    /// Presente se o contrato tiver `AuditLog` (obrigatorio quando `has_audit_log`).
+    #[account(
+        mut,
+        seeds = [b"audit_log", vesting_contract.key().as_ref()],
+        bump = audit_log.bump
+    )]
+    pub audit_log: Option<Account<'info, AuditLog>>,
 }
@@ -0,0 +1,84 @@
+//! Migracao de contratos criados com um layout anterior do `VestingContract`.
+//!
+//! Os campos novos sao sempre acrescentados ao fim da conta e tem zero como valor
+//! inicial (o mesmo gravado por `create_vesting`). Uma conta antiga e menor que
+//! `VestingContract::LEN` e nao desserializa como `Account<VestingContract>`;
+//! `migrate_contract` a realoca para o tamanho atual, completando com zeros, e o
+//! `payer` cobre o aluguel adicional. Campos futuros devem seguir a mesma regra.
+
+use anchor_lang::Discriminator;
+
+use super::*;
+
+pub(crate) fn migrate(ctx: Context<MigrateContract>) -> Result<()> {
+    let contract_info = ctx.accounts.vesting_contract.to_account_info();
+    let old_len = contract_info.data_len();
+
+    {
+        let data = contract_info.try_borrow_data()?;
+        require!(
+            data.len() >= 8 && data[..8] == VestingContract::DISCRIMINATOR,
+            VestingError::InvalidContractLayout
+        );
+    }
+    require!(
+        old_len < VestingContract::LEN,
+        VestingError::ContractAlreadyMigrated
+    );
+
+    let rent_due = Rent::get()?
+        .minimum_balance(VestingContract::LEN)
+        .saturating_sub(contract_info.lamports());
+    if rent_due > 0 {
+        let cpi_accounts = system_program::Transfer {
+            from: ctx.accounts.payer.to_account_info(),
+            to: contract_info.clone(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+        system_program::transfer(cpi_ctx, rent_due)?;
+    }
+
+    contract_info.realloc(VestingContract::LEN, true)?;
+
+    let vesting_contract =
+        VestingContract::try_deserialize(&mut &contract_info.try_borrow_data()?[..])
+            .map_err(|_| VestingError::InvalidContractLayout)?;
+
+    emit!(ContractMigrated {
+        contract_id: vesting_contract.contract_id,
+        vesting_contract: contract_info.key(),
+        old_len: old_len as u32,
+        new_len: VestingContract::LEN as u32,
+    });
+
+    Ok(())
+}
+
+// -------------------------------------------------------------------------
+// EVENTS
+// -------------------------------------------------------------------------
+
+#[event]
+pub struct ContractMigrated {
+    pub contract_id: u64,
+    pub vesting_contract: Pubkey,
+    pub old_len: u32,
+    pub new_len: u32,
+}
+
+// -------------------------------------------------------------------------
+// CONTEXTS
+// -------------------------------------------------------------------------
+
+#[derive(Accounts)]
+pub struct MigrateContract<'info> {
+    /// CHECK: Conta no layout antigo, que `Account<VestingContract>` nao le; o dono
+    /// e conferido aqui e o discriminador em `migrate`.
+    #[account(mut, owner = crate::ID @ VestingError::InvalidContractLayout)]
+    pub vesting_contract: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,345 @@
+//! Liberacao por marcos (`VestingType::Milestones`).
+//!
+//! Os marcos ficam em uma conta propria (`[b"milestones", vesting_contract]`) criada
+//! pelo criador logo apos o contrato. Cada marco tem um valor e um attestor; a
+//! aprovacao soma o valor em `VestingContract::unlocked_amount`, que e o
+//! que `claim_tokens` passa a considerar como liberado. Com prazo definido, marcos nao
+//! aprovados ate o prazo podem ser devolvidos ao criador; o valor devolvido sai de
+//! `total_amount`, mantendo o escrow igual a `total_amount - released_amount`.
+
+use super::*;
+
+/// Limite de marcos por contrato (determina o espaco da conta).
+pub const MAX_MILESTONES: usize = 16;
+
+pub(crate) fn create_schedule(
+    ctx: Context<CreateMilestoneSchedule>,
+    milestones: Vec<MilestoneInit>,
+    deadline: i64,
+) -> Result<()> {
+    let vesting_contract = &ctx.accounts.vesting_contract;
+    require!(
+        vesting_contract.vesting_type == VestingType::Milestones,
+        VestingError::UnsupportedVestingType
+    );
+    require!(
+        !milestones.is_empty() && milestones.len() <= MAX_MILESTONES,
+        VestingError::InvalidMilestones
+    );
+    require!(deadline >= 0, VestingError::InvalidTimeRange);
+
+    let mut total: u64 = 0;
+    for milestone in &milestones {
+        require!(milestone.amount > 0, VestingError::InvalidMilestones);
+        total = total
+            .checked_add(milestone.amount)
+            .ok_or(VestingError::MathOverflow)?;
+    }
+    require!(
+        total == vesting_contract.total_amount,
+        VestingError::InvalidMilestones
+    );
+
+    let schedule = &mut ctx.accounts.milestone_schedule;
+    schedule.vesting_contract = vesting_contract.key();
+    schedule.deadline = deadline;
+    schedule.bump = ctx.bumps.milestone_schedule;
+    schedule.milestones = milestones
+        .iter()
+        .map(|milestone| Milestone {
+            amount: milestone.amount,
+            attestor: milestone.attestor,
+            approved: false,
+            refunded: false,
+        })
+        .collect();
+
+    emit!(MilestoneScheduleCreated {
+        contract_id: vesting_contract.contract_id,
+        milestone_count: schedule.milestones.len() as u8,
+        deadline,
+    });
+
+    Ok(())
+}
+
+pub(crate) fn approve(ctx: Context<ApproveMilestone>, index: u8) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp;
+    let schedule = &mut ctx.accounts.milestone_schedule;
+    let vesting_contract = &mut ctx.accounts.vesting_contract;
+
+    require!(!vesting_contract.is_cancelled, VestingError::ContractCancelled);
+    require!(
+        schedule.deadline == 0 || current_time <= schedule.deadline,
+        VestingError::MilestoneDeadlinePassed
+    );
+
+    let milestone = schedule
+        .milestones
+        .get_mut(index as usize)
+        .ok_or(VestingError::InvalidMilestones)?;
+    require!(
+        milestone.attestor == ctx.accounts.attestor.key(),
+        VestingError::Unauthorized
+    );
+    require!(
+        !milestone.approved && !milestone.refunded,
+        VestingError::MilestoneAlreadySettled
+    );
+
+    milestone.approved = true;
+    vesting_contract.unlocked_amount = vesting_contract
+        .unlocked_amount
+        .checked_add(milestone.amount)
+        .ok_or(VestingError::MathOverflow)?;
+
+    emit!(MilestoneApproved {
+        contract_id: vesting_contract.contract_id,
+        index,
+        attestor: milestone.attestor,
+        amount: milestone.amount,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
+
+/// Devolve ao criador o valor dos marcos nao aprovados ate o prazo.
+pub(crate) fn refund_expired<'info>(
+    ctx: Context<'_, '_, '_, 'info, RefundExpiredMilestones<'info>>,
+) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp;
+
+    let refundable = {
+        let schedule = &mut ctx.accounts.milestone_schedule;
+        require!(
+            schedule.deadline != 0 && current_time > schedule.deadline,
+            VestingError::MilestoneDeadlineNotReached
+        );
+
+        let mut refundable: u64 = 0;
+        for milestone in schedule
+            .milestones
+            .iter_mut()
+            .filter(|milestone| !milestone.approved && !milestone.refunded)
+        {
+            milestone.refunded = true;
+            refundable = refundable
+                .checked_add(milestone.amount)
+                .ok_or(VestingError::MathOverflow)?;
+        }
+        refundable
+    };
+
+    // Um clawback pode ja ter retirado parte do valor nao aprovado
+    let refundable = {
+        let vesting_contract = &mut ctx.accounts.vesting_contract;
+        let outstanding = vesting_contract.total_amount.saturating_sub(
+            vesting_contract
+                .unlocked_amount
+                .max(vesting_contract.released_amount),
+        );
+        let refundable = refundable.min(outstanding);
+        vesting_contract.total_amount -= refundable;
+        refundable
+    };
+
+    require!(refundable > 0, VestingError::NothingToRelease);
+
+    ContractPayout {
+        vesting_contract: &ctx.accounts.vesting_contract,
+        lamports_recipient: ctx.accounts.creator.to_account_info(),
+        escrow_wallet: ctx.accounts.escrow_wallet.as_ref(),
+        destination: ctx.accounts.creator_token_account.as_ref(),
+        mint: ctx.accounts.mint.as_ref(),
+        token_program: ctx.accounts.token_program.as_ref(),
+        remaining_accounts: ctx.remaining_accounts,
+    }
+    .pay(refundable)?;
+
+    audit_log::record(
+        &ctx.accounts.vesting_contract,
+        ctx.accounts.audit_log.as_mut(),
+        ctx.accounts.creator.key(),
+        audit_action::MILESTONE_REFUND,
+        refundable,
+    )?;
+
+    emit_cpi!(MilestonesRefunded {
+        contract_id: ctx.accounts.vesting_contract.contract_id,
+        vesting_contract: ctx.accounts.vesting_contract.key(),
+        creator: ctx.accounts.creator.key(),
+        amount: refundable,
+        total_amount: ctx.accounts.vesting_contract.total_amount,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
+
+// -------------------------------------------------------------------------
+// DATA STRUCTURES
+// -------------------------------------------------------------------------
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct MilestoneInit {
+    pub amount: u64,
+    pub attestor: Pubkey,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct Milestone {
+    pub amount: u64,
+    pub attestor: Pubkey,
+    pub approved: bool,
+    pub refunded: bool,
+}
+
+impl Milestone {
+    pub const LEN: usize = 8 + 32 + 1 + 1;
+}
+
+#[account]
+pub struct MilestoneSchedule {
+    pub vesting_contract: Pubkey,
+    /// Prazo para aprovacao (0 = sem prazo).
+    pub deadline: i64,
+    pub bump: u8,
+    pub milestones: Vec<Milestone>,
+}
+
+impl MilestoneSchedule {
+    pub const LEN: usize =
+        8 + // discriminator
+        32 + // vesting_contract
+        8 + // deadline
+        1 + // bump
+        4 + MAX_MILESTONES * Milestone::LEN; // milestones
+}
+
+// -------------------------------------------------------------------------
+// EVENTS
+// -------------------------------------------------------------------------
+
+#[event]
+pub struct MilestoneScheduleCreated {
+    pub contract_id: u64,
+    pub milestone_count: u8,
+    pub deadline: i64,
+}
+
+#[event]
+pub struct MilestoneApproved {
+    pub contract_id: u64,
+    pub index: u8,
+    pub attestor: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MilestonesRefunded {
+    pub contract_id: u64,
+    pub vesting_contract: Pubkey,
+    pub creator: Pubkey,
+    pub amount: u64,
+    /// `total_amount` do contrato apos a devolucao.
+    pub total_amount: u64,
+    pub timestamp: i64,
+}
+
+// -------------------------------------------------------------------------
+// CONTEXTS
+// -------------------------------------------------------------------------
+
+#[derive(Accounts)]
+pub struct CreateMilestoneSchedule<'info> {
+    #[account(
+        init,
+        payer = creator,
+        seeds = [b"milestones", vesting_contract.key().as_ref()],
+        bump,
+        space = MilestoneSchedule::LEN
+    )]
+    pub milestone_schedule: Account<'info, MilestoneSchedule>,
+
+    #[account(
+        has_one = creator @ VestingError::Unauthorized,
+        constraint = !vesting_contract.is_cancelled @ VestingError::ContractCancelled
+    )]
+    pub vesting_contract: Account<'info, VestingContract>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveMilestone<'info> {
+    #[account(
+        mut,
+        seeds = [b"milestones", vesting_contract.key().as_ref()],
+        bump = milestone_schedule.bump,
+        has_one = vesting_contract @ VestingError::InvalidMilestones
+    )]
+    pub milestone_schedule: Account<'info, MilestoneSchedule>,
+
+    #[account(mut)]
+    pub vesting_contract: Account<'info, VestingContract>,
+
+    pub attestor: Signer<'info>,
+}
+
+/// As contas de token sao opcionais para permitir contratos nativos (`is_native`).
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RefundExpiredMilestones<'info> {
+    #[account(
+        mut,
+        seeds = [b"milestones", vesting_contract.key().as_ref()],
+        bump = milestone_schedule.bump,
+        has_one = vesting_contract @ VestingError::InvalidMilestones
+    )]
+    pub milestone_schedule: Account<'info, MilestoneSchedule>,
+
+    #[account(
+        mut,
+        has_one = creator @ VestingError::Unauthorized,
+        constraint = !vesting_contract.is_cancelled @ VestingError::ContractCancelled
+    )]
+    pub vesting_contract: Account<'info, VestingContract>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", vesting_contract.key().as_ref()],
+        bump,
+        constraint = escrow_wallet.owner == vesting_contract.key() @ VestingError::Unauthorized,
+        constraint = escrow_wallet.mint == vesting_contract.mint @ VestingError::InvalidAmount
+    )]
+    pub escrow_wallet: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = creator_token_account.mint == vesting_contract.mint @ VestingError::InvalidAmount,
+        constraint = creator_token_account.owner == creator.key() @ VestingError::Unauthorized
+    )]
+    pub creator_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        constraint = mint.key() == vesting_contract.mint @ VestingError::InvalidAmount
+    )]
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
+    /// Presente se o contrato tiver `AuditLog` (obrigatorio quando `has_audit_log`).
+    #[account(
+        mut,
+        seeds = [b"audit_log", vesting_contract.key().as_ref()],
+        bump = audit_log.bump
+    )]
+    pub audit_log: Option<Account<'info, AuditLog>>,
+}
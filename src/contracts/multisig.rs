@@ -0,0 +1,432 @@
+//! Autoridade M-de-N para as operacoes do lado do criador.
+//!
+//! Um `MultisigAuthority` (`[b"multisig", payer, multisig_id]`) guarda os membros e o
+//! quorum. Quem atua como `creator` dos contratos e o PDA sem dados
+//! `[b"multisig_signer", multisig]`, que paga o aluguel e detem os tokens a depositar.
+//! Um membro registra a operacao em um `MultisigProposal` (contando como a primeira
+//! aprovacao); atingido o quorum, `execute_multisig_proposal` chama a instrucao
+//! correspondente deste programa assinando como o PDA. As contas da instrucao interna
+//! vem em `remaining_accounts`, na mesma ordem da chamada direta.
+
+use super::*;
+use anchor_lang::InstructionData;
+
+/// Limite de membros por multisig (determina o espaco da conta e cabe em `approvals`).
+pub const MAX_MULTISIG_MEMBERS: usize = 10;
+
+pub const MULTISIG_SIGNER_SEED: &[u8] = b"multisig_signer";
+
+pub(crate) fn create(
+    ctx: Context<CreateMultisig>,
+    multisig_id: u64,
+    members: Vec<Pubkey>,
+    threshold: u8,
+) -> Result<()> {
+    require!(
+        !members.is_empty() && members.len() <= MAX_MULTISIG_MEMBERS,
+        VestingError::InvalidMultisigConfig
+    );
+    require!(
+        threshold > 0 && threshold as usize <= members.len(),
+        VestingError::InvalidMultisigConfig
+    );
+    for (index, member) in members.iter().enumerate() {
+        require!(
+            !members[..index].contains(member),
+            VestingError::InvalidMultisigConfig
+        );
+    }
+
+    let multisig_key = ctx.accounts.multisig.key();
+    let (signer, signer_bump) = Pubkey::find_program_address(
+        &[MULTISIG_SIGNER_SEED, multisig_key.as_ref()],
+        ctx.program_id,
+    );
+
+    let multisig = &mut ctx.accounts.multisig;
+    multisig.multisig_id = multisig_id;
+    multisig.threshold = threshold;
+    multisig.proposal_count = 0;
+    multisig.bump = ctx.bumps.multisig;
+    multisig.signer_bump = signer_bump;
+    multisig.members = members;
+
+    emit!(MultisigCreated {
+        multisig: multisig_key,
+        signer,
+        members: multisig.members.clone(),
+        threshold,
+    });
+
+    Ok(())
+}
+
+pub(crate) fn propose(ctx: Context<CreateMultisigProposal>, action: MultisigAction) -> Result<()> {
+    let proposer = ctx.accounts.proposer.key();
+    let multisig = &mut ctx.accounts.multisig;
+    let member_index = multisig.member_index(&proposer)?;
+
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.multisig = multisig.key();
+    proposal.proposal_id = multisig.proposal_count;
+    proposal.action = action;
+    proposal.approvals = 1 << member_index;
+    proposal.executed = false;
+    proposal.bump = ctx.bumps.proposal;
+
+    multisig.proposal_count = multisig
+        .proposal_count
+        .checked_add(1)
+        .ok_or(VestingError::MathOverflow)?;
+
+    emit!(MultisigProposalCreated {
+        multisig: proposal.multisig,
+        proposal_id: proposal.proposal_id,
+        proposer,
+        action,
+    });
+
+    Ok(())
+}
+
+pub(crate) fn approve(ctx: Context<ApproveMultisigProposal>) -> Result<()> {
+    let member = ctx.accounts.member.key();
+    let member_index = ctx.accounts.multisig.member_index(&member)?;
+    let proposal = &mut ctx.accounts.proposal;
+
+    require!(
+        !proposal.executed,
+        VestingError::MultisigProposalAlreadyExecuted
+    );
+    proposal.approvals |= 1 << member_index;
+
+    emit!(MultisigProposalApproved {
+        multisig: proposal.multisig,
+        proposal_id: proposal.proposal_id,
+        member,
+        approvals: proposal.approvals.count_ones() as u8,
+    });
+
+    Ok(())
+}
+
+pub(crate) fn execute<'info>(
+    ctx: Context<'_, '_, '_, 'info, ExecuteMultisigProposal<'info>>,
+) -> Result<()> {
+    let multisig = &ctx.accounts.multisig;
+    let multisig_key = multisig.key();
+    multisig.member_index(&ctx.accounts.member.key())?;
+
+    let proposal = &mut ctx.accounts.proposal;
+    require!(
+        !proposal.executed,
+        VestingError::MultisigProposalAlreadyExecuted
+    );
+    require!(
+        proposal.approvals.count_ones() >= multisig.threshold as u32,
+        VestingError::MultisigThresholdNotMet
+    );
+
+    let signer = Pubkey::create_program_address(
+        &[
+            MULTISIG_SIGNER_SEED,
+            multisig_key.as_ref(),
+            &[multisig.signer_bump],
+        ],
+        ctx.program_id,
+    )
+    .map_err(|_| VestingError::InvalidMultisigConfig)?;
+
+    // Posicoes fixas das contas da instrucao interna que precisam bater com a proposta
+    let (data, expected_accounts) = match proposal.action {
+        MultisigAction::CreateVesting {
+            contract_id,
+            beneficiary,
+            mint,
+            total_amount,
+            start_time,
+            end_time,
+            vesting_type,
+            allowed_mint_flags,
+        } => (
+            instruction::CreateVesting {
+                contract_id,
+                total_amount,
+                start_time,
+                end_time,
+                vesting_type,
+                allowed_mint_flags,
+            }
+            .data(),
+            vec![(1, signer), (2, beneficiary), (3, mint)],
+        ),
+        MultisigAction::CancelVesting { vesting_contract } => (
+            instruction::CancelVesting {}.data(),
+            vec![(0, vesting_contract), (1, signer)],
+        ),
+        MultisigAction::UpdateBeneficiary {
+            vesting_contract,
+            new_beneficiary,
+        } => (
+            instruction::UpdateBeneficiary {}.data(),
+            vec![(0, vesting_contract), (1, signer), (2, new_beneficiary)],
+        ),
+        MultisigAction::ProposeTimelocked {
+            vesting_contract,
+            action,
+        } => (
+            instruction::ProposeAction { action }.data(),
+            vec![(1, vesting_contract), (2, signer)],
+        ),
+        MultisigAction::ExecuteTimelocked { vesting_contract } => (
+            instruction::ExecuteAction {}.data(),
+            vec![(1, vesting_contract), (2, signer)],
+        ),
+    };
+
+    let remaining_accounts = ctx.remaining_accounts;
+    for (index, key) in expected_accounts {
+        require!(
+            remaining_accounts.get(index).map(|info| info.key) == Some(&key),
+            VestingError::InvalidMultisigAccounts
+        );
+    }
+
+    proposal.executed = true;
+
+    let inner_instruction = Instruction {
+        program_id: crate::ID,
+        accounts: remaining_accounts
+            .iter()
+            .map(|info| AccountMeta {
+                pubkey: info.key(),
+                is_signer: info.is_signer || info.key() == signer,
+                is_writable: info.is_writable,
+            })
+            .collect(),
+        data,
+    };
+    let mut account_infos = remaining_accounts.to_vec();
+    account_infos.push(ctx.accounts.vesting_program.to_account_info());
+
+    invoke_signed(
+        &inner_instruction,
+        &account_infos,
+        &[&[
+            MULTISIG_SIGNER_SEED,
+            multisig_key.as_ref(),
+            &[multisig.signer_bump],
+        ]],
+    )?;
+
+    emit!(MultisigProposalExecuted {
+        multisig: multisig_key,
+        proposal_id: proposal.proposal_id,
+        executor: ctx.accounts.member.key(),
+    });
+
+    Ok(())
+}
+
+// -------------------------------------------------------------------------
+// DATA STRUCTURES
+// -------------------------------------------------------------------------
+
+/// Operacoes que o multisig pode executar como `creator`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum MultisigAction {
+    CreateVesting {
+        contract_id: u64,
+        beneficiary: Pubkey,
+        mint: Pubkey,
+        total_amount: u64,
+        start_time: i64,
+        end_time: i64,
+        vesting_type: VestingType,
+        allowed_mint_flags: u16,
+    },
+    CancelVesting {
+        vesting_contract: Pubkey,
+    },
+    UpdateBeneficiary {
+        vesting_contract: Pubkey,
+        new_beneficiary: Pubkey,
+    },
+    /// `propose_action` em contratos com timelock.
+    ProposeTimelocked {
+        vesting_contract: Pubkey,
+        action: TimelockedAction,
+    },
+    /// `execute_action` em contratos com timelock.
+    ExecuteTimelocked {
+        vesting_contract: Pubkey,
+    },
+}
+
+impl MultisigAction {
+    pub const LEN: usize =
+        1 + // enum tag
+        8 + 32 + 32 + 8 + 8 + 8 + 17 + 2; // maior variante (CreateVesting)
+}
+
+#[account]
+pub struct MultisigAuthority {
+    pub multisig_id: u64,
+    /// Aprovacoes necessarias para executar uma proposta.
+    pub threshold: u8,
+    /// Id da proxima proposta (seed de `MultisigProposal`).
+    pub proposal_count: u64,
+    pub bump: u8,
+    /// Bump do PDA `[b"multisig_signer", multisig]` que atua como `creator`.
+    pub signer_bump: u8,
+    pub members: Vec<Pubkey>,
+}
+
+impl MultisigAuthority {
+    pub const LEN: usize =
+        8 + // discriminator
+        8 + // multisig_id
+        1 + // threshold
+        8 + // proposal_count
+        1 + // bump
+        1 + // signer_bump
+        4 + MAX_MULTISIG_MEMBERS * 32; // members
+
+    pub fn member_index(&self, member: &Pubkey) -> Result<usize> {
+        self.members
+            .iter()
+            .position(|candidate| candidate == member)
+            .ok_or_else(|| error!(VestingError::NotMultisigMember))
+    }
+}
+
+#[account]
+pub struct MultisigProposal {
+    pub multisig: Pubkey,
+    pub proposal_id: u64,
+    pub action: MultisigAction,
+    /// Bit `i` marca a aprovacao de `members[i]`.
+    pub approvals: u16,
+    pub executed: bool,
+    pub bump: u8,
+}
+
+impl MultisigProposal {
+    pub const LEN: usize =
+        8 + // discriminator
+        32 + // multisig
+        8 + // proposal_id
+        MultisigAction::LEN + // action
+        2 + // approvals
+        1 + // executed
+        1; // bump
+}
+
+// -------------------------------------------------------------------------
+// EVENTS
+// -------------------------------------------------------------------------
+
+#[event]
+pub struct MultisigCreated {
+    pub multisig: Pubkey,
+    pub signer: Pubkey,
+    pub members: Vec<Pubkey>,
+    pub threshold: u8,
+}
+
+#[event]
+pub struct MultisigProposalCreated {
+    pub multisig: Pubkey,
+    pub proposal_id: u64,
+    pub proposer: Pubkey,
+    pub action: MultisigAction,
+}
+
+#[event]
+pub struct MultisigProposalApproved {
+    pub multisig: Pubkey,
+    pub proposal_id: u64,
+    pub member: Pubkey,
+    pub approvals: u8,
+}
+
+#[event]
+pub struct MultisigProposalExecuted {
+    pub multisig: Pubkey,
+    pub proposal_id: u64,
+    pub executor: Pubkey,
+}
+
+// -------------------------------------------------------------------------
+// CONTEXTS
+// -------------------------------------------------------------------------
+
+#[derive(Accounts)]
+#[instruction(multisig_id: u64)]
+pub struct CreateMultisig<'info> {
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"multisig", payer.key().as_ref(), &multisig_id.to_le_bytes()],
+        bump,
+        space = MultisigAuthority::LEN
+    )]
+    pub multisig: Account<'info, MultisigAuthority>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreateMultisigProposal<'info> {
+    #[account(
+        init,
+        payer = proposer,
+        seeds = [
+            b"multisig_proposal",
+            multisig.key().as_ref(),
+            &multisig.proposal_count.to_le_bytes()
+        ],
+        bump,
+        space = MultisigProposal::LEN
+    )]
+    pub proposal: Account<'info, MultisigProposal>,
+
+    #[account(mut)]
+    pub multisig: Account<'info, MultisigAuthority>,
+
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveMultisigProposal<'info> {
+    #[account(
+        mut,
+        has_one = multisig @ VestingError::InvalidMultisigAccounts
+    )]
+    pub proposal: Account<'info, MultisigProposal>,
+
+    pub multisig: Account<'info, MultisigAuthority>,
+
+    pub member: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteMultisigProposal<'info> {
+    #[account(
+        mut,
+        has_one = multisig @ VestingError::InvalidMultisigAccounts
+    )]
+    pub proposal: Account<'info, MultisigProposal>,
+
+    pub multisig: Account<'info, MultisigAuthority>,
+
+    pub member: Signer<'info>,
+
+    pub vesting_program: Program<'info, program::VerumVesting>,
+}
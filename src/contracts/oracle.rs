@@ -0,0 +1,70 @@
+//! Leitura de contas de preco no layout do Pyth (v2).
+//!
+//! Apenas os campos usados pelos gatilhos de preco sao lidos: expoente, timestamp de
+//! publicacao e o preco agregado (valor, confianca e status).
+
+use super::*;
+
+const MAGIC: u32 = 0xa1b2_c3d4;
+const VERSION_2: u32 = 2;
+const ACCOUNT_TYPE_PRICE: u32 = 3;
+const STATUS_TRADING: u32 = 1;
+
+const EXPO_OFFSET: usize = 20;
+const TIMESTAMP_OFFSET: usize = 96;
+const AGG_PRICE_OFFSET: usize = 208;
+const AGG_CONF_OFFSET: usize = 216;
+const AGG_STATUS_OFFSET: usize = 224;
+const MIN_ACCOUNT_LEN: usize = AGG_STATUS_OFFSET + 4;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OraclePrice {
+    pub price: i64,
+    pub conf: u64,
+    pub expo: i32,
+    pub publish_time: i64,
+}
+
+impl OraclePrice {
+    /// Le o preco agregado de uma conta no formato Pyth. Rejeita contas com outro
+    /// layout e precos fora do status `Trading`.
+    pub fn load(price_info: &AccountInfo) -> Result<Self> {
+        let data = price_info.try_borrow_data()?;
+        require!(
+            data.len() >= MIN_ACCOUNT_LEN,
+            VestingError::InvalidPriceFeed
+        );
+
+        let read_u32 =
+            |offset: usize| u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap());
+        let read_u64 =
+            |offset: usize| u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
+
+        require!(
+            read_u32(0) == MAGIC && read_u32(4) == VERSION_2 && read_u32(8) == ACCOUNT_TYPE_PRICE,
+            VestingError::InvalidPriceFeed
+        );
+        require!(
+            read_u32(AGG_STATUS_OFFSET) == STATUS_TRADING,
+            VestingError::InvalidPriceFeed
+        );
+
+        Ok(Self {
+            price: read_u64(AGG_PRICE_OFFSET) as i64,
+            conf: read_u64(AGG_CONF_OFFSET),
+            expo: read_u32(EXPO_OFFSET) as i32,
+            publish_time: read_u64(TIMESTAMP_OFFSET) as i64,
+        })
+    }
+
+    /// Intervalo de confianca relativo ao preco, em basis points.
+    pub fn confidence_bps(&self) -> u64 {
+        if self.price <= 0 {
+            return u64::MAX;
+        }
+        ((self.conf as u128)
+            .saturating_mul(10_000)
+            .saturating_div(self.price as u128))
+        .min(u64::MAX as u128) as u64
+    }
+}
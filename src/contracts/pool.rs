@@ -0,0 +1,565 @@
+//! Pool de vesting com varios beneficiarios sobre um unico escrow.
+//!
+//! Cada membro tem uma participacao em basis points do cronograma do pool e resgata
+//! sua parte de `calculate_vested_amount` de forma independente. Quando um membro sai,
+//! o criador congela o que ja foi liberado para ele e transfere a parte ainda nao
+//! liberada da sua participacao para outro beneficiario.
+//!
+//! `share_of` arredonda cada participacao para baixo, entao o escrow pode guardar
+//! alguns tokens que nenhum membro adquire; `sweep_pool_dust` os devolve ao criador
+//! quando nao resta nada a resgatar.
+
+use super::*;
+
+/// Limite de membros por pool (determina o espaco da conta).
+pub const MAX_POOL_MEMBERS: usize = 10;
+/// Soma obrigatoria das participacoes de um pool.
+pub const POOL_TOTAL_BPS: u16 = 10_000;
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn create<'info>(
+    ctx: Context<'_, '_, '_, 'info, CreateVestingPool<'info>>,
+    pool_id: u64,
+    total_amount: u64,
+    start_time: i64,
+    end_time: i64,
+    vesting_type: VestingType,
+    allowed_mint_flags: u16,
+    members: Vec<PoolMemberInit>,
+) -> Result<()> {
+    require!(end_time > start_time, VestingError::InvalidTimeRange);
+    require!(total_amount > 0, VestingError::InvalidAmount);
+    require!(
+        !vesting_type.is_event_driven(),
+        VestingError::UnsupportedVestingType
+    );
+    require!(
+        !members.is_empty() && members.len() <= MAX_POOL_MEMBERS,
+        VestingError::InvalidPoolShares
+    );
+
+    let mut total_bps: u32 = 0;
+    for (index, member) in members.iter().enumerate() {
+        require!(member.share_bps > 0, VestingError::InvalidPoolShares);
+        require!(
+            members[..index]
+                .iter()
+                .all(|other| other.beneficiary != member.beneficiary),
+            VestingError::InvalidPoolShares
+        );
+        total_bps += u32::from(member.share_bps);
+    }
+    require!(
+        total_bps == u32::from(POOL_TOTAL_BPS),
+        VestingError::InvalidPoolShares
+    );
+
+    let mint_info = ctx.accounts.mint.to_account_info();
+    let detected_mint_flags = inspect_mint_flags(&mint_info)?;
+    require!(
+        detected_mint_flags & mint_flags::DANGEROUS & !allowed_mint_flags == 0,
+        VestingError::DangerousMintExtension
+    );
+
+    let pool = &mut ctx.accounts.pool;
+    pool.creator = ctx.accounts.creator.key();
+    pool.mint = ctx.accounts.mint.key();
+    pool.pool_id = pool_id;
+    pool.start_time = start_time;
+    pool.end_time = end_time;
+    pool.vesting_type = vesting_type;
+    pool.bump = ctx.bumps.pool;
+    pool.is_token_2022 = mint_info.owner == &anchor_spl::token_2022::ID;
+    pool.mint_flags = detected_mint_flags;
+    pool.members = members
+        .iter()
+        .map(|member| PoolMember {
+            beneficiary: member.beneficiary,
+            share_bps: member.share_bps,
+            released_amount: 0,
+            offset_amount: 0,
+            settled_amount: 0,
+        })
+        .collect();
+
+    let decimals = ctx.accounts.mint.decimals;
+
+    let cpi_accounts = token_interface::TransferChecked {
+        from: ctx.accounts.sender_token_account.to_account_info(),
+        mint: ctx.accounts.mint.to_account_info(),
+        to: ctx.accounts.escrow_wallet.to_account_info(),
+        authority: ctx.accounts.creator.to_account_info(),
+    };
+
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts)
+        .with_remaining_accounts(ctx.remaining_accounts.to_vec());
+
+    transfer_checked_with_hook(cpi_ctx, total_amount, decimals)?;
+
+    // Assim como no contrato individual, o total e o valor efetivamente recebido
+    ctx.accounts.escrow_wallet.reload()?;
+    pool.total_amount = ctx.accounts.escrow_wallet.amount;
+
+    emit!(PoolCreated {
+        pool_id,
+        creator: pool.creator,
+        mint: pool.mint,
+        total_amount: pool.total_amount,
+        start_time,
+        end_time,
+        member_count: pool.members.len() as u8,
+        mint_flags: detected_mint_flags,
+    });
+
+    Ok(())
+}
+
+pub(crate) fn claim_share<'info>(
+    ctx: Context<'_, '_, '_, 'info, ClaimPoolShare<'info>>,
+) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp;
+    let beneficiary_key = ctx.accounts.beneficiary.key();
+
+    let (releasable, signer_seeds_data) = {
+        let pool = &mut ctx.accounts.pool;
+        let pool_vested = pool.vested_amount(current_time);
+
+        let member = pool
+            .members
+            .iter_mut()
+            .find(|member| member.beneficiary == beneficiary_key)
+            .ok_or(VestingError::PoolMemberNotFound)?;
+
+        let releasable = member
+            .entitled_amount(pool_vested)
+            .checked_sub(member.released_amount)
+            .ok_or(VestingError::MathOverflow)?;
+
+        require!(releasable > 0, VestingError::NothingToRelease);
+
+        member.released_amount = member
+            .released_amount
+            .checked_add(releasable)
+            .ok_or(VestingError::MathOverflow)?;
+
+        (
+            releasable,
+            (pool.creator, pool.mint, pool.pool_id, pool.bump),
+        )
+    };
+
+    let (creator_key, mint_key, pool_id, bump) = signer_seeds_data;
+    let id_bytes = pool_id.to_le_bytes();
+
+    let seeds = &[
+        b"pool",
+        creator_key.as_ref(),
+        mint_key.as_ref(),
+        id_bytes.as_ref(),
+        &[bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    let decimals = ctx.accounts.mint.decimals;
+
+    let cpi_accounts = token_interface::TransferChecked {
+        from: ctx.accounts.escrow_wallet.to_account_info(),
+        mint: ctx.accounts.mint.to_account_info(),
+        to: ctx.accounts.beneficiary_token_account.to_account_info(),
+        authority: ctx.accounts.pool.to_account_info(),
+    };
+
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer,
+    )
+    .with_remaining_accounts(ctx.remaining_accounts.to_vec());
+
+    transfer_checked_with_hook(cpi_ctx, releasable, decimals)?;
+
+    emit!(PoolShareClaimed {
+        pool_id,
+        beneficiary: beneficiary_key,
+        amount: releasable,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
+
+/// Desliga `departed` do pool: o que ja foi liberado da sua participacao ate agora
+/// continua resgatavel por ele, e a parte ainda nao liberada passa a `new_beneficiary`
+/// (membro existente ou novo membro).
+pub(crate) fn reassign_share(
+    ctx: Context<ReassignPoolShare>,
+    departed: Pubkey,
+    new_beneficiary: Pubkey,
+) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp;
+    require!(departed != new_beneficiary, VestingError::InvalidPoolShares);
+
+    let pool = &mut ctx.accounts.pool;
+    let pool_vested = pool.vested_amount(current_time);
+
+    let departed_index = pool
+        .members
+        .iter()
+        .position(|member| member.beneficiary == departed)
+        .ok_or(VestingError::PoolMemberNotFound)?;
+
+    let (share_bps, share_vested, settled_amount) = {
+        let member = &mut pool.members[departed_index];
+        require!(member.share_bps > 0, VestingError::InvalidPoolShares);
+
+        let share_bps = member.share_bps;
+        let share_vested = share_of(pool_vested, share_bps);
+        member.settled_amount = member.entitled_amount(pool_vested);
+        member.share_bps = 0;
+        member.offset_amount = 0;
+
+        (share_bps, share_vested, member.settled_amount)
+    };
+
+    match pool
+        .members
+        .iter_mut()
+        .find(|member| member.beneficiary == new_beneficiary)
+    {
+        Some(member) => {
+            member.share_bps = member
+                .share_bps
+                .checked_add(share_bps)
+                .ok_or(VestingError::MathOverflow)?;
+            member.offset_amount = member
+                .offset_amount
+                .checked_add(share_vested)
+                .ok_or(VestingError::MathOverflow)?;
+        }
+        None => {
+            require!(
+                pool.members.len() < MAX_POOL_MEMBERS,
+                VestingError::PoolFull
+            );
+            pool.members.push(PoolMember {
+                beneficiary: new_beneficiary,
+                share_bps,
+                released_amount: 0,
+                offset_amount: share_vested,
+                settled_amount: 0,
+            });
+        }
+    }
+
+    emit!(PoolShareReassigned {
+        pool_id: pool.pool_id,
+        departed,
+        new_beneficiary,
+        share_bps,
+        settled_amount,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
+
+pub(crate) fn sweep_dust<'info>(
+    ctx: Context<'_, '_, '_, 'info, SweepPoolDust<'info>>,
+) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp;
+    let pool = &ctx.accounts.pool;
+
+    // Inclui membros desligados, cujo `settled_amount` continua resgatavel
+    let pool_vested = pool.vested_amount(current_time);
+    require!(
+        pool_vested == pool.total_amount
+            && pool
+                .members
+                .iter()
+                .all(|member| member.released_amount >= member.entitled_amount(pool_vested)),
+        VestingError::PoolSharesOutstanding
+    );
+
+    let amount = ctx.accounts.escrow_wallet.amount;
+    require!(amount > 0, VestingError::NothingToRelease);
+
+    let id_bytes = pool.pool_id.to_le_bytes();
+    let seeds = &[
+        b"pool",
+        pool.creator.as_ref(),
+        pool.mint.as_ref(),
+        id_bytes.as_ref(),
+        &[pool.bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    let cpi_accounts = token_interface::TransferChecked {
+        from: ctx.accounts.escrow_wallet.to_account_info(),
+        mint: ctx.accounts.mint.to_account_info(),
+        to: ctx.accounts.creator_token_account.to_account_info(),
+        authority: pool.to_account_info(),
+    };
+
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer,
+    )
+    .with_remaining_accounts(ctx.remaining_accounts.to_vec());
+
+    transfer_checked_with_hook(cpi_ctx, amount, ctx.accounts.mint.decimals)?;
+
+    emit!(PoolDustSwept {
+        pool_id: pool.pool_id,
+        creator: pool.creator,
+        amount,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
+
+fn share_of(amount: u64, share_bps: u16) -> u64 {
+    ((amount as u128)
+        .saturating_mul(share_bps as u128)
+        .saturating_div(POOL_TOTAL_BPS as u128)) as u64
+}
+
+// -------------------------------------------------------------------------
+// DATA STRUCTURES
+// -------------------------------------------------------------------------
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct PoolMemberInit {
+    pub beneficiary: Pubkey,
+    pub share_bps: u16,
+}
+
+/// Membro do pool. O direito acumulado e
+/// `share_of(vested) - offset_amount + settled_amount`, onde `offset_amount` desconta a
+/// parte ja liberada de participacoes recebidas de membros desligados e
+/// `settled_amount` congela o que um membro desligado ja havia adquirido.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct PoolMember {
+    pub beneficiary: Pubkey,
+    pub share_bps: u16,
+    pub released_amount: u64,
+    pub offset_amount: u64,
+    pub settled_amount: u64,
+}
+
+impl PoolMember {
+    pub const LEN: usize = 32 + 2 + 8 + 8 + 8;
+
+    pub fn entitled_amount(&self, pool_vested: u64) -> u64 {
+        share_of(pool_vested, self.share_bps)
+            .saturating_sub(self.offset_amount)
+            .saturating_add(self.settled_amount)
+    }
+}
+
+#[account]
+pub struct VestingPool {
+    pub creator: Pubkey,
+    pub mint: Pubkey,
+    pub pool_id: u64,
+    pub total_amount: u64,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub vesting_type: VestingType,
+    pub bump: u8,
+    pub is_token_2022: bool,
+    pub mint_flags: u16,
+    pub members: Vec<PoolMember>,
+}
+
+impl VestingPool {
+    pub const LEN: usize =
+        8 + // discriminator
+        32 + // creator
+        32 + // mint
+        8 + // pool_id
+        8 + // total_amount
+        8 + // start_time
+        8 + // end_time
+        1 + 16 + // vesting_type (enum tag + max variant)
+        1 + // bump
+        1 + // is_token_2022
+        2 + // mint_flags
+        4 + MAX_POOL_MEMBERS * PoolMember::LEN; // members
+
+    pub fn vested_amount(&self, current_time: i64) -> u64 {
+        calculate_vested_amount(
+            current_time,
+            self.start_time,
+            self.end_time,
+            self.total_amount,
+            &self.vesting_type,
+        )
+    }
+}
+
+// -------------------------------------------------------------------------
+// EVENTS
+// -------------------------------------------------------------------------
+
+#[event]
+pub struct PoolCreated {
+    pub pool_id: u64,
+    pub creator: Pubkey,
+    pub mint: Pubkey,
+    pub total_amount: u64,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub member_count: u8,
+    pub mint_flags: u16,
+}
+
+#[event]
+pub struct PoolShareClaimed {
+    pub pool_id: u64,
+    pub beneficiary: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PoolShareReassigned {
+    pub pool_id: u64,
+    pub departed: Pubkey,
+    pub new_beneficiary: Pubkey,
+    pub share_bps: u16,
+    pub settled_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PoolDustSwept {
+    pub pool_id: u64,
+    pub creator: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+// -------------------------------------------------------------------------
+// CONTEXTS
+// -------------------------------------------------------------------------
+
+#[derive(Accounts)]
+#[instruction(pool_id: u64)]
+pub struct CreateVestingPool<'info> {
+    #[account(
+        init,
+        payer = creator,
+        seeds = [
+            b"pool",
+            creator.key().as_ref(),
+            mint.key().as_ref(),
+            &pool_id.to_le_bytes()
+        ],
+        bump,
+        space = VestingPool::LEN
+    )]
+    pub pool: Account<'info, VestingPool>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        payer = creator,
+        token::mint = mint,
+        token::authority = pool,
+        token::token_program = token_program,
+        seeds = [b"escrow", pool.key().as_ref()],
+        bump
+    )]
+    pub escrow_wallet: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = sender_token_account.mint == mint.key() @ VestingError::InvalidAmount,
+        constraint = sender_token_account.owner == creator.key() @ VestingError::Unauthorized
+    )]
+    pub sender_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimPoolShare<'info> {
+    #[account(mut)]
+    pub pool: Account<'info, VestingPool>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", pool.key().as_ref()],
+        bump,
+        constraint = escrow_wallet.owner == pool.key() @ VestingError::Unauthorized,
+        constraint = escrow_wallet.mint == pool.mint @ VestingError::InvalidAmount
+    )]
+    pub escrow_wallet: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = beneficiary_token_account.mint == pool.mint @ VestingError::InvalidAmount,
+        constraint = beneficiary_token_account.owner == beneficiary.key() @ VestingError::Unauthorized
+    )]
+    pub beneficiary_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Validado contra a lista de membros do pool
+    pub beneficiary: UncheckedAccount<'info>,
+
+    #[account(
+        constraint = mint.key() == pool.mint @ VestingError::InvalidAmount
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct ReassignPoolShare<'info> {
+    #[account(
+        mut,
+        has_one = creator @ VestingError::Unauthorized
+    )]
+    pub pool: Account<'info, VestingPool>,
+
+    pub creator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SweepPoolDust<'info> {
+    #[account(
+        has_one = creator @ VestingError::Unauthorized
+    )]
+    pub pool: Account<'info, VestingPool>,
+
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", pool.key().as_ref()],
+        bump,
+        constraint = escrow_wallet.owner == pool.key() @ VestingError::Unauthorized,
+        constraint = escrow_wallet.mint == pool.mint @ VestingError::InvalidAmount
+    )]
+    pub escrow_wallet: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = creator_token_account.mint == pool.mint @ VestingError::InvalidAmount,
+        constraint = creator_token_account.owner == creator.key() @ VestingError::Unauthorized
+    )]
+    pub creator_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        constraint = mint.key() == pool.mint @ VestingError::InvalidAmount
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
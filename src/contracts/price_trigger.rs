@@ -0,0 +1,343 @@
+//! Liberacao por gatilho de preco (`VestingType::PriceTriggered`).
+//!
+//! Cada parcela libera seu valor quando o preco do oraculo permanece acima do limite
+//! por `hold_seconds`. A permanencia e acompanhada pelas observacoes: `claim_tokens` e
+//! `refresh_price_trigger` leem o preco, validam atraso e confianca e atualizam
+//! `above_since` de cada parcela. Uma observacao abaixo do limite reinicia a contagem;
+//! entre duas observacoes separadas por ate `max_staleness` o preco e considerado
+//! continuo, e um intervalo maior tambem reinicia a contagem (keepers devem chamar
+//! `refresh_price_trigger` com essa frequencia). Em `claim_tokens` a observacao e
+//! opcional: sem um preco valido o resgate paga apenas as parcelas ja liberadas.
+
+use super::*;
+
+/// Limite de parcelas por contrato (determina o espaco da conta).
+pub const MAX_PRICE_TRANCHES: usize = 8;
+
+pub(crate) fn create<'info>(
+    ctx: Context<'_, '_, '_, 'info, CreatePriceTrigger<'info>>,
+    config: PriceTriggerConfig,
+    tranches: Vec<PriceTrancheInit>,
+) -> Result<()> {
+    let vesting_contract = &ctx.accounts.vesting_contract;
+    require!(
+        vesting_contract.vesting_type == VestingType::PriceTriggered,
+        VestingError::UnsupportedVestingType
+    );
+    require!(
+        !tranches.is_empty() && tranches.len() <= MAX_PRICE_TRANCHES,
+        VestingError::InvalidPriceTrigger
+    );
+    require!(
+        config.max_staleness > 0 && config.max_confidence_bps > 0,
+        VestingError::InvalidPriceTrigger
+    );
+    require!(
+        ctx.accounts.price_feed.owner == &config.oracle_program,
+        VestingError::InvalidPriceFeed
+    );
+
+    let mut total: u64 = 0;
+    for tranche in &tranches {
+        require!(
+            tranche.amount > 0 && tranche.threshold_price > 0 && tranche.hold_seconds >= 0,
+            VestingError::InvalidPriceTrigger
+        );
+        total = total
+            .checked_add(tranche.amount)
+            .ok_or(VestingError::MathOverflow)?;
+    }
+    require!(
+        total == vesting_contract.total_amount,
+        VestingError::InvalidPriceTrigger
+    );
+
+    let trigger = &mut ctx.accounts.price_trigger;
+    trigger.vesting_contract = vesting_contract.key();
+    trigger.price_feed = ctx.accounts.price_feed.key();
+    trigger.oracle_program = config.oracle_program;
+    trigger.expo = config.expo;
+    trigger.max_staleness = config.max_staleness;
+    trigger.max_confidence_bps = config.max_confidence_bps;
+    trigger.bump = ctx.bumps.price_trigger;
+    trigger.last_observed = 0;
+    trigger.tranches = tranches
+        .iter()
+        .map(|tranche| PriceTranche {
+            threshold_price: tranche.threshold_price,
+            hold_seconds: tranche.hold_seconds,
+            amount: tranche.amount,
+            above_since: 0,
+            unlocked: false,
+        })
+        .collect();
+
+    emit!(PriceTriggerCreated {
+        contract_id: vesting_contract.contract_id,
+        price_feed: trigger.price_feed,
+        tranche_count: trigger.tranches.len() as u8,
+    });
+
+    Ok(())
+}
+
+pub(crate) fn refresh(ctx: Context<RefreshPriceTrigger>) -> Result<()> {
+    observe_price(
+        &mut ctx.accounts.vesting_contract,
+        &mut ctx.accounts.price_trigger,
+        &ctx.accounts.price_feed,
+    )
+}
+
+/// Le o oraculo e atualiza as parcelas do gatilho, somando as liberadas em
+/// `VestingContract::unlocked_amount`.
+pub(crate) fn observe_price(
+    vesting_contract: &mut Account<VestingContract>,
+    trigger: &mut Account<PriceTrigger>,
+    price_feed: &AccountInfo,
+) -> Result<()> {
+    check_accounts(vesting_contract, trigger, price_feed)?;
+    let current_time = Clock::get()?.unix_timestamp;
+    let price = read_price(trigger, price_feed, current_time)?;
+    apply_price(vesting_contract, trigger, price, current_time)
+}
+
+/// Variante de `observe_price` usada por `claim_tokens`: um preco desatualizado,
+/// incerto ou fora de negociacao apenas deixa de atualizar o gatilho. Contas que
+/// nao pertencem ao contrato continuam rejeitadas.
+pub(crate) fn try_observe_price(
+    vesting_contract: &mut Account<VestingContract>,
+    trigger: &mut Account<PriceTrigger>,
+    price_feed: &AccountInfo,
+) -> Result<()> {
+    check_accounts(vesting_contract, trigger, price_feed)?;
+    let current_time = Clock::get()?.unix_timestamp;
+    match read_price(trigger, price_feed, current_time) {
+        Ok(price) => apply_price(vesting_contract, trigger, price, current_time),
+        Err(error) => {
+            msg!("Observacao de preco ignorada: {}", error);
+            Ok(())
+        }
+    }
+}
+
+fn check_accounts(
+    vesting_contract: &Account<VestingContract>,
+    trigger: &PriceTrigger,
+    price_feed: &AccountInfo,
+) -> Result<()> {
+    require!(
+        trigger.vesting_contract == vesting_contract.key(),
+        VestingError::InvalidPriceTrigger
+    );
+    require!(
+        price_feed.key() == trigger.price_feed && price_feed.owner == &trigger.oracle_program,
+        VestingError::InvalidPriceFeed
+    );
+    Ok(())
+}
+
+/// Le o preco e valida expoente, atraso e confianca, sem alterar o gatilho.
+fn read_price(
+    trigger: &PriceTrigger,
+    price_feed: &AccountInfo,
+    current_time: i64,
+) -> Result<OraclePrice> {
+    let price = OraclePrice::load(price_feed)?;
+    require!(price.expo == trigger.expo, VestingError::InvalidPriceFeed);
+    require!(
+        current_time.saturating_sub(price.publish_time) <= trigger.max_staleness,
+        VestingError::StalePrice
+    );
+    require!(
+        price.confidence_bps() <= u64::from(trigger.max_confidence_bps),
+        VestingError::PriceConfidenceTooWide
+    );
+    Ok(price)
+}
+
+fn apply_price(
+    vesting_contract: &mut Account<VestingContract>,
+    trigger: &mut PriceTrigger,
+    price: OraclePrice,
+    current_time: i64,
+) -> Result<()> {
+    // Uma publicacao anterior a ultima aceita nao acrescenta informacao
+    if price.publish_time < trigger.last_observed {
+        return Ok(());
+    }
+    // Sem observacoes no intervalo nao ha como saber se o preco caiu: as contagens
+    // em andamento recomecam
+    if trigger.last_observed != 0
+        && price.publish_time.saturating_sub(trigger.last_observed) > trigger.max_staleness
+    {
+        for tranche in trigger.tranches.iter_mut() {
+            tranche.above_since = 0;
+        }
+    }
+    trigger.last_observed = price.publish_time;
+
+    for (index, tranche) in trigger.tranches.iter_mut().enumerate() {
+        if tranche.unlocked {
+            continue;
+        }
+        if price.price <= tranche.threshold_price {
+            tranche.above_since = 0;
+            continue;
+        }
+        if tranche.above_since == 0 {
+            tranche.above_since = price.publish_time;
+        }
+        if price.publish_time.saturating_sub(tranche.above_since) >= tranche.hold_seconds {
+            tranche.unlocked = true;
+            vesting_contract.unlocked_amount = vesting_contract
+                .unlocked_amount
+                .checked_add(tranche.amount)
+                .ok_or(VestingError::MathOverflow)?;
+
+            emit!(PriceTrancheUnlocked {
+                contract_id: vesting_contract.contract_id,
+                index: index as u8,
+                price: price.price,
+                amount: tranche.amount,
+                timestamp: current_time,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+// -------------------------------------------------------------------------
+// DATA STRUCTURES
+// -------------------------------------------------------------------------
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct PriceTriggerConfig {
+    /// Programa dono da conta de preco (Pyth ou um feed compativel).
+    pub oracle_program: Pubkey,
+    /// Expoente esperado do feed; os limites das parcelas usam a mesma escala.
+    pub expo: i32,
+    /// Atraso maximo, em segundos, entre a publicacao do preco e o relogio.
+    pub max_staleness: i64,
+    /// Confianca maxima aceita, relativa ao preco, em basis points.
+    pub max_confidence_bps: u16,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct PriceTrancheInit {
+    pub threshold_price: i64,
+    pub hold_seconds: i64,
+    pub amount: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct PriceTranche {
+    pub threshold_price: i64,
+    pub hold_seconds: i64,
+    pub amount: u64,
+    /// Publicacao da primeira observacao da sequencia atual acima do limite (0 = nenhuma).
+    pub above_since: i64,
+    pub unlocked: bool,
+}
+
+impl PriceTranche {
+    pub const LEN: usize = 8 + 8 + 8 + 8 + 1;
+}
+
+#[account]
+pub struct PriceTrigger {
+    pub vesting_contract: Pubkey,
+    pub price_feed: Pubkey,
+    pub oracle_program: Pubkey,
+    pub expo: i32,
+    pub max_staleness: i64,
+    pub max_confidence_bps: u16,
+    pub bump: u8,
+    /// Publicacao da ultima observacao aceita (0 = nenhuma).
+    pub last_observed: i64,
+    pub tranches: Vec<PriceTranche>,
+}
+
+impl PriceTrigger {
+    pub const LEN: usize =
+        8 + // discriminator
+        32 + // vesting_contract
+        32 + // price_feed
+        32 + // oracle_program
+        4 + // expo
+        8 + // max_staleness
+        2 + // max_confidence_bps
+        1 + // bump
+        8 + // last_observed
+        4 + MAX_PRICE_TRANCHES * PriceTranche::LEN; // tranches
+}
+
+// -------------------------------------------------------------------------
+// EVENTS
+// -------------------------------------------------------------------------
+
+#[event]
+pub struct PriceTriggerCreated {
+    pub contract_id: u64,
+    pub price_feed: Pubkey,
+    pub tranche_count: u8,
+}
+
+#[event]
+pub struct PriceTrancheUnlocked {
+    pub contract_id: u64,
+    pub index: u8,
+    pub price: i64,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+// -------------------------------------------------------------------------
+// CONTEXTS
+// -------------------------------------------------------------------------
+
+#[derive(Accounts)]
+pub struct CreatePriceTrigger<'info> {
+    #[account(
+        init,
+        payer = creator,
+        seeds = [b"price_trigger", vesting_contract.key().as_ref()],
+        bump,
+        space = PriceTrigger::LEN
+    )]
+    pub price_trigger: Account<'info, PriceTrigger>,
+
+    #[account(
+        has_one = creator @ VestingError::Unauthorized,
+        constraint = !vesting_contract.is_cancelled @ VestingError::ContractCancelled
+    )]
+    pub vesting_contract: Account<'info, VestingContract>,
+
+    /// CHECK: Dono validado contra `oracle_program`; layout validado a cada leitura
+    pub price_feed: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RefreshPriceTrigger<'info> {
+    #[account(
+        mut,
+        seeds = [b"price_trigger", vesting_contract.key().as_ref()],
+        bump = price_trigger.bump
+    )]
+    pub price_trigger: Account<'info, PriceTrigger>,
+
+    #[account(
+        mut,
+        constraint = !vesting_contract.is_cancelled @ VestingError::ContractCancelled
+    )]
+    pub vesting_contract: Account<'info, VestingContract>,
+
+    /// CHECK: Validado contra o `price_trigger`
+    pub price_feed: UncheckedAccount<'info>,
+}
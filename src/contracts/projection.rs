@@ -0,0 +1,146 @@
+//! Projecao do cronograma de um contrato.
+//!
+//! `project_schedule` devolve via return data o calendario de liberacoes e uma curva
+//! amostrada entre o inicio e o fim, para graficos e planejamento tributario. Pausas
+//! deslocam o cronograma; a projecao supoe que uma pausa em andamento termina agora.
+//! Contratos cancelados nao tem liberacoes futuras: calendario e curva vem vazios.
+
+use super::*;
+
+/// Maximo de amostras por projecao (cabe no limite de return data).
+pub const MAX_PROJECTION_POINTS: u8 = 48;
+
+pub(crate) fn read(ctx: Context<ProjectSchedule>, points: u8) -> Result<ScheduleProjection> {
+    let current_time = Clock::get()?.unix_timestamp;
+    Ok(schedule_projection(
+        &ctx.accounts.vesting_contract,
+        current_time,
+        points,
+    ))
+}
+
+/// Atraso acumulado do cronograma em relacao ao relogio, contando a pausa em
+/// andamento ate `current_time`.
+fn pause_shift(vesting_contract: &VestingContract, current_time: i64) -> i64 {
+    vesting_contract
+        .paused_duration
+        .saturating_add(vesting_contract.ongoing_pause(current_time))
+}
+
+pub fn schedule_projection(
+    vesting_contract: &VestingContract,
+    current_time: i64,
+    points: u8,
+) -> ScheduleProjection {
+    let shift = pause_shift(vesting_contract, current_time);
+    let mut projection = ScheduleProjection {
+        total_amount: vesting_contract.total_amount,
+        released_amount: vesting_contract.released_amount,
+        vested_amount: vesting_contract.released_amount,
+        claimable_amount: 0,
+        is_cancelled: vesting_contract.is_cancelled,
+        pause_shift: shift,
+        unlocks: Vec::new(),
+        points: Vec::new(),
+    };
+    if vesting_contract.is_cancelled {
+        return projection;
+    }
+
+    let vested_amount = vesting_contract.vested_amount(current_time);
+    projection.vested_amount = vested_amount;
+    projection.claimable_amount = vested_amount.saturating_sub(vesting_contract.released_amount);
+
+    let start = vesting_contract.start_time;
+    let end = vesting_contract.end_time;
+    let total = vesting_contract.total_amount;
+    let schedule = vesting_contract.vesting_type.schedule();
+
+    projection.unlocks = verum_vesting_math::unlock_events(start, end, total, schedule)
+        .map(|event| ScheduleUnlock {
+            kind: match event.kind {
+                verum_vesting_math::UnlockKind::Start => UnlockKind::Start,
+                verum_vesting_math::UnlockKind::Cliff => UnlockKind::Cliff,
+                verum_vesting_math::UnlockKind::End => UnlockKind::End,
+            },
+            time: event.time.saturating_add(shift),
+            vested_amount: event.vested,
+        })
+        .collect();
+
+    let points = points.min(MAX_PROJECTION_POINTS) as usize;
+    projection.points = verum_vesting_math::sample_times(
+        start.saturating_add(shift),
+        end.saturating_add(shift),
+        points,
+    )
+    .map(|time| SchedulePoint {
+        time,
+        // Liberacoes por evento nao tem curva: o valor fica no desbloqueado ate agora
+        vested_amount: if vesting_contract.vesting_type.is_event_driven() {
+            vested_amount
+        } else {
+            verum_vesting_math::vested_amount(
+                time.saturating_sub(shift),
+                start,
+                end,
+                total,
+                schedule,
+            )
+        },
+    })
+    .collect();
+
+    projection
+}
+
+// -------------------------------------------------------------------------
+// DATA STRUCTURES
+// -------------------------------------------------------------------------
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum UnlockKind {
+    Start,
+    Cliff,
+    End,
+}
+
+/// Ponto de inflexao do cronograma. Entre pontos consecutivos o valor cresce
+/// linearmente, exceto de `Start` ate `Cliff`, quando nada e liberado.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ScheduleUnlock {
+    pub kind: UnlockKind,
+    pub time: i64,
+    /// Valor acumulado liberado em `time`.
+    pub vested_amount: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct SchedulePoint {
+    pub time: i64,
+    pub vested_amount: u64,
+}
+
+/// Resultado de `project_schedule`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ScheduleProjection {
+    pub total_amount: u64,
+    pub released_amount: u64,
+    /// Valor liberado agora; em contratos cancelados, igual a `released_amount`.
+    pub vested_amount: u64,
+    pub claimable_amount: u64,
+    pub is_cancelled: bool,
+    /// Segundos somados aos instantes do cronograma original por causa de pausas.
+    pub pause_shift: i64,
+    pub unlocks: Vec<ScheduleUnlock>,
+    pub points: Vec<SchedulePoint>,
+}
+
+// -------------------------------------------------------------------------
+// CONTEXTS
+// -------------------------------------------------------------------------
+
+#[derive(Accounts)]
+pub struct ProjectSchedule<'info> {
+    pub vesting_contract: Account<'info, VestingContract>,
+}
@@ -0,0 +1,81 @@
+//! Recibos de liberacao.
+//!
+//! Quando o cliente envia `release_receipt` (e quem paga o aluguel) em
+//! `claim_tokens`, o resgate cria um `ReleaseReceipt`
+//! (`[b"release_receipt", vesting_contract, claim_index]`) com valor, conta de
+//! destino, slot e instante. Como e criado na mesma instrucao que transfere os
+//! tokens, a existencia do recibo prova o pagamento sem varrer transacoes.
+
+use super::*;
+
+pub const RELEASE_RECEIPT_SEED: &[u8] = b"release_receipt";
+
+/// Preenche o recibo do resgate `claim_index`.
+pub(crate) fn issue(
+    release_receipt: &mut ReleaseReceipt,
+    vesting_contract: Pubkey,
+    claim_index: u64,
+    destination: Pubkey,
+    amount: u64,
+    bump: u8,
+) -> Result<()> {
+    let clock = Clock::get()?;
+
+    release_receipt.vesting_contract = vesting_contract;
+    release_receipt.claim_index = claim_index;
+    release_receipt.destination = destination;
+    release_receipt.amount = amount;
+    release_receipt.slot = clock.slot;
+    release_receipt.timestamp = clock.unix_timestamp;
+    release_receipt.bump = bump;
+
+    emit!(ReleaseReceiptIssued {
+        vesting_contract,
+        claim_index,
+        destination,
+        amount,
+    });
+
+    Ok(())
+}
+
+// -------------------------------------------------------------------------
+// DATA STRUCTURES
+// -------------------------------------------------------------------------
+
+#[account]
+pub struct ReleaseReceipt {
+    pub vesting_contract: Pubkey,
+    /// Valor de `VestingContract::claim_count` no momento do resgate.
+    pub claim_index: u64,
+    /// Conta de token do beneficiario, ou o proprio beneficiario em contratos nativos.
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub slot: u64,
+    pub timestamp: i64,
+    pub bump: u8,
+}
+
+impl ReleaseReceipt {
+    pub const LEN: usize =
+        8 + // discriminator
+        32 + // vesting_contract
+        8 + // claim_index
+        32 + // destination
+        8 + // amount
+        8 + // slot
+        8 + // timestamp
+        1; // bump
+}
+
+// -------------------------------------------------------------------------
+// EVENTS
+// -------------------------------------------------------------------------
+
+#[event]
+pub struct ReleaseReceiptIssued {
+    pub vesting_contract: Pubkey,
+    pub claim_index: u64,
+    pub destination: Pubkey,
+    pub amount: u64,
+}
@@ -0,0 +1,454 @@
+//! Staking do saldo ainda bloqueado no escrow.
+//!
+//! O criador autoriza, por contrato, um programa de staking (`set_staking_program`).
+//! O beneficiario pode entao delegar a esse programa ate o saldo ainda nao liberado e
+//! resgatar as recompensas direto na sua conta. O valor delegado fica em
+//! `VestingContract::staked_amount`: enquanto ele for positivo, `cancel_vesting` e
+//! pagamentos que excedam o saldo do escrow exigem o `unstake_locked` antes (o
+//! criador tambem pode fazer o unstake, de modo que o staking nunca bloqueia o
+//! cancelamento).
+//!
+//! O programa autorizado deve implementar a interface abaixo (a mesma do
+//! `mock_staking`), assinada pelo PDA do contrato como dono da posicao:
+//!
+//!   - `Stake { amount }` (tag 1): registro, dono, escrow, cofre, mint, token
+//!     program, pagador, system program.
+//!   - `Unstake { amount }` (tag 2) e `ClaimRewards` (tag 3): registro, dono,
+//!     destino, cofre, autoridade do cofre, mint, token program.
+//!
+//! Como o PDA do contrato assina a CPI e tambem e dono do escrow, o programa de
+//! staking poderia aprovar um delegate ou trocar as autoridades do escrow sem mover
+//! tokens. Depois de cada CPI que recebe o escrow (`Stake` e `Unstake`) ele e
+//! recarregado e validado por `require_escrow_untouched`; `ClaimRewards` nao recebe o
+//! escrow.
+
+use super::*;
+
+const STAKE_TAG: u8 = 1;
+const UNSTAKE_TAG: u8 = 2;
+const CLAIM_REWARDS_TAG: u8 = 3;
+
+/// Caracteristicas do mint incompativeis com a contabilidade do staking: o valor que
+/// chega ao cofre ou volta ao escrow poderia diferir do valor delegado.
+const UNSUPPORTED_MINT_FLAGS: u16 = mint_flags::TRANSFER_FEE | mint_flags::TRANSFER_HOOK;
+
+pub(crate) fn set_program(ctx: Context<SetStakingProgram>, staking_program: Pubkey) -> Result<()> {
+    let vesting_contract = &mut ctx.accounts.vesting_contract;
+
+    require!(
+        !vesting_contract.is_native,
+        VestingError::UnsupportedVestingType
+    );
+    require!(
+        vesting_contract.mint_flags & UNSUPPORTED_MINT_FLAGS == 0,
+        VestingError::UnsupportedStakingMint
+    );
+    require!(
+        vesting_contract.staked_amount == 0,
+        VestingError::TokensStaked
+    );
+
+    vesting_contract.staking_program = staking_program;
+
+    emit!(StakingProgramUpdated {
+        contract_id: vesting_contract.contract_id,
+        staking_program,
+    });
+
+    Ok(())
+}
+
+pub(crate) fn stake(ctx: Context<StakeLocked>, amount: u64) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp;
+    let vesting_contract = &ctx.accounts.vesting_contract;
+
+    require!(amount > 0, VestingError::InvalidAmount);
+    // Apos um clawback o valor liberado pode ficar abaixo de `released_amount`
+    let locked = vesting_contract.total_amount.saturating_sub(
+        vesting_contract
+            .vested_amount(current_time)
+            .max(vesting_contract.released_amount),
+    );
+    let staked_amount = vesting_contract
+        .staked_amount
+        .checked_add(amount)
+        .ok_or(VestingError::MathOverflow)?;
+    require!(staked_amount <= locked, VestingError::StakeExceedsLocked);
+
+    let escrow_before = ctx.accounts.escrow_wallet.amount;
+    let instruction = Instruction {
+        program_id: ctx.accounts.staking_program.key(),
+        accounts: vec![
+            AccountMeta::new(ctx.accounts.stake_record.key(), false),
+            AccountMeta::new_readonly(vesting_contract.key(), true),
+            AccountMeta::new(ctx.accounts.escrow_wallet.key(), false),
+            AccountMeta::new(ctx.accounts.stake_vault.key(), false),
+            AccountMeta::new_readonly(ctx.accounts.mint.key(), false),
+            AccountMeta::new_readonly(ctx.accounts.token_program.key(), false),
+            AccountMeta::new(ctx.accounts.beneficiary.key(), true),
+            AccountMeta::new_readonly(ctx.accounts.system_program.key(), false),
+        ],
+        data: [&[STAKE_TAG][..], &amount.to_le_bytes()].concat(),
+    };
+    invoke_as_contract(
+        vesting_contract,
+        &instruction,
+        &[
+            ctx.accounts.stake_record.to_account_info(),
+            vesting_contract.to_account_info(),
+            ctx.accounts.escrow_wallet.to_account_info(),
+            ctx.accounts.stake_vault.to_account_info(),
+            ctx.accounts.mint.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.beneficiary.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            ctx.accounts.staking_program.to_account_info(),
+        ],
+    )?;
+
+    ctx.accounts.escrow_wallet.reload()?;
+    require_escrow_untouched(&ctx.accounts.escrow_wallet, &ctx.accounts.vesting_contract)?;
+    require!(
+        escrow_before.checked_sub(ctx.accounts.escrow_wallet.amount) == Some(amount),
+        VestingError::StakingBalanceMismatch
+    );
+
+    let vesting_contract = &mut ctx.accounts.vesting_contract;
+    vesting_contract.staked_amount = staked_amount;
+
+    emit!(TokensStaked {
+        contract_id: vesting_contract.contract_id,
+        staking_program: vesting_contract.staking_program,
+        amount,
+        staked_amount,
+    });
+
+    Ok(())
+}
+
+/// Devolve ao escrow parte do valor delegado; assinada pelo criador ou pelo
+/// beneficiario.
+pub(crate) fn unstake(ctx: Context<UnstakeLocked>, amount: u64) -> Result<()> {
+    let authority = ctx.accounts.authority.key();
+    let vesting_contract = &ctx.accounts.vesting_contract;
+
+    require!(
+        authority == vesting_contract.creator || authority == vesting_contract.beneficiary,
+        VestingError::Unauthorized
+    );
+    require!(amount > 0, VestingError::InvalidAmount);
+    let staked_amount = vesting_contract
+        .staked_amount
+        .checked_sub(amount)
+        .ok_or(VestingError::InvalidAmount)?;
+
+    let escrow_before = ctx.accounts.escrow_wallet.amount;
+    StakingWithdrawal {
+        vesting_contract,
+        stake_record: ctx.accounts.stake_record.to_account_info(),
+        destination: ctx.accounts.escrow_wallet.to_account_info(),
+        stake_vault: ctx.accounts.stake_vault.to_account_info(),
+        vault_authority: ctx.accounts.vault_authority.to_account_info(),
+        mint: ctx.accounts.mint.to_account_info(),
+        token_program: ctx.accounts.token_program.to_account_info(),
+        staking_program: ctx.accounts.staking_program.to_account_info(),
+    }
+    .invoke([&[UNSTAKE_TAG][..], &amount.to_le_bytes()].concat())?;
+
+    ctx.accounts.escrow_wallet.reload()?;
+    require_escrow_untouched(&ctx.accounts.escrow_wallet, &ctx.accounts.vesting_contract)?;
+    require!(
+        ctx.accounts.escrow_wallet.amount.checked_sub(escrow_before) == Some(amount),
+        VestingError::StakingBalanceMismatch
+    );
+
+    let vesting_contract = &mut ctx.accounts.vesting_contract;
+    vesting_contract.staked_amount = staked_amount;
+
+    emit!(TokensUnstaked {
+        contract_id: vesting_contract.contract_id,
+        authority,
+        amount,
+        staked_amount,
+    });
+
+    Ok(())
+}
+
+/// Recompensas vao direto para a conta do beneficiario e nao alteram o cronograma.
+pub(crate) fn claim_rewards(ctx: Context<ClaimStakingRewards>) -> Result<()> {
+    let balance_before = ctx.accounts.beneficiary_token_account.amount;
+
+    StakingWithdrawal {
+        vesting_contract: &ctx.accounts.vesting_contract,
+        stake_record: ctx.accounts.stake_record.to_account_info(),
+        destination: ctx.accounts.beneficiary_token_account.to_account_info(),
+        stake_vault: ctx.accounts.stake_vault.to_account_info(),
+        vault_authority: ctx.accounts.vault_authority.to_account_info(),
+        mint: ctx.accounts.mint.to_account_info(),
+        token_program: ctx.accounts.token_program.to_account_info(),
+        staking_program: ctx.accounts.staking_program.to_account_info(),
+    }
+    .invoke(vec![CLAIM_REWARDS_TAG])?;
+
+    ctx.accounts.beneficiary_token_account.reload()?;
+    let amount = ctx
+        .accounts
+        .beneficiary_token_account
+        .amount
+        .saturating_sub(balance_before);
+
+    emit!(StakingRewardsClaimed {
+        contract_id: ctx.accounts.vesting_contract.contract_id,
+        beneficiary: ctx.accounts.beneficiary.key(),
+        amount,
+    });
+
+    Ok(())
+}
+
+/// Contas das instrucoes `Unstake` e `ClaimRewards` da interface de staking.
+struct StakingWithdrawal<'a, 'info> {
+    vesting_contract: &'a Account<'info, VestingContract>,
+    stake_record: AccountInfo<'info>,
+    destination: AccountInfo<'info>,
+    stake_vault: AccountInfo<'info>,
+    vault_authority: AccountInfo<'info>,
+    mint: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+    staking_program: AccountInfo<'info>,
+}
+
+impl<'a, 'info> StakingWithdrawal<'a, 'info> {
+    fn invoke(self, data: Vec<u8>) -> Result<()> {
+        let instruction = Instruction {
+            program_id: self.staking_program.key(),
+            accounts: vec![
+                AccountMeta::new(self.stake_record.key(), false),
+                AccountMeta::new_readonly(self.vesting_contract.key(), true),
+                AccountMeta::new(self.destination.key(), false),
+                AccountMeta::new(self.stake_vault.key(), false),
+                AccountMeta::new_readonly(self.vault_authority.key(), false),
+                AccountMeta::new_readonly(self.mint.key(), false),
+                AccountMeta::new_readonly(self.token_program.key(), false),
+            ],
+            data,
+        };
+        invoke_as_contract(
+            self.vesting_contract,
+            &instruction,
+            &[
+                self.stake_record,
+                self.vesting_contract.to_account_info(),
+                self.destination,
+                self.stake_vault,
+                self.vault_authority,
+                self.mint,
+                self.token_program,
+                self.staking_program,
+            ],
+        )
+    }
+}
+
+/// O escrow continua controlado apenas pelo PDA do contrato apos a CPI.
+fn require_escrow_untouched(
+    escrow_wallet: &InterfaceAccount<TokenAccount>,
+    vesting_contract: &Account<VestingContract>,
+) -> Result<()> {
+    require!(
+        escrow_wallet.owner == vesting_contract.key()
+            && escrow_wallet.delegate.is_none()
+            && escrow_wallet.close_authority.is_none(),
+        VestingError::EscrowAuthorityChanged
+    );
+    Ok(())
+}
+
+fn invoke_as_contract(
+    vesting_contract: &Account<VestingContract>,
+    instruction: &Instruction,
+    account_infos: &[AccountInfo],
+) -> Result<()> {
+    let id_bytes = vesting_contract.contract_id.to_le_bytes();
+    let seeds = &[
+        b"vesting",
+        vesting_contract.creator.as_ref(),
+        vesting_contract.mint.as_ref(),
+        id_bytes.as_ref(),
+        &[vesting_contract.bump],
+    ];
+
+    invoke_signed(instruction, account_infos, &[&seeds[..]]).map_err(Into::into)
+}
+
+// -------------------------------------------------------------------------
+// EVENTS
+// -------------------------------------------------------------------------
+
+#[event]
+pub struct StakingProgramUpdated {
+    pub contract_id: u64,
+    pub staking_program: Pubkey,
+}
+
+#[event]
+pub struct TokensStaked {
+    pub contract_id: u64,
+    pub staking_program: Pubkey,
+    pub amount: u64,
+    pub staked_amount: u64,
+}
+
+#[event]
+pub struct TokensUnstaked {
+    pub contract_id: u64,
+    pub authority: Pubkey,
+    pub amount: u64,
+    pub staked_amount: u64,
+}
+
+#[event]
+pub struct StakingRewardsClaimed {
+    pub contract_id: u64,
+    pub beneficiary: Pubkey,
+    pub amount: u64,
+}
+
+// -------------------------------------------------------------------------
+// CONTEXTS
+// -------------------------------------------------------------------------
+
+#[derive(Accounts)]
+pub struct SetStakingProgram<'info> {
+    #[account(
+        mut,
+        has_one = creator @ VestingError::Unauthorized,
+        constraint = !vesting_contract.is_cancelled @ VestingError::ContractCancelled
+    )]
+    pub vesting_contract: Account<'info, VestingContract>,
+
+    pub creator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct StakeLocked<'info> {
+    #[account(
+        mut,
+        has_one = beneficiary @ VestingError::Unauthorized,
+        constraint = !vesting_contract.is_cancelled @ VestingError::ContractCancelled
+    )]
+    pub vesting_contract: Account<'info, VestingContract>,
+
+    #[account(mut)]
+    pub beneficiary: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", vesting_contract.key().as_ref()],
+        bump
+    )]
+    pub escrow_wallet: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        constraint = mint.key() == vesting_contract.mint @ VestingError::InvalidAmount
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// CHECK: Deve ser o programa autorizado pelo criador
+    #[account(
+        executable,
+        constraint = staking_program.key() == vesting_contract.staking_program @ VestingError::StakingProgramNotAllowed
+    )]
+    pub staking_program: UncheckedAccount<'info>,
+
+    /// CHECK: Validado pelo programa de staking
+    #[account(mut)]
+    pub stake_record: UncheckedAccount<'info>,
+
+    /// CHECK: Validado pelo programa de staking
+    #[account(mut)]
+    pub stake_vault: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// `authority` e o criador ou o beneficiario do contrato.
+#[derive(Accounts)]
+pub struct UnstakeLocked<'info> {
+    #[account(mut)]
+    pub vesting_contract: Account<'info, VestingContract>,
+
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", vesting_contract.key().as_ref()],
+        bump
+    )]
+    pub escrow_wallet: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        constraint = mint.key() == vesting_contract.mint @ VestingError::InvalidAmount
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// CHECK: Deve ser o programa autorizado pelo criador
+    #[account(
+        executable,
+        constraint = staking_program.key() == vesting_contract.staking_program @ VestingError::StakingProgramNotAllowed
+    )]
+    pub staking_program: UncheckedAccount<'info>,
+
+    /// CHECK: Validado pelo programa de staking
+    #[account(mut)]
+    pub stake_record: UncheckedAccount<'info>,
+
+    /// CHECK: Validado pelo programa de staking
+    #[account(mut)]
+    pub stake_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Validado pelo programa de staking
+    pub vault_authority: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimStakingRewards<'info> {
+    #[account(has_one = beneficiary @ VestingError::Unauthorized)]
+    pub vesting_contract: Account<'info, VestingContract>,
+
+    pub beneficiary: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = beneficiary_token_account.mint == vesting_contract.mint @ VestingError::InvalidAmount,
+        constraint = beneficiary_token_account.owner == beneficiary.key() @ VestingError::Unauthorized
+    )]
+    pub beneficiary_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        constraint = mint.key() == vesting_contract.mint @ VestingError::InvalidAmount
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// CHECK: Deve ser o programa autorizado pelo criador
+    #[account(
+        executable,
+        constraint = staking_program.key() == vesting_contract.staking_program @ VestingError::StakingProgramNotAllowed
+    )]
+    pub staking_program: UncheckedAccount<'info>,
+
+    /// CHECK: Validado pelo programa de staking
+    #[account(mut)]
+    pub stake_record: UncheckedAccount<'info>,
+
+    /// CHECK: Validado pelo programa de staking
+    #[account(mut)]
+    pub stake_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Validado pelo programa de staking
+    pub vault_authority: UncheckedAccount<'info>,
+}
@@ -0,0 +1,551 @@
+//! Streams de pagamento com taxa fixa por segundo.
+//!
+//! Diferente do `VestingContract`, um stream nao tem total nem data final: o pagador
+//! deposita saldo quando quiser e o destinatario saca o que ja acumulou. Quando o
+//! acumulado alcanca o depositado o stream fica pausado ate o proximo aporte, sem
+//! acumular o intervalo em que ficou sem saldo. `cancel_stream` encerra o stream
+//! acertando o acumulado com o destinatario e devolvendo o restante ao pagador.
+
+use super::*;
+
+pub(crate) fn create<'info>(
+    ctx: Context<'_, '_, '_, 'info, CreateStream<'info>>,
+    stream_id: u64,
+    rate_per_second: u64,
+    start_time: i64,
+    initial_deposit: u64,
+    allowed_mint_flags: u16,
+) -> Result<()> {
+    require!(rate_per_second > 0, VestingError::InvalidAmount);
+    require!(initial_deposit > 0, VestingError::InvalidAmount);
+
+    let mint_info = ctx.accounts.mint.to_account_info();
+    let detected_mint_flags = inspect_mint_flags(&mint_info)?;
+    require!(
+        detected_mint_flags & mint_flags::DANGEROUS & !allowed_mint_flags == 0,
+        VestingError::DangerousMintExtension
+    );
+
+    let stream = &mut ctx.accounts.stream;
+    stream.payer = ctx.accounts.payer.key();
+    stream.recipient = ctx.accounts.recipient.key();
+    stream.mint = ctx.accounts.mint.key();
+    stream.stream_id = stream_id;
+    stream.rate_per_second = rate_per_second;
+    stream.start_time = start_time;
+    stream.checkpoint_time = start_time;
+    stream.accrued_at_checkpoint = 0;
+    stream.withdrawn_amount = 0;
+    stream.bump = ctx.bumps.stream;
+    stream.is_token_2022 = mint_info.owner == &anchor_spl::token_2022::ID;
+    stream.mint_flags = detected_mint_flags;
+
+    let decimals = ctx.accounts.mint.decimals;
+
+    let cpi_accounts = token_interface::TransferChecked {
+        from: ctx.accounts.payer_token_account.to_account_info(),
+        mint: ctx.accounts.mint.to_account_info(),
+        to: ctx.accounts.escrow_wallet.to_account_info(),
+        authority: ctx.accounts.payer.to_account_info(),
+    };
+
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts)
+        .with_remaining_accounts(ctx.remaining_accounts.to_vec());
+
+    transfer_checked_with_hook(cpi_ctx, initial_deposit, decimals)?;
+
+    // Considera apenas o valor efetivamente recebido (Transfer Fee)
+    ctx.accounts.escrow_wallet.reload()?;
+    stream.deposited_amount = ctx.accounts.escrow_wallet.amount;
+
+    emit!(StreamCreated {
+        stream_id,
+        payer: stream.payer,
+        recipient: stream.recipient,
+        mint: stream.mint,
+        rate_per_second,
+        start_time,
+        deposited_amount: stream.deposited_amount,
+        mint_flags: detected_mint_flags,
+    });
+
+    Ok(())
+}
+
+/// Novo aporte do pagador. Se o stream estava pausado por falta de saldo, ele
+/// volta a correr a partir de agora.
+pub(crate) fn top_up<'info>(
+    ctx: Context<'_, '_, '_, 'info, TopUpStream<'info>>,
+    amount: u64,
+) -> Result<()> {
+    require!(amount > 0, VestingError::InvalidAmount);
+    let current_time = Clock::get()?.unix_timestamp;
+
+    let balance_before = ctx.accounts.escrow_wallet.amount;
+    let decimals = ctx.accounts.mint.decimals;
+
+    let cpi_accounts = token_interface::TransferChecked {
+        from: ctx.accounts.payer_token_account.to_account_info(),
+        mint: ctx.accounts.mint.to_account_info(),
+        to: ctx.accounts.escrow_wallet.to_account_info(),
+        authority: ctx.accounts.payer.to_account_info(),
+    };
+
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts)
+        .with_remaining_accounts(ctx.remaining_accounts.to_vec());
+
+    transfer_checked_with_hook(cpi_ctx, amount, decimals)?;
+
+    ctx.accounts.escrow_wallet.reload()?;
+    let received = ctx
+        .accounts
+        .escrow_wallet
+        .amount
+        .checked_sub(balance_before)
+        .ok_or(VestingError::MathOverflow)?;
+
+    let stream = &mut ctx.accounts.stream;
+    let was_paused = stream.is_paused(current_time);
+    stream.checkpoint(current_time);
+    stream.deposited_amount = stream
+        .deposited_amount
+        .checked_add(received)
+        .ok_or(VestingError::MathOverflow)?;
+
+    emit!(StreamToppedUp {
+        stream_id: stream.stream_id,
+        payer: stream.payer,
+        amount: received,
+        deposited_amount: stream.deposited_amount,
+        resumed: was_paused,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
+
+pub(crate) fn withdraw<'info>(
+    ctx: Context<'_, '_, '_, 'info, WithdrawFromStream<'info>>,
+) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp;
+
+    let (withdrawable, signer_seeds_data) = {
+        let stream = &mut ctx.accounts.stream;
+
+        let withdrawable = stream
+            .accrued_amount(current_time)
+            .checked_sub(stream.withdrawn_amount)
+            .ok_or(VestingError::MathOverflow)?;
+
+        require!(withdrawable > 0, VestingError::NothingToRelease);
+
+        stream.withdrawn_amount = stream
+            .withdrawn_amount
+            .checked_add(withdrawable)
+            .ok_or(VestingError::MathOverflow)?;
+
+        (
+            withdrawable,
+            (stream.payer, stream.mint, stream.stream_id, stream.bump),
+        )
+    };
+
+    let (payer_key, mint_key, stream_id, bump) = signer_seeds_data;
+    let id_bytes = stream_id.to_le_bytes();
+
+    let seeds = &[
+        b"stream",
+        payer_key.as_ref(),
+        mint_key.as_ref(),
+        id_bytes.as_ref(),
+        &[bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    EscrowPayout {
+        stream: &ctx.accounts.stream,
+        escrow_wallet: &ctx.accounts.escrow_wallet,
+        mint: &ctx.accounts.mint,
+        token_program: &ctx.accounts.token_program,
+        remaining_accounts: ctx.remaining_accounts,
+        signer,
+    }
+    .pay(&ctx.accounts.recipient_token_account, withdrawable)?;
+
+    emit!(StreamWithdrawn {
+        stream_id,
+        recipient: ctx.accounts.recipient.key(),
+        amount: withdrawable,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
+
+/// Encerra o stream a pedido do pagador: o acumulado ainda nao sacado vai ao
+/// destinatario, o restante do escrow volta ao pagador e as contas sao fechadas com
+/// o aluguel devolvido a ele. Com TransferFee o escrow retem taxas e nao pode ser
+/// fechado, entao fica aberto (vazio).
+pub(crate) fn cancel<'info>(ctx: Context<'_, '_, '_, 'info, CancelStream<'info>>) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp;
+    let stream = &ctx.accounts.stream;
+
+    let escrow_balance = ctx.accounts.escrow_wallet.amount;
+    let owed = stream
+        .accrued_amount(current_time)
+        .checked_sub(stream.withdrawn_amount)
+        .ok_or(VestingError::MathOverflow)?
+        .min(escrow_balance);
+    let refunded = escrow_balance - owed;
+
+    let id_bytes = stream.stream_id.to_le_bytes();
+    let seeds = &[
+        b"stream",
+        stream.payer.as_ref(),
+        stream.mint.as_ref(),
+        id_bytes.as_ref(),
+        &[stream.bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    let payout = EscrowPayout {
+        stream,
+        escrow_wallet: &ctx.accounts.escrow_wallet,
+        mint: &ctx.accounts.mint,
+        token_program: &ctx.accounts.token_program,
+        remaining_accounts: ctx.remaining_accounts,
+        signer,
+    };
+    if owed > 0 {
+        payout.pay(&ctx.accounts.recipient_token_account, owed)?;
+    }
+    if refunded > 0 {
+        payout.pay(&ctx.accounts.payer_token_account, refunded)?;
+    }
+
+    if stream.mint_flags & mint_flags::TRANSFER_FEE == 0 {
+        let cpi_accounts = token_interface::CloseAccount {
+            account: ctx.accounts.escrow_wallet.to_account_info(),
+            destination: ctx.accounts.payer.to_account_info(),
+            authority: stream.to_account_info(),
+        };
+        token_interface::close_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer,
+        ))?;
+    }
+
+    emit!(StreamCancelled {
+        stream_id: stream.stream_id,
+        payer: stream.payer,
+        recipient: stream.recipient,
+        paid_to_recipient: owed,
+        refunded_to_payer: refunded,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
+
+/// Contas para pagar a partir do escrow de um stream, assinando com o PDA do stream.
+struct EscrowPayout<'a, 'info> {
+    stream: &'a Account<'info, PaymentStream>,
+    escrow_wallet: &'a InterfaceAccount<'info, TokenAccount>,
+    mint: &'a InterfaceAccount<'info, Mint>,
+    token_program: &'a Interface<'info, TokenInterface>,
+    remaining_accounts: &'a [AccountInfo<'info>],
+    signer: &'a [&'a [&'a [u8]]],
+}
+
+impl<'a, 'info> EscrowPayout<'a, 'info> {
+    fn pay(&self, destination: &InterfaceAccount<'info, TokenAccount>, amount: u64) -> Result<()> {
+        let cpi_accounts = token_interface::TransferChecked {
+            from: self.escrow_wallet.to_account_info(),
+            mint: self.mint.to_account_info(),
+            to: destination.to_account_info(),
+            authority: self.stream.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            cpi_accounts,
+            self.signer,
+        )
+        .with_remaining_accounts(self.remaining_accounts.to_vec());
+
+        transfer_checked_with_hook(cpi_ctx, amount, self.mint.decimals)
+    }
+}
+
+// -------------------------------------------------------------------------
+// DATA STRUCTURES
+// -------------------------------------------------------------------------
+
+#[account]
+pub struct PaymentStream {
+    pub payer: Pubkey,
+    pub recipient: Pubkey,
+    pub mint: Pubkey,
+    pub stream_id: u64,
+    pub rate_per_second: u64,
+    pub start_time: i64,
+    /// Total ja depositado no escrow desde a criacao.
+    pub deposited_amount: u64,
+    pub withdrawn_amount: u64,
+    /// Ultimo ponto em que o acumulado foi consolidado (criacao ou aporte).
+    pub checkpoint_time: i64,
+    pub accrued_at_checkpoint: u64,
+    pub bump: u8,
+    pub is_token_2022: bool,
+    pub mint_flags: u16,
+}
+
+impl PaymentStream {
+    pub const LEN: usize =
+        8 + // discriminator
+        32 + // payer
+        32 + // recipient
+        32 + // mint
+        8 + // stream_id
+        8 + // rate_per_second
+        8 + // start_time
+        8 + // deposited_amount
+        8 + // withdrawn_amount
+        8 + // checkpoint_time
+        8 + // accrued_at_checkpoint
+        1 + // bump
+        1 + // is_token_2022
+        2;  // mint_flags
+
+    /// Total acumulado para o destinatario ate `current_time`, limitado ao depositado.
+    pub fn accrued_amount(&self, current_time: i64) -> u64 {
+        let elapsed = current_time.saturating_sub(self.checkpoint_time).max(0) as u128;
+        let streamed = (self.rate_per_second as u128).saturating_mul(elapsed);
+        let accrued = (self.accrued_at_checkpoint as u128).saturating_add(streamed);
+        accrued.min(self.deposited_amount as u128) as u64
+    }
+
+    /// O stream esta pausado quando todo o saldo depositado ja foi acumulado.
+    pub fn is_paused(&self, current_time: i64) -> bool {
+        self.accrued_amount(current_time) >= self.deposited_amount
+    }
+
+    fn checkpoint(&mut self, current_time: i64) {
+        if current_time > self.checkpoint_time {
+            self.accrued_at_checkpoint = self.accrued_amount(current_time);
+            self.checkpoint_time = current_time;
+        }
+    }
+}
+
+// -------------------------------------------------------------------------
+// EVENTS
+// -------------------------------------------------------------------------
+
+#[event]
+pub struct StreamCreated {
+    pub stream_id: u64,
+    pub payer: Pubkey,
+    pub recipient: Pubkey,
+    pub mint: Pubkey,
+    pub rate_per_second: u64,
+    pub start_time: i64,
+    pub deposited_amount: u64,
+    pub mint_flags: u16,
+}
+
+#[event]
+pub struct StreamToppedUp {
+    pub stream_id: u64,
+    pub payer: Pubkey,
+    pub amount: u64,
+    pub deposited_amount: u64,
+    pub resumed: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StreamWithdrawn {
+    pub stream_id: u64,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StreamCancelled {
+    pub stream_id: u64,
+    pub payer: Pubkey,
+    pub recipient: Pubkey,
+    /// Acumulado ainda nao sacado, pago ao destinatario no encerramento.
+    pub paid_to_recipient: u64,
+    /// Saldo nao transmitido devolvido ao pagador.
+    pub refunded_to_payer: u64,
+    pub timestamp: i64,
+}
+
+// -------------------------------------------------------------------------
+// CONTEXTS
+// -------------------------------------------------------------------------
+
+#[derive(Accounts)]
+#[instruction(stream_id: u64)]
+pub struct CreateStream<'info> {
+    #[account(
+        init,
+        payer = payer,
+        seeds = [
+            b"stream",
+            payer.key().as_ref(),
+            mint.key().as_ref(),
+            &stream_id.to_le_bytes()
+        ],
+        bump,
+        space = PaymentStream::LEN
+    )]
+    pub stream: Account<'info, PaymentStream>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Apenas endereco para armazenamento
+    pub recipient: UncheckedAccount<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        payer = payer,
+        token::mint = mint,
+        token::authority = stream,
+        token::token_program = token_program,
+        seeds = [b"escrow", stream.key().as_ref()],
+        bump
+    )]
+    pub escrow_wallet: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = payer_token_account.mint == mint.key() @ VestingError::InvalidAmount,
+        constraint = payer_token_account.owner == payer.key() @ VestingError::Unauthorized
+    )]
+    pub payer_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct TopUpStream<'info> {
+    #[account(
+        mut,
+        has_one = payer @ VestingError::Unauthorized
+    )]
+    pub stream: Account<'info, PaymentStream>,
+
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", stream.key().as_ref()],
+        bump,
+        constraint = escrow_wallet.mint == stream.mint @ VestingError::InvalidAmount
+    )]
+    pub escrow_wallet: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = payer_token_account.mint == stream.mint @ VestingError::InvalidAmount,
+        constraint = payer_token_account.owner == payer.key() @ VestingError::Unauthorized
+    )]
+    pub payer_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        constraint = mint.key() == stream.mint @ VestingError::InvalidAmount
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawFromStream<'info> {
+    #[account(
+        mut,
+        has_one = recipient @ VestingError::Unauthorized
+    )]
+    pub stream: Account<'info, PaymentStream>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", stream.key().as_ref()],
+        bump,
+        constraint = escrow_wallet.owner == stream.key() @ VestingError::Unauthorized,
+        constraint = escrow_wallet.mint == stream.mint @ VestingError::InvalidAmount
+    )]
+    pub escrow_wallet: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = recipient_token_account.mint == stream.mint @ VestingError::InvalidAmount,
+        constraint = recipient_token_account.owner == recipient.key() @ VestingError::Unauthorized
+    )]
+    pub recipient_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Validado via has_one no stream
+    pub recipient: UncheckedAccount<'info>,
+
+    #[account(
+        constraint = mint.key() == stream.mint @ VestingError::InvalidAmount
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct CancelStream<'info> {
+    #[account(
+        mut,
+        has_one = payer @ VestingError::Unauthorized,
+        has_one = recipient @ VestingError::Unauthorized,
+        close = payer
+    )]
+    pub stream: Account<'info, PaymentStream>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", stream.key().as_ref()],
+        bump,
+        constraint = escrow_wallet.owner == stream.key() @ VestingError::Unauthorized,
+        constraint = escrow_wallet.mint == stream.mint @ VestingError::InvalidAmount
+    )]
+    pub escrow_wallet: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = payer_token_account.mint == stream.mint @ VestingError::InvalidAmount,
+        constraint = payer_token_account.owner == payer.key() @ VestingError::Unauthorized
+    )]
+    pub payer_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = recipient_token_account.mint == stream.mint @ VestingError::InvalidAmount,
+        constraint = recipient_token_account.owner == recipient.key() @ VestingError::Unauthorized
+    )]
+    pub recipient_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Validado via has_one no stream
+    pub recipient: UncheckedAccount<'info>,
+
+    #[account(
+        constraint = mint.key() == stream.mint @ VestingError::InvalidAmount
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
@@ -0,0 +1,393 @@
+//! Acoes administrativas com timelock.
+//!
+//! Com `timelock_delay` maior que zero, troca de beneficiario, cancelamento e
+//! alteracao do cronograma deixam de ser imediatas: o criador registra a acao em
+//! `propose_action` (`[b"pending_action", vesting_contract]`, uma por contrato), que
+//! so pode ser aplicada por `execute_action` apos o atraso. Ate la o beneficiario ou
+//! o `guardian` podem veta-la com `veto_action`, e o criador pode retira-la com
+//! `withdraw_action` para liberar o PDA. Sem timelock nao ha propostas: a alteracao do
+//! cronograma so existe por aqui e nao pode ser aplicada sem atraso.
+
+use super::*;
+
+/// Maior atraso aceito em `set_timelock` (um ano).
+pub const MAX_TIMELOCK_DELAY: i64 = 365 * 24 * 60 * 60;
+
+pub(crate) fn set_timelock(ctx: Context<SetTimelock>, delay: i64, guardian: Pubkey) -> Result<()> {
+    require!(
+        (0..=MAX_TIMELOCK_DELAY).contains(&delay),
+        VestingError::InvalidTimelockDelay
+    );
+
+    let vesting_contract = &mut ctx.accounts.vesting_contract;
+    vesting_contract.timelock_delay = delay;
+    vesting_contract.guardian = guardian;
+
+    emit!(TimelockUpdated {
+        contract_id: vesting_contract.contract_id,
+        delay,
+        guardian,
+    });
+
+    Ok(())
+}
+
+pub(crate) fn propose(ctx: Context<ProposeAction>, action: TimelockedAction) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp;
+    let vesting_contract = &ctx.accounts.vesting_contract;
+
+    // Com atraso zero a acao poderia ser executada no mesmo slot, sem chance de veto
+    require!(
+        vesting_contract.timelock_delay > 0,
+        VestingError::TimelockDisabled
+    );
+
+    // Valida ja na proposta; `execute_action` repete as verificacoes
+    match action {
+        TimelockedAction::UpdateBeneficiary { new_beneficiary } => {
+            require!(
+                new_beneficiary != Pubkey::default(),
+                VestingError::InvalidTimelockAction
+            );
+        }
+        TimelockedAction::Cancel => {}
+        TimelockedAction::AmendSchedule { end_time } => {
+            validate_amendment(vesting_contract, end_time)?;
+        }
+    }
+
+    let executable_at = current_time
+        .checked_add(vesting_contract.timelock_delay)
+        .ok_or(VestingError::MathOverflow)?;
+
+    let pending_action = &mut ctx.accounts.pending_action;
+    pending_action.vesting_contract = vesting_contract.key();
+    pending_action.action = action;
+    pending_action.proposed_at = current_time;
+    pending_action.executable_at = executable_at;
+    pending_action.bump = ctx.bumps.pending_action;
+
+    emit!(ActionProposed {
+        contract_id: vesting_contract.contract_id,
+        action,
+        executable_at,
+    });
+
+    Ok(())
+}
+
+pub(crate) fn execute<'info>(ctx: Context<'_, '_, '_, 'info, ExecuteAction<'info>>) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp;
+    let action = ctx.accounts.pending_action.action;
+
+    require!(
+        current_time >= ctx.accounts.pending_action.executable_at,
+        VestingError::TimelockNotElapsed
+    );
+
+    let (audit_action, amount) = match action {
+        TimelockedAction::UpdateBeneficiary { new_beneficiary } => {
+            let event = replace_beneficiary(&mut ctx.accounts.vesting_contract, new_beneficiary)?;
+            emit_cpi!(event);
+            (audit_action::UPDATE_BENEFICIARY, 0)
+        }
+        TimelockedAction::Cancel => {
+            let event = cancel_contract(
+                &mut ctx.accounts.vesting_contract,
+                ctx.accounts.creator.to_account_info(),
+                ctx.accounts.escrow_wallet.as_ref(),
+                ctx.accounts.creator_token_account.as_ref(),
+                ctx.accounts.mint.as_ref(),
+                ctx.accounts.token_program.as_ref(),
+                ctx.remaining_accounts,
+            )?;
+            let refunded = event.remaining_amount;
+            emit_cpi!(event);
+            (audit_action::CANCEL, refunded)
+        }
+        TimelockedAction::AmendSchedule { end_time } => {
+            let vesting_contract = &mut ctx.accounts.vesting_contract;
+            validate_amendment(vesting_contract, end_time)?;
+            vesting_contract.end_time = end_time;
+            (audit_action::AMEND_SCHEDULE, 0)
+        }
+    };
+
+    audit_log::record(
+        &ctx.accounts.vesting_contract,
+        ctx.accounts.audit_log.as_mut(),
+        ctx.accounts.creator.key(),
+        audit_action,
+        amount,
+    )?;
+
+    emit!(ActionExecuted {
+        contract_id: ctx.accounts.vesting_contract.contract_id,
+        action,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
+
+pub(crate) fn veto(ctx: Context<VetoAction>) -> Result<()> {
+    let authority = ctx.accounts.authority.key();
+    let vesting_contract = &ctx.accounts.vesting_contract;
+
+    require!(
+        authority == vesting_contract.beneficiary
+            || (vesting_contract.guardian != Pubkey::default()
+                && authority == vesting_contract.guardian),
+        VestingError::Unauthorized
+    );
+
+    emit!(ActionVetoed {
+        contract_id: vesting_contract.contract_id,
+        action: ctx.accounts.pending_action.action,
+        authority,
+    });
+
+    Ok(())
+}
+
+pub(crate) fn withdraw(ctx: Context<WithdrawAction>) -> Result<()> {
+    emit!(ActionWithdrawn {
+        contract_id: ctx.accounts.vesting_contract.contract_id,
+        action: ctx.accounts.pending_action.action,
+    });
+
+    Ok(())
+}
+
+/// A alteracao do cronograma so move `end_time`, e apenas em contratos liberados
+/// pelo tempo.
+fn validate_amendment(vesting_contract: &VestingContract, end_time: i64) -> Result<()> {
+    require!(
+        !vesting_contract.vesting_type.is_event_driven(),
+        VestingError::UnsupportedVestingType
+    );
+    require!(
+        end_time > vesting_contract.start_time,
+        VestingError::InvalidTimeRange
+    );
+    if let VestingType::Cliff(cliff_time, _) = vesting_contract.vesting_type {
+        require!(end_time >= cliff_time, VestingError::InvalidTimeRange);
+    }
+    Ok(())
+}
+
+// -------------------------------------------------------------------------
+// DATA STRUCTURES
+// -------------------------------------------------------------------------
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum TimelockedAction {
+    UpdateBeneficiary {
+        new_beneficiary: Pubkey,
+    },
+    Cancel,
+    /// Novo `end_time` do cronograma.
+    AmendSchedule {
+        end_time: i64,
+    },
+}
+
+impl TimelockedAction {
+    pub const LEN: usize = 1 + 32; // enum tag + maior variante
+}
+
+#[account]
+pub struct PendingAction {
+    pub vesting_contract: Pubkey,
+    pub action: TimelockedAction,
+    pub proposed_at: i64,
+    /// Primeiro instante em que `execute_action` aceita a acao.
+    pub executable_at: i64,
+    pub bump: u8,
+}
+
+impl PendingAction {
+    pub const LEN: usize =
+        8 + // discriminator
+        32 + // vesting_contract
+        TimelockedAction::LEN + // action
+        8 + // proposed_at
+        8 + // executable_at
+        1; // bump
+}
+
+// -------------------------------------------------------------------------
+// EVENTS
+// -------------------------------------------------------------------------
+
+#[event]
+pub struct TimelockUpdated {
+    pub contract_id: u64,
+    pub delay: i64,
+    pub guardian: Pubkey,
+}
+
+#[event]
+pub struct ActionProposed {
+    pub contract_id: u64,
+    pub action: TimelockedAction,
+    pub executable_at: i64,
+}
+
+#[event]
+pub struct ActionExecuted {
+    pub contract_id: u64,
+    pub action: TimelockedAction,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ActionVetoed {
+    pub contract_id: u64,
+    pub action: TimelockedAction,
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct ActionWithdrawn {
+    pub contract_id: u64,
+    pub action: TimelockedAction,
+}
+
+// -------------------------------------------------------------------------
+// CONTEXTS
+// -------------------------------------------------------------------------
+
+/// Exige a assinatura do criador e do beneficiario: o timelock protege os dois lados.
+#[derive(Accounts)]
+pub struct SetTimelock<'info> {
+    #[account(
+        mut,
+        has_one = creator @ VestingError::Unauthorized,
+        has_one = beneficiary @ VestingError::Unauthorized,
+        constraint = !vesting_contract.is_cancelled @ VestingError::ContractCancelled
+    )]
+    pub vesting_contract: Account<'info, VestingContract>,
+
+    pub creator: Signer<'info>,
+    pub beneficiary: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeAction<'info> {
+    #[account(
+        init,
+        payer = creator,
+        seeds = [b"pending_action", vesting_contract.key().as_ref()],
+        bump,
+        space = PendingAction::LEN
+    )]
+    pub pending_action: Account<'info, PendingAction>,
+
+    #[account(
+        has_one = creator @ VestingError::Unauthorized,
+        constraint = !vesting_contract.is_cancelled @ VestingError::ContractCancelled
+    )]
+    pub vesting_contract: Account<'info, VestingContract>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// As contas de token so sao usadas por `TimelockedAction::Cancel` e sao opcionais
+/// para permitir contratos nativos (`is_native`).
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExecuteAction<'info> {
+    #[account(
+        mut,
+        close = creator,
+        seeds = [b"pending_action", vesting_contract.key().as_ref()],
+        bump = pending_action.bump,
+        has_one = vesting_contract @ VestingError::InvalidTimelockAction
+    )]
+    pub pending_action: Account<'info, PendingAction>,
+
+    #[account(
+        mut,
+        has_one = creator @ VestingError::Unauthorized,
+        constraint = !vesting_contract.is_cancelled @ VestingError::ContractCancelled
+    )]
+    pub vesting_contract: Account<'info, VestingContract>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", vesting_contract.key().as_ref()],
+        bump,
+        constraint = escrow_wallet.owner == vesting_contract.key() @ VestingError::Unauthorized,
+        constraint = escrow_wallet.mint == vesting_contract.mint @ VestingError::InvalidAmount
+    )]
+    pub escrow_wallet: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = creator_token_account.mint == vesting_contract.mint @ VestingError::InvalidAmount,
+        constraint = creator_token_account.owner == creator.key() @ VestingError::Unauthorized
+    )]
+    pub creator_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        constraint = mint.key() == vesting_contract.mint @ VestingError::InvalidAmount
+    )]
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
+    /// Presente se o contrato tiver `AuditLog` (obrigatorio quando `has_audit_log`).
+    #[account(
+        mut,
+        seeds = [b"audit_log", vesting_contract.key().as_ref()],
+        bump = audit_log.bump
+    )]
+    pub audit_log: Option<Account<'info, AuditLog>>,
+}
+
+/// O aluguel da acao vetada volta ao criador, que a pagou.
+#[derive(Accounts)]
+pub struct VetoAction<'info> {
+    #[account(
+        mut,
+        close = creator,
+        seeds = [b"pending_action", vesting_contract.key().as_ref()],
+        bump = pending_action.bump,
+        has_one = vesting_contract @ VestingError::InvalidTimelockAction
+    )]
+    pub pending_action: Account<'info, PendingAction>,
+
+    #[account(has_one = creator @ VestingError::Unauthorized)]
+    pub vesting_contract: Account<'info, VestingContract>,
+
+    /// CHECK: Validado via has_one no vesting_contract; recebe o aluguel
+    #[account(mut)]
+    pub creator: UncheckedAccount<'info>,
+
+    pub authority: Signer<'info>,
+}
+
+/// O criador desiste da acao pendente e recupera o aluguel.
+#[derive(Accounts)]
+pub struct WithdrawAction<'info> {
+    #[account(
+        mut,
+        close = creator,
+        seeds = [b"pending_action", vesting_contract.key().as_ref()],
+        bump = pending_action.bump,
+        has_one = vesting_contract @ VestingError::InvalidTimelockAction
+    )]
+    pub pending_action: Account<'info, PendingAction>,
+
+    #[account(has_one = creator @ VestingError::Unauthorized)]
+    pub vesting_contract: Account<'info, VestingContract>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+}
@@ -0,0 +1,329 @@
+//! Plugin de peso de voto (voter-weight add-in) para o SPL Governance.
+//!
+//! O `Registrar` de um realm define o mint de governanca e o bonus opcional por tempo
+//! restante de bloqueio. `update_voter_weight_record` soma o saldo ainda nao liberado
+//! dos `VestingContract`s do beneficiario (passados em `remaining_accounts`) e grava o
+//! resultado em um `VoterWeightRecord` no layout do `spl-governance-addin-api`, valido
+//! apenas no slot atual, como o governance exige. Um contrato cujo beneficiario foi
+//! trocado no slot atual e recusado: o registro do beneficiario anterior ainda vale
+//! ate o fim do slot e o mesmo saldo nao pode ser contado duas vezes.
+
+use super::*;
+
+const REALM_V1: u8 = 1;
+const REALM_V2: u8 = 16;
+// account_type + community_mint + RealmConfig ate `council_mint`
+const REALM_COUNCIL_MINT_OFFSET: usize = 1 + 32 + 1 + 1 + 6 + 8 + 9;
+// reserved + legacy1 entre `RealmConfig` e `authority`
+const REALM_AUTHORITY_GAP: usize = 6 + 2;
+pub const MAX_LOCKUP_BONUS_BPS: u16 = 10_000;
+
+pub(crate) fn create_registrar(
+    ctx: Context<CreateRegistrar>,
+    lockup_bonus_bps: u16,
+    lockup_saturation_secs: i64,
+) -> Result<()> {
+    require!(
+        lockup_bonus_bps <= MAX_LOCKUP_BONUS_BPS && lockup_saturation_secs >= 0,
+        VestingError::InvalidRegistrarConfig
+    );
+    require!(
+        lockup_bonus_bps == 0 || lockup_saturation_secs > 0,
+        VestingError::InvalidRegistrarConfig
+    );
+
+    let realm_info = ctx.accounts.realm.to_account_info();
+    require!(
+        realm_info.owner == ctx.accounts.governance_program_id.key,
+        VestingError::InvalidRealm
+    );
+    let realm = RealmHeader::load(&realm_info)?;
+    require!(
+        realm.authority == Some(ctx.accounts.realm_authority.key()),
+        VestingError::InvalidRealm
+    );
+    let governing_token_mint = ctx.accounts.governing_token_mint.key();
+    require!(
+        realm.community_mint == governing_token_mint
+            || realm.council_mint == Some(governing_token_mint),
+        VestingError::InvalidRealm
+    );
+
+    let registrar = &mut ctx.accounts.registrar;
+    registrar.governance_program_id = ctx.accounts.governance_program_id.key();
+    registrar.realm = realm_info.key();
+    registrar.governing_token_mint = governing_token_mint;
+    registrar.lockup_bonus_bps = lockup_bonus_bps;
+    registrar.lockup_saturation_secs = lockup_saturation_secs;
+    registrar.bump = ctx.bumps.registrar;
+
+    Ok(())
+}
+
+pub(crate) fn create_record(ctx: Context<CreateVoterWeightRecord>) -> Result<()> {
+    let registrar = &ctx.accounts.registrar;
+    let record = &mut ctx.accounts.voter_weight_record;
+    record.realm = registrar.realm;
+    record.governing_token_mint = registrar.governing_token_mint;
+    record.governing_token_owner = ctx.accounts.governing_token_owner.key();
+    record.voter_weight = 0;
+    record.voter_weight_expiry = Some(0);
+    record.weight_action = None;
+    record.weight_action_target = None;
+    record.reserved = [0; 8];
+
+    Ok(())
+}
+
+pub(crate) fn update_record<'info>(
+    ctx: Context<'_, '_, 'info, 'info, UpdateVoterWeightRecord<'info>>,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let registrar = &ctx.accounts.registrar;
+    let owner = ctx.accounts.voter_weight_record.governing_token_owner;
+
+    let mut seen: Vec<Pubkey> = Vec::with_capacity(ctx.remaining_accounts.len());
+    let mut voter_weight: u64 = 0;
+    for contract_info in ctx.remaining_accounts.iter() {
+        require!(
+            !seen.contains(contract_info.key),
+            VestingError::InvalidVoterWeightContract
+        );
+        seen.push(*contract_info.key);
+
+        let vesting_contract = Account::<VestingContract>::try_from(contract_info)?;
+        require!(
+            vesting_contract.beneficiary == owner
+                && vesting_contract.mint == registrar.governing_token_mint,
+            VestingError::InvalidVoterWeightContract
+        );
+        require!(
+            vesting_contract.beneficiary_updated_slot == 0
+                || vesting_contract.beneficiary_updated_slot != clock.slot,
+            VestingError::BeneficiaryChangedThisSlot
+        );
+
+        voter_weight = voter_weight
+            .checked_add(registrar.contract_weight(&vesting_contract, clock.unix_timestamp))
+            .ok_or(VestingError::MathOverflow)?;
+    }
+
+    let record = &mut ctx.accounts.voter_weight_record;
+    record.voter_weight = voter_weight;
+    record.voter_weight_expiry = Some(clock.slot);
+    record.weight_action = None;
+    record.weight_action_target = None;
+
+    Ok(())
+}
+
+/// Campos do `Realm` (V1 ou V2) do SPL Governance usados pelo registrar.
+struct RealmHeader {
+    community_mint: Pubkey,
+    council_mint: Option<Pubkey>,
+    authority: Option<Pubkey>,
+}
+
+impl RealmHeader {
+    fn load(realm_info: &AccountInfo) -> Result<Self> {
+        let data = realm_info.try_borrow_data()?;
+        require!(
+            matches!(data.first(), Some(&REALM_V1) | Some(&REALM_V2)),
+            VestingError::InvalidRealm
+        );
+
+        let community_mint = read_pubkey(&data, 1)?;
+        let (council_mint, offset) = read_option_pubkey(&data, REALM_COUNCIL_MINT_OFFSET)?;
+        let (authority, _) = read_option_pubkey(&data, offset + REALM_AUTHORITY_GAP)?;
+
+        Ok(Self {
+            community_mint,
+            council_mint,
+            authority,
+        })
+    }
+}
+
+fn read_pubkey(data: &[u8], offset: usize) -> Result<Pubkey> {
+    let bytes: [u8; 32] = data
+        .get(offset..offset + 32)
+        .and_then(|slice| slice.try_into().ok())
+        .ok_or(VestingError::InvalidRealm)?;
+    Ok(Pubkey::new_from_array(bytes))
+}
+
+/// Le um `Option<Pubkey>` Borsh e devolve o valor e o offset seguinte.
+fn read_option_pubkey(data: &[u8], offset: usize) -> Result<(Option<Pubkey>, usize)> {
+    match data.get(offset) {
+        Some(0) => Ok((None, offset + 1)),
+        Some(1) => Ok((Some(read_pubkey(data, offset + 1)?), offset + 33)),
+        _ => err!(VestingError::InvalidRealm),
+    }
+}
+
+// -------------------------------------------------------------------------
+// DATA STRUCTURES
+// -------------------------------------------------------------------------
+
+#[account]
+pub struct Registrar {
+    pub governance_program_id: Pubkey,
+    pub realm: Pubkey,
+    pub governing_token_mint: Pubkey,
+    /// Bonus maximo sobre o saldo bloqueado, em basis points (0 desativa).
+    pub lockup_bonus_bps: u16,
+    /// Tempo restante de bloqueio a partir do qual o bonus e integral.
+    pub lockup_saturation_secs: i64,
+    pub bump: u8,
+}
+
+impl Registrar {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // governance_program_id
+        32 + // realm
+        32 + // governing_token_mint
+        2 + // lockup_bonus_bps
+        8 + // lockup_saturation_secs
+        1; // bump
+
+    /// Peso de um contrato: saldo ainda nao liberado mais o bonus proporcional ao
+    /// tempo restante ate o fim do cronograma (sem bonus para liberacoes por evento).
+    pub fn contract_weight(&self, vesting_contract: &VestingContract, current_time: i64) -> u64 {
+        if vesting_contract.is_cancelled {
+            return 0;
+        }
+        let locked = vesting_contract
+            .total_amount
+            .saturating_sub(vesting_contract.released_amount);
+        if self.lockup_bonus_bps == 0 || vesting_contract.vesting_type.is_event_driven() {
+            return locked;
+        }
+
+        let remaining = vesting_contract
+            .end_time
+            .saturating_sub(vesting_contract.effective_time(current_time))
+            .clamp(0, self.lockup_saturation_secs);
+        let bonus = (locked as u128)
+            .saturating_mul(self.lockup_bonus_bps as u128)
+            .saturating_mul(remaining as u128)
+            .saturating_div(MAX_LOCKUP_BONUS_BPS as u128 * self.lockup_saturation_secs as u128);
+
+        locked.saturating_add(bonus.min(u64::MAX as u128) as u64)
+    }
+}
+
+/// Acao de governanca a que o peso se aplica (`spl-governance-addin-api`).
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum VoterWeightAction {
+    CastVote,
+    CommentProposal,
+    CreateGovernance,
+    CreateProposal,
+    SignOffProposal,
+}
+
+/// Mesmo layout e discriminador do `VoterWeightRecord` do `spl-governance-addin-api`
+/// (o discriminador Anchor de `account:VoterWeightRecord` e o definido pela API).
+#[account]
+pub struct VoterWeightRecord {
+    pub realm: Pubkey,
+    pub governing_token_mint: Pubkey,
+    pub governing_token_owner: Pubkey,
+    pub voter_weight: u64,
+    pub voter_weight_expiry: Option<u64>,
+    pub weight_action: Option<VoterWeightAction>,
+    pub weight_action_target: Option<Pubkey>,
+    pub reserved: [u8; 8],
+}
+
+impl VoterWeightRecord {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // realm
+        32 + // governing_token_mint
+        32 + // governing_token_owner
+        8 + // voter_weight
+        1 + 8 + // voter_weight_expiry
+        1 + 1 + // weight_action
+        1 + 32 + // weight_action_target
+        8; // reserved
+}
+
+// -------------------------------------------------------------------------
+// CONTEXTS
+// -------------------------------------------------------------------------
+
+#[derive(Accounts)]
+pub struct CreateRegistrar<'info> {
+    #[account(
+        init,
+        payer = payer,
+        seeds = [
+            b"registrar",
+            realm.key().as_ref(),
+            governing_token_mint.key().as_ref()
+        ],
+        bump,
+        space = Registrar::LEN
+    )]
+    pub registrar: Account<'info, Registrar>,
+
+    /// CHECK: Dono e layout validados contra `governance_program_id`
+    pub realm: UncheckedAccount<'info>,
+
+    /// CHECK: Programa do SPL Governance dono do realm
+    pub governance_program_id: UncheckedAccount<'info>,
+
+    pub governing_token_mint: InterfaceAccount<'info, Mint>,
+
+    pub realm_authority: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreateVoterWeightRecord<'info> {
+    pub registrar: Account<'info, Registrar>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [
+            b"voter-weight-record",
+            registrar.key().as_ref(),
+            governing_token_owner.key().as_ref()
+        ],
+        bump,
+        space = VoterWeightRecord::LEN
+    )]
+    pub voter_weight_record: Account<'info, VoterWeightRecord>,
+
+    /// CHECK: Apenas endereco do votante (beneficiario)
+    pub governing_token_owner: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Os `VestingContract`s do votante vao em `remaining_accounts`.
+#[derive(Accounts)]
+pub struct UpdateVoterWeightRecord<'info> {
+    pub registrar: Account<'info, Registrar>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"voter-weight-record",
+            registrar.key().as_ref(),
+            voter_weight_record.governing_token_owner.as_ref()
+        ],
+        bump,
+        constraint = voter_weight_record.realm == registrar.realm @ VestingError::InvalidRealm,
+        constraint = voter_weight_record.governing_token_mint == registrar.governing_token_mint @ VestingError::InvalidRealm
+    )]
+    pub voter_weight_record: Account<'info, VoterWeightRecord>,
+}
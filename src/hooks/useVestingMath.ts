@@ -0,0 +1,25 @@
+import { useEffect, useState } from 'react';
+import { isVestingMathLoaded, loadVestingMath } from '@/utils/date-utils';
+
+/**
+ * `true` once the vesting math WebAssembly module is loaded; screens that show
+ * vested amounts wait for it instead of rendering zeros.
+ */
+export function useVestingMath(): boolean {
+    const [ready, setReady] = useState(isVestingMathLoaded);
+
+    useEffect(() => {
+        if (ready) return;
+        let cancelled = false;
+        loadVestingMath()
+            .then(() => {
+                if (!cancelled) setReady(true);
+            })
+            .catch((e) => console.error("Erro ao carregar o cálculo de vesting (wasm):", e));
+        return () => {
+            cancelled = true;
+        };
+    }, [ready]);
+
+    return ready;
+}
@@ -0,0 +1,76 @@
+import { Connection, PublicKey } from '@solana/web3.js';
+
+// Espelha `audit_action` do programa
+export const AUDIT_ACTIONS: Record<number, string> = {
+    0: 'Criação',
+    1: 'Resgate',
+    2: 'Troca de beneficiário',
+    3: 'Cancelamento',
+    4: 'Clawback',
+    5: 'Alteração de cronograma',
+};
+
+export interface AuditEntry {
+    sequence: string;
+    actor: string;
+    actionCode: number;
+    action: string;
+    amount: string;
+    timestamp: number;
+}
+
+export interface AuditLogAccount {
+    address: string;
+    totalEntries: string;
+    entries: AuditEntry[];
+}
+
+export const findAuditLogAddress = (vestingContract: PublicKey, programId: PublicKey): PublicKey =>
+    PublicKey.findProgramAddressSync(
+        [Buffer.from('audit_log'), vestingContract.toBuffer()],
+        programId
+    )[0];
+
+/**
+ * Lê o `AuditLog` (buffer circular) de um contrato de vesting. Retorna `null` se o
+ * contrato não tiver log; as entradas voltam em ordem cronológica.
+ */
+export const fetchAuditLog = async (
+    connection: Connection,
+    vestingContract: PublicKey,
+    programId: PublicKey
+): Promise<AuditLogAccount | null> => {
+    const address = findAuditLogAddress(vestingContract, programId);
+    const info = await connection.getAccountInfo(address);
+    if (!info || !info.owner.equals(programId)) return null;
+
+    const data = info.data;
+    // discriminador (8) + vesting_contract (32) + bump (1)
+    let offset = 8 + 32 + 1;
+    const totalEntries = data.readBigUInt64LE(offset);
+    offset += 8;
+    const length = data.readUInt32LE(offset);
+    offset += 4;
+
+    const entries: AuditEntry[] = [];
+    for (let i = 0; i < length; i++) {
+        const sequence = data.readBigUInt64LE(offset).toString();
+        const actor = new PublicKey(data.subarray(offset + 8, offset + 40)).toBase58();
+        const actionCode = data.readUInt8(offset + 40);
+        const amount = data.readBigUInt64LE(offset + 41).toString();
+        const timestamp = Number(data.readBigInt64LE(offset + 49));
+        offset += 57;
+
+        entries.push({
+            sequence,
+            actor,
+            actionCode,
+            action: AUDIT_ACTIONS[actionCode] || `Ação ${actionCode}`,
+            amount,
+            timestamp,
+        });
+    }
+    entries.sort((a, b) => (BigInt(a.sequence) < BigInt(b.sequence) ? -1 : 1));
+
+    return { address: address.toBase58(), totalEntries: totalEntries.toString(), entries };
+};
@@ -0,0 +1,67 @@
+import { Connection, PublicKey } from '@solana/web3.js';
+import { extractTransactionEvents } from './vesting-events';
+
+export const CLAWBACK_REASONS: Record<number, string> = {
+    1: 'Sanções',
+    2: 'Ordem judicial',
+    3: 'Fraude',
+    4: 'Solicitação regulatória',
+    5: 'Desligamento por justa causa',
+    255: 'Outro',
+};
+
+export interface ClawbackEvent {
+    signature: string;
+    contractId: string;
+    vestingContract: string;
+    complianceAuthority: string;
+    destination: string;
+    amount: string;
+    reasonCode: number;
+    reason: string;
+    memo: string;
+    timestamp: number;
+}
+
+/**
+ * Busca os eventos `Clawback` emitidos para um contrato de vesting, decodificando as
+ * instruções internas de `emit_cpi!` das transações que tocaram o endereço.
+ */
+export const fetchClawbackEvents = async (
+    connection: Connection,
+    vestingContract: PublicKey,
+    programId: PublicKey,
+    limit: number = 50
+): Promise<ClawbackEvent[]> => {
+    const signatures = await connection.getSignaturesForAddress(vestingContract, { limit });
+    const events: ClawbackEvent[] = [];
+
+    for (const { signature, err } of signatures) {
+        if (err) continue;
+
+        const tx = await connection.getTransaction(signature, {
+            commitment: 'confirmed',
+            maxSupportedTransactionVersion: 0,
+        });
+        if (!tx) continue;
+
+        for (const event of extractTransactionEvents(tx, programId)) {
+            if (event.name !== 'Clawback' || event.vestingContract !== vestingContract.toBase58()) continue;
+
+            events.push({
+                signature,
+                contractId: event.contractId,
+                vestingContract: event.vestingContract,
+                complianceAuthority: event.complianceAuthority,
+                destination: event.destination,
+                amount: event.amount,
+                reasonCode: event.reasonCode,
+                reason: CLAWBACK_REASONS[event.reasonCode] || `Código ${event.reasonCode}`,
+                memo: event.memo,
+                timestamp: event.timestamp,
+            });
+        }
+    }
+
+    return events;
+};
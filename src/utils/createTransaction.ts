@@ -2,6 +2,8 @@
 import { Program, BN } from '@project-serum/anchor';
 import { Connection, PublicKey, SystemProgram, Transaction } from '@solana/web3.js';
 import { detectTokenProgram } from './tokenProgram';
+import { DEFAULT_ALLOWED_MINT_FLAGS } from './mint-flags';
+import { eventCpiAccounts } from './vesting-idl';
 import {
     getAssociatedTokenAddress,
     createAssociatedTokenAccountIdempotentInstruction
@@ -26,6 +28,8 @@ export const createVestingTransaction = async (
         mintAddress: string;
         creator: PublicKey;
         beneficiary: PublicKey;
+        // Mascara de mint_flags de risco aceitas pelo criador (padrao: FREEZE_AUTHORITY)
+        allowedMintFlags?: number;
     }
 ) => {
     const {
@@ -36,7 +40,8 @@ export const createVestingTransaction = async (
         vestingType,
         mintAddress,
         creator,
-        beneficiary
+        beneficiary,
+        allowedMintFlags = DEFAULT_ALLOWED_MINT_FLAGS
     } = params;
 
     try {
@@ -95,7 +100,8 @@ export const createVestingTransaction = async (
                 totalAmount instanceof BN ? totalAmount : new BN(totalAmount),
                 startTime instanceof BN ? startTime : new BN(startTime),
                 endTime instanceof BN ? endTime : new BN(endTime),
-                vestingType
+                vestingType,
+                allowedMintFlags
             )
             .accounts({
                 vestingContract,
@@ -106,10 +112,14 @@ export const createVestingTransaction = async (
                 senderTokenAccount,
                 systemProgram: SystemProgram.programId,
                 tokenProgram: tokenProgramId, // detectado dinamicamente — Interface<TokenInterface>
+                auditLog: program.programId, // None: contrato criado sem AuditLog
+                ...eventCpiAccounts(program.programId),
             })
             .instruction();
 
         transaction.add(ix);
+        transaction.feePayer = creator;
+        transaction.recentBlockhash = (await connection.getLatestBlockhash('confirmed')).blockhash;
 
         // 🧪 SIMULAÇÃO — revela erros reais antes de enviar
         const simulation = await connection.simulateTransaction(transaction);
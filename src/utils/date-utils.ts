@@ -1,6 +1,10 @@
 /**
  * Utility for safe date parsing and formatting across the application.
+ *
+ * Vesting amounts come from the WebAssembly build of `verum_vesting_math`, the same
+ * crate the on-chain program uses (`npm run build:wasm`).
  */
+import initVestingMath, { vestedAmount } from '@/wasm/vesting-math/verum_vesting_math_wasm';
 
 /**
  * Parses a date string in the format "DD/MM/YYYY, HH:MM" or variations.
@@ -72,85 +76,110 @@ export const formatVestingDate = (date: Date): string => {
     return `${day}/${month}/${year}, ${hour}:${minute}`;
 };
 
+let vestingMathReady: Promise<void> | null = null;
+let vestingMathLoaded = false;
+
 /**
- * Calculates current progress (0 to 1) for a vesting contract, perfectly mirroring the on-chain logic 
- * generated in \`revisar/page.tsx\`. Supports both Linear and Cliff schedules.
- * @param contract The local contract data object
- * @param nowMillis Timestamp to calculate against (defaults to Date.now())
+ * Loads the vesting math module once. Browser only: call it from an effect (see
+ * `useVestingMath`) before using `calculateVestingProgress` or `calculateUnlockedAmount`.
  */
-export const calculateVestingProgress = (contract: any, nowMillis: number = Date.now()): number => {
-    const startDate = parseVestingDate(contract.vestingStartDate);
-    if (!startDate) return 0;
-
-    const start = startDate.getTime();
-
-    // Cliff logic: hybrid unlock (percentage at cliff date + linear remainder until end date)
-    if (contract.selectedSchedule && contract.selectedSchedule.toLowerCase().includes('cliff')) {
-        if (nowMillis < start) return 0;
-
-        const cliffPercentage = parseInt(contract.cliffAmount || "0") / 100; // e.g., 0.1 for 10%
-
-        const duration = parseInt(contract.vestingDuration || "1");
-        const unit = (contract.selectedTimeUnit || "").toLowerCase();
-        const endDateObj = new Date(start);
-        if (unit.includes('dia')) endDateObj.setDate(endDateObj.getDate() + duration);
-        else if (unit.includes('mês') || unit.includes('mes')) endDateObj.setMonth(endDateObj.getMonth() + duration);
-        else if (unit.includes('ano')) endDateObj.setFullYear(endDateObj.getFullYear() + duration);
-        else if (unit.includes('hora')) endDateObj.setHours(endDateObj.getHours() + duration);
-        else if (unit.includes('semana')) endDateObj.setDate(endDateObj.getDate() + (duration * 7));
-        else if (unit.includes('minuto')) endDateObj.setMinutes(endDateObj.getMinutes() + duration);
-
-        const end = endDateObj.getTime();
-        if (nowMillis >= end) return 1;
-        if (end <= start) return 1;
+export const loadVestingMath = (): Promise<void> => {
+    if (!vestingMathReady) {
+        vestingMathReady = initVestingMath().then(() => {
+            vestingMathLoaded = true;
+        });
+    }
+    return vestingMathReady;
+};
 
-        // Progress from 0 (at cliff date) to 1 (at end date)
-        const linearProgress = (nowMillis - start) / (end - start);
+export const isVestingMathLoaded = (): boolean => vestingMathLoaded;
+
+/** Schedule in the shape the program stores it: unix seconds and the `VestingType` name. */
+export interface VestingSchedule {
+    start: bigint;
+    end: bigint;
+    kind: 'linear' | 'cliff';
+    cliffTime: bigint;
+    cliffPercentage: bigint;
+}
+
+/** Adds `duration` in the unit chosen on the creation form (dias, meses, ...). */
+const addDuration = (start: Date, duration: number, unitLabel: string): Date => {
+    const unit = unitLabel.toLowerCase();
+    const end = new Date(start);
+    if (unit.includes('dia')) end.setDate(end.getDate() + duration);
+    else if (unit.includes('mês') || unit.includes('mes')) end.setMonth(end.getMonth() + duration);
+    else if (unit.includes('ano')) end.setFullYear(end.getFullYear() + duration);
+    else if (unit.includes('hora')) end.setHours(end.getHours() + duration);
+    else if (unit.includes('semana')) end.setDate(end.getDate() + (duration * 7));
+    else if (unit.includes('minuto')) end.setMinutes(end.getMinutes() + duration);
+    return end;
+};
 
-        // Final progress = cliffPercentage + (remaining percentage * linear progress)
-        return cliffPercentage + ((1 - cliffPercentage) * linearProgress);
-    }
+/**
+ * Rebuilds the schedule sent to `create_vesting` (see `revisar/page.tsx`): on-chain
+ * contracts carry the duration in seconds, drafts carry it in the form's time unit.
+ * The app creates cliffs at the start date with `cliffAmount` percent.
+ */
+export const vestingSchedule = (contract: any): VestingSchedule | null => {
+    const startDate = parseVestingDate(contract.vestingStartDate);
+    if (!startDate) return null;
+
+    const inSeconds = contract.onChain || (typeof contract.vestingDuration === 'number' && !contract.selectedTimeUnit);
+    const endDate = inSeconds
+        ? new Date(startDate.getTime() + (Number(contract.vestingDuration) || 0) * 1000)
+        : addDuration(startDate, parseInt(contract.vestingDuration || "1"), contract.selectedTimeUnit || "");
+
+    const start = BigInt(Math.floor(startDate.getTime() / 1000));
+    const isCliff = (contract.selectedSchedule || "").toLowerCase().includes('cliff');
+    return {
+        start,
+        end: BigInt(Math.floor(endDate.getTime() / 1000)),
+        kind: isCliff ? 'cliff' : 'linear',
+        cliffTime: start,
+        cliffPercentage: BigInt(isCliff ? Math.max(0, parseInt(contract.cliffAmount || "0") || 0) : 0),
+    };
+};
 
-    // Case for blockchain contracts where duration is already in seconds
-    if (contract.onChain || (typeof contract.vestingDuration === 'number' && !contract.selectedTimeUnit)) {
-        const end = start + (contract.vestingDuration * 1000);
-        if (nowMillis >= end) return 1;
-        if (end <= start) return 1;
-
-        if (contract.selectedSchedule && contract.selectedSchedule.toLowerCase().includes('cliff')) {
-            if (nowMillis < start) return 0;
-            const cliffPercentage = parseInt(contract.cliffAmount || "0") / 100;
-            const linearProgress = (nowMillis - start) / (end - start);
-            return cliffPercentage + ((1 - cliffPercentage) * linearProgress);
-        }
+const vestedBaseUnits = (schedule: VestingSchedule, total: bigint, nowMillis: number): bigint =>
+    vestedAmount(
+        BigInt(Math.floor(nowMillis / 1000)),
+        schedule.start,
+        schedule.end,
+        total,
+        schedule.kind,
+        schedule.cliffTime,
+        schedule.cliffPercentage
+    );
 
-        const rawP = (nowMillis - start) / (end - start);
-        return Math.min(1, Math.max(0, rawP));
-    }
+// Resolution of the fraction returned by calculateVestingProgress
+const PROGRESS_SCALE = BigInt(1_000_000);
 
-    // Default logic (UI Drafts / Local Storage)
-    const duration = parseInt(contract.vestingDuration || "1");
-    const unit = (contract.selectedTimeUnit || "").toLowerCase();
-
-    // Replicate exactly what `revisar/page.tsx` does for setting endDate
-    const endDateObj = new Date(start);
-    if (unit.includes('dia')) endDateObj.setDate(endDateObj.getDate() + duration);
-    else if (unit.includes('mês') || unit.includes('mes')) endDateObj.setMonth(endDateObj.getMonth() + duration);
-    else if (unit.includes('ano')) endDateObj.setFullYear(endDateObj.getFullYear() + duration);
-    else if (unit.includes('hora')) endDateObj.setHours(endDateObj.getHours() + duration);
-    else if (unit.includes('semana')) endDateObj.setDate(endDateObj.getDate() + (duration * 7));
-    else if (unit.includes('minuto')) endDateObj.setMinutes(endDateObj.getMinutes() + duration);
-
-    const end = endDateObj.getTime();
-    if (end <= start) return 1;
-
-    if (contract.selectedSchedule && contract.selectedSchedule.toLowerCase().includes('cliff')) {
-        if (nowMillis < start) return 0;
-        const cliffPercentage = parseInt(contract.cliffAmount || "0") / 100;
-        const linearProgress = (nowMillis - start) / (end - start);
-        return cliffPercentage + ((1 - cliffPercentage) * linearProgress);
-    }
+/**
+ * Calculates current progress (0 to 1) for a vesting contract with the program's own
+ * schedule math. Supports both Linear and Cliff schedules.
+ * @param contract The local contract data object
+ * @param nowMillis Timestamp to calculate against (defaults to Date.now())
+ */
+export const calculateVestingProgress = (contract: any, nowMillis: number = Date.now()): number => {
+    const schedule = vestingSchedule(contract);
+    if (!schedule) return 0;
+    return Number(vestedBaseUnits(schedule, PROGRESS_SCALE, nowMillis)) / Number(PROGRESS_SCALE);
+};
 
-    const rawP = (nowMillis - start) / (end - start);
-    return Math.min(1, Math.max(0, rawP));
+/**
+ * Unlocked part of `total` (in tokens), rounded in base units exactly like the program.
+ * @param decimals Token decimals; defaults to the token picked on the form or 6
+ */
+export const calculateUnlockedAmount = (
+    contract: any,
+    total: number,
+    nowMillis: number = Date.now(),
+    decimals: number = contract.selectedToken?.decimals ?? 6
+): number => {
+    const schedule = vestingSchedule(contract);
+    if (!schedule || !(total > 0)) return 0;
+    const scale = Math.pow(10, decimals);
+    const vested = vestedBaseUnits(schedule, BigInt(Math.round(total * scale)), nowMillis);
+    return Number(vested) / scale;
 };
@@ -0,0 +1,103 @@
+import { Connection, PublicKey } from '@solana/web3.js';
+import {
+    AccountState,
+    ExtensionType,
+    getDefaultAccountState,
+    getExtensionTypes,
+    getMint,
+    getMintCloseAuthority,
+    getPermanentDelegate,
+    getTransferHook,
+} from '@solana/spl-token';
+
+// Espelho de `mint_flags` do programa (src/contracts/lib.rs)
+export const MINT_FLAGS = {
+    PERMANENT_DELEGATE: 1 << 0,
+    FREEZE_AUTHORITY: 1 << 1,
+    NON_TRANSFERABLE: 1 << 2,
+    DEFAULT_FROZEN: 1 << 3,
+    TRANSFER_FEE: 1 << 4,
+    TRANSFER_HOOK: 1 << 5,
+    MINT_CLOSE_AUTHORITY: 1 << 6,
+    CONFIDENTIAL_TRANSFER: 1 << 7,
+} as const;
+
+// Caracteristicas que o programa so aceita se estiverem em `allowed_mint_flags`
+export const DANGEROUS_MINT_FLAGS =
+    MINT_FLAGS.PERMANENT_DELEGATE |
+    MINT_FLAGS.FREEZE_AUTHORITY |
+    MINT_FLAGS.NON_TRANSFERABLE |
+    MINT_FLAGS.DEFAULT_FROZEN;
+
+// Politica padrao do app: stablecoins (USDC, USDT) tem freeze authority
+export const DEFAULT_ALLOWED_MINT_FLAGS = MINT_FLAGS.FREEZE_AUTHORITY;
+
+export const MINT_FLAG_LABELS: Record<number, string> = {
+    [MINT_FLAGS.PERMANENT_DELEGATE]: 'Delegado permanente (pode mover ou queimar o saldo do escrow)',
+    [MINT_FLAGS.FREEZE_AUTHORITY]: 'Freeze authority (o escrow pode ser congelado)',
+    [MINT_FLAGS.NON_TRANSFERABLE]: 'Intransferível (os tokens não saem do escrow)',
+    [MINT_FLAGS.DEFAULT_FROZEN]: 'Contas novas nascem congeladas',
+    [MINT_FLAGS.TRANSFER_FEE]: 'Taxa de transferência em cada liberação',
+    [MINT_FLAGS.TRANSFER_HOOK]: 'Transfer hook (programa externo autoriza cada transferência)',
+    [MINT_FLAGS.MINT_CLOSE_AUTHORITY]: 'Autoridade de fechamento do mint',
+    [MINT_FLAGS.CONFIDENTIAL_TRANSFER]: 'Transferências confidenciais habilitadas',
+};
+
+/** Lista os bits definidos em `flags`, na ordem de `MINT_FLAGS`. */
+export const listMintFlags = (flags: number): number[] =>
+    Object.values(MINT_FLAGS).filter((flag) => (flags & flag) !== 0);
+
+/**
+ * Calcula a mascara de `mint_flags` como `inspect_mint_flags` faz na criacao, para
+ * que o criador veja e aceite as caracteristicas de risco antes de enviar.
+ */
+export const detectMintFlags = async (
+    connection: Connection,
+    mint: PublicKey,
+    tokenProgramId: PublicKey
+): Promise<number> => {
+    const mintInfo = await getMint(connection, mint, 'confirmed', tokenProgramId);
+
+    let flags = 0;
+    if (mintInfo.freezeAuthority) flags |= MINT_FLAGS.FREEZE_AUTHORITY;
+
+    for (const extension of getExtensionTypes(mintInfo.tlvData)) {
+        switch (extension) {
+            case ExtensionType.PermanentDelegate: {
+                const delegate = getPermanentDelegate(mintInfo);
+                if (delegate && !delegate.delegate.equals(PublicKey.default)) {
+                    flags |= MINT_FLAGS.PERMANENT_DELEGATE;
+                }
+                break;
+            }
+            case ExtensionType.NonTransferable:
+                flags |= MINT_FLAGS.NON_TRANSFERABLE;
+                break;
+            case ExtensionType.DefaultAccountState:
+                if (getDefaultAccountState(mintInfo)?.state === AccountState.Frozen) {
+                    flags |= MINT_FLAGS.DEFAULT_FROZEN;
+                }
+                break;
+            case ExtensionType.TransferFeeConfig:
+                flags |= MINT_FLAGS.TRANSFER_FEE;
+                break;
+            case ExtensionType.TransferHook: {
+                const hook = getTransferHook(mintInfo);
+                if (hook && !hook.programId.equals(PublicKey.default)) flags |= MINT_FLAGS.TRANSFER_HOOK;
+                break;
+            }
+            case ExtensionType.MintCloseAuthority: {
+                const closeAuthority = getMintCloseAuthority(mintInfo);
+                if (closeAuthority && !closeAuthority.closeAuthority.equals(PublicKey.default)) {
+                    flags |= MINT_FLAGS.MINT_CLOSE_AUTHORITY;
+                }
+                break;
+            }
+            case ExtensionType.ConfidentialTransferMint:
+                flags |= MINT_FLAGS.CONFIDENTIAL_TRANSFER;
+                break;
+        }
+    }
+
+    return flags;
+};
@@ -0,0 +1,71 @@
+import { Connection, PublicKey } from '@solana/web3.js';
+
+// sha256("account:ReleaseReceipt")[0..8] - discriminador Anchor da conta
+const RELEASE_RECEIPT_DISCRIMINATOR = [76, 104, 183, 121, 145, 97, 118, 212];
+
+export interface ReleaseReceipt {
+    address: string;
+    vestingContract: string;
+    claimIndex: string;
+    destination: string;
+    amount: string;
+    slot: string;
+    timestamp: number;
+}
+
+export const findReleaseReceiptAddress = (
+    vestingContract: PublicKey,
+    claimIndex: bigint,
+    programId: PublicKey
+): PublicKey => {
+    const index = Buffer.alloc(8);
+    index.writeBigUInt64LE(claimIndex);
+    return PublicKey.findProgramAddressSync(
+        [Buffer.from('release_receipt'), vestingContract.toBuffer(), index],
+        programId
+    )[0];
+};
+
+/**
+ * Lê um `ReleaseReceipt` pelo endereço. Retorna `null` se a conta não existir ou
+ * não for um recibo; a existência do recibo comprova o pagamento registrado nele.
+ */
+export const fetchReleaseReceipt = async (
+    connection: Connection,
+    address: PublicKey
+): Promise<ReleaseReceipt | null> => {
+    const info = await connection.getAccountInfo(address);
+    if (!info || info.data.length < 8) return null;
+
+    const data = info.data;
+    if (!RELEASE_RECEIPT_DISCRIMINATOR.every((byte, i) => data[i] === byte)) return null;
+
+    let offset = 8;
+    const readPubkey = () => {
+        const key = new PublicKey(data.subarray(offset, offset + 32)).toBase58();
+        offset += 32;
+        return key;
+    };
+    const readU64 = () => {
+        const value = data.readBigUInt64LE(offset).toString();
+        offset += 8;
+        return value;
+    };
+
+    const vestingContract = readPubkey();
+    const claimIndex = readU64();
+    const destination = readPubkey();
+    const amount = readU64();
+    const slot = readU64();
+    const timestamp = Number(data.readBigInt64LE(offset));
+
+    return {
+        address: address.toBase58(),
+        vestingContract,
+        claimIndex,
+        destination,
+        amount,
+        slot,
+        timestamp,
+    };
+};
@@ -0,0 +1,276 @@
+import { PublicKey, VersionedTransactionResponse } from '@solana/web3.js';
+import { utils } from '@coral-xyz/anchor';
+
+// sha256("anchor:event")[0..8] em little-endian - prefixo das instruções de `emit_cpi!`
+const EVENT_IX_TAG_LE = [228, 69, 165, 46, 81, 203, 154, 29];
+
+// sha256("event:<Nome>")[0..8] - discriminadores Anchor dos eventos
+const DISCRIMINATORS = {
+    VestingCreated: [181, 223, 229, 220, 204, 6, 169, 125],
+    TokensClaimed: [25, 128, 244, 55, 241, 136, 200, 91],
+    BeneficiaryUpdated: [154, 201, 61, 115, 198, 239, 210, 212],
+    VestingCancelled: [82, 94, 141, 247, 214, 188, 13, 187],
+    Clawback: [239, 144, 30, 69, 80, 59, 142, 64],
+};
+
+const VESTING_TYPES = ['Linear', 'Cliff', 'Milestones', 'PriceTriggered'];
+
+export type VestingEvent =
+    | {
+        name: 'VestingCreated';
+        contractId: string;
+        creator: string;
+        beneficiary: string;
+        mint: string;
+        totalAmount: string;
+        startTime: number;
+        endTime: number;
+        mintFlags: number;
+        vestingContract: string;
+        vestingType: string;
+        slot: string;
+    }
+    | {
+        name: 'TokensClaimed';
+        contractId: string;
+        beneficiary: string;
+        amount: string;
+        timestamp: number;
+        vestingContract: string;
+        releasedAmount: string;
+        remainingAmount: string;
+        startTime: number;
+        endTime: number;
+        slot: string;
+    }
+    | {
+        name: 'BeneficiaryUpdated';
+        contractId: string;
+        oldBeneficiary: string;
+        newBeneficiary: string;
+        vestingContract: string;
+        timestamp: number;
+        slot: string;
+    }
+    | {
+        name: 'VestingCancelled';
+        contractId: string;
+        creator: string;
+        remainingAmount: string;
+        timestamp: number;
+        vestingContract: string;
+        releasedAmount: string;
+        slot: string;
+    }
+    | {
+        name: 'Clawback';
+        contractId: string;
+        vestingContract: string;
+        complianceAuthority: string;
+        destination: string;
+        amount: string;
+        reasonCode: number;
+        memo: string;
+        timestamp: number;
+    };
+
+/** PDA `[b"__event_authority"]` que assina as instruções internas de `emit_cpi!`. */
+export const getEventAuthority = (programId: PublicKey): PublicKey =>
+    PublicKey.findProgramAddressSync([Buffer.from('__event_authority')], programId)[0];
+
+// Leitor Borsh sequencial
+class Reader {
+    private offset = 0;
+    constructor(private data: Buffer) {}
+
+    pubkey() {
+        const key = new PublicKey(this.data.subarray(this.offset, this.offset + 32)).toBase58();
+        this.offset += 32;
+        return key;
+    }
+    u64() {
+        const value = this.data.readBigUInt64LE(this.offset).toString();
+        this.offset += 8;
+        return value;
+    }
+    i64() {
+        const value = Number(this.data.readBigInt64LE(this.offset));
+        this.offset += 8;
+        return value;
+    }
+    u16() {
+        const value = this.data.readUInt16LE(this.offset);
+        this.offset += 2;
+        return value;
+    }
+    u8() {
+        const value = this.data.readUInt8(this.offset);
+        this.offset += 1;
+        return value;
+    }
+    string() {
+        const length = this.data.readUInt32LE(this.offset);
+        this.offset += 4;
+        const value = this.data.subarray(this.offset, this.offset + length).toString('utf8');
+        this.offset += length;
+        return value;
+    }
+    vestingType() {
+        const tag = this.data.readUInt8(this.offset);
+        this.offset += 1;
+        // Cliff(i64, u64)
+        if (tag === 1) this.offset += 16;
+        return VESTING_TYPES[tag] || `Tipo ${tag}`;
+    }
+}
+
+const matches = (data: Buffer, offset: number, bytes: number[]) =>
+    data.length >= offset + bytes.length && bytes.every((byte, i) => data[offset + i] === byte);
+
+/** Decodifica os dados de uma instrução interna de `emit_cpi!`; `null` se não for um evento conhecido. */
+export const decodeVestingEvent = (data: Buffer): VestingEvent | null => {
+    if (!matches(data, 0, EVENT_IX_TAG_LE)) return null;
+
+    const name = (Object.keys(DISCRIMINATORS) as (keyof typeof DISCRIMINATORS)[])
+        .find((key) => matches(data, 8, DISCRIMINATORS[key]));
+    if (!name) return null;
+
+    const r = new Reader(data.subarray(16));
+    switch (name) {
+        case 'VestingCreated':
+            return {
+                name,
+                contractId: r.u64(),
+                creator: r.pubkey(),
+                beneficiary: r.pubkey(),
+                mint: r.pubkey(),
+                totalAmount: r.u64(),
+                startTime: r.i64(),
+                endTime: r.i64(),
+                mintFlags: r.u16(),
+                vestingContract: r.pubkey(),
+                vestingType: r.vestingType(),
+                slot: r.u64(),
+            };
+        case 'TokensClaimed':
+            return {
+                name,
+                contractId: r.u64(),
+                beneficiary: r.pubkey(),
+                amount: r.u64(),
+                timestamp: r.i64(),
+                vestingContract: r.pubkey(),
+                releasedAmount: r.u64(),
+                remainingAmount: r.u64(),
+                startTime: r.i64(),
+                endTime: r.i64(),
+                slot: r.u64(),
+            };
+        case 'BeneficiaryUpdated':
+            return {
+                name,
+                contractId: r.u64(),
+                oldBeneficiary: r.pubkey(),
+                newBeneficiary: r.pubkey(),
+                vestingContract: r.pubkey(),
+                timestamp: r.i64(),
+                slot: r.u64(),
+            };
+        case 'VestingCancelled':
+            return {
+                name,
+                contractId: r.u64(),
+                creator: r.pubkey(),
+                remainingAmount: r.u64(),
+                timestamp: r.i64(),
+                vestingContract: r.pubkey(),
+                releasedAmount: r.u64(),
+                slot: r.u64(),
+            };
+        case 'Clawback':
+            return {
+                name,
+                contractId: r.u64(),
+                vestingContract: r.pubkey(),
+                complianceAuthority: r.pubkey(),
+                destination: r.pubkey(),
+                amount: r.u64(),
+                reasonCode: r.u8(),
+                memo: r.string(),
+                timestamp: r.i64(),
+            };
+    }
+};
+
+/**
+ * Extrai os eventos de uma transação lida por `getTransaction`. Só considera
+ * instruções internas do programa assinadas pela event authority; transações com
+ * erro não emitiram eventos.
+ */
+export const extractTransactionEvents = (
+    tx: VersionedTransactionResponse,
+    programId: PublicKey
+): VestingEvent[] => {
+    if (!tx.meta || tx.meta.err) return [];
+
+    const accountKeys = tx.transaction.message.getAccountKeys({
+        accountKeysFromLookups: tx.meta.loadedAddresses,
+    });
+    const eventAuthority = getEventAuthority(programId);
+    const events: VestingEvent[] = [];
+
+    for (const inner of tx.meta.innerInstructions || []) {
+        for (const ix of inner.instructions) {
+            const ixProgram = accountKeys.get(ix.programIdIndex);
+            const firstAccount = ix.accounts.length > 0 ? accountKeys.get(ix.accounts[0]) : undefined;
+            if (!ixProgram?.equals(programId) || !firstAccount?.equals(eventAuthority)) continue;
+
+            try {
+                const event = decodeVestingEvent(Buffer.from(utils.bytes.bs58.decode(ix.data)));
+                if (event) events.push(event);
+            } catch (e) {
+                console.warn('[VestingEvents] Instrução interna inválida', e);
+            }
+        }
+    }
+    return events;
+};
+
+interface HeliusInstruction {
+    programId: string;
+    accounts?: string[];
+    data: string; // base58
+    innerInstructions?: HeliusInstruction[];
+}
+
+/**
+ * Extrai os eventos do programa de uma transação no formato "enhanced" da Helius,
+ * lendo as instruções internas de `emit_cpi!` em vez dos logs (que podem ser truncados).
+ * Instruções de topo são ignoradas: qualquer um pode enviar ao programa dados com o
+ * prefixo de evento, mas só o próprio programa assina com a event authority.
+ */
+export const extractVestingEvents = (
+    tx: { instructions?: HeliusInstruction[]; transactionError?: unknown },
+    programId: string
+): VestingEvent[] => {
+    // Transação revertida: os eventos não valem
+    if (tx.transactionError) return [];
+
+    const eventAuthority = getEventAuthority(new PublicKey(programId)).toBase58();
+    const events: VestingEvent[] = [];
+    const visit = (instructions: HeliusInstruction[] = []) => {
+        for (const ix of instructions) {
+            if (ix.programId === programId && ix.accounts?.[0] === eventAuthority && ix.data) {
+                try {
+                    const event = decodeVestingEvent(Buffer.from(utils.bytes.bs58.decode(ix.data)));
+                    if (event) events.push(event);
+                } catch (e) {
+                    console.warn('[VestingEvents] Instrução interna inválida', e);
+                }
+            }
+            visit(ix.innerInstructions);
+        }
+    };
+    for (const ix of tx.instructions || []) visit(ix.innerInstructions);
+    return events;
+};
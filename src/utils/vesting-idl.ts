@@ -0,0 +1,123 @@
+import { Connection, PublicKey } from '@solana/web3.js';
+import { Idl } from '@project-serum/anchor';
+import { findAuditLogAddress } from './audit-log';
+import { getEventAuthority } from './vesting-events';
+
+// Contas que `#[event_cpi]` acrescenta ao fim das instruções que emitem eventos
+const EVENT_CPI_ACCOUNTS = [
+    { "name": "eventAuthority", "isMut": false, "isSigner": false },
+    { "name": "program", "isMut": false, "isSigner": false }
+];
+
+/**
+ * IDL parcial usado pelas telas do app. A ordem das contas segue os `#[derive(Accounts)]`
+ * de src/contracts/lib.rs; contas opcionais recebem o id do programa quando ausentes,
+ * que o Anchor lê como `None` (por isso `receiptPayer` não é marcado como signer).
+ */
+export const VESTING_IDL: Idl = {
+    "version": "0.1.0",
+    "name": "verum_vesting",
+    "instructions": [
+        {
+            "name": "createVesting",
+            "accounts": [
+                { "name": "vestingContract", "isMut": true, "isSigner": false },
+                { "name": "creator", "isMut": true, "isSigner": true },
+                { "name": "beneficiary", "isMut": false, "isSigner": false },
+                { "name": "mint", "isMut": false, "isSigner": false },
+                { "name": "escrowWallet", "isMut": true, "isSigner": false },
+                { "name": "senderTokenAccount", "isMut": true, "isSigner": false },
+                { "name": "systemProgram", "isMut": false, "isSigner": false },
+                { "name": "tokenProgram", "isMut": false, "isSigner": false },
+                { "name": "auditLog", "isMut": true, "isSigner": false },
+                ...EVENT_CPI_ACCOUNTS
+            ],
+            "args": [
+                { "name": "contractId", "type": "u64" },
+                { "name": "totalAmount", "type": "u64" },
+                { "name": "startTime", "type": "i64" },
+                { "name": "endTime", "type": "i64" },
+                { "name": "vestingType", "type": { "defined": "VestingType" } },
+                { "name": "allowedMintFlags", "type": "u16" }
+            ]
+        },
+        {
+            "name": "claimTokens",
+            "accounts": [
+                { "name": "vestingContract", "isMut": true, "isSigner": false },
+                { "name": "escrowWallet", "isMut": true, "isSigner": false },
+                { "name": "beneficiaryTokenAccount", "isMut": true, "isSigner": false },
+                { "name": "beneficiary", "isMut": false, "isSigner": false },
+                { "name": "mint", "isMut": false, "isSigner": false },
+                { "name": "tokenProgram", "isMut": false, "isSigner": false },
+                { "name": "priceTrigger", "isMut": true, "isSigner": false },
+                { "name": "priceFeed", "isMut": false, "isSigner": false },
+                { "name": "auditLog", "isMut": true, "isSigner": false },
+                { "name": "releaseReceipt", "isMut": true, "isSigner": false },
+                { "name": "receiptPayer", "isMut": true, "isSigner": false },
+                { "name": "systemProgram", "isMut": false, "isSigner": false },
+                ...EVENT_CPI_ACCOUNTS
+            ],
+            "args": []
+        },
+        {
+            "name": "cancelVesting",
+            "accounts": [
+                { "name": "vestingContract", "isMut": true, "isSigner": false },
+                { "name": "creator", "isMut": true, "isSigner": true },
+                { "name": "escrowWallet", "isMut": true, "isSigner": false },
+                { "name": "creatorTokenAccount", "isMut": true, "isSigner": false },
+                { "name": "mint", "isMut": false, "isSigner": false },
+                { "name": "tokenProgram", "isMut": false, "isSigner": false },
+                { "name": "auditLog", "isMut": true, "isSigner": false },
+                ...EVENT_CPI_ACCOUNTS
+            ],
+            "args": []
+        },
+        {
+            "name": "updateBeneficiary",
+            "accounts": [
+                { "name": "vestingContract", "isMut": true, "isSigner": false },
+                { "name": "creator", "isMut": true, "isSigner": true },
+                { "name": "newBeneficiary", "isMut": false, "isSigner": false },
+                { "name": "auditLog", "isMut": true, "isSigner": false },
+                ...EVENT_CPI_ACCOUNTS
+            ],
+            "args": []
+        }
+    ],
+    "types": [
+        {
+            "name": "VestingType",
+            "type": {
+                "kind": "enum",
+                "variants": [
+                    { "name": "Linear" },
+                    { "name": "Cliff", "fields": ["i64", "u64"] },
+                    { "name": "Milestones" },
+                    { "name": "PriceTriggered" }
+                ]
+            }
+        }
+    ]
+};
+
+/** Contas de `emit_cpi!` para o programa indicado. */
+export const eventCpiAccounts = (programId: PublicKey) => ({
+    eventAuthority: getEventAuthority(programId),
+    program: programId,
+});
+
+/**
+ * `AuditLog` do contrato, exigido pelo programa quando existe; sem log devolve o id
+ * do programa (`None`).
+ */
+export const resolveAuditLog = async (
+    connection: Connection,
+    vestingContract: PublicKey,
+    programId: PublicKey
+): Promise<PublicKey> => {
+    const address = findAuditLogAddress(vestingContract, programId);
+    const info = await connection.getAccountInfo(address);
+    return info && info.owner.equals(programId) ? address : programId;
+};
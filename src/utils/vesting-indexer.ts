@@ -0,0 +1,42 @@
+// Cliente da API do indexador (crates/verum_vesting_indexer).
+// Fonte única do histórico de eventos para dashboard, auditoria e notificações.
+
+export const INDEXER_URL = process.env.NEXT_PUBLIC_INDEXER_URL || 'http://127.0.0.1:8787';
+
+export type IndexedEventKind = 'VestingCreated' | 'TokensClaimed' | 'BeneficiaryUpdated' | 'VestingCancelled';
+
+export interface IndexedEvent {
+    signature: string;
+    index: number;
+    slot: number;
+    block_time: number | null;
+    kind: IndexedEventKind;
+    vesting_contract: string;
+    contract_id: number;
+    wallets: string[];
+    amount: number | null;
+    payload: Record<string, unknown>;
+}
+
+export interface IndexedEventQuery {
+    contract?: string;
+    wallet?: string;
+    kind?: IndexedEventKind;
+    sinceSlot?: number;
+    limit?: number;
+}
+
+export const fetchIndexedEvents = async (query: IndexedEventQuery = {}): Promise<IndexedEvent[]> => {
+    const params = new URLSearchParams();
+    if (query.contract) params.set('contract', query.contract);
+    if (query.wallet) params.set('wallet', query.wallet);
+    if (query.kind) params.set('kind', query.kind);
+    if (query.sinceSlot !== undefined) params.set('since_slot', String(query.sinceSlot));
+    if (query.limit !== undefined) params.set('limit', String(query.limit));
+
+    const response = await fetch(`${INDEXER_URL}/events?${params.toString()}`);
+    if (!response.ok) {
+        throw new Error(`Indexador respondeu ${response.status}`);
+    }
+    return response.json();
+};
@@ -0,0 +1,125 @@
+mod common;
+
+use anchor_lang::AnchorDeserialize;
+use common::*;
+use solana_sdk::{pubkey::Pubkey, signature::Signer};
+use verum_vesting::{
+    audit_action, AuditLog, AuditLogPage, VestingError, VestingType, AUDIT_LOG_CAPACITY,
+};
+
+async fn setup_audit(with_log: bool) -> ContractFixture {
+    TestEnv::new()
+        .await
+        .create_contract(VestingType::Linear, 0, 1_000, with_log)
+        .await
+}
+
+impl ContractFixture {
+    async fn audit_log(&mut self) -> AuditLog {
+        let address = audit_log_address(&self.vesting_contract);
+        self.env.anchor_account(&address).await
+    }
+
+    async fn read_page(&mut self, start_sequence: u64, limit: u8) -> AuditLogPage {
+        let instruction = read_audit_log_ix(&self.vesting_contract, start_sequence, limit);
+        let data = self.env.simulate_return_data(instruction).await;
+        AuditLogPage::try_from_slice(&data).unwrap()
+    }
+}
+
+#[tokio::test]
+async fn audit_log_records_each_operation() {
+    let mut fixture = setup_audit(true).await;
+    let creator = fixture.creator.pubkey();
+    let beneficiary = fixture.beneficiary.insecure_clone();
+
+    fixture.env.set_time(fixture.start_time + 500).await;
+    let claim = fixture.claim_ix();
+    fixture
+        .env
+        .process(&[claim], &[])
+        .await
+        .unwrap();
+    let claimed = fixture
+        .env
+        .token_balance(&fixture.beneficiary_token_account)
+        .await;
+
+    let cancel = cancel_vesting_ix(
+        &fixture.vesting_contract,
+        &creator,
+        &fixture.creator_token_account,
+        &fixture.mint,
+        &TOKEN_PROGRAM_ID,
+        &[],
+    );
+    fixture.env.process(&[cancel], &[]).await.unwrap();
+    let refunded = fixture
+        .env
+        .token_balance(&fixture.creator_token_account)
+        .await;
+
+    let audit_log = fixture.audit_log().await;
+    assert_eq!(audit_log.vesting_contract, fixture.vesting_contract);
+    assert_eq!(audit_log.total_entries, 3);
+    let entries: Vec<_> = audit_log
+        .entries
+        .iter()
+        .map(|entry| (entry.sequence, entry.actor, entry.action, entry.amount))
+        .collect();
+    assert_eq!(
+        entries,
+        vec![
+            (0, creator, audit_action::CREATE, CONTRACT_TOTAL),
+            (1, beneficiary.pubkey(), audit_action::CLAIM, claimed),
+            (2, creator, audit_action::CANCEL, refunded),
+        ]
+    );
+    assert_eq!(audit_log.entries[1].timestamp, fixture.start_time + 500);
+
+    let page = fixture.read_page(1, 10).await;
+    assert_eq!(page.total_entries, 3);
+    assert_eq!(page.oldest_sequence, 0);
+    assert_eq!(page.entries, audit_log.entries[1..].to_vec());
+}
+
+#[tokio::test]
+async fn audit_log_wraps_and_becomes_mandatory() {
+    let mut fixture = setup_audit(false).await;
+    let creator = fixture.creator.pubkey();
+    let vesting_contract = fixture.vesting_contract;
+
+    let instruction = create_audit_log_ix(&vesting_contract, &creator);
+    fixture.env.process(&[instruction], &[]).await.unwrap();
+    let contract = fixture.env.vesting_contract(&vesting_contract).await;
+    assert!(contract.has_audit_log);
+
+    fixture.env.set_time(fixture.start_time + 500).await;
+    let claim = without_audit_log(fixture.claim_ix(), &vesting_contract);
+    let result = fixture.env.process(&[claim], &[]).await;
+    assert_vesting_error(result, VestingError::MissingAuditLog);
+
+    let updates = AUDIT_LOG_CAPACITY as u64 + 8;
+    for _ in 0..updates {
+        let instruction = update_beneficiary_ix(&vesting_contract, &creator, &Pubkey::new_unique());
+        fixture.env.process(&[instruction], &[]).await.unwrap();
+    }
+
+    let audit_log = fixture.audit_log().await;
+    assert_eq!(audit_log.total_entries, updates);
+    assert_eq!(audit_log.entries.len(), AUDIT_LOG_CAPACITY);
+    assert_eq!(audit_log.oldest_sequence(), 8);
+
+    let page = fixture.read_page(0, u8::MAX).await;
+    assert_eq!(page.oldest_sequence, 8);
+    let sequences: Vec<u64> = page.entries.iter().map(|entry| entry.sequence).collect();
+    assert_eq!(sequences, (8..24).collect::<Vec<_>>());
+    assert!(page
+        .entries
+        .iter()
+        .all(|entry| entry.action == audit_action::UPDATE_BENEFICIARY));
+
+    let page = fixture.read_page(updates - 2, 16).await;
+    let sequences: Vec<u64> = page.entries.iter().map(|entry| entry.sequence).collect();
+    assert_eq!(sequences, vec![updates - 2, updates - 1]);
+}
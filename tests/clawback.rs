@@ -0,0 +1,201 @@
+mod common;
+
+use common::*;
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+use verum_vesting::{clawback_reason, Clawback, VestingError, VestingType};
+
+/// Autoridade de compliance e tesouraria que recebe o clawback.
+struct Compliance {
+    authority: Keypair,
+    treasury: Pubkey,
+    treasury_token_account: Pubkey,
+}
+
+type ClawbackFixture = ContractFixture<Compliance>;
+
+async fn setup_clawback() -> ClawbackFixture {
+    let mut contract = TestEnv::new()
+        .await
+        .create_linear_contract(VestingType::Linear, 1_000)
+        .await;
+    let authority = Keypair::new();
+    let treasury = Pubkey::new_unique();
+    let mint = contract.mint;
+    let treasury_token_account = contract.env.create_token_account(&mint, &treasury).await;
+
+    let instruction = set_compliance_authority_ix(
+        &contract.vesting_contract,
+        &contract.creator.pubkey(),
+        &contract.beneficiary.pubkey(),
+        &authority.pubkey(),
+    );
+    let beneficiary = contract.beneficiary.insecure_clone();
+    contract
+        .env
+        .process(&[instruction], &[&beneficiary])
+        .await
+        .unwrap();
+
+    contract.with(Compliance {
+        authority,
+        treasury,
+        treasury_token_account,
+    })
+}
+
+impl ClawbackFixture {
+    fn args(&self, amount: u64, reason_code: u8, memo: &str) -> ClawbackArgs {
+        ClawbackArgs {
+            compliance_authority: self.extra.authority.pubkey(),
+            destination: self.extra.treasury,
+            destination_token_account: self.extra.treasury_token_account,
+            mint: self.mint,
+            token_program: TOKEN_PROGRAM_ID,
+            amount,
+            reason_code,
+            memo: memo.to_string(),
+        }
+    }
+
+    async fn claim(&mut self) -> Result<(), solana_program_test::BanksClientError> {
+        let instruction = self.claim_ix();
+        self.env.process(&[instruction], &[]).await
+    }
+}
+
+#[tokio::test]
+async fn clawback_reaches_vested_unclaimed_tokens() {
+    let mut fixture = setup_clawback().await;
+    fixture.env.set_time(fixture.start_time + 500).await;
+
+    let instruction = clawback_ix(
+        &fixture.vesting_contract,
+        &fixture.args(700_000, clawback_reason::COURT_ORDER, "Processo 123/2026"),
+    );
+    let compliance = fixture.extra.authority.insecure_clone();
+    fixture
+        .env
+        .process(&[instruction], &[&compliance])
+        .await
+        .unwrap();
+
+    let treasury_token_account = fixture.extra.treasury_token_account;
+    assert_eq!(
+        fixture.env.token_balance(&treasury_token_account).await,
+        700_000
+    );
+    let vesting_contract = fixture
+        .env
+        .vesting_contract(&fixture.vesting_contract)
+        .await;
+    assert_eq!(vesting_contract.clawed_back_amount, 700_000);
+    assert_eq!(vesting_contract.total_amount, CONTRACT_TOTAL - 700_000);
+    assert_eq!(vesting_contract.released_amount, 0);
+
+    let events: Vec<Clawback> = cpi_events::<Clawback>()
+        .into_iter()
+        .filter(|event| event.vesting_contract == fixture.vesting_contract)
+        .collect();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].amount, 700_000);
+    assert_eq!(events[0].reason_code, clawback_reason::COURT_ORDER);
+    assert_eq!(events[0].memo, "Processo 123/2026");
+
+    // O cronograma passa a liberar os 300k restantes: metade ja esta liberada
+    fixture.claim().await.unwrap();
+    let beneficiary_token_account = fixture.beneficiary_token_account;
+    assert_eq!(
+        fixture.env.token_balance(&beneficiary_token_account).await,
+        150_000
+    );
+
+    fixture.env.set_time(fixture.start_time + 1_000).await;
+    fixture.claim().await.unwrap();
+    assert_eq!(
+        fixture.env.token_balance(&beneficiary_token_account).await,
+        CONTRACT_TOTAL - 700_000
+    );
+    let vesting_contract = fixture
+        .env
+        .vesting_contract(&fixture.vesting_contract)
+        .await;
+    assert_eq!(vesting_contract.released_amount, CONTRACT_TOTAL - 700_000);
+}
+
+#[tokio::test]
+async fn beneficiary_can_still_be_replaced_after_clawback() {
+    let mut fixture = setup_clawback().await;
+    let compliance = fixture.extra.authority.insecure_clone();
+    let instruction = clawback_ix(
+        &fixture.vesting_contract,
+        &fixture.args(100_000, clawback_reason::SANCTIONS, ""),
+    );
+    fixture
+        .env
+        .process(&[instruction], &[&compliance])
+        .await
+        .unwrap();
+
+    let creator = fixture.env.payer();
+    let new_beneficiary = Pubkey::new_unique();
+    let instruction =
+        update_beneficiary_ix(&fixture.vesting_contract, &creator.pubkey(), &new_beneficiary);
+    fixture.env.process(&[instruction], &[]).await.unwrap();
+    let vesting_contract = fixture
+        .env
+        .vesting_contract(&fixture.vesting_contract)
+        .await;
+    assert_eq!(vesting_contract.beneficiary, new_beneficiary);
+}
+
+#[tokio::test]
+async fn clawback_validates_authority_reason_and_amount() {
+    let mut fixture = setup_clawback().await;
+    let compliance = fixture.extra.authority.insecure_clone();
+
+    let impostor = Keypair::new();
+    let mut args = fixture.args(100_000, clawback_reason::FRAUD, "");
+    args.compliance_authority = impostor.pubkey();
+    let result = fixture
+        .env
+        .process(
+            &[clawback_ix(&fixture.vesting_contract, &args)],
+            &[&impostor],
+        )
+        .await;
+    assert_vesting_error(result, VestingError::Unauthorized);
+
+    let args = fixture.args(100_000, 42, "");
+    let result = fixture
+        .env
+        .process(
+            &[clawback_ix(&fixture.vesting_contract, &args)],
+            &[&compliance],
+        )
+        .await;
+    assert_vesting_error(result, VestingError::InvalidClawbackReason);
+
+    let memo = "x".repeat(verum_vesting::MAX_CLAWBACK_MEMO_LEN + 1);
+    let args = fixture.args(100_000, clawback_reason::OTHER, &memo);
+    let result = fixture
+        .env
+        .process(
+            &[clawback_ix(&fixture.vesting_contract, &args)],
+            &[&compliance],
+        )
+        .await;
+    assert_vesting_error(result, VestingError::ClawbackMemoTooLong);
+
+    let args = fixture.args(CONTRACT_TOTAL + 1, clawback_reason::SANCTIONS, "");
+    let result = fixture
+        .env
+        .process(
+            &[clawback_ix(&fixture.vesting_contract, &args)],
+            &[&compliance],
+        )
+        .await;
+    assert_vesting_error(result, VestingError::InsufficientEscrowBalance);
+}
@@ -0,0 +1,2127 @@
+//! Utilitarios compartilhados pelos testes de integracao do verum_vesting.
+//!
+//! O programa roda nativamente dentro do `solana-program-test`; os programas
+//! SPL Token e Token-2022 sao os binarios embutidos no proprio program-test.
+
+#![allow(dead_code)]
+
+use std::sync::Mutex;
+
+use anchor_lang::{InstructionData, ToAccountMetas};
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    account_info::AccountInfo,
+    clock::Clock,
+    entrypoint::ProgramResult,
+    instruction::InstructionError,
+    instruction::{AccountMeta, Instruction},
+    program_pack::Pack,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_instruction,
+    transaction::{Transaction, TransactionError},
+};
+use spl_tlv_account_resolution::{
+    account::ExtraAccountMeta, seeds::Seed, state::ExtraAccountMetaList,
+};
+use spl_token_2022::extension::{BaseStateWithExtensions, ExtensionType, StateWithExtensions};
+use spl_transfer_hook_interface::{
+    get_extra_account_metas_address, instruction::ExecuteInstruction,
+};
+use verum_vesting::VestingType;
+
+pub const TOKEN_PROGRAM_ID: Pubkey = anchor_spl::token::ID;
+pub const TOKEN_2022_PROGRAM_ID: Pubkey = anchor_spl::token_2022::ID;
+/// Expoente usado pelos feeds de preco de teste.
+pub const PRICE_EXPO: i32 = -6;
+
+/// Eventos recebidos via `emit_cpi!` (discriminador + payload), na ordem em que o
+/// programa os emitiu. Compartilhado pelos testes do mesmo binario.
+static CPI_EVENTS: Mutex<Vec<Vec<u8>>> = Mutex::new(Vec::new());
+
+/// Eventos `E` ja emitidos via `emit_cpi!`, decodificados como faria um indexador a
+/// partir das instrucoes internas.
+pub fn cpi_events<E: anchor_lang::Event + anchor_lang::AnchorDeserialize>() -> Vec<E> {
+    CPI_EVENTS
+        .lock()
+        .unwrap()
+        .iter()
+        .filter_map(|data| {
+            let payload = data.strip_prefix(E::DISCRIMINATOR.as_slice())?;
+            Some(E::deserialize(&mut &payload[..]).unwrap())
+        })
+        .collect()
+}
+
+// O `entry` gerado pelo Anchor exige `&'info [AccountInfo<'info>]`, mais
+// restritivo do que o `processor!` oferece.
+fn process_vesting_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> ProgramResult {
+    if let Some(event) = data.strip_prefix(anchor_lang::event::EVENT_IX_TAG_LE.as_slice()) {
+        CPI_EVENTS.lock().unwrap().push(event.to_vec());
+    }
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    verum_vesting::entry(program_id, accounts, data)
+}
+
+pub fn program_test() -> ProgramTest {
+    let mut program_test = ProgramTest::new(
+        "verum_vesting",
+        verum_vesting::ID,
+        processor!(process_vesting_instruction),
+    );
+    program_test.add_program(
+        "dummy_transfer_hook",
+        dummy_transfer_hook::ID,
+        processor!(dummy_transfer_hook::process_instruction),
+    );
+    program_test.add_program(
+        "mock_price_feed",
+        mock_price_feed::ID,
+        processor!(mock_price_feed::process_instruction),
+    );
+    program_test.add_program(
+        "mock_staking",
+        mock_staking::ID,
+        processor!(mock_staking::process_instruction),
+    );
+    program_test
+}
+
+pub fn vesting_address(creator: &Pubkey, mint: &Pubkey, contract_id: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            b"vesting",
+            creator.as_ref(),
+            mint.as_ref(),
+            &contract_id.to_le_bytes(),
+        ],
+        &verum_vesting::ID,
+    )
+    .0
+}
+
+pub fn pool_address(creator: &Pubkey, mint: &Pubkey, pool_id: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            b"pool",
+            creator.as_ref(),
+            mint.as_ref(),
+            &pool_id.to_le_bytes(),
+        ],
+        &verum_vesting::ID,
+    )
+    .0
+}
+
+pub fn stream_address(payer: &Pubkey, mint: &Pubkey, stream_id: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            b"stream",
+            payer.as_ref(),
+            mint.as_ref(),
+            &stream_id.to_le_bytes(),
+        ],
+        &verum_vesting::ID,
+    )
+    .0
+}
+
+pub fn milestone_schedule_address(vesting_contract: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"milestones", vesting_contract.as_ref()],
+        &verum_vesting::ID,
+    )
+    .0
+}
+
+pub fn price_trigger_address(vesting_contract: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"price_trigger", vesting_contract.as_ref()],
+        &verum_vesting::ID,
+    )
+    .0
+}
+
+pub fn escrow_address(vesting_contract: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"escrow", vesting_contract.as_ref()], &verum_vesting::ID).0
+}
+
+/// PDA que assina os eventos emitidos via `emit_cpi!`.
+pub fn event_authority_address() -> Pubkey {
+    Pubkey::find_program_address(&[b"__event_authority"], &verum_vesting::ID).0
+}
+
+pub fn audit_log_address(vesting_contract: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"audit_log", vesting_contract.as_ref()],
+        &verum_vesting::ID,
+    )
+    .0
+}
+
+/// Confere que a transacao falhou com o erro esperado do programa de vesting.
+pub fn assert_vesting_error(
+    result: Result<(), BanksClientError>,
+    expected: verum_vesting::VestingError,
+) {
+    match result.unwrap_err().unwrap() {
+        TransactionError::InstructionError(_, InstructionError::Custom(code)) => {
+            assert_eq!(code, u32::from(expected));
+        }
+        other => panic!("erro inesperado: {other:?}"),
+    }
+}
+
+/// Confere que a transacao falhou com um erro do proprio Anchor (restricoes de
+/// contas, assinaturas ausentes etc.).
+pub fn assert_anchor_error(
+    result: Result<(), BanksClientError>,
+    expected: anchor_lang::error::ErrorCode,
+) {
+    match result.unwrap_err().unwrap() {
+        TransactionError::InstructionError(_, InstructionError::Custom(code)) => {
+            assert_eq!(code, u32::from(expected));
+        }
+        other => panic!("erro inesperado: {other:?}"),
+    }
+}
+
+pub struct MintConfig {
+    pub token_program: Pubkey,
+    pub decimals: u8,
+    pub freeze_authority: Option<Pubkey>,
+    pub transfer_hook_program: Option<Pubkey>,
+    pub permanent_delegate: Option<Pubkey>,
+    pub transfer_fee_basis_points: Option<u16>,
+}
+
+impl Default for MintConfig {
+    fn default() -> Self {
+        Self {
+            token_program: TOKEN_2022_PROGRAM_ID,
+            decimals: 6,
+            freeze_authority: None,
+            transfer_hook_program: None,
+            permanent_delegate: None,
+            transfer_fee_basis_points: None,
+        }
+    }
+}
+
+pub struct TestEnv {
+    pub context: ProgramTestContext,
+}
+
+impl TestEnv {
+    pub async fn new() -> Self {
+        Self::with_program_test(program_test()).await
+    }
+
+    /// Para testes que registram programas extras sobre `program_test()`.
+    pub async fn with_program_test(program_test: ProgramTest) -> Self {
+        Self {
+            context: program_test.start_with_context().await,
+        }
+    }
+
+    pub fn payer(&self) -> Keypair {
+        self.context.payer.insecure_clone()
+    }
+
+    /// Cada transacao usa um blockhash diferente do anterior, para que reenviar a
+    /// mesma instrucao nao devolva o resultado em cache do BanksClient.
+    pub async fn process(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> Result<(), BanksClientError> {
+        let payer = self.payer();
+        let mut all_signers = vec![&payer];
+        all_signers.extend_from_slice(signers);
+        let blockhash = self.context.get_new_latest_blockhash().await?;
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&payer.pubkey()),
+            &all_signers,
+            blockhash,
+        );
+        self.context
+            .banks_client
+            .process_transaction(transaction)
+            .await
+    }
+
+    /// Simula a transacao e devolve o return data da ultima instrucao.
+    pub async fn simulate_return_data(&mut self, instruction: Instruction) -> Vec<u8> {
+        let payer = self.payer();
+        let blockhash = self.context.last_blockhash;
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&payer.pubkey()),
+            &[&payer],
+            blockhash,
+        );
+        let simulation = self
+            .context
+            .banks_client
+            .simulate_transaction(transaction)
+            .await
+            .unwrap();
+        simulation.result.unwrap().unwrap();
+        simulation
+            .simulation_details
+            .and_then(|details| details.return_data)
+            .map(|return_data| return_data.data)
+            .unwrap_or_default()
+    }
+
+    /// Processa a transacao e devolve as unidades de computo consumidas, medidas
+    /// por uma simulacao identica logo antes.
+    pub async fn process_metered(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> Result<u64, BanksClientError> {
+        let payer = self.payer();
+        let mut all_signers = vec![&payer];
+        all_signers.extend_from_slice(signers);
+        let blockhash = self.context.get_new_latest_blockhash().await?;
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&payer.pubkey()),
+            &all_signers,
+            blockhash,
+        );
+        let simulation = self
+            .context
+            .banks_client
+            .simulate_transaction(transaction.clone())
+            .await?;
+        let units_consumed = simulation
+            .simulation_details
+            .map(|details| details.units_consumed)
+            .unwrap_or_default();
+        self.context
+            .banks_client
+            .process_transaction(transaction)
+            .await?;
+        Ok(units_consumed)
+    }
+
+    pub async fn fund(&mut self, recipient: &Pubkey, lamports: u64) {
+        let payer = self.payer();
+        self.process(
+            &[system_instruction::transfer(
+                &payer.pubkey(),
+                recipient,
+                lamports,
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+    }
+
+    pub async fn now(&mut self) -> i64 {
+        let clock: Clock = self.context.banks_client.get_sysvar().await.unwrap();
+        clock.unix_timestamp
+    }
+
+    /// Avanca um slot (novo blockhash, evitando que transacoes identicas sejam
+    /// deduplicadas) e fixa o relogio em `unix_timestamp`.
+    pub async fn set_time(&mut self, unix_timestamp: i64) {
+        let clock: Clock = self.context.banks_client.get_sysvar().await.unwrap();
+        self.context.warp_to_slot(clock.slot + 1).unwrap();
+        let mut clock: Clock = self.context.banks_client.get_sysvar().await.unwrap();
+        clock.unix_timestamp = unix_timestamp;
+        self.context.set_sysvar(&clock);
+    }
+
+    /// Cria um mint com autoridade no payer. Para Token-2022 e possivel
+    /// apontar a extensao TransferHook para um programa.
+    pub async fn create_mint(
+        &mut self,
+        token_program: &Pubkey,
+        decimals: u8,
+        transfer_hook_program: Option<Pubkey>,
+    ) -> Pubkey {
+        self.create_mint_with(MintConfig {
+            token_program: *token_program,
+            decimals,
+            transfer_hook_program,
+            ..MintConfig::default()
+        })
+        .await
+    }
+
+    pub async fn create_mint_with(&mut self, config: MintConfig) -> Pubkey {
+        let payer = self.payer();
+        let mint = Keypair::new();
+        let token_program = &config.token_program;
+
+        let mut extensions = vec![];
+        let mut extension_instructions = vec![];
+        if let Some(hook_program) = config.transfer_hook_program {
+            extensions.push(ExtensionType::TransferHook);
+            extension_instructions.push(
+                spl_token_2022::extension::transfer_hook::instruction::initialize(
+                    token_program,
+                    &mint.pubkey(),
+                    Some(payer.pubkey()),
+                    Some(hook_program),
+                )
+                .unwrap(),
+            );
+        }
+        if let Some(delegate) = config.permanent_delegate {
+            extensions.push(ExtensionType::PermanentDelegate);
+            extension_instructions.push(
+                spl_token_2022::instruction::initialize_permanent_delegate(
+                    token_program,
+                    &mint.pubkey(),
+                    &delegate,
+                )
+                .unwrap(),
+            );
+        }
+        if let Some(basis_points) = config.transfer_fee_basis_points {
+            extensions.push(ExtensionType::TransferFeeConfig);
+            extension_instructions.push(
+                spl_token_2022::extension::transfer_fee::instruction::initialize_transfer_fee_config(
+                    token_program,
+                    &mint.pubkey(),
+                    Some(&payer.pubkey()),
+                    Some(&payer.pubkey()),
+                    basis_points,
+                    u64::MAX,
+                )
+                .unwrap(),
+            );
+        }
+
+        let space =
+            ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(&extensions)
+                .unwrap();
+        let rent = self.context.banks_client.get_rent().await.unwrap();
+
+        let mut instructions = vec![system_instruction::create_account(
+            &payer.pubkey(),
+            &mint.pubkey(),
+            rent.minimum_balance(space),
+            space as u64,
+            token_program,
+        )];
+        instructions.extend(extension_instructions);
+        instructions.push(
+            spl_token_2022::instruction::initialize_mint2(
+                token_program,
+                &mint.pubkey(),
+                &payer.pubkey(),
+                config.freeze_authority.as_ref(),
+                config.decimals,
+            )
+            .unwrap(),
+        );
+        self.process(&instructions, &[&mint]).await.unwrap();
+        mint.pubkey()
+    }
+
+    /// Cria uma conta de token (nao associada) ja dimensionada para as
+    /// extensoes que o mint exige.
+    pub async fn create_token_account(&mut self, mint: &Pubkey, owner: &Pubkey) -> Pubkey {
+        let payer = self.payer();
+        let account = Keypair::new();
+        let mint_account = self
+            .context
+            .banks_client
+            .get_account(*mint)
+            .await
+            .unwrap()
+            .unwrap();
+        let token_program = mint_account.owner;
+        let mint_state =
+            StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_account.data).unwrap();
+        let account_extensions = ExtensionType::get_required_init_account_extensions(
+            &mint_state.get_extension_types().unwrap(),
+        );
+        let space = ExtensionType::try_calculate_account_len::<spl_token_2022::state::Account>(
+            &account_extensions,
+        )
+        .unwrap();
+        let rent = self.context.banks_client.get_rent().await.unwrap();
+
+        self.process(
+            &[
+                system_instruction::create_account(
+                    &payer.pubkey(),
+                    &account.pubkey(),
+                    rent.minimum_balance(space),
+                    space as u64,
+                    &token_program,
+                ),
+                spl_token_2022::instruction::initialize_account3(
+                    &token_program,
+                    &account.pubkey(),
+                    mint,
+                    owner,
+                )
+                .unwrap(),
+            ],
+            &[&account],
+        )
+        .await
+        .unwrap();
+        account.pubkey()
+    }
+
+    pub async fn mint_to(&mut self, mint: &Pubkey, destination: &Pubkey, amount: u64) {
+        let payer = self.payer();
+        let token_program = self.owner_of(mint).await;
+        self.process(
+            &[spl_token_2022::instruction::mint_to(
+                &token_program,
+                mint,
+                destination,
+                &payer.pubkey(),
+                &[],
+                amount,
+            )
+            .unwrap()],
+            &[],
+        )
+        .await
+        .unwrap();
+    }
+
+    pub async fn lamports(&mut self, address: &Pubkey) -> u64 {
+        self.context
+            .banks_client
+            .get_balance(*address)
+            .await
+            .unwrap()
+    }
+
+    /// Cria uma conta de preco do programa `mock_price_feed` (layout Pyth).
+    pub async fn create_price_feed(&mut self) -> Pubkey {
+        let payer = self.payer();
+        let price_feed = Keypair::new();
+        let rent = self.context.banks_client.get_rent().await.unwrap();
+        self.process(
+            &[system_instruction::create_account(
+                &payer.pubkey(),
+                &price_feed.pubkey(),
+                rent.minimum_balance(mock_price_feed::PRICE_ACCOUNT_LEN),
+                mock_price_feed::PRICE_ACCOUNT_LEN as u64,
+                &mock_price_feed::ID,
+            )],
+            &[&price_feed],
+        )
+        .await
+        .unwrap();
+        price_feed.pubkey()
+    }
+
+    pub async fn set_price(
+        &mut self,
+        price_feed: &Pubkey,
+        price: i64,
+        conf: u64,
+        publish_time: i64,
+    ) {
+        let data = mock_price_feed::SetPrice {
+            price,
+            conf,
+            expo: PRICE_EXPO,
+            publish_time,
+            status: mock_price_feed::STATUS_TRADING,
+        }
+        .pack();
+        self.process(
+            &[Instruction {
+                program_id: mock_price_feed::ID,
+                accounts: vec![AccountMeta::new(*price_feed, false)],
+                data,
+            }],
+            &[],
+        )
+        .await
+        .unwrap();
+    }
+
+    pub async fn owner_of(&mut self, address: &Pubkey) -> Pubkey {
+        self.context
+            .banks_client
+            .get_account(*address)
+            .await
+            .unwrap()
+            .unwrap()
+            .owner
+    }
+
+    pub async fn token_balance(&mut self, token_account: &Pubkey) -> u64 {
+        let account = self
+            .context
+            .banks_client
+            .get_account(*token_account)
+            .await
+            .unwrap()
+            .unwrap();
+        let data = &account.data[..spl_token_2022::state::Account::LEN];
+        spl_token_2022::state::Account::unpack_from_slice(data)
+            .unwrap()
+            .amount
+    }
+
+    /// Le e desserializa uma conta Anchor do programa de vesting.
+    pub async fn anchor_account<T: anchor_lang::AccountDeserialize>(
+        &mut self,
+        address: &Pubkey,
+    ) -> T {
+        let account = self
+            .context
+            .banks_client
+            .get_account(*address)
+            .await
+            .unwrap()
+            .unwrap();
+        T::try_deserialize(&mut account.data.as_slice()).unwrap()
+    }
+
+    pub async fn vesting_contract(&mut self, address: &Pubkey) -> verum_vesting::VestingContract {
+        self.anchor_account(address).await
+    }
+
+    pub async fn vesting_pool(&mut self, address: &Pubkey) -> verum_vesting::VestingPool {
+        self.anchor_account(address).await
+    }
+
+    pub async fn payment_stream(&mut self, address: &Pubkey) -> verum_vesting::PaymentStream {
+        self.anchor_account(address).await
+    }
+
+    /// Grava uma conta `RealmV2` do SPL Governance com o layout minimo lido pelo
+    /// registrar, pertencente a `governance_program`.
+    pub async fn create_realm(
+        &mut self,
+        governance_program: &Pubkey,
+        community_mint: &Pubkey,
+        authority: &Pubkey,
+    ) -> Pubkey {
+        let mut data = vec![16u8]; // GovernanceAccountType::RealmV2
+        data.extend_from_slice(community_mint.as_ref());
+        data.extend_from_slice(&[0; 2 + 6 + 8 + 9]); // RealmConfig ate council_mint
+        data.push(0); // council_mint: None
+        data.extend_from_slice(&[0; 6 + 2]);
+        data.push(1);
+        data.extend_from_slice(authority.as_ref());
+        data.extend_from_slice(&4u32.to_le_bytes());
+        data.extend_from_slice(b"test");
+        data.extend_from_slice(&[0; 128]);
+
+        let rent = self.context.banks_client.get_rent().await.unwrap();
+        let realm = Pubkey::new_unique();
+        let mut account = solana_sdk::account::AccountSharedData::new(
+            rent.minimum_balance(data.len()),
+            data.len(),
+            governance_program,
+        );
+        solana_sdk::account::WritableAccount::data_as_mut_slice(&mut account)
+            .copy_from_slice(&data);
+        self.context.set_account(&realm, &account);
+        realm
+    }
+
+    /// Cria o cofre do `mock_staking` para o mint.
+    pub async fn initialize_staking_vault(&mut self, mint: &Pubkey, token_program: &Pubkey) {
+        let payer = self.payer();
+        let instruction = Instruction {
+            program_id: mock_staking::ID,
+            accounts: vec![
+                AccountMeta::new(payer.pubkey(), true),
+                AccountMeta::new(mock_staking::get_vault_address(mint).0, false),
+                AccountMeta::new_readonly(mock_staking::get_vault_authority_address().0, false),
+                AccountMeta::new_readonly(*mint, false),
+                AccountMeta::new_readonly(*token_program, false),
+                AccountMeta::new_readonly(solana_sdk::system_program::ID, false),
+            ],
+            data: mock_staking::StakingInstruction::InitializeVault.pack(),
+        };
+        self.process(&[instruction], &[]).await.unwrap();
+    }
+
+    /// Credita recompensas no registro de stake do contrato, pagas pelo payer.
+    pub async fn fund_staking_rewards(
+        &mut self,
+        vesting_contract: &Pubkey,
+        mint: &Pubkey,
+        token_program: &Pubkey,
+        funder_token_account: &Pubkey,
+        amount: u64,
+    ) {
+        let payer = self.payer();
+        let instruction = Instruction {
+            program_id: mock_staking::ID,
+            accounts: vec![
+                AccountMeta::new(stake_record_address(vesting_contract, mint), false),
+                AccountMeta::new_readonly(payer.pubkey(), true),
+                AccountMeta::new(*funder_token_account, false),
+                AccountMeta::new(mock_staking::get_vault_address(mint).0, false),
+                AccountMeta::new_readonly(*mint, false),
+                AccountMeta::new_readonly(*token_program, false),
+            ],
+            data: mock_staking::StakingInstruction::FundRewards { amount }.pack(),
+        };
+        self.process(&[instruction], &[]).await.unwrap();
+    }
+
+    pub async fn voter_weight_record(
+        &mut self,
+        address: &Pubkey,
+    ) -> verum_vesting::VoterWeightRecord {
+        self.anchor_account(address).await
+    }
+
+    /// Registra a `ExtraAccountMetaList` do hook de teste para o mint, exigindo
+    /// o contador `[b"counter", mint]` como conta extra gravavel.
+    pub async fn initialize_transfer_hook(&mut self, mint: &Pubkey) {
+        let payer = self.payer();
+        let extra_account_metas = [ExtraAccountMeta::new_with_seeds(
+            &[
+                Seed::Literal {
+                    bytes: dummy_transfer_hook::COUNTER_SEED.to_vec(),
+                },
+                Seed::AccountKey { index: 1 },
+            ],
+            false,
+            true,
+        )
+        .unwrap()];
+        let mut instruction =
+            spl_transfer_hook_interface::instruction::initialize_extra_account_meta_list(
+                &dummy_transfer_hook::ID,
+                &get_extra_account_metas_address(mint, &dummy_transfer_hook::ID),
+                mint,
+                &payer.pubkey(),
+                &extra_account_metas,
+            );
+        instruction.accounts.push(AccountMeta::new(
+            dummy_transfer_hook::get_counter_address(mint).0,
+            false,
+        ));
+        self.process(&[instruction], &[]).await.unwrap();
+    }
+
+    pub async fn transfer_hook_counter(&mut self, mint: &Pubkey) -> u64 {
+        let account = self
+            .context
+            .banks_client
+            .get_account(dummy_transfer_hook::get_counter_address(mint).0)
+            .await
+            .unwrap()
+            .unwrap();
+        u64::from_le_bytes(account.data[..8].try_into().unwrap())
+    }
+
+    /// Resolve, como faria um cliente off-chain, as contas que o programa
+    /// precisa receber em `remaining_accounts` para transferir um mint com hook.
+    pub async fn transfer_hook_accounts(
+        &mut self,
+        mint: &Pubkey,
+        source: &Pubkey,
+        destination: &Pubkey,
+        authority: &Pubkey,
+        amount: u64,
+    ) -> Vec<AccountMeta> {
+        let hook_program = dummy_transfer_hook::ID;
+        let validation = get_extra_account_metas_address(mint, &hook_program);
+        let validation_data = self
+            .context
+            .banks_client
+            .get_account(validation)
+            .await
+            .unwrap()
+            .unwrap()
+            .data;
+
+        let mut execute = spl_transfer_hook_interface::instruction::execute(
+            &hook_program,
+            source,
+            mint,
+            destination,
+            authority,
+            &validation,
+            amount,
+        );
+        let base_len = execute.accounts.len();
+        let banks_client = self.context.banks_client.clone();
+        ExtraAccountMetaList::add_to_instruction::<ExecuteInstruction, _, _>(
+            &mut execute,
+            |address| {
+                let mut banks_client = banks_client.clone();
+                async move {
+                    Ok(banks_client
+                        .get_account(address)
+                        .await
+                        .ok()
+                        .flatten()
+                        .map(|account| account.data))
+                }
+            },
+            &validation_data,
+        )
+        .await
+        .unwrap();
+
+        let mut metas = execute.accounts.split_off(base_len);
+        metas.push(AccountMeta::new_readonly(hook_program, false));
+        metas.push(AccountMeta::new_readonly(validation, false));
+        metas
+    }
+
+    /// Contrato de `CONTRACT_TOTAL` que comeca agora e termina em `end_offset`
+    /// segundos.
+    pub async fn create_linear_contract(
+        self,
+        vesting_type: VestingType,
+        end_offset: i64,
+    ) -> ContractFixture {
+        self.create_contract(vesting_type, 0, end_offset, true)
+            .await
+    }
+
+    /// Cria mint SPL Token, contas do criador (o pagador) e do beneficiario e o
+    /// contrato `1`, com inicio e fim relativos ao relogio atual. Sem `with_audit_log`
+    /// o contrato nasce sem `AuditLog`.
+    pub async fn create_contract(
+        mut self,
+        vesting_type: VestingType,
+        start_offset: i64,
+        end_offset: i64,
+        with_audit_log: bool,
+    ) -> ContractFixture {
+        let creator = self.payer();
+        let beneficiary = Keypair::new();
+        let mint = self.create_mint(&TOKEN_PROGRAM_ID, 6, None).await;
+        let creator_token_account = self.create_token_account(&mint, &creator.pubkey()).await;
+        let beneficiary_token_account = self
+            .create_token_account(&mint, &beneficiary.pubkey())
+            .await;
+        self.mint_to(&mint, &creator_token_account, CONTRACT_TOTAL)
+            .await;
+
+        let now = self.now().await;
+        let start_time = now + start_offset;
+        let args = CreateVestingArgs {
+            creator: creator.pubkey(),
+            beneficiary: beneficiary.pubkey(),
+            mint,
+            token_program: TOKEN_PROGRAM_ID,
+            sender_token_account: creator_token_account,
+            contract_id: 1,
+            total_amount: CONTRACT_TOTAL,
+            start_time,
+            end_time: now + end_offset,
+            vesting_type,
+            allowed_mint_flags: 0,
+        };
+        let vesting_contract = vesting_address(&creator.pubkey(), &mint, 1);
+        let mut instruction = create_vesting_ix(&args, &[]);
+        if !with_audit_log {
+            instruction = without_audit_log(instruction, &vesting_contract);
+        }
+        self.process(&[instruction], &[]).await.unwrap();
+
+        ContractFixture {
+            env: self,
+            creator,
+            beneficiary,
+            mint,
+            creator_token_account,
+            beneficiary_token_account,
+            vesting_contract,
+            start_time,
+            extra: (),
+        }
+    }
+}
+
+/// Valor dos contratos criados por `TestEnv::create_contract`.
+pub const CONTRACT_TOTAL: u64 = 1_000_000;
+
+/// Contrato criado por `TestEnv::create_contract`; cada arquivo de teste acrescenta
+/// o proprio setup sobre ele e guarda o que precisar em `extra` (ver `with`).
+pub struct ContractFixture<T = ()> {
+    pub env: TestEnv,
+    pub creator: Keypair,
+    pub beneficiary: Keypair,
+    pub mint: Pubkey,
+    pub creator_token_account: Pubkey,
+    pub beneficiary_token_account: Pubkey,
+    pub vesting_contract: Pubkey,
+    pub start_time: i64,
+    pub extra: T,
+}
+
+impl<T> ContractFixture<T> {
+    pub fn with<U>(self, extra: U) -> ContractFixture<U> {
+        ContractFixture {
+            env: self.env,
+            creator: self.creator,
+            beneficiary: self.beneficiary,
+            mint: self.mint,
+            creator_token_account: self.creator_token_account,
+            beneficiary_token_account: self.beneficiary_token_account,
+            vesting_contract: self.vesting_contract,
+            start_time: self.start_time,
+            extra,
+        }
+    }
+
+    pub fn claim_ix(&self) -> Instruction {
+        claim_tokens_ix(
+            &self.vesting_contract,
+            &self.beneficiary.pubkey(),
+            &self.beneficiary_token_account,
+            &self.mint,
+            &TOKEN_PROGRAM_ID,
+            &[],
+        )
+    }
+}
+
+/// Troca a conta `audit_log` pelo marcador de conta opcional ausente.
+pub fn without_audit_log(mut instruction: Instruction, vesting_contract: &Pubkey) -> Instruction {
+    let audit_log = audit_log_address(vesting_contract);
+    for meta in instruction.accounts.iter_mut() {
+        if meta.pubkey == audit_log {
+            *meta = AccountMeta::new_readonly(verum_vesting::ID, false);
+        }
+    }
+    instruction
+}
+
+pub struct CreateVestingArgs {
+    pub creator: Pubkey,
+    pub beneficiary: Pubkey,
+    pub mint: Pubkey,
+    pub token_program: Pubkey,
+    pub sender_token_account: Pubkey,
+    pub contract_id: u64,
+    pub total_amount: u64,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub vesting_type: VestingType,
+    pub allowed_mint_flags: u16,
+}
+
+pub fn create_vesting_ix(
+    args: &CreateVestingArgs,
+    remaining_accounts: &[AccountMeta],
+) -> Instruction {
+    let vesting_contract = vesting_address(&args.creator, &args.mint, args.contract_id);
+    let mut accounts = verum_vesting::accounts::CreateVesting {
+        vesting_contract,
+        creator: args.creator,
+        beneficiary: args.beneficiary,
+        mint: args.mint,
+        escrow_wallet: escrow_address(&vesting_contract),
+        sender_token_account: args.sender_token_account,
+        system_program: solana_sdk::system_program::ID,
+        token_program: args.token_program,
+        audit_log: Some(audit_log_address(&vesting_contract)),
+        event_authority: event_authority_address(),
+        program: verum_vesting::ID,
+    }
+    .to_account_metas(None);
+    accounts.extend_from_slice(remaining_accounts);
+
+    Instruction {
+        program_id: verum_vesting::ID,
+        accounts,
+        data: verum_vesting::instruction::CreateVesting {
+            contract_id: args.contract_id,
+            total_amount: args.total_amount,
+            start_time: args.start_time,
+            end_time: args.end_time,
+            vesting_type: args.vesting_type,
+            allowed_mint_flags: args.allowed_mint_flags,
+        }
+        .data(),
+    }
+}
+
+pub fn claim_tokens_ix(
+    vesting_contract: &Pubkey,
+    beneficiary: &Pubkey,
+    beneficiary_token_account: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+    remaining_accounts: &[AccountMeta],
+) -> Instruction {
+    let mut accounts = verum_vesting::accounts::ClaimTokens {
+        vesting_contract: *vesting_contract,
+        escrow_wallet: Some(escrow_address(vesting_contract)),
+        beneficiary_token_account: Some(*beneficiary_token_account),
+        beneficiary: *beneficiary,
+        mint: Some(*mint),
+        token_program: Some(*token_program),
+        price_trigger: None,
+        price_feed: None,
+        audit_log: Some(audit_log_address(vesting_contract)),
+        release_receipt: None,
+        receipt_payer: None,
+        system_program: None,
+        event_authority: event_authority_address(),
+        program: verum_vesting::ID,
+    }
+    .to_account_metas(None);
+    accounts.extend_from_slice(remaining_accounts);
+
+    Instruction {
+        program_id: verum_vesting::ID,
+        accounts,
+        data: verum_vesting::instruction::ClaimTokens {}.data(),
+    }
+}
+
+pub fn release_receipt_address(vesting_contract: &Pubkey, claim_index: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            verum_vesting::RELEASE_RECEIPT_SEED,
+            vesting_contract.as_ref(),
+            &claim_index.to_le_bytes(),
+        ],
+        &verum_vesting::ID,
+    )
+    .0
+}
+
+/// `claim_tokens` que emite o `ReleaseReceipt` do resgate `claim_index`.
+pub fn claim_with_receipt_ix(
+    vesting_contract: &Pubkey,
+    beneficiary: &Pubkey,
+    beneficiary_token_account: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+    claim_index: u64,
+    receipt_payer: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: verum_vesting::ID,
+        accounts: verum_vesting::accounts::ClaimTokens {
+            vesting_contract: *vesting_contract,
+            escrow_wallet: Some(escrow_address(vesting_contract)),
+            beneficiary_token_account: Some(*beneficiary_token_account),
+            beneficiary: *beneficiary,
+            mint: Some(*mint),
+            token_program: Some(*token_program),
+            price_trigger: None,
+            price_feed: None,
+            audit_log: Some(audit_log_address(vesting_contract)),
+            release_receipt: Some(release_receipt_address(vesting_contract, claim_index)),
+            receipt_payer: Some(*receipt_payer),
+            system_program: Some(solana_sdk::system_program::ID),
+            event_authority: event_authority_address(),
+            program: verum_vesting::ID,
+        }
+        .to_account_metas(None),
+        data: verum_vesting::instruction::ClaimTokens {}.data(),
+    }
+}
+
+pub fn update_beneficiary_ix(
+    vesting_contract: &Pubkey,
+    creator: &Pubkey,
+    new_beneficiary: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: verum_vesting::ID,
+        accounts: verum_vesting::accounts::UpdateBeneficiary {
+            vesting_contract: *vesting_contract,
+            creator: *creator,
+            new_beneficiary: *new_beneficiary,
+            audit_log: Some(audit_log_address(vesting_contract)),
+            event_authority: event_authority_address(),
+            program: verum_vesting::ID,
+        }
+        .to_account_metas(None),
+        data: verum_vesting::instruction::UpdateBeneficiary {}.data(),
+    }
+}
+
+pub fn cancel_vesting_ix(
+    vesting_contract: &Pubkey,
+    creator: &Pubkey,
+    creator_token_account: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+    remaining_accounts: &[AccountMeta],
+) -> Instruction {
+    let mut accounts = verum_vesting::accounts::CancelVesting {
+        vesting_contract: *vesting_contract,
+        creator: *creator,
+        escrow_wallet: Some(escrow_address(vesting_contract)),
+        creator_token_account: Some(*creator_token_account),
+        mint: Some(*mint),
+        token_program: Some(*token_program),
+        audit_log: Some(audit_log_address(vesting_contract)),
+        event_authority: event_authority_address(),
+        program: verum_vesting::ID,
+    }
+    .to_account_metas(None);
+    accounts.extend_from_slice(remaining_accounts);
+
+    Instruction {
+        program_id: verum_vesting::ID,
+        accounts,
+        data: verum_vesting::instruction::CancelVesting {}.data(),
+    }
+}
+
+pub fn set_pause_policy_ix(
+    vesting_contract: &Pubkey,
+    creator: &Pubkey,
+    beneficiary: &Pubkey,
+    pause_policy: u8,
+) -> Instruction {
+    Instruction {
+        program_id: verum_vesting::ID,
+        accounts: verum_vesting::accounts::SetPausePolicy {
+            vesting_contract: *vesting_contract,
+            creator: *creator,
+            beneficiary: *beneficiary,
+        }
+        .to_account_metas(None),
+        data: verum_vesting::instruction::SetPausePolicy { pause_policy }.data(),
+    }
+}
+
+pub fn pause_vesting_ix(vesting_contract: &Pubkey, authority: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: verum_vesting::ID,
+        accounts: verum_vesting::accounts::PauseVesting {
+            vesting_contract: *vesting_contract,
+            authority: *authority,
+        }
+        .to_account_metas(None),
+        data: verum_vesting::instruction::PauseVesting {}.data(),
+    }
+}
+
+pub fn resume_vesting_ix(vesting_contract: &Pubkey, authority: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: verum_vesting::ID,
+        accounts: verum_vesting::accounts::PauseVesting {
+            vesting_contract: *vesting_contract,
+            authority: *authority,
+        }
+        .to_account_metas(None),
+        data: verum_vesting::instruction::ResumeVesting {}.data(),
+    }
+}
+
+pub fn create_milestone_schedule_ix(
+    vesting_contract: &Pubkey,
+    creator: &Pubkey,
+    milestones: Vec<verum_vesting::MilestoneInit>,
+    deadline: i64,
+) -> Instruction {
+    Instruction {
+        program_id: verum_vesting::ID,
+        accounts: verum_vesting::accounts::CreateMilestoneSchedule {
+            milestone_schedule: milestone_schedule_address(vesting_contract),
+            vesting_contract: *vesting_contract,
+            creator: *creator,
+            system_program: solana_sdk::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: verum_vesting::instruction::CreateMilestoneSchedule {
+            milestones,
+            deadline,
+        }
+        .data(),
+    }
+}
+
+pub fn approve_milestone_ix(
+    vesting_contract: &Pubkey,
+    attestor: &Pubkey,
+    index: u8,
+) -> Instruction {
+    Instruction {
+        program_id: verum_vesting::ID,
+        accounts: verum_vesting::accounts::ApproveMilestone {
+            milestone_schedule: milestone_schedule_address(vesting_contract),
+            vesting_contract: *vesting_contract,
+            attestor: *attestor,
+        }
+        .to_account_metas(None),
+        data: verum_vesting::instruction::ApproveMilestone { index }.data(),
+    }
+}
+
+pub fn refund_expired_milestones_ix(
+    vesting_contract: &Pubkey,
+    creator: &Pubkey,
+    creator_token_account: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+    remaining_accounts: &[AccountMeta],
+) -> Instruction {
+    let mut accounts = verum_vesting::accounts::RefundExpiredMilestones {
+        milestone_schedule: milestone_schedule_address(vesting_contract),
+        vesting_contract: *vesting_contract,
+        creator: *creator,
+        escrow_wallet: Some(escrow_address(vesting_contract)),
+        creator_token_account: Some(*creator_token_account),
+        mint: Some(*mint),
+        token_program: Some(*token_program),
+        audit_log: Some(audit_log_address(vesting_contract)),
+        event_authority: event_authority_address(),
+        program: verum_vesting::ID,
+    }
+    .to_account_metas(None);
+    accounts.extend_from_slice(remaining_accounts);
+
+    Instruction {
+        program_id: verum_vesting::ID,
+        accounts,
+        data: verum_vesting::instruction::RefundExpiredMilestones {}.data(),
+    }
+}
+
+/// `claim_tokens` de um contrato `PriceTriggered`, incluindo o gatilho e o feed.
+pub fn claim_price_triggered_ix(
+    vesting_contract: &Pubkey,
+    beneficiary: &Pubkey,
+    beneficiary_token_account: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+    price_feed: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: verum_vesting::ID,
+        accounts: verum_vesting::accounts::ClaimTokens {
+            vesting_contract: *vesting_contract,
+            escrow_wallet: Some(escrow_address(vesting_contract)),
+            beneficiary_token_account: Some(*beneficiary_token_account),
+            beneficiary: *beneficiary,
+            mint: Some(*mint),
+            token_program: Some(*token_program),
+            price_trigger: Some(price_trigger_address(vesting_contract)),
+            price_feed: Some(*price_feed),
+            audit_log: Some(audit_log_address(vesting_contract)),
+            release_receipt: None,
+            receipt_payer: None,
+            system_program: None,
+            event_authority: event_authority_address(),
+            program: verum_vesting::ID,
+        }
+        .to_account_metas(None),
+        data: verum_vesting::instruction::ClaimTokens {}.data(),
+    }
+}
+
+pub fn create_price_trigger_ix(
+    vesting_contract: &Pubkey,
+    creator: &Pubkey,
+    price_feed: &Pubkey,
+    config: verum_vesting::PriceTriggerConfig,
+    tranches: Vec<verum_vesting::PriceTrancheInit>,
+) -> Instruction {
+    Instruction {
+        program_id: verum_vesting::ID,
+        accounts: verum_vesting::accounts::CreatePriceTrigger {
+            price_trigger: price_trigger_address(vesting_contract),
+            vesting_contract: *vesting_contract,
+            price_feed: *price_feed,
+            creator: *creator,
+            system_program: solana_sdk::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: verum_vesting::instruction::CreatePriceTrigger { config, tranches }.data(),
+    }
+}
+
+pub fn refresh_price_trigger_ix(vesting_contract: &Pubkey, price_feed: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: verum_vesting::ID,
+        accounts: verum_vesting::accounts::RefreshPriceTrigger {
+            price_trigger: price_trigger_address(vesting_contract),
+            vesting_contract: *vesting_contract,
+            price_feed: *price_feed,
+        }
+        .to_account_metas(None),
+        data: verum_vesting::instruction::RefreshPriceTrigger {}.data(),
+    }
+}
+
+pub fn native_vesting_address(creator: &Pubkey, contract_id: u64) -> Pubkey {
+    vesting_address(
+        creator,
+        &anchor_spl::token::spl_token::native_mint::ID,
+        contract_id,
+    )
+}
+
+pub fn create_native_vesting_ix(
+    creator: &Pubkey,
+    beneficiary: &Pubkey,
+    contract_id: u64,
+    total_amount: u64,
+    start_time: i64,
+    end_time: i64,
+    vesting_type: VestingType,
+) -> Instruction {
+    let vesting_contract = native_vesting_address(creator, contract_id);
+    Instruction {
+        program_id: verum_vesting::ID,
+        accounts: verum_vesting::accounts::CreateNativeVesting {
+            vesting_contract,
+            creator: *creator,
+            beneficiary: *beneficiary,
+            system_program: solana_sdk::system_program::ID,
+            audit_log: Some(audit_log_address(&vesting_contract)),
+            event_authority: event_authority_address(),
+            program: verum_vesting::ID,
+        }
+        .to_account_metas(None),
+        data: verum_vesting::instruction::CreateNativeVesting {
+            contract_id,
+            total_amount,
+            start_time,
+            end_time,
+            vesting_type,
+        }
+        .data(),
+    }
+}
+
+/// `claim_tokens` de um contrato nativo: sem contas de token e com o
+/// beneficiario writable para receber os lamports.
+pub fn claim_native_ix(vesting_contract: &Pubkey, beneficiary: &Pubkey) -> Instruction {
+    let mut accounts = verum_vesting::accounts::ClaimTokens {
+        vesting_contract: *vesting_contract,
+        escrow_wallet: None,
+        beneficiary_token_account: None,
+        beneficiary: *beneficiary,
+        mint: None,
+        token_program: None,
+        price_trigger: None,
+        price_feed: None,
+        audit_log: Some(audit_log_address(vesting_contract)),
+        release_receipt: None,
+        receipt_payer: None,
+        system_program: None,
+        event_authority: event_authority_address(),
+        program: verum_vesting::ID,
+    }
+    .to_account_metas(None);
+    for meta in accounts.iter_mut() {
+        if meta.pubkey == *beneficiary {
+            meta.is_writable = true;
+        }
+    }
+
+    Instruction {
+        program_id: verum_vesting::ID,
+        accounts,
+        data: verum_vesting::instruction::ClaimTokens {}.data(),
+    }
+}
+
+pub fn cancel_native_ix(vesting_contract: &Pubkey, creator: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: verum_vesting::ID,
+        accounts: verum_vesting::accounts::CancelVesting {
+            vesting_contract: *vesting_contract,
+            creator: *creator,
+            escrow_wallet: None,
+            creator_token_account: None,
+            mint: None,
+            token_program: None,
+            audit_log: Some(audit_log_address(vesting_contract)),
+            event_authority: event_authority_address(),
+            program: verum_vesting::ID,
+        }
+        .to_account_metas(None),
+        data: verum_vesting::instruction::CancelVesting {}.data(),
+    }
+}
+
+pub struct CreatePoolArgs {
+    pub creator: Pubkey,
+    pub mint: Pubkey,
+    pub token_program: Pubkey,
+    pub sender_token_account: Pubkey,
+    pub pool_id: u64,
+    pub total_amount: u64,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub vesting_type: VestingType,
+    pub allowed_mint_flags: u16,
+    pub members: Vec<verum_vesting::PoolMemberInit>,
+}
+
+pub fn create_vesting_pool_ix(
+    args: &CreatePoolArgs,
+    remaining_accounts: &[AccountMeta],
+) -> Instruction {
+    let pool = pool_address(&args.creator, &args.mint, args.pool_id);
+    let mut accounts = verum_vesting::accounts::CreateVestingPool {
+        pool,
+        creator: args.creator,
+        mint: args.mint,
+        escrow_wallet: escrow_address(&pool),
+        sender_token_account: args.sender_token_account,
+        system_program: solana_sdk::system_program::ID,
+        token_program: args.token_program,
+    }
+    .to_account_metas(None);
+    accounts.extend_from_slice(remaining_accounts);
+
+    Instruction {
+        program_id: verum_vesting::ID,
+        accounts,
+        data: verum_vesting::instruction::CreateVestingPool {
+            pool_id: args.pool_id,
+            total_amount: args.total_amount,
+            start_time: args.start_time,
+            end_time: args.end_time,
+            vesting_type: args.vesting_type,
+            allowed_mint_flags: args.allowed_mint_flags,
+            members: args.members.clone(),
+        }
+        .data(),
+    }
+}
+
+pub fn claim_pool_share_ix(
+    pool: &Pubkey,
+    beneficiary: &Pubkey,
+    beneficiary_token_account: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+    remaining_accounts: &[AccountMeta],
+) -> Instruction {
+    let mut accounts = verum_vesting::accounts::ClaimPoolShare {
+        pool: *pool,
+        escrow_wallet: escrow_address(pool),
+        beneficiary_token_account: *beneficiary_token_account,
+        beneficiary: *beneficiary,
+        mint: *mint,
+        token_program: *token_program,
+    }
+    .to_account_metas(None);
+    accounts.extend_from_slice(remaining_accounts);
+
+    Instruction {
+        program_id: verum_vesting::ID,
+        accounts,
+        data: verum_vesting::instruction::ClaimPoolShare {}.data(),
+    }
+}
+
+pub fn reassign_pool_share_ix(
+    pool: &Pubkey,
+    creator: &Pubkey,
+    departed: &Pubkey,
+    new_beneficiary: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: verum_vesting::ID,
+        accounts: verum_vesting::accounts::ReassignPoolShare {
+            pool: *pool,
+            creator: *creator,
+        }
+        .to_account_metas(None),
+        data: verum_vesting::instruction::ReassignPoolShare {
+            departed: *departed,
+            new_beneficiary: *new_beneficiary,
+        }
+        .data(),
+    }
+}
+
+pub fn sweep_pool_dust_ix(
+    pool: &Pubkey,
+    creator: &Pubkey,
+    creator_token_account: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: verum_vesting::ID,
+        accounts: verum_vesting::accounts::SweepPoolDust {
+            pool: *pool,
+            creator: *creator,
+            escrow_wallet: escrow_address(pool),
+            creator_token_account: *creator_token_account,
+            mint: *mint,
+            token_program: *token_program,
+        }
+        .to_account_metas(None),
+        data: verum_vesting::instruction::SweepPoolDust {}.data(),
+    }
+}
+
+pub struct CreateStreamArgs {
+    pub payer: Pubkey,
+    pub recipient: Pubkey,
+    pub mint: Pubkey,
+    pub token_program: Pubkey,
+    pub payer_token_account: Pubkey,
+    pub stream_id: u64,
+    pub rate_per_second: u64,
+    pub start_time: i64,
+    pub initial_deposit: u64,
+    pub allowed_mint_flags: u16,
+}
+
+pub fn create_stream_ix(
+    args: &CreateStreamArgs,
+    remaining_accounts: &[AccountMeta],
+) -> Instruction {
+    let stream = stream_address(&args.payer, &args.mint, args.stream_id);
+    let mut accounts = verum_vesting::accounts::CreateStream {
+        stream,
+        payer: args.payer,
+        recipient: args.recipient,
+        mint: args.mint,
+        escrow_wallet: escrow_address(&stream),
+        payer_token_account: args.payer_token_account,
+        system_program: solana_sdk::system_program::ID,
+        token_program: args.token_program,
+    }
+    .to_account_metas(None);
+    accounts.extend_from_slice(remaining_accounts);
+
+    Instruction {
+        program_id: verum_vesting::ID,
+        accounts,
+        data: verum_vesting::instruction::CreateStream {
+            stream_id: args.stream_id,
+            rate_per_second: args.rate_per_second,
+            start_time: args.start_time,
+            initial_deposit: args.initial_deposit,
+            allowed_mint_flags: args.allowed_mint_flags,
+        }
+        .data(),
+    }
+}
+
+pub fn top_up_stream_ix(
+    stream: &Pubkey,
+    payer: &Pubkey,
+    payer_token_account: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+    amount: u64,
+    remaining_accounts: &[AccountMeta],
+) -> Instruction {
+    let mut accounts = verum_vesting::accounts::TopUpStream {
+        stream: *stream,
+        payer: *payer,
+        escrow_wallet: escrow_address(stream),
+        payer_token_account: *payer_token_account,
+        mint: *mint,
+        token_program: *token_program,
+    }
+    .to_account_metas(None);
+    accounts.extend_from_slice(remaining_accounts);
+
+    Instruction {
+        program_id: verum_vesting::ID,
+        accounts,
+        data: verum_vesting::instruction::TopUpStream { amount }.data(),
+    }
+}
+
+pub fn withdraw_from_stream_ix(
+    stream: &Pubkey,
+    recipient: &Pubkey,
+    recipient_token_account: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+    remaining_accounts: &[AccountMeta],
+) -> Instruction {
+    let mut accounts = verum_vesting::accounts::WithdrawFromStream {
+        stream: *stream,
+        escrow_wallet: escrow_address(stream),
+        recipient_token_account: *recipient_token_account,
+        recipient: *recipient,
+        mint: *mint,
+        token_program: *token_program,
+    }
+    .to_account_metas(None);
+    accounts.extend_from_slice(remaining_accounts);
+
+    Instruction {
+        program_id: verum_vesting::ID,
+        accounts,
+        data: verum_vesting::instruction::WithdrawFromStream {}.data(),
+    }
+}
+
+pub struct CancelStreamArgs {
+    pub payer: Pubkey,
+    pub recipient: Pubkey,
+    pub payer_token_account: Pubkey,
+    pub recipient_token_account: Pubkey,
+    pub mint: Pubkey,
+    pub token_program: Pubkey,
+}
+
+pub fn cancel_stream_ix(
+    stream: &Pubkey,
+    args: &CancelStreamArgs,
+    remaining_accounts: &[AccountMeta],
+) -> Instruction {
+    let mut accounts = verum_vesting::accounts::CancelStream {
+        stream: *stream,
+        payer: args.payer,
+        escrow_wallet: escrow_address(stream),
+        payer_token_account: args.payer_token_account,
+        recipient_token_account: args.recipient_token_account,
+        recipient: args.recipient,
+        mint: args.mint,
+        token_program: args.token_program,
+    }
+    .to_account_metas(None);
+    accounts.extend_from_slice(remaining_accounts);
+
+    Instruction {
+        program_id: verum_vesting::ID,
+        accounts,
+        data: verum_vesting::instruction::CancelStream {}.data(),
+    }
+}
+
+pub fn registrar_address(realm: &Pubkey, governing_token_mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"registrar", realm.as_ref(), governing_token_mint.as_ref()],
+        &verum_vesting::ID,
+    )
+    .0
+}
+
+pub fn voter_weight_record_address(registrar: &Pubkey, governing_token_owner: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            b"voter-weight-record",
+            registrar.as_ref(),
+            governing_token_owner.as_ref(),
+        ],
+        &verum_vesting::ID,
+    )
+    .0
+}
+
+pub fn create_registrar_ix(
+    realm: &Pubkey,
+    governance_program: &Pubkey,
+    governing_token_mint: &Pubkey,
+    realm_authority: &Pubkey,
+    payer: &Pubkey,
+    lockup_bonus_bps: u16,
+    lockup_saturation_secs: i64,
+) -> Instruction {
+    Instruction {
+        program_id: verum_vesting::ID,
+        accounts: verum_vesting::accounts::CreateRegistrar {
+            registrar: registrar_address(realm, governing_token_mint),
+            realm: *realm,
+            governance_program_id: *governance_program,
+            governing_token_mint: *governing_token_mint,
+            realm_authority: *realm_authority,
+            payer: *payer,
+            system_program: solana_sdk::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: verum_vesting::instruction::CreateRegistrar {
+            lockup_bonus_bps,
+            lockup_saturation_secs,
+        }
+        .data(),
+    }
+}
+
+pub fn create_voter_weight_record_ix(
+    registrar: &Pubkey,
+    governing_token_owner: &Pubkey,
+    payer: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: verum_vesting::ID,
+        accounts: verum_vesting::accounts::CreateVoterWeightRecord {
+            registrar: *registrar,
+            voter_weight_record: voter_weight_record_address(registrar, governing_token_owner),
+            governing_token_owner: *governing_token_owner,
+            payer: *payer,
+            system_program: solana_sdk::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: verum_vesting::instruction::CreateVoterWeightRecord {}.data(),
+    }
+}
+
+/// `update_voter_weight_record` com os contratos do votante em `remaining_accounts`.
+pub fn update_voter_weight_record_ix(
+    registrar: &Pubkey,
+    governing_token_owner: &Pubkey,
+    vesting_contracts: &[Pubkey],
+) -> Instruction {
+    let mut accounts = verum_vesting::accounts::UpdateVoterWeightRecord {
+        registrar: *registrar,
+        voter_weight_record: voter_weight_record_address(registrar, governing_token_owner),
+    }
+    .to_account_metas(None);
+    accounts.extend(
+        vesting_contracts
+            .iter()
+            .map(|contract| AccountMeta::new_readonly(*contract, false)),
+    );
+
+    Instruction {
+        program_id: verum_vesting::ID,
+        accounts,
+        data: verum_vesting::instruction::UpdateVoterWeightRecord {}.data(),
+    }
+}
+
+pub fn stake_record_address(vesting_contract: &Pubkey, mint: &Pubkey) -> Pubkey {
+    mock_staking::get_stake_record_address(vesting_contract, mint).0
+}
+
+pub fn set_staking_program_ix(
+    vesting_contract: &Pubkey,
+    creator: &Pubkey,
+    staking_program: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: verum_vesting::ID,
+        accounts: verum_vesting::accounts::SetStakingProgram {
+            vesting_contract: *vesting_contract,
+            creator: *creator,
+        }
+        .to_account_metas(None),
+        data: verum_vesting::instruction::SetStakingProgram {
+            staking_program: *staking_program,
+        }
+        .data(),
+    }
+}
+
+/// `stake_locked` contra o `mock_staking`.
+pub fn stake_locked_ix(
+    vesting_contract: &Pubkey,
+    beneficiary: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    Instruction {
+        program_id: verum_vesting::ID,
+        accounts: verum_vesting::accounts::StakeLocked {
+            vesting_contract: *vesting_contract,
+            beneficiary: *beneficiary,
+            escrow_wallet: escrow_address(vesting_contract),
+            mint: *mint,
+            token_program: *token_program,
+            staking_program: mock_staking::ID,
+            stake_record: stake_record_address(vesting_contract, mint),
+            stake_vault: mock_staking::get_vault_address(mint).0,
+            system_program: solana_sdk::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: verum_vesting::instruction::StakeLocked { amount }.data(),
+    }
+}
+
+/// `unstake_locked` contra o `mock_staking`.
+pub fn unstake_locked_ix(
+    vesting_contract: &Pubkey,
+    authority: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    Instruction {
+        program_id: verum_vesting::ID,
+        accounts: verum_vesting::accounts::UnstakeLocked {
+            vesting_contract: *vesting_contract,
+            authority: *authority,
+            escrow_wallet: escrow_address(vesting_contract),
+            mint: *mint,
+            token_program: *token_program,
+            staking_program: mock_staking::ID,
+            stake_record: stake_record_address(vesting_contract, mint),
+            stake_vault: mock_staking::get_vault_address(mint).0,
+            vault_authority: mock_staking::get_vault_authority_address().0,
+        }
+        .to_account_metas(None),
+        data: verum_vesting::instruction::UnstakeLocked { amount }.data(),
+    }
+}
+
+/// `claim_staking_rewards` contra o `mock_staking`.
+pub fn claim_staking_rewards_ix(
+    vesting_contract: &Pubkey,
+    beneficiary: &Pubkey,
+    beneficiary_token_account: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: verum_vesting::ID,
+        accounts: verum_vesting::accounts::ClaimStakingRewards {
+            vesting_contract: *vesting_contract,
+            beneficiary: *beneficiary,
+            beneficiary_token_account: *beneficiary_token_account,
+            mint: *mint,
+            token_program: *token_program,
+            staking_program: mock_staking::ID,
+            stake_record: stake_record_address(vesting_contract, mint),
+            stake_vault: mock_staking::get_vault_address(mint).0,
+            vault_authority: mock_staking::get_vault_authority_address().0,
+        }
+        .to_account_metas(None),
+        data: verum_vesting::instruction::ClaimStakingRewards {}.data(),
+    }
+}
+
+pub fn set_compliance_authority_ix(
+    vesting_contract: &Pubkey,
+    creator: &Pubkey,
+    beneficiary: &Pubkey,
+    compliance_authority: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: verum_vesting::ID,
+        accounts: verum_vesting::accounts::SetComplianceAuthority {
+            vesting_contract: *vesting_contract,
+            creator: *creator,
+            beneficiary: *beneficiary,
+        }
+        .to_account_metas(None),
+        data: verum_vesting::instruction::SetComplianceAuthority {
+            compliance_authority: *compliance_authority,
+        }
+        .data(),
+    }
+}
+
+pub struct ClawbackArgs {
+    pub compliance_authority: Pubkey,
+    pub destination: Pubkey,
+    pub destination_token_account: Pubkey,
+    pub mint: Pubkey,
+    pub token_program: Pubkey,
+    pub amount: u64,
+    pub reason_code: u8,
+    pub memo: String,
+}
+
+pub fn clawback_ix(vesting_contract: &Pubkey, args: &ClawbackArgs) -> Instruction {
+    Instruction {
+        program_id: verum_vesting::ID,
+        accounts: verum_vesting::accounts::ClawbackTokens {
+            vesting_contract: *vesting_contract,
+            compliance_authority: args.compliance_authority,
+            destination: args.destination,
+            escrow_wallet: Some(escrow_address(vesting_contract)),
+            destination_token_account: Some(args.destination_token_account),
+            mint: Some(args.mint),
+            token_program: Some(args.token_program),
+            audit_log: Some(audit_log_address(vesting_contract)),
+            event_authority: event_authority_address(),
+            program: verum_vesting::ID,
+        }
+        .to_account_metas(None),
+        data: verum_vesting::instruction::Clawback {
+            amount: args.amount,
+            reason_code: args.reason_code,
+            memo: args.memo.clone(),
+        }
+        .data(),
+    }
+}
+
+pub fn pending_action_address(vesting_contract: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"pending_action", vesting_contract.as_ref()],
+        &verum_vesting::ID,
+    )
+    .0
+}
+
+pub fn set_timelock_ix(
+    vesting_contract: &Pubkey,
+    creator: &Pubkey,
+    beneficiary: &Pubkey,
+    delay: i64,
+    guardian: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: verum_vesting::ID,
+        accounts: verum_vesting::accounts::SetTimelock {
+            vesting_contract: *vesting_contract,
+            creator: *creator,
+            beneficiary: *beneficiary,
+        }
+        .to_account_metas(None),
+        data: verum_vesting::instruction::SetTimelock {
+            delay,
+            guardian: *guardian,
+        }
+        .data(),
+    }
+}
+
+pub fn propose_action_ix(
+    vesting_contract: &Pubkey,
+    creator: &Pubkey,
+    action: verum_vesting::TimelockedAction,
+) -> Instruction {
+    Instruction {
+        program_id: verum_vesting::ID,
+        accounts: verum_vesting::accounts::ProposeAction {
+            pending_action: pending_action_address(vesting_contract),
+            vesting_contract: *vesting_contract,
+            creator: *creator,
+            system_program: solana_sdk::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: verum_vesting::instruction::ProposeAction { action }.data(),
+    }
+}
+
+pub fn execute_action_ix(
+    vesting_contract: &Pubkey,
+    creator: &Pubkey,
+    creator_token_account: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: verum_vesting::ID,
+        accounts: verum_vesting::accounts::ExecuteAction {
+            pending_action: pending_action_address(vesting_contract),
+            vesting_contract: *vesting_contract,
+            creator: *creator,
+            escrow_wallet: Some(escrow_address(vesting_contract)),
+            creator_token_account: Some(*creator_token_account),
+            mint: Some(*mint),
+            token_program: Some(*token_program),
+            audit_log: Some(audit_log_address(vesting_contract)),
+            event_authority: event_authority_address(),
+            program: verum_vesting::ID,
+        }
+        .to_account_metas(None),
+        data: verum_vesting::instruction::ExecuteAction {}.data(),
+    }
+}
+
+pub fn veto_action_ix(
+    vesting_contract: &Pubkey,
+    creator: &Pubkey,
+    authority: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: verum_vesting::ID,
+        accounts: verum_vesting::accounts::VetoAction {
+            pending_action: pending_action_address(vesting_contract),
+            vesting_contract: *vesting_contract,
+            creator: *creator,
+            authority: *authority,
+        }
+        .to_account_metas(None),
+        data: verum_vesting::instruction::VetoAction {}.data(),
+    }
+}
+
+pub fn withdraw_action_ix(vesting_contract: &Pubkey, creator: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: verum_vesting::ID,
+        accounts: verum_vesting::accounts::WithdrawAction {
+            pending_action: pending_action_address(vesting_contract),
+            vesting_contract: *vesting_contract,
+            creator: *creator,
+        }
+        .to_account_metas(None),
+        data: verum_vesting::instruction::WithdrawAction {}.data(),
+    }
+}
+
+pub fn multisig_address(payer: &Pubkey, multisig_id: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"multisig", payer.as_ref(), &multisig_id.to_le_bytes()],
+        &verum_vesting::ID,
+    )
+    .0
+}
+
+pub fn multisig_signer_address(multisig: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[verum_vesting::MULTISIG_SIGNER_SEED, multisig.as_ref()],
+        &verum_vesting::ID,
+    )
+    .0
+}
+
+pub fn multisig_proposal_address(multisig: &Pubkey, proposal_id: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            b"multisig_proposal",
+            multisig.as_ref(),
+            &proposal_id.to_le_bytes(),
+        ],
+        &verum_vesting::ID,
+    )
+    .0
+}
+
+pub fn create_multisig_ix(
+    payer: &Pubkey,
+    multisig_id: u64,
+    members: Vec<Pubkey>,
+    threshold: u8,
+) -> Instruction {
+    Instruction {
+        program_id: verum_vesting::ID,
+        accounts: verum_vesting::accounts::CreateMultisig {
+            multisig: multisig_address(payer, multisig_id),
+            payer: *payer,
+            system_program: solana_sdk::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: verum_vesting::instruction::CreateMultisig {
+            multisig_id,
+            members,
+            threshold,
+        }
+        .data(),
+    }
+}
+
+pub fn create_multisig_proposal_ix(
+    multisig: &Pubkey,
+    proposal_id: u64,
+    proposer: &Pubkey,
+    action: verum_vesting::MultisigAction,
+) -> Instruction {
+    Instruction {
+        program_id: verum_vesting::ID,
+        accounts: verum_vesting::accounts::CreateMultisigProposal {
+            proposal: multisig_proposal_address(multisig, proposal_id),
+            multisig: *multisig,
+            proposer: *proposer,
+            system_program: solana_sdk::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: verum_vesting::instruction::CreateMultisigProposal { action }.data(),
+    }
+}
+
+pub fn approve_multisig_proposal_ix(
+    multisig: &Pubkey,
+    proposal_id: u64,
+    member: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: verum_vesting::ID,
+        accounts: verum_vesting::accounts::ApproveMultisigProposal {
+            proposal: multisig_proposal_address(multisig, proposal_id),
+            multisig: *multisig,
+            member: *member,
+        }
+        .to_account_metas(None),
+        data: verum_vesting::instruction::ApproveMultisigProposal {}.data(),
+    }
+}
+
+/// `inner` e a instrucao que o multisig executa como `creator`; suas contas viram
+/// `remaining_accounts`, sem a assinatura do PDA (feita pelo programa).
+pub fn execute_multisig_proposal_ix(
+    multisig: &Pubkey,
+    proposal_id: u64,
+    member: &Pubkey,
+    inner: &Instruction,
+) -> Instruction {
+    let signer = multisig_signer_address(multisig);
+    let mut accounts = verum_vesting::accounts::ExecuteMultisigProposal {
+        proposal: multisig_proposal_address(multisig, proposal_id),
+        multisig: *multisig,
+        member: *member,
+        vesting_program: verum_vesting::ID,
+    }
+    .to_account_metas(None);
+    accounts.extend(inner.accounts.iter().map(|meta| AccountMeta {
+        pubkey: meta.pubkey,
+        is_signer: meta.is_signer && meta.pubkey != signer,
+        is_writable: meta.is_writable,
+    }));
+
+    Instruction {
+        program_id: verum_vesting::ID,
+        accounts,
+        data: verum_vesting::instruction::ExecuteMultisigProposal {}.data(),
+    }
+}
+
+pub fn create_audit_log_ix(vesting_contract: &Pubkey, creator: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: verum_vesting::ID,
+        accounts: verum_vesting::accounts::CreateAuditLog {
+            audit_log: audit_log_address(vesting_contract),
+            vesting_contract: *vesting_contract,
+            creator: *creator,
+            system_program: solana_sdk::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: verum_vesting::instruction::CreateAuditLog {}.data(),
+    }
+}
+
+pub fn read_audit_log_ix(vesting_contract: &Pubkey, start_sequence: u64, limit: u8) -> Instruction {
+    Instruction {
+        program_id: verum_vesting::ID,
+        accounts: verum_vesting::accounts::ReadAuditLog {
+            audit_log: audit_log_address(vesting_contract),
+        }
+        .to_account_metas(None),
+        data: verum_vesting::instruction::ReadAuditLog {
+            start_sequence,
+            limit,
+        }
+        .data(),
+    }
+}
+
+pub fn project_schedule_ix(vesting_contract: &Pubkey, points: u8) -> Instruction {
+    Instruction {
+        program_id: verum_vesting::ID,
+        accounts: verum_vesting::accounts::ProjectSchedule {
+            vesting_contract: *vesting_contract,
+        }
+        .to_account_metas(None),
+        data: verum_vesting::instruction::ProjectSchedule { points }.data(),
+    }
+}
+
+pub fn migrate_contract_ix(vesting_contract: &Pubkey, payer: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: verum_vesting::ID,
+        accounts: verum_vesting::accounts::MigrateContract {
+            vesting_contract: *vesting_contract,
+            payer: *payer,
+            system_program: solana_sdk::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: verum_vesting::instruction::MigrateContract {}.data(),
+    }
+}
@@ -0,0 +1,231 @@
+//! Unidades de computo de cada instrucao, com mints SPL Token e Token-2022, comparadas
+//! com a baseline versionada em `tests/compute_units.baseline`. O teste falha quando
+//! uma instrucao passa do seu orcamento em mais de `MARGIN_PERCENT`%.
+//!
+//! Sem `SBF_OUT_DIR` o programa roda nativamente e so os CPIs (programas de token,
+//! eventos) e as syscalls sao medidos (modo `native`); com `SBF_OUT_DIR` apontando para
+//! a saida do `cargo build-sbf`, o binario e carregado e a medicao e a real (`sbf`).
+//! Para regravar a baseline do modo atual: `CU_BASELINE=update cargo test --test compute_units`.
+
+mod common;
+
+use std::fmt::Write as _;
+
+use common::*;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use verum_vesting::VestingType;
+
+const TOTAL: u64 = 1_000_000;
+const MARGIN_PERCENT: u64 = 10;
+const BASELINE_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/compute_units.baseline");
+
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct Entry {
+    mode: String,
+    instruction: String,
+    token: String,
+    units: u64,
+}
+
+impl Entry {
+    fn same_key(&self, other: &Entry) -> bool {
+        self.mode == other.mode
+            && self.instruction == other.instruction
+            && self.token == other.token
+    }
+}
+
+fn mode() -> &'static str {
+    if std::env::var("SBF_OUT_DIR").is_ok() || std::env::var("BPF_OUT_DIR").is_ok() {
+        "sbf"
+    } else {
+        "native"
+    }
+}
+
+fn token_label(token_program: &Pubkey) -> &'static str {
+    if *token_program == TOKEN_2022_PROGRAM_ID {
+        "token-2022"
+    } else {
+        "spl-token"
+    }
+}
+
+fn load_baseline() -> Vec<Entry> {
+    let contents = std::fs::read_to_string(BASELINE_PATH).unwrap_or_default();
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let [mode, instruction, token, units] = fields[..] else {
+                panic!("linha invalida na baseline: {line}");
+            };
+            Entry {
+                mode: mode.to_string(),
+                instruction: instruction.to_string(),
+                token: token.to_string(),
+                units: units.parse().expect("unidades invalidas na baseline"),
+            }
+        })
+        .collect()
+}
+
+fn write_baseline(mut entries: Vec<Entry>) {
+    entries.sort();
+    let mut contents = String::from(
+        "# Unidades de computo por instrucao, medidas por tests/compute_units.rs.\n\
+         # Regerar com: CU_BASELINE=update cargo test --test compute_units\n\
+         # modo    instrucao           token          unidades\n",
+    );
+    for entry in entries {
+        writeln!(
+            contents,
+            "{:<9} {:<19} {:<14} {}",
+            entry.mode, entry.instruction, entry.token, entry.units
+        )
+        .unwrap();
+    }
+    std::fs::write(BASELINE_PATH, contents).unwrap();
+}
+
+/// Percorre criacao, troca de beneficiario, projecao, resgate e cancelamento de um
+/// contrato com cliff, medindo cada instrucao.
+async fn measure(token_program: Pubkey) -> Vec<Entry> {
+    let mut env = TestEnv::new().await;
+    let creator = env.payer();
+    let mint = env.create_mint(&token_program, 6, None).await;
+    let creator_token_account = env.create_token_account(&mint, &creator.pubkey()).await;
+    env.mint_to(&mint, &creator_token_account, TOTAL).await;
+    let beneficiary = Keypair::new();
+    let beneficiary_token_account = env.create_token_account(&mint, &beneficiary.pubkey()).await;
+
+    let start_time = env.now().await;
+    let args = CreateVestingArgs {
+        creator: creator.pubkey(),
+        beneficiary: Pubkey::new_unique(),
+        mint,
+        token_program,
+        sender_token_account: creator_token_account,
+        contract_id: 1,
+        total_amount: TOTAL,
+        start_time,
+        end_time: start_time + 1_000,
+        vesting_type: VestingType::Cliff(start_time + 250, 20),
+        allowed_mint_flags: 0,
+    };
+    let vesting_contract = vesting_address(&creator.pubkey(), &mint, 1);
+
+    let mut measured = vec![];
+    let mut record = |instruction: &str, units: u64| {
+        measured.push(Entry {
+            mode: mode().to_string(),
+            instruction: instruction.to_string(),
+            token: token_label(&token_program).to_string(),
+            units,
+        });
+    };
+
+    let units = env
+        .process_metered(&[create_vesting_ix(&args, &[])], &[])
+        .await
+        .unwrap();
+    record("create_vesting", units);
+
+    let units = env
+        .process_metered(
+            &[update_beneficiary_ix(
+                &vesting_contract,
+                &creator.pubkey(),
+                &beneficiary.pubkey(),
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+    record("update_beneficiary", units);
+
+    env.set_time(start_time + 500).await;
+    let units = env
+        .process_metered(&[project_schedule_ix(&vesting_contract, 16)], &[])
+        .await
+        .unwrap();
+    record("project_schedule", units);
+
+    let units = env
+        .process_metered(
+            &[claim_tokens_ix(
+                &vesting_contract,
+                &beneficiary.pubkey(),
+                &beneficiary_token_account,
+                &mint,
+                &token_program,
+                &[],
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+    record("claim_tokens", units);
+
+    let units = env
+        .process_metered(
+            &[cancel_vesting_ix(
+                &vesting_contract,
+                &creator.pubkey(),
+                &creator_token_account,
+                &mint,
+                &token_program,
+                &[],
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+    record("cancel_vesting", units);
+
+    measured
+}
+
+#[tokio::test]
+async fn instructions_stay_within_compute_budget() {
+    let mut measured = measure(TOKEN_PROGRAM_ID).await;
+    measured.extend(measure(TOKEN_2022_PROGRAM_ID).await);
+    let mut baseline = load_baseline();
+
+    if std::env::var("CU_BASELINE").as_deref() == Ok("update") {
+        baseline.retain(|entry| !measured.iter().any(|new| new.same_key(entry)));
+        baseline.extend(measured);
+        write_baseline(baseline);
+        return;
+    }
+
+    let mut failures = vec![];
+    for entry in &measured {
+        let Some(budget) = baseline.iter().find(|known| known.same_key(entry)) else {
+            failures.push(format!(
+                "{} {} ({}): sem baseline, medido {}",
+                entry.mode, entry.instruction, entry.token, entry.units
+            ));
+            continue;
+        };
+        let limit = budget.units + budget.units * MARGIN_PERCENT / 100;
+        println!(
+            "{:<9} {:<19} {:<14} {:>7} (baseline {})",
+            entry.mode, entry.instruction, entry.token, entry.units, budget.units
+        );
+        if entry.units > limit {
+            failures.push(format!(
+                "{} {} ({}): {} unidades, acima do limite de {} (baseline {} + {MARGIN_PERCENT}%)",
+                entry.mode, entry.instruction, entry.token, entry.units, limit, budget.units
+            ));
+        }
+    }
+    assert!(
+        failures.is_empty(),
+        "orcamento de computo excedido; se o aumento for esperado, rode com CU_BASELINE=update:\n{}",
+        failures.join("\n")
+    );
+}
@@ -0,0 +1,109 @@
+mod common;
+
+use common::*;
+use solana_sdk::signature::{Keypair, Signer};
+use verum_vesting::{
+    BeneficiaryUpdated, TokensClaimed, VestingCancelled, VestingCreated, VestingType,
+};
+
+const TOTAL: u64 = 1_000_000;
+
+#[tokio::test]
+async fn lifecycle_events_are_emitted_as_inner_instructions() {
+    let mut env = TestEnv::new().await;
+    let creator = env.payer();
+    let beneficiary = Keypair::new();
+    let mint = env.create_mint(&TOKEN_PROGRAM_ID, 6, None).await;
+    let creator_token_account = env.create_token_account(&mint, &creator.pubkey()).await;
+    env.mint_to(&mint, &creator_token_account, TOTAL).await;
+
+    let start_time = env.now().await;
+    let args = CreateVestingArgs {
+        creator: creator.pubkey(),
+        beneficiary: beneficiary.pubkey(),
+        mint,
+        token_program: TOKEN_PROGRAM_ID,
+        sender_token_account: creator_token_account,
+        contract_id: 1,
+        total_amount: TOTAL,
+        start_time,
+        end_time: start_time + 1_000,
+        vesting_type: VestingType::Linear,
+        allowed_mint_flags: 0,
+    };
+    let vesting_contract = vesting_address(&creator.pubkey(), &mint, 1);
+    env.process(&[create_vesting_ix(&args, &[])], &[])
+        .await
+        .unwrap();
+
+    let new_beneficiary = Keypair::new();
+    let new_beneficiary_token_account = env
+        .create_token_account(&mint, &new_beneficiary.pubkey())
+        .await;
+    let update = update_beneficiary_ix(
+        &vesting_contract,
+        &creator.pubkey(),
+        &new_beneficiary.pubkey(),
+    );
+    env.process(&[update], &[]).await.unwrap();
+
+    env.set_time(start_time + 400).await;
+    let claim = claim_tokens_ix(
+        &vesting_contract,
+        &new_beneficiary.pubkey(),
+        &new_beneficiary_token_account,
+        &mint,
+        &TOKEN_PROGRAM_ID,
+        &[],
+    );
+    env.process(&[claim], &[]).await.unwrap();
+
+    let cancel = cancel_vesting_ix(
+        &vesting_contract,
+        &creator.pubkey(),
+        &creator_token_account,
+        &mint,
+        &TOKEN_PROGRAM_ID,
+        &[],
+    );
+    env.process(&[cancel], &[]).await.unwrap();
+
+    let created: Vec<VestingCreated> = cpi_events::<VestingCreated>()
+        .into_iter()
+        .filter(|event| event.vesting_contract == vesting_contract)
+        .collect();
+    assert_eq!(created.len(), 1);
+    assert_eq!(created[0].beneficiary, beneficiary.pubkey());
+    assert_eq!(created[0].total_amount, TOTAL);
+    assert_eq!(created[0].end_time, start_time + 1_000);
+    assert!(created[0].vesting_type == VestingType::Linear);
+
+    let updated: Vec<BeneficiaryUpdated> = cpi_events::<BeneficiaryUpdated>()
+        .into_iter()
+        .filter(|event| event.vesting_contract == vesting_contract)
+        .collect();
+    assert_eq!(updated.len(), 1);
+    assert_eq!(updated[0].old_beneficiary, beneficiary.pubkey());
+    assert_eq!(updated[0].new_beneficiary, new_beneficiary.pubkey());
+
+    let claimed: Vec<TokensClaimed> = cpi_events::<TokensClaimed>()
+        .into_iter()
+        .filter(|event| event.vesting_contract == vesting_contract)
+        .collect();
+    assert_eq!(claimed.len(), 1);
+    assert_eq!(claimed[0].amount, 400_000);
+    assert_eq!(claimed[0].released_amount, 400_000);
+    assert_eq!(claimed[0].remaining_amount, 600_000);
+    assert_eq!(claimed[0].start_time, start_time);
+    assert_eq!(claimed[0].timestamp, start_time + 400);
+    assert!(claimed[0].slot > 0);
+
+    let cancelled: Vec<VestingCancelled> = cpi_events::<VestingCancelled>()
+        .into_iter()
+        .filter(|event| event.vesting_contract == vesting_contract)
+        .collect();
+    assert_eq!(cancelled.len(), 1);
+    assert_eq!(cancelled[0].released_amount, 400_000);
+    assert_eq!(cancelled[0].remaining_amount, 600_000);
+    assert_eq!(env.token_balance(&creator_token_account).await, 600_000);
+}
@@ -0,0 +1,276 @@
+//! Fuzzer com estado: sequencias pseudoaleatorias (semente fixa) de resgates, pausas,
+//! trocas de beneficiario, clawbacks, cancelamentos e saltos de relogio sobre um
+//! contrato real. Apos cada passo, bem-sucedido ou nao, o saldo do escrow deve ser
+//! `total_amount - released_amount` e nenhum token pode surgir ou sumir.
+
+mod common;
+
+use common::*;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use verum_vesting::{clawback_reason, pause_policy, VestingType};
+
+const SEEDS: u64 = 12;
+const STEPS: usize = 40;
+const TOTAL: u64 = 1_000_000;
+
+#[derive(Debug)]
+enum Step {
+    Warp(i64),
+    Claim,
+    Pause { by_creator: bool },
+    Resume { by_creator: bool },
+    UpdateBeneficiary,
+    Clawback(u64),
+    Cancel,
+}
+
+fn any_step(rng: &mut StdRng) -> Step {
+    match rng.gen_range(0..16) {
+        0..=4 => Step::Warp(rng.gen_range(0..400)),
+        5..=8 => Step::Claim,
+        9 => Step::Pause {
+            by_creator: rng.gen(),
+        },
+        10 => Step::Resume {
+            by_creator: rng.gen(),
+        },
+        11 => Step::UpdateBeneficiary,
+        12 | 13 => Step::Clawback(rng.gen_range(1..=TOTAL / 20)),
+        // Cancelamento raro, para que a maior parte da sequencia rode com o contrato aberto
+        _ if rng.gen_range(0..10) == 0 => Step::Cancel,
+        _ => Step::Claim,
+    }
+}
+
+struct Fuzz {
+    env: TestEnv,
+    creator: Keypair,
+    compliance: Keypair,
+    mint: Pubkey,
+    token_program: Pubkey,
+    creator_token_account: Pubkey,
+    treasury_token_account: Pubkey,
+    /// Beneficiarios que ja passaram pelo contrato e suas contas de token.
+    beneficiaries: Vec<(Keypair, Pubkey)>,
+    vesting_contract: Pubkey,
+}
+
+impl Fuzz {
+    async fn new(rng: &mut StdRng) -> Self {
+        let mut env = TestEnv::new().await;
+        let creator = env.payer();
+        let compliance = Keypair::new();
+        let token_program = if rng.gen() {
+            TOKEN_PROGRAM_ID
+        } else {
+            TOKEN_2022_PROGRAM_ID
+        };
+        let mint = env.create_mint(&token_program, 6, None).await;
+        let creator_token_account = env.create_token_account(&mint, &creator.pubkey()).await;
+        let treasury_token_account = env.create_token_account(&mint, &compliance.pubkey()).await;
+        env.mint_to(&mint, &creator_token_account, TOTAL).await;
+
+        let beneficiary = Keypair::new();
+        let beneficiary_token_account =
+            env.create_token_account(&mint, &beneficiary.pubkey()).await;
+
+        let start_time = env.now().await + rng.gen_range(0..200);
+        let end_time = start_time + rng.gen_range(1..2_000);
+        let vesting_type = if rng.gen() {
+            VestingType::Linear
+        } else {
+            VestingType::Cliff(rng.gen_range(start_time..=end_time), rng.gen_range(0..=100))
+        };
+        let args = CreateVestingArgs {
+            creator: creator.pubkey(),
+            beneficiary: beneficiary.pubkey(),
+            mint,
+            token_program,
+            sender_token_account: creator_token_account,
+            contract_id: 1,
+            total_amount: TOTAL,
+            start_time,
+            end_time,
+            vesting_type,
+            allowed_mint_flags: 0,
+        };
+        env.process(&[create_vesting_ix(&args, &[])], &[])
+            .await
+            .unwrap();
+        let vesting_contract = vesting_address(&creator.pubkey(), &mint, 1);
+
+        env.process(
+            &[
+                set_pause_policy_ix(
+                    &vesting_contract,
+                    &creator.pubkey(),
+                    &beneficiary.pubkey(),
+                    pause_policy::ALL,
+                ),
+                set_compliance_authority_ix(
+                    &vesting_contract,
+                    &creator.pubkey(),
+                    &beneficiary.pubkey(),
+                    &compliance.pubkey(),
+                ),
+            ],
+            &[&beneficiary],
+        )
+        .await
+        .unwrap();
+
+        Self {
+            env,
+            creator,
+            compliance,
+            mint,
+            token_program,
+            creator_token_account,
+            treasury_token_account,
+            beneficiaries: vec![(beneficiary, beneficiary_token_account)],
+            vesting_contract,
+        }
+    }
+
+    /// Beneficiario atual do contrato e sua conta de token.
+    async fn beneficiary(&mut self) -> (Keypair, Pubkey) {
+        let contract = self.env.vesting_contract(&self.vesting_contract).await;
+        let (keypair, token_account) = self
+            .beneficiaries
+            .iter()
+            .find(|(keypair, _)| keypair.pubkey() == contract.beneficiary)
+            .unwrap();
+        (keypair.insecure_clone(), *token_account)
+    }
+
+    /// Executa o passo; falhas esperadas do programa (nada a resgatar, contrato
+    /// cancelado, ...) sao ignoradas, so importa o estado resultante.
+    async fn apply(&mut self, step: &Step) {
+        let vesting_contract = self.vesting_contract;
+        let creator = self.creator.pubkey();
+        let (beneficiary, beneficiary_token_account) = self.beneficiary().await;
+        let _ = match *step {
+            Step::Warp(seconds) => {
+                let now = self.env.now().await;
+                self.env.set_time(now + seconds).await;
+                Ok(())
+            }
+            Step::Claim => {
+                let instruction = claim_tokens_ix(
+                    &vesting_contract,
+                    &beneficiary.pubkey(),
+                    &beneficiary_token_account,
+                    &self.mint,
+                    &self.token_program,
+                    &[],
+                );
+                self.env.process(&[instruction], &[]).await
+            }
+            Step::Pause { by_creator } | Step::Resume { by_creator } => {
+                let authority = if by_creator {
+                    self.creator.insecure_clone()
+                } else {
+                    beneficiary
+                };
+                let instruction = match step {
+                    Step::Pause { .. } => pause_vesting_ix(&vesting_contract, &authority.pubkey()),
+                    _ => resume_vesting_ix(&vesting_contract, &authority.pubkey()),
+                };
+                self.env.process(&[instruction], &[&authority]).await
+            }
+            Step::UpdateBeneficiary => {
+                let next = Keypair::new();
+                let next_token_account = self
+                    .env
+                    .create_token_account(&self.mint, &next.pubkey())
+                    .await;
+                let instruction =
+                    update_beneficiary_ix(&vesting_contract, &creator, &next.pubkey());
+                self.beneficiaries.push((next, next_token_account));
+                self.env.process(&[instruction], &[]).await
+            }
+            Step::Clawback(amount) => {
+                let args = ClawbackArgs {
+                    compliance_authority: self.compliance.pubkey(),
+                    destination: self.compliance.pubkey(),
+                    destination_token_account: self.treasury_token_account,
+                    mint: self.mint,
+                    token_program: self.token_program,
+                    amount,
+                    reason_code: clawback_reason::COURT_ORDER,
+                    memo: "fuzz".to_string(),
+                };
+                let compliance = self.compliance.insecure_clone();
+                self.env
+                    .process(&[clawback_ix(&vesting_contract, &args)], &[&compliance])
+                    .await
+            }
+            Step::Cancel => {
+                let instruction = cancel_vesting_ix(
+                    &vesting_contract,
+                    &creator,
+                    &self.creator_token_account,
+                    &self.mint,
+                    &self.token_program,
+                    &[],
+                );
+                self.env.process(&[instruction], &[]).await
+            }
+        };
+    }
+
+    async fn check_invariants(&mut self, released_before: u64, context: &str) -> u64 {
+        let contract = self.env.vesting_contract(&self.vesting_contract).await;
+        let escrow = self
+            .env
+            .token_balance(&escrow_address(&self.vesting_contract))
+            .await;
+        assert_eq!(
+            escrow,
+            contract.total_amount - contract.released_amount,
+            "escrow diverge de total - liberado {context}"
+        );
+        assert!(
+            contract.released_amount >= released_before,
+            "released_amount diminuiu {context}"
+        );
+        if contract.is_cancelled {
+            assert_eq!(escrow, 0, "escrow com saldo apos cancelamento {context}");
+        }
+
+        let mut circulating = escrow
+            + self.env.token_balance(&self.creator_token_account).await
+            + self.env.token_balance(&self.treasury_token_account).await;
+        for index in 0..self.beneficiaries.len() {
+            let token_account = self.beneficiaries[index].1;
+            circulating += self.env.token_balance(&token_account).await;
+        }
+        assert_eq!(circulating, TOTAL, "tokens criados ou perdidos {context}");
+
+        contract.released_amount
+    }
+}
+
+#[tokio::test]
+async fn random_instruction_sequences_keep_escrow_consistent() {
+    for seed in 0..SEEDS {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut fuzz = Fuzz::new(&mut rng).await;
+        let mut history = Vec::new();
+        let mut released = fuzz
+            .check_invariants(0, &format!("(semente {seed}, criacao)"))
+            .await;
+
+        for _ in 0..STEPS {
+            let step = any_step(&mut rng);
+            fuzz.apply(&step).await;
+            history.push(step);
+            released = fuzz
+                .check_invariants(released, &format!("(semente {seed}, passos {history:?})"))
+                .await;
+        }
+    }
+}
@@ -0,0 +1,129 @@
+mod common;
+
+use common::*;
+use solana_sdk::account::{AccountSharedData, ReadableAccount, WritableAccount};
+use solana_sdk::signature::{Keypair, Signer};
+use verum_vesting::{VestingContract, VestingError, VestingType};
+
+const TOTAL: u64 = 1_000_000;
+
+/// Tamanho do `VestingContract` antes dos campos acrescentados ao fim da conta.
+const LEGACY_LEN: usize = 8 + 32 * 3 + 8 * 5 + (1 + 16) + 3;
+/// Fim dos campos serializados de um contrato `Linear` no layout antigo; o restante
+/// ate `LEGACY_LEN` era preenchimento com zeros.
+const LEGACY_LINEAR_END: usize = 8 + 32 * 3 + 8 * 5 + 1 + 3;
+
+#[tokio::test]
+async fn migrates_legacy_contract_and_keeps_it_claimable() {
+    let mut env = TestEnv::new().await;
+    let creator = env.payer();
+    let beneficiary = Keypair::new();
+    let mint = env.create_mint(&TOKEN_PROGRAM_ID, 6, None).await;
+    let creator_token_account = env.create_token_account(&mint, &creator.pubkey()).await;
+    let beneficiary_token_account = env.create_token_account(&mint, &beneficiary.pubkey()).await;
+    env.mint_to(&mint, &creator_token_account, TOTAL).await;
+
+    let start_time = env.now().await;
+    let args = CreateVestingArgs {
+        creator: creator.pubkey(),
+        beneficiary: beneficiary.pubkey(),
+        mint,
+        token_program: TOKEN_PROGRAM_ID,
+        sender_token_account: creator_token_account,
+        contract_id: 1,
+        total_amount: TOTAL,
+        start_time,
+        end_time: start_time + 1_000,
+        vesting_type: VestingType::Linear,
+        allowed_mint_flags: 0,
+    };
+    env.process(&[create_vesting_ix(&args, &[])], &[])
+        .await
+        .unwrap();
+    let vesting_contract = vesting_address(&creator.pubkey(), &mint, 1);
+
+    // Reescreve a conta como um deploy anterior a teria deixado
+    let account = env
+        .context
+        .banks_client
+        .get_account(vesting_contract)
+        .await
+        .unwrap()
+        .unwrap();
+    let mut legacy_data = account.data()[..LEGACY_LEN].to_vec();
+    legacy_data[LEGACY_LINEAR_END..].fill(0);
+    let rent = env.context.banks_client.get_rent().await.unwrap();
+    let mut legacy = AccountSharedData::new(
+        rent.minimum_balance(LEGACY_LEN),
+        LEGACY_LEN,
+        &verum_vesting::ID,
+    );
+    legacy.data_as_mut_slice().copy_from_slice(&legacy_data);
+    env.context.set_account(&vesting_contract, &legacy);
+
+    env.set_time(start_time + 500).await;
+    let claim = claim_tokens_ix(
+        &vesting_contract,
+        &beneficiary.pubkey(),
+        &beneficiary_token_account,
+        &mint,
+        &TOKEN_PROGRAM_ID,
+        &[],
+    );
+    let result = env.process(std::slice::from_ref(&claim), &[]).await;
+    assert_anchor_error(result, anchor_lang::error::ErrorCode::AccountDidNotDeserialize);
+
+    env.process(&[migrate_contract_ix(&vesting_contract, &creator.pubkey())], &[])
+        .await
+        .unwrap();
+
+    let account = env
+        .context
+        .banks_client
+        .get_account(vesting_contract)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(account.data.len(), VestingContract::LEN);
+    assert!(account.lamports >= rent.minimum_balance(VestingContract::LEN));
+
+    let contract = env.vesting_contract(&vesting_contract).await;
+    assert_eq!(contract.beneficiary, beneficiary.pubkey());
+    assert_eq!(contract.total_amount, TOTAL);
+    assert_eq!(contract.released_amount, 0);
+    assert!(!contract.is_native);
+    assert!(!contract.is_paused());
+    assert_eq!(contract.timelock_delay, 0);
+    assert_eq!(contract.claim_count, 0);
+
+    env.process(&[claim], &[]).await.unwrap();
+    assert_eq!(env.token_balance(&beneficiary_token_account).await, TOTAL / 2);
+
+    let result = env
+        .process(&[migrate_contract_ix(&vesting_contract, &creator.pubkey())], &[])
+        .await;
+    assert_vesting_error(result, VestingError::ContractAlreadyMigrated);
+}
+
+#[tokio::test]
+async fn rejects_accounts_that_are_not_vesting_contracts() {
+    let mut env = TestEnv::new().await;
+    let payer = env.payer();
+    let mint = env.create_mint(&TOKEN_PROGRAM_ID, 6, None).await;
+    let token_account = env.create_token_account(&mint, &payer.pubkey()).await;
+
+    let result = env
+        .process(&[migrate_contract_ix(&token_account, &payer.pubkey())], &[])
+        .await;
+    assert_vesting_error(result, VestingError::InvalidContractLayout);
+
+    let foreign_account = Keypair::new().pubkey();
+    let mut account = AccountSharedData::new(10_000_000, 64, &verum_vesting::ID);
+    account.data_as_mut_slice()[..8].copy_from_slice(&[1; 8]);
+    env.context.set_account(&foreign_account, &account);
+
+    let result = env
+        .process(&[migrate_contract_ix(&foreign_account, &payer.pubkey())], &[])
+        .await;
+    assert_vesting_error(result, VestingError::InvalidContractLayout);
+}
@@ -0,0 +1,194 @@
+mod common;
+
+use common::*;
+use solana_sdk::signature::{Keypair, Signer};
+use verum_vesting::{audit_action, AuditLog, MilestoneInit, VestingError, VestingType};
+
+/// Contrato `Milestones` com os dois atestadores dos marcos em `extra`.
+type MilestoneFixture = ContractFixture<[Keypair; 2]>;
+
+async fn setup_milestones() -> MilestoneFixture {
+    let mut contract = TestEnv::new()
+        .await
+        .create_linear_contract(VestingType::Milestones, 1)
+        .await;
+    let attestors = [Keypair::new(), Keypair::new()];
+    for attestor in &attestors {
+        contract.env.fund(&attestor.pubkey(), 1_000_000_000).await;
+    }
+
+    contract.with(attestors)
+}
+
+impl MilestoneFixture {
+    fn milestones(&self, amounts: [u64; 2]) -> Vec<MilestoneInit> {
+        self.extra
+            .iter()
+            .zip(amounts)
+            .map(|(attestor, amount)| MilestoneInit {
+                amount,
+                attestor: attestor.pubkey(),
+            })
+            .collect()
+    }
+
+    async fn approve(
+        &mut self,
+        attestor: usize,
+        index: u8,
+    ) -> Result<(), solana_program_test::BanksClientError> {
+        let attestor = self.extra[attestor].insecure_clone();
+        let instruction = approve_milestone_ix(&self.vesting_contract, &attestor.pubkey(), index);
+        self.env.process(&[instruction], &[&attestor]).await
+    }
+
+    async fn claim(&mut self) -> Result<(), solana_program_test::BanksClientError> {
+        let instruction = self.claim_ix();
+        self.env.process(&[instruction], &[]).await
+    }
+}
+
+#[tokio::test]
+async fn approved_milestones_are_claimable_and_expired_ones_refunded() {
+    let mut fixture = setup_milestones().await;
+    let deadline = fixture.start_time + 1_000;
+    let instruction = create_milestone_schedule_ix(
+        &fixture.vesting_contract,
+        &fixture.creator.pubkey(),
+        fixture.milestones([300_000, 700_000]),
+        deadline,
+    );
+    fixture.env.process(&[instruction], &[]).await.unwrap();
+
+    // O fim do cronograma nao libera nada sem aprovacao
+    fixture.env.set_time(fixture.start_time + 10).await;
+    let result = fixture.claim().await;
+    assert_vesting_error(result, VestingError::NothingToRelease);
+
+    fixture.approve(0, 0).await.unwrap();
+    fixture.env.set_time(fixture.start_time + 20).await;
+    fixture.claim().await.unwrap();
+    assert_eq!(
+        fixture
+            .env
+            .token_balance(&fixture.beneficiary_token_account)
+            .await,
+        300_000
+    );
+
+    let result = fixture.approve(0, 1).await;
+    assert_vesting_error(result, VestingError::Unauthorized);
+
+    let refund = refund_expired_milestones_ix(
+        &fixture.vesting_contract,
+        &fixture.creator.pubkey(),
+        &fixture.creator_token_account,
+        &fixture.mint,
+        &TOKEN_PROGRAM_ID,
+        &[],
+    );
+    let result = fixture
+        .env
+        .process(std::slice::from_ref(&refund), &[])
+        .await;
+    assert_vesting_error(result, VestingError::MilestoneDeadlineNotReached);
+
+    fixture.env.set_time(deadline + 1).await;
+    let result = fixture.approve(1, 1).await;
+    assert_vesting_error(result, VestingError::MilestoneDeadlinePassed);
+
+    fixture.env.process(&[refund], &[]).await.unwrap();
+    assert_eq!(
+        fixture
+            .env
+            .token_balance(&fixture.creator_token_account)
+            .await,
+        700_000
+    );
+    assert_eq!(
+        fixture
+            .env
+            .token_balance(&escrow_address(&fixture.vesting_contract))
+            .await,
+        0
+    );
+}
+
+#[tokio::test]
+async fn refund_lowers_total_and_keeps_escrow_consistent() {
+    let mut fixture = setup_milestones().await;
+    let deadline = fixture.start_time + 1_000;
+    let instruction = create_milestone_schedule_ix(
+        &fixture.vesting_contract,
+        &fixture.creator.pubkey(),
+        fixture.milestones([300_000, 700_000]),
+        deadline,
+    );
+    fixture.env.process(&[instruction], &[]).await.unwrap();
+    fixture.approve(0, 0).await.unwrap();
+
+    // Devolucao antes do resgate do marco aprovado
+    fixture.env.set_time(deadline + 1).await;
+    let refund = refund_expired_milestones_ix(
+        &fixture.vesting_contract,
+        &fixture.creator.pubkey(),
+        &fixture.creator_token_account,
+        &fixture.mint,
+        &TOKEN_PROGRAM_ID,
+        &[],
+    );
+    fixture
+        .env
+        .process(std::slice::from_ref(&refund), &[])
+        .await
+        .unwrap();
+
+    let escrow = escrow_address(&fixture.vesting_contract);
+    let contract = fixture.env.vesting_contract(&fixture.vesting_contract).await;
+    assert_eq!(contract.total_amount, 300_000);
+    assert_eq!(contract.released_amount, 0);
+    assert_eq!(
+        fixture.env.token_balance(&escrow).await,
+        contract.total_amount - contract.released_amount
+    );
+
+    let audit_log: AuditLog = fixture
+        .env
+        .anchor_account(&audit_log_address(&fixture.vesting_contract))
+        .await;
+    let entry = audit_log.entries.last().unwrap();
+    assert_eq!(entry.action, audit_action::MILESTONE_REFUND);
+    assert_eq!(entry.actor, fixture.creator.pubkey());
+    assert_eq!(entry.amount, 700_000);
+
+    let result = fixture.env.process(&[refund], &[]).await;
+    assert_vesting_error(result, VestingError::NothingToRelease);
+
+    fixture.claim().await.unwrap();
+    let contract = fixture.env.vesting_contract(&fixture.vesting_contract).await;
+    assert_eq!(contract.released_amount, 300_000);
+    assert_eq!(
+        fixture.env.token_balance(&escrow).await,
+        contract.total_amount - contract.released_amount
+    );
+    assert_eq!(
+        fixture
+            .env
+            .token_balance(&fixture.beneficiary_token_account)
+            .await,
+        300_000
+    );
+}
+
+#[tokio::test]
+async fn milestone_amounts_must_match_total() {
+    let mut fixture = setup_milestones().await;
+    let instruction = create_milestone_schedule_ix(
+        &fixture.vesting_contract,
+        &fixture.creator.pubkey(),
+        fixture.milestones([300_000, 600_000]),
+        0,
+    );
+    let result = fixture.env.process(&[instruction], &[]).await;
+    assert_vesting_error(result, VestingError::InvalidMilestones);
+}
@@ -0,0 +1,128 @@
+mod common;
+
+use common::*;
+use solana_program_test::BanksClientError;
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+use verum_vesting::{mint_flags, VestingError, VestingType};
+
+const TOTAL: u64 = 1_000_000;
+
+/// Cria o mint descrito, financia o criador e tenta abrir um contrato com a
+/// politica informada.
+async fn try_create_vesting(
+    env: &mut TestEnv,
+    config: MintConfig,
+    allowed_mint_flags: u16,
+) -> (Result<(), BanksClientError>, Pubkey) {
+    let creator = env.payer();
+    let beneficiary = Keypair::new();
+    let token_program = config.token_program;
+    let mint = env.create_mint_with(config).await;
+    let creator_token_account = env.create_token_account(&mint, &creator.pubkey()).await;
+    env.mint_to(&mint, &creator_token_account, TOTAL).await;
+
+    let start_time = env.now().await;
+    let args = CreateVestingArgs {
+        creator: creator.pubkey(),
+        beneficiary: beneficiary.pubkey(),
+        mint,
+        token_program,
+        sender_token_account: creator_token_account,
+        contract_id: 1,
+        total_amount: TOTAL,
+        start_time,
+        end_time: start_time + 1_000,
+        vesting_type: VestingType::Linear,
+        allowed_mint_flags,
+    };
+    let result = env.process(&[create_vesting_ix(&args, &[])], &[]).await;
+    (result, vesting_address(&creator.pubkey(), &mint, 1))
+}
+
+#[tokio::test]
+async fn permanent_delegate_is_rejected_by_default() {
+    let mut env = TestEnv::new().await;
+    let (result, _) = try_create_vesting(
+        &mut env,
+        MintConfig {
+            permanent_delegate: Some(Pubkey::new_unique()),
+            ..MintConfig::default()
+        },
+        0,
+    )
+    .await;
+    assert_vesting_error(result, VestingError::DangerousMintExtension);
+}
+
+#[tokio::test]
+async fn permanent_delegate_is_flagged_when_allowed() {
+    let mut env = TestEnv::new().await;
+    let (result, vesting_contract) = try_create_vesting(
+        &mut env,
+        MintConfig {
+            permanent_delegate: Some(Pubkey::new_unique()),
+            ..MintConfig::default()
+        },
+        mint_flags::PERMANENT_DELEGATE,
+    )
+    .await;
+    result.unwrap();
+
+    let contract = env.vesting_contract(&vesting_contract).await;
+    assert_eq!(contract.mint_flags, mint_flags::PERMANENT_DELEGATE);
+}
+
+#[tokio::test]
+async fn spl_token_freeze_authority_requires_opt_in() {
+    let mut env = TestEnv::new().await;
+    let freeze_authority = Some(Pubkey::new_unique());
+    let (result, _) = try_create_vesting(
+        &mut env,
+        MintConfig {
+            token_program: TOKEN_PROGRAM_ID,
+            freeze_authority,
+            ..MintConfig::default()
+        },
+        0,
+    )
+    .await;
+    assert_vesting_error(result, VestingError::DangerousMintExtension);
+
+    let (result, vesting_contract) = try_create_vesting(
+        &mut env,
+        MintConfig {
+            token_program: TOKEN_PROGRAM_ID,
+            freeze_authority,
+            ..MintConfig::default()
+        },
+        mint_flags::FREEZE_AUTHORITY,
+    )
+    .await;
+    result.unwrap();
+
+    let contract = env.vesting_contract(&vesting_contract).await;
+    assert_eq!(contract.mint_flags, mint_flags::FREEZE_AUTHORITY);
+    assert!(!contract.is_token_2022);
+}
+
+#[tokio::test]
+async fn transfer_fee_is_informational() {
+    let mut env = TestEnv::new().await;
+    let (result, vesting_contract) = try_create_vesting(
+        &mut env,
+        MintConfig {
+            transfer_fee_basis_points: Some(100),
+            ..MintConfig::default()
+        },
+        0,
+    )
+    .await;
+    result.unwrap();
+
+    let contract = env.vesting_contract(&vesting_contract).await;
+    assert_eq!(contract.mint_flags, mint_flags::TRANSFER_FEE);
+    assert_eq!(contract.total_amount, TOTAL - TOTAL / 100);
+}
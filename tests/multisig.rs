@@ -0,0 +1,239 @@
+mod common;
+
+use common::*;
+use solana_sdk::{
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+use verum_vesting::{MultisigAction, VestingError, VestingType};
+
+const TOTAL: u64 = 1_000_000;
+
+struct MultisigFixture {
+    env: TestEnv,
+    members: Vec<Keypair>,
+    multisig: Pubkey,
+    signer: Pubkey,
+    beneficiary: Pubkey,
+    mint: Pubkey,
+    treasury_token_account: Pubkey,
+    next_proposal: u64,
+}
+
+/// Multisig 2-de-3 cujo PDA tem SOL para aluguel e `TOTAL` tokens em tesouraria.
+async fn setup_multisig() -> MultisigFixture {
+    let mut env = TestEnv::new().await;
+    let payer = env.payer();
+    let members: Vec<Keypair> = (0..3).map(|_| Keypair::new()).collect();
+    for member in &members {
+        env.fund(&member.pubkey(), 1_000_000_000).await;
+    }
+
+    let instruction = create_multisig_ix(
+        &payer.pubkey(),
+        7,
+        members.iter().map(|member| member.pubkey()).collect(),
+        2,
+    );
+    env.process(&[instruction], &[]).await.unwrap();
+    let multisig = multisig_address(&payer.pubkey(), 7);
+    let signer = multisig_signer_address(&multisig);
+    env.fund(&signer, 1_000_000_000).await;
+
+    let mint = env.create_mint(&TOKEN_PROGRAM_ID, 6, None).await;
+    let treasury_token_account = env.create_token_account(&mint, &signer).await;
+    env.mint_to(&mint, &treasury_token_account, TOTAL).await;
+
+    MultisigFixture {
+        env,
+        members,
+        multisig,
+        signer,
+        beneficiary: Pubkey::new_unique(),
+        mint,
+        treasury_token_account,
+        next_proposal: 0,
+    }
+}
+
+impl MultisigFixture {
+    fn vesting_contract(&self) -> Pubkey {
+        vesting_address(&self.signer, &self.mint, 1)
+    }
+
+    async fn propose(&mut self, member: usize, action: MultisigAction) -> u64 {
+        let proposal_id = self.next_proposal;
+        let proposer = self.members[member].insecure_clone();
+        let instruction =
+            create_multisig_proposal_ix(&self.multisig, proposal_id, &proposer.pubkey(), action);
+        self.env
+            .process(&[instruction], &[&proposer])
+            .await
+            .unwrap();
+        self.next_proposal += 1;
+        proposal_id
+    }
+
+    async fn approve(
+        &mut self,
+        member: &Keypair,
+        proposal_id: u64,
+    ) -> Result<(), solana_program_test::BanksClientError> {
+        let instruction =
+            approve_multisig_proposal_ix(&self.multisig, proposal_id, &member.pubkey());
+        self.env.process(&[instruction], &[member]).await
+    }
+
+    async fn execute(
+        &mut self,
+        proposal_id: u64,
+        inner: &Instruction,
+    ) -> Result<(), solana_program_test::BanksClientError> {
+        let member = self.members[0].insecure_clone();
+        let instruction =
+            execute_multisig_proposal_ix(&self.multisig, proposal_id, &member.pubkey(), inner);
+        self.env.process(&[instruction], &[&member]).await
+    }
+
+    fn create_vesting_args(&self, start_time: i64) -> CreateVestingArgs {
+        CreateVestingArgs {
+            creator: self.signer,
+            beneficiary: self.beneficiary,
+            mint: self.mint,
+            token_program: TOKEN_PROGRAM_ID,
+            sender_token_account: self.treasury_token_account,
+            contract_id: 1,
+            total_amount: TOTAL,
+            start_time,
+            end_time: start_time + 1_000,
+            vesting_type: VestingType::Linear,
+            allowed_mint_flags: 0,
+        }
+    }
+
+    async fn create_vesting(&mut self) {
+        let start_time = self.env.now().await;
+        let args = self.create_vesting_args(start_time);
+        let proposal_id = self
+            .propose(
+                0,
+                MultisigAction::CreateVesting {
+                    contract_id: 1,
+                    beneficiary: self.beneficiary,
+                    mint: self.mint,
+                    total_amount: TOTAL,
+                    start_time,
+                    end_time: start_time + 1_000,
+                    vesting_type: VestingType::Linear,
+                    allowed_mint_flags: 0,
+                },
+            )
+            .await;
+        let member = self.members[1].insecure_clone();
+        self.approve(&member, proposal_id).await.unwrap();
+        self.execute(proposal_id, &create_vesting_ix(&args, &[]))
+            .await
+            .unwrap();
+    }
+}
+
+#[tokio::test]
+async fn multisig_creates_and_cancels_as_creator() {
+    let mut fixture = setup_multisig().await;
+    let start_time = fixture.env.now().await;
+    let args = fixture.create_vesting_args(start_time);
+    let proposal_id = fixture
+        .propose(
+            0,
+            MultisigAction::CreateVesting {
+                contract_id: 1,
+                beneficiary: fixture.beneficiary,
+                mint: fixture.mint,
+                total_amount: TOTAL,
+                start_time,
+                end_time: start_time + 1_000,
+                vesting_type: VestingType::Linear,
+                allowed_mint_flags: 0,
+            },
+        )
+        .await;
+
+    let inner = create_vesting_ix(&args, &[]);
+    let result = fixture.execute(proposal_id, &inner).await;
+    assert_vesting_error(result, VestingError::MultisigThresholdNotMet);
+
+    let outsider = Keypair::new();
+    let result = fixture.approve(&outsider, proposal_id).await;
+    assert_vesting_error(result, VestingError::NotMultisigMember);
+
+    let member = fixture.members[2].insecure_clone();
+    fixture.approve(&member, proposal_id).await.unwrap();
+    fixture.execute(proposal_id, &inner).await.unwrap();
+
+    let vesting_contract_address = fixture.vesting_contract();
+    let vesting_contract = fixture
+        .env
+        .vesting_contract(&vesting_contract_address)
+        .await;
+    assert_eq!(vesting_contract.creator, fixture.signer);
+    assert_eq!(vesting_contract.total_amount, TOTAL);
+
+    let result = fixture.execute(proposal_id, &inner).await;
+    assert_vesting_error(result, VestingError::MultisigProposalAlreadyExecuted);
+
+    let proposal_id = fixture
+        .propose(
+            1,
+            MultisigAction::CancelVesting {
+                vesting_contract: vesting_contract_address,
+            },
+        )
+        .await;
+    let member = fixture.members[0].insecure_clone();
+    fixture.approve(&member, proposal_id).await.unwrap();
+    let cancel = cancel_vesting_ix(
+        &vesting_contract_address,
+        &fixture.signer,
+        &fixture.treasury_token_account,
+        &fixture.mint,
+        &TOKEN_PROGRAM_ID,
+        &[],
+    );
+    fixture.execute(proposal_id, &cancel).await.unwrap();
+
+    let treasury_token_account = fixture.treasury_token_account;
+    assert_eq!(
+        fixture.env.token_balance(&treasury_token_account).await,
+        TOTAL
+    );
+}
+
+#[tokio::test]
+async fn multisig_execution_must_match_the_proposal() {
+    let mut fixture = setup_multisig().await;
+    fixture.create_vesting().await;
+    let vesting_contract = fixture.vesting_contract();
+
+    let approved_beneficiary = Pubkey::new_unique();
+    let proposal_id = fixture
+        .propose(
+            2,
+            MultisigAction::UpdateBeneficiary {
+                vesting_contract,
+                new_beneficiary: approved_beneficiary,
+            },
+        )
+        .await;
+    let member = fixture.members[1].insecure_clone();
+    fixture.approve(&member, proposal_id).await.unwrap();
+
+    let other = update_beneficiary_ix(&vesting_contract, &fixture.signer, &Pubkey::new_unique());
+    let result = fixture.execute(proposal_id, &other).await;
+    assert_vesting_error(result, VestingError::InvalidMultisigAccounts);
+
+    let update = update_beneficiary_ix(&vesting_contract, &fixture.signer, &approved_beneficiary);
+    fixture.execute(proposal_id, &update).await.unwrap();
+    let vesting_contract = fixture.env.vesting_contract(&vesting_contract).await;
+    assert_eq!(vesting_contract.beneficiary, approved_beneficiary);
+}
@@ -0,0 +1,100 @@
+mod common;
+
+use common::*;
+use solana_sdk::{
+    instruction::AccountMeta,
+    signature::{Keypair, Signer},
+};
+use verum_vesting::{VestingError, VestingType};
+
+const TOTAL: u64 = 2_000_000_000;
+
+#[tokio::test]
+async fn native_vesting_pays_lamports_and_keeps_rent_exemption() {
+    let mut env = TestEnv::new().await;
+    let creator = env.payer();
+    let beneficiary = Keypair::new();
+    env.fund(&beneficiary.pubkey(), 1_000_000).await;
+
+    let start_time = env.now().await;
+    let vesting_contract = native_vesting_address(&creator.pubkey(), 1);
+    env.process(
+        &[create_native_vesting_ix(
+            &creator.pubkey(),
+            &beneficiary.pubkey(),
+            1,
+            TOTAL,
+            start_time,
+            start_time + 1_000,
+            VestingType::Linear,
+        )],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let contract = env.vesting_contract(&vesting_contract).await;
+    assert!(contract.is_native);
+    assert_eq!(contract.total_amount, TOTAL);
+    let rent_exempt_minimum = env.lamports(&vesting_contract).await - TOTAL;
+
+    env.set_time(start_time + 500).await;
+    env.process(
+        &[claim_native_ix(&vesting_contract, &beneficiary.pubkey())],
+        &[],
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        env.lamports(&beneficiary.pubkey()).await,
+        1_000_000 + TOTAL / 2
+    );
+
+    let creator_before = env.lamports(&creator.pubkey()).await;
+    env.process(
+        &[cancel_native_ix(&vesting_contract, &creator.pubkey())],
+        &[],
+    )
+    .await
+    .unwrap();
+    assert_eq!(env.lamports(&vesting_contract).await, rent_exempt_minimum);
+    assert!(env.lamports(&creator.pubkey()).await > creator_before + TOTAL / 2 - 10_000);
+
+    let contract = env.vesting_contract(&vesting_contract).await;
+    assert!(contract.is_cancelled);
+}
+
+#[tokio::test]
+async fn native_claim_requires_writable_beneficiary() {
+    let mut env = TestEnv::new().await;
+    let creator = env.payer();
+    let beneficiary = Keypair::new();
+    env.fund(&beneficiary.pubkey(), 1_000_000).await;
+
+    let start_time = env.now().await;
+    let vesting_contract = native_vesting_address(&creator.pubkey(), 2);
+    env.process(
+        &[create_native_vesting_ix(
+            &creator.pubkey(),
+            &beneficiary.pubkey(),
+            2,
+            TOTAL,
+            start_time,
+            start_time + 100,
+            VestingType::Linear,
+        )],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    env.set_time(start_time + 100).await;
+    let mut instruction = claim_native_ix(&vesting_contract, &beneficiary.pubkey());
+    for meta in instruction.accounts.iter_mut() {
+        if meta.pubkey == beneficiary.pubkey() {
+            *meta = AccountMeta::new_readonly(beneficiary.pubkey(), false);
+        }
+    }
+    let result = env.process(&[instruction], &[]).await;
+    assert_vesting_error(result, VestingError::RecipientNotWritable);
+}
@@ -0,0 +1,212 @@
+mod common;
+
+use common::*;
+use solana_sdk::signature::Signer;
+use verum_vesting::{pause_policy, VestingError, VestingType};
+
+async fn setup_contract() -> ContractFixture {
+    TestEnv::new()
+        .await
+        .create_linear_contract(VestingType::Linear, 1_000)
+        .await
+}
+
+impl ContractFixture {
+    async fn set_policy(&mut self, policy: u8) {
+        let instruction = set_pause_policy_ix(
+            &self.vesting_contract,
+            &self.creator.pubkey(),
+            &self.beneficiary.pubkey(),
+            policy,
+        );
+        let beneficiary = self.beneficiary.insecure_clone();
+        self.env
+            .process(&[instruction], &[&beneficiary])
+            .await
+            .unwrap();
+    }
+
+    async fn pause(&mut self) {
+        let instruction = pause_vesting_ix(&self.vesting_contract, &self.creator.pubkey());
+        self.env.process(&[instruction], &[]).await.unwrap();
+    }
+
+    async fn resume(&mut self) {
+        let instruction = resume_vesting_ix(&self.vesting_contract, &self.creator.pubkey());
+        self.env.process(&[instruction], &[]).await.unwrap();
+    }
+
+    async fn paused_duration(&mut self) -> i64 {
+        let vesting_contract = self.vesting_contract;
+        self.env
+            .vesting_contract(&vesting_contract)
+            .await
+            .paused_duration
+    }
+
+    async fn claim(&mut self) {
+        let instruction = self.claim_ix();
+        self.env.process(&[instruction], &[]).await.unwrap();
+    }
+
+    async fn claimed(&mut self) -> u64 {
+        let token_account = self.beneficiary_token_account;
+        self.env.token_balance(&token_account).await
+    }
+}
+
+#[tokio::test]
+async fn paused_time_is_excluded_from_the_schedule() {
+    let mut fixture = setup_contract().await;
+    fixture.set_policy(pause_policy::CREATOR).await;
+
+    fixture.env.set_time(fixture.start_time + 200).await;
+    fixture
+        .env
+        .process(
+            &[pause_vesting_ix(
+                &fixture.vesting_contract,
+                &fixture.creator.pubkey(),
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+
+    fixture.env.set_time(fixture.start_time + 500).await;
+    fixture.claim().await;
+    assert_eq!(fixture.claimed().await, 200_000);
+
+    fixture.env.set_time(fixture.start_time + 600).await;
+    fixture
+        .env
+        .process(
+            &[resume_vesting_ix(
+                &fixture.vesting_contract,
+                &fixture.creator.pubkey(),
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+    let contract = fixture
+        .env
+        .vesting_contract(&fixture.vesting_contract)
+        .await;
+    assert_eq!(contract.paused_duration, 400);
+    assert_eq!(contract.pause_count, 1);
+    assert!(!contract.is_paused());
+
+    fixture.env.set_time(fixture.start_time + 700).await;
+    fixture.claim().await;
+    assert_eq!(fixture.claimed().await, 300_000);
+
+    // O fim do cronograma foi deslocado pelos 400s de pausa
+    fixture.env.set_time(fixture.start_time + 1_200).await;
+    fixture.claim().await;
+    assert_eq!(fixture.claimed().await, 800_000);
+
+    fixture.env.set_time(fixture.start_time + 1_400).await;
+    fixture.claim().await;
+    assert_eq!(fixture.claimed().await, CONTRACT_TOTAL);
+}
+
+#[tokio::test]
+async fn pause_requires_policy() {
+    let mut fixture = setup_contract().await;
+
+    let result = fixture
+        .env
+        .process(
+            &[pause_vesting_ix(
+                &fixture.vesting_contract,
+                &fixture.creator.pubkey(),
+            )],
+            &[],
+        )
+        .await;
+    assert_vesting_error(result, VestingError::PauseNotAllowed);
+
+    fixture.set_policy(pause_policy::CREATOR).await;
+    let beneficiary = fixture.beneficiary.insecure_clone();
+    fixture.env.fund(&beneficiary.pubkey(), 1_000_000_000).await;
+    let result = fixture
+        .env
+        .process(
+            &[pause_vesting_ix(
+                &fixture.vesting_contract,
+                &beneficiary.pubkey(),
+            )],
+            &[&beneficiary],
+        )
+        .await;
+    assert_vesting_error(result, VestingError::PauseNotAllowed);
+
+    let result = fixture
+        .env
+        .process(
+            &[resume_vesting_ix(
+                &fixture.vesting_contract,
+                &fixture.creator.pubkey(),
+            )],
+            &[],
+        )
+        .await;
+    assert_vesting_error(result, VestingError::NotPaused);
+}
+
+#[tokio::test]
+async fn pause_rejects_event_driven_contracts() {
+    for vesting_type in [VestingType::Milestones, VestingType::PriceTriggered] {
+        let mut fixture = TestEnv::new()
+            .await
+            .create_linear_contract(vesting_type, 1_000)
+            .await;
+        fixture.set_policy(pause_policy::ALL).await;
+
+        let result = fixture
+            .env
+            .process(
+                &[pause_vesting_ix(
+                    &fixture.vesting_contract,
+                    &fixture.creator.pubkey(),
+                )],
+                &[],
+            )
+            .await;
+        assert_vesting_error(result, VestingError::UnsupportedVestingType);
+    }
+}
+
+#[tokio::test]
+async fn pause_before_start_only_counts_from_start() {
+    let mut fixture = TestEnv::new()
+        .await
+        .create_contract(VestingType::Linear, 100, 1_100, true)
+        .await;
+    fixture.set_policy(pause_policy::CREATOR).await;
+    fixture.pause().await;
+
+    fixture.env.set_time(fixture.start_time + 300).await;
+    fixture.resume().await;
+    assert_eq!(fixture.paused_duration().await, 300);
+
+    fixture.env.set_time(fixture.start_time + 400).await;
+    fixture.claim().await;
+    assert_eq!(fixture.claimed().await, 100_000);
+}
+
+#[tokio::test]
+async fn pause_after_end_does_not_shift_the_schedule() {
+    let mut fixture = setup_contract().await;
+    fixture.set_policy(pause_policy::CREATOR).await;
+
+    fixture.env.set_time(fixture.start_time + 1_100).await;
+    fixture.pause().await;
+    fixture.claim().await;
+    assert_eq!(fixture.claimed().await, CONTRACT_TOTAL);
+
+    fixture.env.set_time(fixture.start_time + 1_300).await;
+    fixture.resume().await;
+    assert_eq!(fixture.paused_duration().await, 0);
+}
@@ -0,0 +1,205 @@
+mod common;
+
+use common::*;
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+use verum_vesting::VestingError;
+
+const RATE: u64 = 100;
+const DEPOSIT: u64 = 10_000;
+
+struct StreamFixture {
+    env: TestEnv,
+    payer: Keypair,
+    recipient: Keypair,
+    mint: Pubkey,
+    payer_token_account: Pubkey,
+    recipient_token_account: Pubkey,
+    stream: Pubkey,
+    start_time: i64,
+}
+
+async fn setup_stream() -> StreamFixture {
+    let mut env = TestEnv::new().await;
+    let payer = env.payer();
+    let recipient = Keypair::new();
+    let mint = env.create_mint(&TOKEN_2022_PROGRAM_ID, 6, None).await;
+    let payer_token_account = env.create_token_account(&mint, &payer.pubkey()).await;
+    let recipient_token_account = env.create_token_account(&mint, &recipient.pubkey()).await;
+    env.mint_to(&mint, &payer_token_account, DEPOSIT * 10).await;
+
+    let start_time = env.now().await;
+    let args = CreateStreamArgs {
+        payer: payer.pubkey(),
+        recipient: recipient.pubkey(),
+        mint,
+        token_program: TOKEN_2022_PROGRAM_ID,
+        payer_token_account,
+        stream_id: 1,
+        rate_per_second: RATE,
+        start_time,
+        initial_deposit: DEPOSIT,
+        allowed_mint_flags: 0,
+    };
+    env.process(&[create_stream_ix(&args, &[])], &[])
+        .await
+        .unwrap();
+
+    StreamFixture {
+        env,
+        stream: stream_address(&payer.pubkey(), &mint, 1),
+        payer,
+        recipient,
+        mint,
+        payer_token_account,
+        recipient_token_account,
+        start_time,
+    }
+}
+
+impl StreamFixture {
+    async fn withdraw(&mut self) -> Result<(), solana_program_test::BanksClientError> {
+        let instruction = withdraw_from_stream_ix(
+            &self.stream,
+            &self.recipient.pubkey(),
+            &self.recipient_token_account,
+            &self.mint,
+            &TOKEN_2022_PROGRAM_ID,
+            &[],
+        );
+        self.env.process(&[instruction], &[]).await
+    }
+
+    async fn top_up(&mut self, amount: u64) {
+        let instruction = top_up_stream_ix(
+            &self.stream,
+            &self.payer.pubkey(),
+            &self.payer_token_account,
+            &self.mint,
+            &TOKEN_2022_PROGRAM_ID,
+            amount,
+            &[],
+        );
+        self.env.process(&[instruction], &[]).await.unwrap();
+    }
+
+    async fn cancel(&mut self, payer: &Keypair) -> Result<(), solana_program_test::BanksClientError> {
+        let args = CancelStreamArgs {
+            payer: payer.pubkey(),
+            recipient: self.recipient.pubkey(),
+            payer_token_account: self.payer_token_account,
+            recipient_token_account: self.recipient_token_account,
+            mint: self.mint,
+            token_program: TOKEN_2022_PROGRAM_ID,
+        };
+        let instruction = cancel_stream_ix(&self.stream, &args, &[]);
+        self.env.process(&[instruction], &[payer]).await
+    }
+
+    async fn received(&mut self) -> u64 {
+        let token_account = self.recipient_token_account;
+        self.env.token_balance(&token_account).await
+    }
+}
+
+#[tokio::test]
+async fn stream_pauses_when_deposit_runs_out_and_resumes_on_top_up() {
+    let mut fixture = setup_stream().await;
+
+    fixture.env.set_time(fixture.start_time + 50).await;
+    fixture.withdraw().await.unwrap();
+    assert_eq!(fixture.received().await, 50 * RATE);
+
+    // Saldo acaba em 100s; o acumulado fica limitado ao depositado
+    fixture.env.set_time(fixture.start_time + 200).await;
+    fixture.withdraw().await.unwrap();
+    assert_eq!(fixture.received().await, DEPOSIT);
+    let stream = fixture.payment_stream().await;
+    assert!(stream.is_paused(fixture.start_time + 200));
+
+    // O intervalo sem saldo nao e acumulado: o stream volta a correr no aporte
+    fixture.env.set_time(fixture.start_time + 300).await;
+    fixture.top_up(DEPOSIT).await;
+    let stream = fixture.payment_stream().await;
+    assert!(!stream.is_paused(fixture.start_time + 300));
+    assert_eq!(stream.deposited_amount, 2 * DEPOSIT);
+
+    fixture.env.set_time(fixture.start_time + 330).await;
+    fixture.withdraw().await.unwrap();
+    assert_eq!(fixture.received().await, DEPOSIT + 30 * RATE);
+    assert_eq!(
+        fixture
+            .env
+            .token_balance(&escrow_address(&fixture.stream))
+            .await,
+        DEPOSIT - 30 * RATE
+    );
+}
+
+#[tokio::test]
+async fn top_up_before_depletion_keeps_stream_continuous() {
+    let mut fixture = setup_stream().await;
+
+    fixture.env.set_time(fixture.start_time + 40).await;
+    fixture.top_up(DEPOSIT).await;
+
+    fixture.env.set_time(fixture.start_time + 150).await;
+    fixture.withdraw().await.unwrap();
+    assert_eq!(fixture.received().await, 150 * RATE);
+}
+
+#[tokio::test]
+async fn cancel_settles_accrued_refunds_rest_and_closes_accounts() {
+    let mut fixture = setup_stream().await;
+
+    fixture.env.set_time(fixture.start_time + 20).await;
+    fixture.withdraw().await.unwrap();
+
+    // Apenas o pagador pode encerrar
+    fixture.env.set_time(fixture.start_time + 30).await;
+    let intruder = Keypair::new();
+    fixture.env.fund(&intruder.pubkey(), 1_000_000_000).await;
+    let result = fixture.cancel(&intruder).await;
+    assert_vesting_error(result, VestingError::Unauthorized);
+
+    let payer_balance = fixture.env.token_balance(&fixture.payer_token_account).await;
+    let payer = fixture.payer.insecure_clone();
+    fixture.cancel(&payer).await.unwrap();
+
+    assert_eq!(fixture.received().await, 30 * RATE);
+    assert_eq!(
+        fixture.env.token_balance(&fixture.payer_token_account).await,
+        payer_balance + DEPOSIT - 30 * RATE
+    );
+    let stream = fixture.stream;
+    let escrow = escrow_address(&stream);
+    for closed in [stream, escrow] {
+        assert!(fixture
+            .env
+            .context
+            .banks_client
+            .get_account(closed)
+            .await
+            .unwrap()
+            .is_none());
+    }
+}
+
+#[tokio::test]
+async fn nothing_to_withdraw_before_start() {
+    let mut fixture = setup_stream().await;
+    let stream = fixture.payment_stream().await;
+    assert_eq!(stream.accrued_amount(fixture.start_time), 0);
+
+    let result = fixture.withdraw().await;
+    assert_vesting_error(result, VestingError::NothingToRelease);
+}
+
+impl StreamFixture {
+    async fn payment_stream(&mut self) -> verum_vesting::PaymentStream {
+        let stream = self.stream;
+        self.env.payment_stream(&stream).await
+    }
+}
@@ -0,0 +1,228 @@
+mod common;
+
+use common::*;
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+use verum_vesting::{PriceTrancheInit, PriceTriggerConfig, VestingError, VestingType};
+
+const TOTAL: u64 = 1_000_000;
+const DAY: i64 = 86_400;
+
+struct PriceFixture {
+    env: TestEnv,
+    beneficiary: Keypair,
+    mint: Pubkey,
+    beneficiary_token_account: Pubkey,
+    vesting_contract: Pubkey,
+    price_feed: Pubkey,
+    start_time: i64,
+}
+
+async fn setup_price_trigger() -> PriceFixture {
+    let mut env = TestEnv::new().await;
+    let creator = env.payer();
+    let beneficiary = Keypair::new();
+    let mint = env.create_mint(&TOKEN_PROGRAM_ID, 6, None).await;
+    let creator_token_account = env.create_token_account(&mint, &creator.pubkey()).await;
+    let beneficiary_token_account = env.create_token_account(&mint, &beneficiary.pubkey()).await;
+    env.mint_to(&mint, &creator_token_account, TOTAL).await;
+
+    let start_time = env.now().await;
+    let args = CreateVestingArgs {
+        creator: creator.pubkey(),
+        beneficiary: beneficiary.pubkey(),
+        mint,
+        token_program: TOKEN_PROGRAM_ID,
+        sender_token_account: creator_token_account,
+        contract_id: 1,
+        total_amount: TOTAL,
+        start_time,
+        end_time: start_time + 1,
+        vesting_type: VestingType::PriceTriggered,
+        allowed_mint_flags: 0,
+    };
+    env.process(&[create_vesting_ix(&args, &[])], &[])
+        .await
+        .unwrap();
+
+    let price_feed = env.create_price_feed().await;
+    env.set_price(&price_feed, 1_500_000, 1_000, start_time)
+        .await;
+
+    let vesting_contract = vesting_address(&creator.pubkey(), &mint, 1);
+    let config = PriceTriggerConfig {
+        oracle_program: mock_price_feed::ID,
+        expo: PRICE_EXPO,
+        max_staleness: DAY,
+        max_confidence_bps: 100,
+    };
+    // 20% acima de 2.00 de imediato; 80% apos 3 dias seguidos acima de 3.00
+    let tranches = vec![
+        PriceTrancheInit {
+            threshold_price: 2_000_000,
+            hold_seconds: 0,
+            amount: 200_000,
+        },
+        PriceTrancheInit {
+            threshold_price: 3_000_000,
+            hold_seconds: 3 * DAY,
+            amount: 800_000,
+        },
+    ];
+    env.process(
+        &[create_price_trigger_ix(
+            &vesting_contract,
+            &creator.pubkey(),
+            &price_feed,
+            config,
+            tranches,
+        )],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    PriceFixture {
+        env,
+        beneficiary,
+        mint,
+        beneficiary_token_account,
+        vesting_contract,
+        price_feed,
+        start_time,
+    }
+}
+
+impl PriceFixture {
+    /// Avanca o relogio e publica um novo preco com confianca estreita.
+    async fn publish(&mut self, offset: i64, price: i64) {
+        let now = self.start_time + offset;
+        self.env.set_time(now).await;
+        let price_feed = self.price_feed;
+        self.env.set_price(&price_feed, price, 1_000, now).await;
+    }
+
+    async fn claim(&mut self) -> Result<(), solana_program_test::BanksClientError> {
+        let instruction = claim_price_triggered_ix(
+            &self.vesting_contract,
+            &self.beneficiary.pubkey(),
+            &self.beneficiary_token_account,
+            &self.mint,
+            &TOKEN_PROGRAM_ID,
+            &self.price_feed,
+        );
+        self.env.process(&[instruction], &[]).await
+    }
+
+    async fn refresh(&mut self) -> Result<(), solana_program_test::BanksClientError> {
+        let instruction = refresh_price_trigger_ix(&self.vesting_contract, &self.price_feed);
+        self.env.process(&[instruction], &[]).await
+    }
+
+    async fn claimed(&mut self) -> u64 {
+        let token_account = self.beneficiary_token_account;
+        self.env.token_balance(&token_account).await
+    }
+}
+
+#[tokio::test]
+async fn tranches_unlock_when_price_holds_above_threshold() {
+    let mut fixture = setup_price_trigger().await;
+
+    let result = fixture.claim().await;
+    assert_vesting_error(result, VestingError::NothingToRelease);
+
+    fixture.publish(100, 3_500_000).await;
+    fixture.claim().await.unwrap();
+    assert_eq!(fixture.claimed().await, 200_000);
+
+    fixture.publish(100 + DAY, 3_200_000).await;
+    fixture.refresh().await.unwrap();
+
+    // Uma observacao abaixo do limite reinicia a contagem
+    fixture.publish(100 + 2 * DAY, 2_500_000).await;
+    fixture.refresh().await.unwrap();
+    fixture.publish(100 + 3 * DAY, 3_500_000).await;
+    fixture.refresh().await.unwrap();
+    fixture.publish(100 + 4 * DAY, 3_500_000).await;
+    fixture.refresh().await.unwrap();
+
+    fixture.publish(100 + 5 * DAY, 3_500_000).await;
+    fixture.refresh().await.unwrap();
+    let result = fixture.claim().await;
+    assert_vesting_error(result, VestingError::NothingToRelease);
+
+    fixture.publish(100 + 6 * DAY, 3_500_000).await;
+    fixture.claim().await.unwrap();
+    assert_eq!(fixture.claimed().await, TOTAL);
+}
+
+#[tokio::test]
+async fn hold_restarts_when_observations_are_too_far_apart() {
+    let mut fixture = setup_price_trigger().await;
+
+    fixture.publish(100, 3_500_000).await;
+    fixture.claim().await.unwrap();
+    assert_eq!(fixture.claimed().await, 200_000);
+
+    // Nada foi observado por mais de `max_staleness`: o preco pode ter caido
+    fixture.publish(100 + 3 * DAY, 3_500_000).await;
+    let result = fixture.claim().await;
+    assert_vesting_error(result, VestingError::NothingToRelease);
+
+    for day in 4..=7 {
+        fixture.publish(100 + day * DAY, 3_500_000).await;
+        fixture.refresh().await.unwrap();
+    }
+    fixture.claim().await.unwrap();
+    assert_eq!(fixture.claimed().await, TOTAL);
+}
+
+#[tokio::test]
+async fn stale_or_uncertain_prices_are_rejected() {
+    let mut fixture = setup_price_trigger().await;
+
+    fixture.env.set_time(fixture.start_time + DAY + 120).await;
+    let price_feed = fixture.price_feed;
+    fixture
+        .env
+        .set_price(&price_feed, 3_500_000, 1_000, fixture.start_time)
+        .await;
+    let result = fixture.refresh().await;
+    assert_vesting_error(result, VestingError::StalePrice);
+
+    fixture.env.set_time(fixture.start_time + DAY + 130).await;
+    fixture
+        .env
+        .set_price(&price_feed, 3_500_000, 100_000, fixture.start_time + DAY + 130)
+        .await;
+    let result = fixture.refresh().await;
+    assert_vesting_error(result, VestingError::PriceConfidenceTooWide);
+}
+
+#[tokio::test]
+async fn claim_pays_unlocked_tranches_without_a_valid_price() {
+    let mut fixture = setup_price_trigger().await;
+
+    fixture.publish(100, 3_500_000).await;
+    fixture.refresh().await.unwrap();
+
+    // O feed parou de publicar: o resgate ignora a observacao e paga o ja liberado
+    fixture.env.set_time(fixture.start_time + 3 * DAY).await;
+    fixture.claim().await.unwrap();
+    assert_eq!(fixture.claimed().await, 200_000);
+
+    // Sem as contas de preco o resgate tambem funciona
+    let instruction = claim_tokens_ix(
+        &fixture.vesting_contract,
+        &fixture.beneficiary.pubkey(),
+        &fixture.beneficiary_token_account,
+        &fixture.mint,
+        &TOKEN_PROGRAM_ID,
+        &[],
+    );
+    let result = fixture.env.process(&[instruction], &[]).await;
+    assert_vesting_error(result, VestingError::NothingToRelease);
+}
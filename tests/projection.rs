@@ -0,0 +1,152 @@
+mod common;
+
+use anchor_lang::AnchorDeserialize;
+use common::*;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use verum_vesting::{
+    pause_policy, SchedulePoint, ScheduleProjection, ScheduleUnlock, UnlockKind, VestingType,
+    MAX_PROJECTION_POINTS,
+};
+
+const TOTAL: u64 = 1_000_000;
+
+async fn project(env: &mut TestEnv, vesting_contract: &Pubkey, points: u8) -> ScheduleProjection {
+    let data = env
+        .simulate_return_data(project_schedule_ix(vesting_contract, points))
+        .await;
+    ScheduleProjection::try_from_slice(&data).unwrap()
+}
+
+#[tokio::test]
+async fn projects_cliff_schedule_through_pause_and_cancel() {
+    let mut env = TestEnv::new().await;
+    let creator = env.payer();
+    let beneficiary = Keypair::new();
+    let mint = env.create_mint(&TOKEN_PROGRAM_ID, 6, None).await;
+    let creator_token_account = env.create_token_account(&mint, &creator.pubkey()).await;
+    env.mint_to(&mint, &creator_token_account, TOTAL).await;
+
+    let start_time = env.now().await;
+    let args = CreateVestingArgs {
+        creator: creator.pubkey(),
+        beneficiary: beneficiary.pubkey(),
+        mint,
+        token_program: TOKEN_PROGRAM_ID,
+        sender_token_account: creator_token_account,
+        contract_id: 1,
+        total_amount: TOTAL,
+        start_time,
+        end_time: start_time + 1_000,
+        vesting_type: VestingType::Cliff(start_time + 400, 25),
+        allowed_mint_flags: 0,
+    };
+    env.process(&[create_vesting_ix(&args, &[])], &[])
+        .await
+        .unwrap();
+    let vesting_contract = vesting_address(&creator.pubkey(), &mint, 1);
+
+    env.set_time(start_time + 100).await;
+    let projection = project(&mut env, &vesting_contract, 5).await;
+    assert_eq!(projection.total_amount, TOTAL);
+    assert_eq!(projection.vested_amount, 0);
+    assert_eq!(projection.claimable_amount, 0);
+    assert_eq!(projection.pause_shift, 0);
+    assert!(!projection.is_cancelled);
+    assert_eq!(
+        projection.unlocks,
+        vec![
+            ScheduleUnlock {
+                kind: UnlockKind::Start,
+                time: start_time,
+                vested_amount: 0,
+            },
+            ScheduleUnlock {
+                kind: UnlockKind::Cliff,
+                time: start_time + 400,
+                vested_amount: 250_000,
+            },
+            ScheduleUnlock {
+                kind: UnlockKind::End,
+                time: start_time + 1_000,
+                vested_amount: TOTAL,
+            },
+        ]
+    );
+    let curve: Vec<(i64, u64)> = projection
+        .points
+        .iter()
+        .map(|point| (point.time - start_time, point.vested_amount))
+        .collect();
+    assert_eq!(
+        curve,
+        vec![
+            (0, 0),
+            (250, 0),
+            (500, 375_000),
+            (750, 687_500),
+            (1_000, TOTAL)
+        ]
+    );
+
+    assert_eq!(
+        project(&mut env, &vesting_contract, u8::MAX)
+            .await
+            .points
+            .len(),
+        MAX_PROJECTION_POINTS as usize
+    );
+
+    // Pausa em +500 ainda em andamento em +700: o restante do cronograma anda 200s
+    let set_policy = set_pause_policy_ix(
+        &vesting_contract,
+        &creator.pubkey(),
+        &beneficiary.pubkey(),
+        pause_policy::CREATOR,
+    );
+    env.process(&[set_policy], &[&beneficiary]).await.unwrap();
+    env.set_time(start_time + 500).await;
+    env.process(
+        &[pause_vesting_ix(&vesting_contract, &creator.pubkey())],
+        &[],
+    )
+    .await
+    .unwrap();
+    env.set_time(start_time + 700).await;
+
+    let projection = project(&mut env, &vesting_contract, 2).await;
+    assert_eq!(projection.pause_shift, 200);
+    assert_eq!(projection.vested_amount, 375_000);
+    assert_eq!(projection.claimable_amount, 375_000);
+    assert_eq!(projection.unlocks[1].time, start_time + 600);
+    assert_eq!(projection.unlocks[2].time, start_time + 1_200);
+    assert_eq!(
+        projection.points,
+        vec![
+            SchedulePoint {
+                time: start_time + 200,
+                vested_amount: 0,
+            },
+            SchedulePoint {
+                time: start_time + 1_200,
+                vested_amount: TOTAL,
+            },
+        ]
+    );
+
+    // Cancelado: nenhuma liberacao futura
+    let cancel = cancel_vesting_ix(
+        &vesting_contract,
+        &creator.pubkey(),
+        &creator_token_account,
+        &mint,
+        &TOKEN_PROGRAM_ID,
+        &[],
+    );
+    env.process(&[cancel], &[]).await.unwrap();
+    let projection = project(&mut env, &vesting_contract, 5).await;
+    assert!(projection.is_cancelled);
+    assert_eq!(projection.claimable_amount, 0);
+    assert!(projection.unlocks.is_empty());
+    assert!(projection.points.is_empty());
+}
@@ -0,0 +1,96 @@
+mod common;
+
+use common::*;
+use solana_sdk::signature::{Keypair, Signer};
+use verum_vesting::{ReleaseReceipt, VestingType};
+
+const TOTAL: u64 = 1_000_000;
+
+#[tokio::test]
+async fn claims_issue_receipts_by_index() {
+    let mut env = TestEnv::new().await;
+    let creator = env.payer();
+    let beneficiary = Keypair::new();
+    let mint = env.create_mint(&TOKEN_PROGRAM_ID, 6, None).await;
+    let creator_token_account = env.create_token_account(&mint, &creator.pubkey()).await;
+    let beneficiary_token_account = env.create_token_account(&mint, &beneficiary.pubkey()).await;
+    env.mint_to(&mint, &creator_token_account, TOTAL).await;
+
+    let start_time = env.now().await;
+    let args = CreateVestingArgs {
+        creator: creator.pubkey(),
+        beneficiary: beneficiary.pubkey(),
+        mint,
+        token_program: TOKEN_PROGRAM_ID,
+        sender_token_account: creator_token_account,
+        contract_id: 1,
+        total_amount: TOTAL,
+        start_time,
+        end_time: start_time + 1_000,
+        vesting_type: VestingType::Linear,
+        allowed_mint_flags: 0,
+    };
+    env.process(&[create_vesting_ix(&args, &[])], &[])
+        .await
+        .unwrap();
+    let vesting_contract = vesting_address(&creator.pubkey(), &mint, 1);
+
+    // Qualquer um pode pagar o aluguel do recibo
+    let receipt_payer = Keypair::new();
+    env.fund(&receipt_payer.pubkey(), 1_000_000_000).await;
+    let claim_with_receipt = |claim_index| {
+        claim_with_receipt_ix(
+            &vesting_contract,
+            &beneficiary.pubkey(),
+            &beneficiary_token_account,
+            &mint,
+            &TOKEN_PROGRAM_ID,
+            claim_index,
+            &receipt_payer.pubkey(),
+        )
+    };
+
+    env.set_time(start_time + 250).await;
+    env.process(&[claim_with_receipt(0)], &[&receipt_payer])
+        .await
+        .unwrap();
+
+    let receipt: ReleaseReceipt = env
+        .anchor_account(&release_receipt_address(&vesting_contract, 0))
+        .await;
+    assert_eq!(receipt.vesting_contract, vesting_contract);
+    assert_eq!(receipt.claim_index, 0);
+    assert_eq!(receipt.destination, beneficiary_token_account);
+    assert_eq!(receipt.amount, TOTAL / 4);
+    assert_eq!(receipt.timestamp, start_time + 250);
+    assert!(receipt.slot > 0);
+
+    // Resgate sem recibo tambem consome um indice
+    env.set_time(start_time + 500).await;
+    let claim = claim_tokens_ix(
+        &vesting_contract,
+        &beneficiary.pubkey(),
+        &beneficiary_token_account,
+        &mint,
+        &TOKEN_PROGRAM_ID,
+        &[],
+    );
+    env.process(&[claim], &[]).await.unwrap();
+
+    env.set_time(start_time + 1_000).await;
+    assert!(env
+        .process(&[claim_with_receipt(1)], &[&receipt_payer])
+        .await
+        .is_err());
+    env.process(&[claim_with_receipt(2)], &[&receipt_payer])
+        .await
+        .unwrap();
+
+    let receipt: ReleaseReceipt = env
+        .anchor_account(&release_receipt_address(&vesting_contract, 2))
+        .await;
+    assert_eq!(receipt.claim_index, 2);
+    assert_eq!(receipt.amount, TOTAL / 2);
+    assert_eq!(env.vesting_contract(&vesting_contract).await.claim_count, 3);
+    assert_eq!(env.token_balance(&beneficiary_token_account).await, TOTAL);
+}
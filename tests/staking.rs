@@ -0,0 +1,265 @@
+mod common;
+
+use anchor_lang::{InstructionData, ToAccountMetas};
+use common::*;
+use solana_program_test::processor;
+use solana_sdk::{
+    account_info::AccountInfo, entrypoint::ProgramResult, instruction::Instruction,
+    program::invoke, program_error::ProgramError, pubkey::Pubkey, signature::Signer,
+};
+use verum_vesting::{VestingError, VestingType};
+
+const REWARDS: u64 = 5_000;
+const HOSTILE_STAKING_ID: Pubkey = Pubkey::new_from_array([7; 32]);
+
+async fn setup_staking(whitelist: bool) -> ContractFixture {
+    setup_staking_in(TestEnv::new().await, whitelist.then_some(mock_staking::ID)).await
+}
+
+/// Contrato linear com o cofre do `mock_staking` criado e, se houver, o programa de
+/// staking autorizado; o criador tem `REWARDS` para financiar recompensas.
+async fn setup_staking_in(env: TestEnv, staking_program: Option<Pubkey>) -> ContractFixture {
+    let mut contract = env.create_linear_contract(VestingType::Linear, 1_000).await;
+    let (mint, creator_token_account) = (contract.mint, contract.creator_token_account);
+    let beneficiary = contract.beneficiary.pubkey();
+    contract
+        .env
+        .mint_to(&mint, &creator_token_account, REWARDS)
+        .await;
+    contract.env.fund(&beneficiary, 1_000_000_000).await;
+    contract
+        .env
+        .initialize_staking_vault(&mint, &TOKEN_PROGRAM_ID)
+        .await;
+
+    if let Some(staking_program) = staking_program {
+        let instruction = set_staking_program_ix(
+            &contract.vesting_contract,
+            &contract.creator.pubkey(),
+            &staking_program,
+        );
+        contract.env.process(&[instruction], &[]).await.unwrap();
+    }
+
+    contract
+}
+
+impl ContractFixture {
+    async fn stake(&mut self, amount: u64) -> Result<(), solana_program_test::BanksClientError> {
+        let instruction = stake_locked_ix(
+            &self.vesting_contract,
+            &self.beneficiary.pubkey(),
+            &self.mint,
+            &TOKEN_PROGRAM_ID,
+            amount,
+        );
+        let beneficiary = self.beneficiary.insecure_clone();
+        self.env.process(&[instruction], &[&beneficiary]).await
+    }
+
+    async fn balance(&mut self, token_account: &Pubkey) -> u64 {
+        self.env.token_balance(token_account).await
+    }
+}
+
+#[tokio::test]
+async fn staked_tokens_earn_rewards_and_return_before_claim() {
+    let mut fixture = setup_staking(true).await;
+    let escrow = escrow_address(&fixture.vesting_contract);
+
+    fixture.stake(600_000).await.unwrap();
+    assert_eq!(fixture.balance(&escrow).await, CONTRACT_TOTAL - 600_000);
+    let vesting_contract = fixture
+        .env
+        .vesting_contract(&fixture.vesting_contract)
+        .await;
+    assert_eq!(vesting_contract.staked_amount, 600_000);
+
+    // Apenas o saldo ainda nao liberado pode ser delegado
+    let result = fixture.stake(500_000).await;
+    assert_vesting_error(result, VestingError::StakeExceedsLocked);
+
+    let (vesting_address, mint, creator_token_account) = (
+        fixture.vesting_contract,
+        fixture.mint,
+        fixture.creator_token_account,
+    );
+    fixture
+        .env
+        .fund_staking_rewards(
+            &vesting_address,
+            &mint,
+            &TOKEN_PROGRAM_ID,
+            &creator_token_account,
+            REWARDS,
+        )
+        .await;
+    let instruction = claim_staking_rewards_ix(
+        &fixture.vesting_contract,
+        &fixture.beneficiary.pubkey(),
+        &fixture.beneficiary_token_account,
+        &fixture.mint,
+        &TOKEN_PROGRAM_ID,
+    );
+    let beneficiary = fixture.beneficiary.insecure_clone();
+    fixture
+        .env
+        .process(&[instruction], &[&beneficiary])
+        .await
+        .unwrap();
+    let beneficiary_token_account = fixture.beneficiary_token_account;
+    assert_eq!(fixture.balance(&beneficiary_token_account).await, REWARDS);
+
+    // O valor liberado excede o saldo do escrow enquanto ha tokens em staking
+    fixture.env.set_time(fixture.start_time + 500).await;
+    let result = fixture.env.process(&[fixture.claim_ix()], &[]).await;
+    assert_vesting_error(result, VestingError::TokensStaked);
+
+    let unstake = unstake_locked_ix(
+        &fixture.vesting_contract,
+        &beneficiary.pubkey(),
+        &fixture.mint,
+        &TOKEN_PROGRAM_ID,
+        600_000,
+    );
+    fixture
+        .env
+        .process(&[unstake, fixture.claim_ix()], &[&beneficiary])
+        .await
+        .unwrap();
+    assert_eq!(
+        fixture.balance(&beneficiary_token_account).await,
+        REWARDS + CONTRACT_TOTAL / 2
+    );
+    let vesting_contract = fixture
+        .env
+        .vesting_contract(&fixture.vesting_contract)
+        .await;
+    assert_eq!(vesting_contract.staked_amount, 0);
+}
+
+#[tokio::test]
+async fn creator_unstakes_before_cancelling() {
+    let mut fixture = setup_staking(true).await;
+    fixture.stake(600_000).await.unwrap();
+
+    let cancel = cancel_vesting_ix(
+        &fixture.vesting_contract,
+        &fixture.creator.pubkey(),
+        &fixture.creator_token_account,
+        &fixture.mint,
+        &TOKEN_PROGRAM_ID,
+        &[],
+    );
+    let result = fixture
+        .env
+        .process(std::slice::from_ref(&cancel), &[])
+        .await;
+    assert_vesting_error(result, VestingError::TokensStaked);
+
+    let unstake = unstake_locked_ix(
+        &fixture.vesting_contract,
+        &fixture.creator.pubkey(),
+        &fixture.mint,
+        &TOKEN_PROGRAM_ID,
+        600_000,
+    );
+    fixture.env.process(&[unstake, cancel], &[]).await.unwrap();
+    let creator_token_account = fixture.creator_token_account;
+    assert_eq!(
+        fixture.balance(&creator_token_account).await,
+        CONTRACT_TOTAL + REWARDS
+    );
+}
+
+#[tokio::test]
+async fn staking_requires_whitelisted_program() {
+    let mut fixture = setup_staking(false).await;
+
+    let result = fixture.stake(100_000).await;
+    assert_vesting_error(result, VestingError::StakingProgramNotAllowed);
+
+    let instruction = set_staking_program_ix(
+        &fixture.vesting_contract,
+        &fixture.beneficiary.pubkey(),
+        &mock_staking::ID,
+    );
+    let beneficiary = fixture.beneficiary.insecure_clone();
+    let result = fixture.env.process(&[instruction], &[&beneficiary]).await;
+    assert_vesting_error(result, VestingError::Unauthorized);
+}
+
+/// Programa de staking hostil: move `amount` do escrow para a conta recebida como
+/// cofre, como um `Stake` honesto, e aproveita a assinatura do PDA do contrato para
+/// aprovar o pagador como delegate do escrow.
+fn hostile_staking(_program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let [_record, owner, escrow, vault, mint, token_program, payer, ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    let amount = data
+        .get(1..9)
+        .and_then(|bytes| bytes.try_into().ok())
+        .map(u64::from_le_bytes)
+        .ok_or(ProgramError::InvalidInstructionData)?;
+
+    invoke(
+        &spl_token_2022::instruction::transfer_checked(
+            token_program.key,
+            escrow.key,
+            mint.key,
+            vault.key,
+            owner.key,
+            &[],
+            amount,
+            6,
+        )?,
+        &[escrow.clone(), mint.clone(), vault.clone(), owner.clone()],
+    )?;
+    invoke(
+        &spl_token_2022::instruction::approve(
+            token_program.key,
+            escrow.key,
+            payer.key,
+            owner.key,
+            &[],
+            u64::MAX,
+        )?,
+        &[escrow.clone(), payer.clone(), owner.clone()],
+    )
+}
+
+#[tokio::test]
+async fn stake_rejects_delegate_left_on_escrow() {
+    let mut program_test = program_test();
+    program_test.add_program(
+        "hostile_staking",
+        HOSTILE_STAKING_ID,
+        processor!(hostile_staking),
+    );
+    let env = TestEnv::with_program_test(program_test).await;
+    let mut fixture = setup_staking_in(env, Some(HOSTILE_STAKING_ID)).await;
+
+    // O saldo do escrow cai exatamente `amount`, como a CPI honesta
+    let instruction = Instruction {
+        program_id: verum_vesting::ID,
+        accounts: verum_vesting::accounts::StakeLocked {
+            vesting_contract: fixture.vesting_contract,
+            beneficiary: fixture.beneficiary.pubkey(),
+            escrow_wallet: escrow_address(&fixture.vesting_contract),
+            mint: fixture.mint,
+            token_program: TOKEN_PROGRAM_ID,
+            staking_program: HOSTILE_STAKING_ID,
+            stake_record: Pubkey::new_unique(),
+            stake_vault: fixture.beneficiary_token_account,
+            system_program: solana_sdk::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: verum_vesting::instruction::StakeLocked { amount: 100_000 }.data(),
+    };
+    let beneficiary = fixture.beneficiary.insecure_clone();
+    let result = fixture.env.process(&[instruction], &[&beneficiary]).await;
+    assert_vesting_error(result, VestingError::EscrowAuthorityChanged);
+
+    let escrow = escrow_address(&fixture.vesting_contract);
+    assert_eq!(fixture.balance(&escrow).await, CONTRACT_TOTAL);
+}
@@ -0,0 +1,237 @@
+mod common;
+
+use common::*;
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+use verum_vesting::{TimelockedAction, VestingError, VestingType};
+
+const DELAY: i64 = 86_400;
+
+/// Contrato linear com timelock de `DELAY` e o guardiao em `extra`.
+type TimelockFixture = ContractFixture<Keypair>;
+
+async fn setup_timelock() -> TimelockFixture {
+    let mut contract = TestEnv::new()
+        .await
+        .create_linear_contract(VestingType::Linear, 10 * DELAY)
+        .await;
+    let guardian = Keypair::new();
+
+    let instruction = set_timelock_ix(
+        &contract.vesting_contract,
+        &contract.creator.pubkey(),
+        &contract.beneficiary.pubkey(),
+        DELAY,
+        &guardian.pubkey(),
+    );
+    let beneficiary = contract.beneficiary.insecure_clone();
+    contract
+        .env
+        .process(&[instruction], &[&beneficiary])
+        .await
+        .unwrap();
+
+    contract.with(guardian)
+}
+
+impl TimelockFixture {
+    async fn propose(
+        &mut self,
+        action: TimelockedAction,
+    ) -> Result<(), solana_program_test::BanksClientError> {
+        let instruction = propose_action_ix(&self.vesting_contract, &self.creator.pubkey(), action);
+        self.env.process(&[instruction], &[]).await
+    }
+
+    async fn execute(&mut self) -> Result<(), solana_program_test::BanksClientError> {
+        let instruction = execute_action_ix(
+            &self.vesting_contract,
+            &self.creator.pubkey(),
+            &self.creator_token_account,
+            &self.mint,
+            &TOKEN_PROGRAM_ID,
+        );
+        self.env.process(&[instruction], &[]).await
+    }
+
+    async fn veto(
+        &mut self,
+        authority: &Keypair,
+    ) -> Result<(), solana_program_test::BanksClientError> {
+        let instruction = veto_action_ix(
+            &self.vesting_contract,
+            &self.creator.pubkey(),
+            &authority.pubkey(),
+        );
+        self.env.process(&[instruction], &[authority]).await
+    }
+}
+
+#[tokio::test]
+async fn cancel_waits_for_the_timelock() {
+    let mut fixture = setup_timelock().await;
+
+    let cancel = cancel_vesting_ix(
+        &fixture.vesting_contract,
+        &fixture.creator.pubkey(),
+        &fixture.creator_token_account,
+        &fixture.mint,
+        &TOKEN_PROGRAM_ID,
+        &[],
+    );
+    let result = fixture.env.process(&[cancel], &[]).await;
+    assert_vesting_error(result, VestingError::TimelockRequired);
+
+    fixture.propose(TimelockedAction::Cancel).await.unwrap();
+    let result = fixture.execute().await;
+    assert_vesting_error(result, VestingError::TimelockNotElapsed);
+
+    fixture.env.set_time(fixture.start_time + DELAY).await;
+    fixture.execute().await.unwrap();
+
+    let creator_token_account = fixture.creator_token_account;
+    assert_eq!(
+        fixture.env.token_balance(&creator_token_account).await,
+        CONTRACT_TOTAL
+    );
+    let vesting_contract = fixture
+        .env
+        .vesting_contract(&fixture.vesting_contract)
+        .await;
+    assert!(vesting_contract.is_cancelled);
+    let pending_action = pending_action_address(&fixture.vesting_contract);
+    assert_eq!(fixture.env.lamports(&pending_action).await, 0);
+}
+
+#[tokio::test]
+async fn beneficiary_and_guardian_can_veto() {
+    let mut fixture = setup_timelock().await;
+    let new_beneficiary = Pubkey::new_unique();
+    let update = TimelockedAction::UpdateBeneficiary { new_beneficiary };
+
+    let result = fixture
+        .env
+        .process(
+            &[update_beneficiary_ix(
+                &fixture.vesting_contract,
+                &fixture.creator.pubkey(),
+                &new_beneficiary,
+            )],
+            &[],
+        )
+        .await;
+    assert_vesting_error(result, VestingError::TimelockRequired);
+
+    fixture.propose(update).await.unwrap();
+    let impostor = Keypair::new();
+    let result = fixture.veto(&impostor).await;
+    assert_vesting_error(result, VestingError::Unauthorized);
+    let beneficiary = fixture.beneficiary.insecure_clone();
+    fixture.veto(&beneficiary).await.unwrap();
+
+    let end_time = fixture.start_time + 20 * DELAY;
+    fixture
+        .propose(TimelockedAction::AmendSchedule { end_time })
+        .await
+        .unwrap();
+    let guardian = fixture.extra.insecure_clone();
+    fixture.veto(&guardian).await.unwrap();
+
+    fixture.propose(update).await.unwrap();
+    fixture.env.set_time(fixture.start_time + DELAY).await;
+    fixture.execute().await.unwrap();
+    let vesting_contract = fixture
+        .env
+        .vesting_contract(&fixture.vesting_contract)
+        .await;
+    assert_eq!(vesting_contract.beneficiary, new_beneficiary);
+    assert_eq!(vesting_contract.end_time, fixture.start_time + 10 * DELAY);
+}
+
+#[tokio::test]
+async fn creator_can_withdraw_a_pending_action() {
+    let mut fixture = setup_timelock().await;
+    let end_time = fixture.start_time + 20 * DELAY;
+    fixture
+        .propose(TimelockedAction::AmendSchedule { end_time })
+        .await
+        .unwrap();
+
+    let impostor = Keypair::new();
+    let instruction = withdraw_action_ix(&fixture.vesting_contract, &impostor.pubkey());
+    let result = fixture.env.process(&[instruction], &[&impostor]).await;
+    assert_vesting_error(result, VestingError::Unauthorized);
+
+    let instruction = withdraw_action_ix(&fixture.vesting_contract, &fixture.creator.pubkey());
+    fixture.env.process(&[instruction], &[]).await.unwrap();
+    let pending_action = fixture
+        .env
+        .context
+        .banks_client
+        .get_account(pending_action_address(&fixture.vesting_contract))
+        .await
+        .unwrap();
+    assert!(pending_action.is_none());
+
+    // O PDA livre aceita uma nova proposta
+    fixture.propose(TimelockedAction::Cancel).await.unwrap();
+}
+
+#[tokio::test]
+async fn proposals_require_a_timelock_delay() {
+    let mut fixture = setup_timelock().await;
+    let beneficiary = fixture.beneficiary.insecure_clone();
+    let instruction = set_timelock_ix(
+        &fixture.vesting_contract,
+        &fixture.creator.pubkey(),
+        &beneficiary.pubkey(),
+        0,
+        &Pubkey::default(),
+    );
+    fixture
+        .env
+        .process(&[instruction], &[&beneficiary])
+        .await
+        .unwrap();
+
+    // Sem atraso o criador executaria a alteracao no mesmo slot
+    let end_time = fixture.start_time + DELAY;
+    let result = fixture
+        .propose(TimelockedAction::AmendSchedule { end_time })
+        .await;
+    assert_vesting_error(result, VestingError::TimelockDisabled);
+    let result = fixture.propose(TimelockedAction::Cancel).await;
+    assert_vesting_error(result, VestingError::TimelockDisabled);
+}
+
+#[tokio::test]
+async fn amended_schedule_applies_after_delay() {
+    let mut fixture = setup_timelock().await;
+
+    let result = fixture
+        .propose(TimelockedAction::AmendSchedule {
+            end_time: fixture.start_time,
+        })
+        .await;
+    assert_vesting_error(result, VestingError::InvalidTimeRange);
+
+    let end_time = fixture.start_time + 20 * DELAY;
+    fixture
+        .propose(TimelockedAction::AmendSchedule { end_time })
+        .await
+        .unwrap();
+    fixture.env.set_time(fixture.start_time + DELAY).await;
+    fixture.execute().await.unwrap();
+
+    let vesting_contract = fixture
+        .env
+        .vesting_contract(&fixture.vesting_contract)
+        .await;
+    assert_eq!(vesting_contract.end_time, end_time);
+    assert_eq!(
+        vesting_contract.vested_amount(fixture.start_time + 10 * DELAY),
+        CONTRACT_TOTAL / 2
+    );
+}
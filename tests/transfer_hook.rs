@@ -0,0 +1,217 @@
+mod common;
+
+use common::*;
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+use verum_vesting::{VestingError, VestingType};
+
+const TOTAL: u64 = 1_000_000;
+
+struct HookFixture {
+    env: TestEnv,
+    creator: Keypair,
+    beneficiary: Keypair,
+    mint: Pubkey,
+    creator_token_account: Pubkey,
+    beneficiary_token_account: Pubkey,
+    start_time: i64,
+}
+
+async fn setup_hook_mint() -> HookFixture {
+    let mut env = TestEnv::new().await;
+    let creator = env.payer();
+    let beneficiary = Keypair::new();
+
+    let mint = env
+        .create_mint(&TOKEN_2022_PROGRAM_ID, 6, Some(dummy_transfer_hook::ID))
+        .await;
+    env.initialize_transfer_hook(&mint).await;
+
+    let creator_token_account = env.create_token_account(&mint, &creator.pubkey()).await;
+    let beneficiary_token_account = env.create_token_account(&mint, &beneficiary.pubkey()).await;
+    env.mint_to(&mint, &creator_token_account, TOTAL).await;
+
+    let start_time = env.now().await;
+    HookFixture {
+        env,
+        creator,
+        beneficiary,
+        mint,
+        creator_token_account,
+        beneficiary_token_account,
+        start_time,
+    }
+}
+
+fn create_args(fixture: &HookFixture) -> CreateVestingArgs {
+    CreateVestingArgs {
+        creator: fixture.creator.pubkey(),
+        beneficiary: fixture.beneficiary.pubkey(),
+        mint: fixture.mint,
+        token_program: TOKEN_2022_PROGRAM_ID,
+        sender_token_account: fixture.creator_token_account,
+        contract_id: 1,
+        total_amount: TOTAL,
+        start_time: fixture.start_time,
+        end_time: fixture.start_time + 1_000,
+        vesting_type: VestingType::Linear,
+        allowed_mint_flags: 0,
+    }
+}
+
+#[tokio::test]
+async fn create_claim_and_cancel_with_transfer_hook_mint() {
+    let mut fixture = setup_hook_mint().await;
+    let args = create_args(&fixture);
+    let vesting_contract = vesting_address(&args.creator, &args.mint, args.contract_id);
+    let escrow = escrow_address(&vesting_contract);
+
+    let hook_accounts = fixture
+        .env
+        .transfer_hook_accounts(
+            &fixture.mint,
+            &fixture.creator_token_account,
+            &escrow,
+            &fixture.creator.pubkey(),
+            TOTAL,
+        )
+        .await;
+    fixture
+        .env
+        .process(&[create_vesting_ix(&args, &hook_accounts)], &[])
+        .await
+        .unwrap();
+    assert_eq!(fixture.env.token_balance(&escrow).await, TOTAL);
+    assert_eq!(fixture.env.transfer_hook_counter(&fixture.mint).await, 1);
+
+    fixture.env.set_time(fixture.start_time + 500).await;
+    let hook_accounts = fixture
+        .env
+        .transfer_hook_accounts(
+            &fixture.mint,
+            &escrow,
+            &fixture.beneficiary_token_account,
+            &vesting_contract,
+            TOTAL / 2,
+        )
+        .await;
+    fixture
+        .env
+        .process(
+            &[claim_tokens_ix(
+                &vesting_contract,
+                &fixture.beneficiary.pubkey(),
+                &fixture.beneficiary_token_account,
+                &fixture.mint,
+                &TOKEN_2022_PROGRAM_ID,
+                &hook_accounts,
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        fixture
+            .env
+            .token_balance(&fixture.beneficiary_token_account)
+            .await,
+        TOTAL / 2
+    );
+    assert_eq!(fixture.env.transfer_hook_counter(&fixture.mint).await, 2);
+
+    let hook_accounts = fixture
+        .env
+        .transfer_hook_accounts(
+            &fixture.mint,
+            &escrow,
+            &fixture.creator_token_account,
+            &vesting_contract,
+            TOTAL / 2,
+        )
+        .await;
+    fixture
+        .env
+        .process(
+            &[cancel_vesting_ix(
+                &vesting_contract,
+                &fixture.creator.pubkey(),
+                &fixture.creator_token_account,
+                &fixture.mint,
+                &TOKEN_2022_PROGRAM_ID,
+                &hook_accounts,
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+    assert_eq!(fixture.env.token_balance(&escrow).await, 0);
+    assert_eq!(
+        fixture
+            .env
+            .token_balance(&fixture.creator_token_account)
+            .await,
+        TOTAL / 2
+    );
+    assert_eq!(fixture.env.transfer_hook_counter(&fixture.mint).await, 3);
+}
+
+#[tokio::test]
+async fn create_vesting_without_hook_accounts_fails() {
+    let mut fixture = setup_hook_mint().await;
+    let args = create_args(&fixture);
+
+    let result = fixture
+        .env
+        .process(&[create_vesting_ix(&args, &[])], &[])
+        .await;
+    assert_vesting_error(result, VestingError::MissingTransferHookAccounts);
+    assert_eq!(fixture.env.transfer_hook_counter(&fixture.mint).await, 0);
+}
+
+#[tokio::test]
+async fn spl_token_mint_needs_no_extra_accounts() {
+    let mut env = TestEnv::new().await;
+    let creator = env.payer();
+    let beneficiary = Keypair::new();
+    let mint = env.create_mint(&TOKEN_PROGRAM_ID, 6, None).await;
+    let creator_token_account = env.create_token_account(&mint, &creator.pubkey()).await;
+    let beneficiary_token_account = env.create_token_account(&mint, &beneficiary.pubkey()).await;
+    env.mint_to(&mint, &creator_token_account, TOTAL).await;
+
+    let start_time = env.now().await;
+    let args = CreateVestingArgs {
+        creator: creator.pubkey(),
+        beneficiary: beneficiary.pubkey(),
+        mint,
+        token_program: TOKEN_PROGRAM_ID,
+        sender_token_account: creator_token_account,
+        contract_id: 7,
+        total_amount: TOTAL,
+        start_time,
+        end_time: start_time + 100,
+        vesting_type: VestingType::Linear,
+        allowed_mint_flags: 0,
+    };
+    env.process(&[create_vesting_ix(&args, &[])], &[])
+        .await
+        .unwrap();
+
+    env.set_time(start_time + 100).await;
+    let vesting_contract = vesting_address(&creator.pubkey(), &mint, 7);
+    env.process(
+        &[claim_tokens_ix(
+            &vesting_contract,
+            &beneficiary.pubkey(),
+            &beneficiary_token_account,
+            &mint,
+            &TOKEN_PROGRAM_ID,
+            &[],
+        )],
+        &[],
+    )
+    .await
+    .unwrap();
+    assert_eq!(env.token_balance(&beneficiary_token_account).await, TOTAL);
+}
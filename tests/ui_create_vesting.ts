@@ -0,0 +1,67 @@
+import * as anchor from "@coral-xyz/anchor";
+import { AnchorProvider as SerumProvider, Program as SerumProgram, BN } from "@project-serum/anchor";
+import { createAssociatedTokenAccount, createMint, getAccount, mintTo } from "@solana/spl-token";
+import { assert } from "chai";
+import { createVestingTransaction } from "../src/utils/createTransaction";
+import { findAuditLogAddress } from "../src/utils/audit-log";
+import { extractTransactionEvents } from "../src/utils/vesting-events";
+import { VESTING_IDL } from "../src/utils/vesting-idl";
+
+// Caminho de criacao do app: IDL embutido, contas montadas por createVestingTransaction
+// e simulacao antes do envio, contra o programa do workspace.
+describe("ui create_vesting", () => {
+    const provider = anchor.AnchorProvider.env();
+    anchor.setProvider(provider);
+
+    const programId = anchor.workspace.VerumVesting.programId as anchor.web3.PublicKey;
+    const sender = provider.wallet as anchor.Wallet;
+    const uiProvider = new SerumProvider(provider.connection, sender as any, { commitment: "confirmed" });
+    const program = new SerumProgram(VESTING_IDL, programId, uiProvider);
+
+    it("simulates and sends the app's create_vesting with event CPI accounts", async () => {
+        // Freeze authority presente, como em USDC/USDT: aceita pelo padrao do app
+        const mint = await createMint(provider.connection, sender.payer, sender.publicKey, sender.publicKey, 6);
+        const senderTokenAccount = await createAssociatedTokenAccount(
+            provider.connection,
+            sender.payer,
+            mint,
+            sender.publicKey
+        );
+        await mintTo(provider.connection, sender.payer, mint, senderTokenAccount, sender.payer, 1_000_000);
+
+        const beneficiary = anchor.web3.Keypair.generate().publicKey;
+        const now = Math.floor(Date.now() / 1000);
+        const { tx, vestingContract } = await createVestingTransaction(program, provider.connection, {
+            contractId: new BN(Date.now()),
+            totalAmount: new BN(1_000_000),
+            startTime: new BN(now),
+            endTime: new BN(now + 1_000),
+            vestingType: { linear: {} },
+            mintAddress: mint.toBase58(),
+            creator: sender.publicKey,
+            beneficiary,
+        });
+
+        const vestingContractKey = new anchor.web3.PublicKey(vestingContract);
+        const [escrowWallet] = anchor.web3.PublicKey.findProgramAddressSync(
+            [Buffer.from("escrow"), vestingContractKey.toBuffer()],
+            programId
+        );
+        const escrow = await getAccount(provider.connection, escrowWallet);
+        assert.equal(Number(escrow.amount), 1_000_000);
+
+        // `auditLog` = id do programa e lido como None: nenhum log e criado
+        const auditLog = await provider.connection.getAccountInfo(findAuditLogAddress(vestingContractKey, programId));
+        assert.isNull(auditLog);
+
+        const confirmed = await provider.connection.getTransaction(tx, {
+            commitment: "confirmed",
+            maxSupportedTransactionVersion: 0,
+        });
+        assert.isNotNull(confirmed);
+        const created = extractTransactionEvents(confirmed!, programId).filter(
+            (event) => event.name === "VestingCreated" && event.vestingContract === vestingContract
+        );
+        assert.lengthOf(created, 1);
+    });
+});
@@ -0,0 +1,478 @@
+mod common;
+
+use anchor_lang::error::ErrorCode;
+use common::*;
+use solana_sdk::instruction::AccountMeta;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use verum_vesting::{VestingError, VestingType};
+
+const TOTAL: u64 = 1_000_000;
+
+struct Fixture {
+    env: TestEnv,
+    creator: Keypair,
+    mint: Pubkey,
+    token_program: Pubkey,
+    creator_token_account: Pubkey,
+    start_time: i64,
+}
+
+impl Fixture {
+    /// Mint do `token_program` com `TOTAL` tokens por contrato na conta do criador.
+    async fn new(token_program: Pubkey, contracts: u64) -> Self {
+        let mut env = TestEnv::new().await;
+        let creator = env.payer();
+        let mint = env.create_mint(&token_program, 6, None).await;
+        let creator_token_account = env.create_token_account(&mint, &creator.pubkey()).await;
+        env.mint_to(&mint, &creator_token_account, TOTAL * contracts)
+            .await;
+        let start_time = env.now().await;
+        Self {
+            env,
+            creator,
+            mint,
+            token_program,
+            creator_token_account,
+            start_time,
+        }
+    }
+
+    /// Cria o contrato `contract_id` com duracao de 1000s a partir de `start_time`.
+    async fn create(
+        &mut self,
+        contract_id: u64,
+        beneficiary: &Pubkey,
+        vesting_type: VestingType,
+    ) -> Pubkey {
+        let args = CreateVestingArgs {
+            creator: self.creator.pubkey(),
+            beneficiary: *beneficiary,
+            mint: self.mint,
+            token_program: self.token_program,
+            sender_token_account: self.creator_token_account,
+            contract_id,
+            total_amount: TOTAL,
+            start_time: self.start_time,
+            end_time: self.start_time + 1_000,
+            vesting_type,
+            allowed_mint_flags: 0,
+        };
+        self.env
+            .process(&[create_vesting_ix(&args, &[])], &[])
+            .await
+            .unwrap();
+        vesting_address(&self.creator.pubkey(), &self.mint, contract_id)
+    }
+
+    async fn claim(
+        &mut self,
+        vesting_contract: &Pubkey,
+        beneficiary: &Pubkey,
+        beneficiary_token_account: &Pubkey,
+    ) -> Result<(), solana_program_test::BanksClientError> {
+        let instruction = claim_tokens_ix(
+            vesting_contract,
+            beneficiary,
+            beneficiary_token_account,
+            &self.mint,
+            &self.token_program,
+            &[],
+        );
+        self.env.process(&[instruction], &[]).await
+    }
+
+    async fn cancel(
+        &mut self,
+        vesting_contract: &Pubkey,
+    ) -> Result<(), solana_program_test::BanksClientError> {
+        let instruction = cancel_vesting_ix(
+            vesting_contract,
+            &self.creator.pubkey(),
+            &self.creator_token_account,
+            &self.mint,
+            &self.token_program,
+            &[],
+        );
+        self.env.process(&[instruction], &[]).await
+    }
+}
+
+/// Cliff de 25% em 400s, troca de beneficiario antes do primeiro resgate e resgate
+/// final apos o fim.
+async fn run_cliff_lifecycle(token_program: Pubkey) {
+    let mut fixture = Fixture::new(token_program, 1).await;
+    let start_time = fixture.start_time;
+    let beneficiary = Keypair::new();
+    let beneficiary_token_account = fixture
+        .env
+        .create_token_account(&fixture.mint, &beneficiary.pubkey())
+        .await;
+    let vesting_contract = fixture
+        .create(
+            1,
+            &beneficiary.pubkey(),
+            VestingType::Cliff(start_time + 400, 25),
+        )
+        .await;
+
+    let escrow = escrow_address(&vesting_contract);
+    assert_eq!(fixture.env.owner_of(&escrow).await, token_program);
+    assert_eq!(fixture.env.token_balance(&escrow).await, TOTAL);
+    assert_eq!(
+        fixture
+            .env
+            .token_balance(&fixture.creator_token_account)
+            .await,
+        0
+    );
+
+    fixture.env.set_time(start_time + 399).await;
+    assert_vesting_error(
+        fixture
+            .claim(
+                &vesting_contract,
+                &beneficiary.pubkey(),
+                &beneficiary_token_account,
+            )
+            .await,
+        VestingError::NothingToRelease,
+    );
+
+    let new_beneficiary = Keypair::new();
+    let new_beneficiary_token_account = fixture
+        .env
+        .create_token_account(&fixture.mint, &new_beneficiary.pubkey())
+        .await;
+    let creator = fixture.creator.pubkey();
+    fixture
+        .env
+        .process(
+            &[update_beneficiary_ix(
+                &vesting_contract,
+                &creator,
+                &new_beneficiary.pubkey(),
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+
+    // O beneficiario anterior nao resgata mais
+    fixture.env.set_time(start_time + 400).await;
+    assert_vesting_error(
+        fixture
+            .claim(
+                &vesting_contract,
+                &beneficiary.pubkey(),
+                &beneficiary_token_account,
+            )
+            .await,
+        VestingError::Unauthorized,
+    );
+
+    fixture
+        .claim(
+            &vesting_contract,
+            &new_beneficiary.pubkey(),
+            &new_beneficiary_token_account,
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        fixture
+            .env
+            .token_balance(&new_beneficiary_token_account)
+            .await,
+        250_000
+    );
+
+    // Apos o primeiro resgate o beneficiario fica fixo
+    assert_vesting_error(
+        fixture
+            .env
+            .process(
+                &[update_beneficiary_ix(
+                    &vesting_contract,
+                    &creator,
+                    &beneficiary.pubkey(),
+                )],
+                &[],
+            )
+            .await,
+        VestingError::AlreadyClaimed,
+    );
+
+    fixture.env.set_time(start_time + 700).await;
+    // 25% no cliff + 75% lineares entre o cliff e o fim: 250_000 + 750_000 * 300 / 600
+    fixture
+        .claim(
+            &vesting_contract,
+            &new_beneficiary.pubkey(),
+            &new_beneficiary_token_account,
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        fixture
+            .env
+            .token_balance(&new_beneficiary_token_account)
+            .await,
+        625_000
+    );
+
+    fixture.env.set_time(start_time + 5_000).await;
+    fixture
+        .claim(
+            &vesting_contract,
+            &new_beneficiary.pubkey(),
+            &new_beneficiary_token_account,
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        fixture
+            .env
+            .token_balance(&new_beneficiary_token_account)
+            .await,
+        TOTAL
+    );
+    assert_eq!(fixture.env.token_balance(&escrow).await, 0);
+
+    let contract = fixture.env.vesting_contract(&vesting_contract).await;
+    assert_eq!(contract.beneficiary, new_beneficiary.pubkey());
+    assert_eq!(contract.released_amount, TOTAL);
+    assert_eq!(contract.claim_count, 3);
+
+    fixture.env.set_time(start_time + 5_001).await;
+    assert_vesting_error(
+        fixture
+            .claim(
+                &vesting_contract,
+                &new_beneficiary.pubkey(),
+                &new_beneficiary_token_account,
+            )
+            .await,
+        VestingError::NothingToRelease,
+    );
+}
+
+/// Cancelamento no meio do cronograma devolve o saldo do escrow ao criador e
+/// encerra o contrato para resgates e novos cancelamentos.
+async fn run_cancel(token_program: Pubkey) {
+    let mut fixture = Fixture::new(token_program, 1).await;
+    let start_time = fixture.start_time;
+    let beneficiary = Keypair::new();
+    let beneficiary_token_account = fixture
+        .env
+        .create_token_account(&fixture.mint, &beneficiary.pubkey())
+        .await;
+    let vesting_contract = fixture
+        .create(1, &beneficiary.pubkey(), VestingType::Linear)
+        .await;
+
+    fixture.env.set_time(start_time + 250).await;
+    fixture
+        .claim(
+            &vesting_contract,
+            &beneficiary.pubkey(),
+            &beneficiary_token_account,
+        )
+        .await
+        .unwrap();
+
+    fixture.env.set_time(start_time + 600).await;
+    fixture.cancel(&vesting_contract).await.unwrap();
+    assert_eq!(
+        fixture
+            .env
+            .token_balance(&fixture.creator_token_account)
+            .await,
+        750_000
+    );
+    assert_eq!(
+        fixture
+            .env
+            .token_balance(&escrow_address(&vesting_contract))
+            .await,
+        0
+    );
+
+    let contract = fixture.env.vesting_contract(&vesting_contract).await;
+    assert!(contract.is_cancelled);
+    assert_eq!(contract.released_amount, TOTAL);
+
+    fixture.env.set_time(start_time + 601).await;
+    assert_vesting_error(
+        fixture.cancel(&vesting_contract).await,
+        VestingError::ContractCancelled,
+    );
+    assert_vesting_error(
+        fixture
+            .claim(
+                &vesting_contract,
+                &beneficiary.pubkey(),
+                &beneficiary_token_account,
+            )
+            .await,
+        VestingError::ContractCancelled,
+    );
+    let creator = fixture.creator.pubkey();
+    assert_vesting_error(
+        fixture
+            .env
+            .process(
+                &[update_beneficiary_ix(
+                    &vesting_contract,
+                    &creator,
+                    &Pubkey::new_unique(),
+                )],
+                &[],
+            )
+            .await,
+        VestingError::ContractCancelled,
+    );
+}
+
+#[tokio::test]
+async fn cliff_lifecycle_with_spl_token() {
+    run_cliff_lifecycle(TOKEN_PROGRAM_ID).await;
+}
+
+#[tokio::test]
+async fn cliff_lifecycle_with_token_2022() {
+    run_cliff_lifecycle(TOKEN_2022_PROGRAM_ID).await;
+}
+
+#[tokio::test]
+async fn cancel_with_spl_token() {
+    run_cancel(TOKEN_PROGRAM_ID).await;
+}
+
+#[tokio::test]
+async fn cancel_with_token_2022() {
+    run_cancel(TOKEN_2022_PROGRAM_ID).await;
+}
+
+#[tokio::test]
+async fn rejects_unauthorized_signers() {
+    let mut fixture = Fixture::new(TOKEN_PROGRAM_ID, 1).await;
+    let beneficiary = Keypair::new();
+    let vesting_contract = fixture
+        .create(1, &beneficiary.pubkey(), VestingType::Linear)
+        .await;
+
+    let intruder = Keypair::new();
+    fixture.env.fund(&intruder.pubkey(), 1_000_000_000).await;
+    let intruder_token_account = fixture
+        .env
+        .create_token_account(&fixture.mint, &intruder.pubkey())
+        .await;
+
+    assert_vesting_error(
+        fixture
+            .env
+            .process(
+                &[update_beneficiary_ix(
+                    &vesting_contract,
+                    &intruder.pubkey(),
+                    &intruder.pubkey(),
+                )],
+                &[&intruder],
+            )
+            .await,
+        VestingError::Unauthorized,
+    );
+
+    let mint = fixture.mint;
+    assert_vesting_error(
+        fixture
+            .env
+            .process(
+                &[cancel_vesting_ix(
+                    &vesting_contract,
+                    &intruder.pubkey(),
+                    &intruder_token_account,
+                    &mint,
+                    &TOKEN_PROGRAM_ID,
+                    &[],
+                )],
+                &[&intruder],
+            )
+            .await,
+        VestingError::Unauthorized,
+    );
+
+    // Resgate para uma conta de token que nao pertence ao beneficiario
+    fixture.env.set_time(fixture.start_time + 500).await;
+    assert_vesting_error(
+        fixture
+            .claim(
+                &vesting_contract,
+                &beneficiary.pubkey(),
+                &intruder_token_account,
+            )
+            .await,
+        VestingError::Unauthorized,
+    );
+
+    let contract = fixture.env.vesting_contract(&vesting_contract).await;
+    assert_eq!(contract.beneficiary, beneficiary.pubkey());
+    assert_eq!(contract.released_amount, 0);
+    assert!(!contract.is_cancelled);
+}
+
+#[tokio::test]
+async fn rejects_escrow_of_another_contract() {
+    let mut fixture = Fixture::new(TOKEN_2022_PROGRAM_ID, 2).await;
+    let beneficiary = Keypair::new();
+    let beneficiary_token_account = fixture
+        .env
+        .create_token_account(&fixture.mint, &beneficiary.pubkey())
+        .await;
+    let first = fixture
+        .create(1, &beneficiary.pubkey(), VestingType::Linear)
+        .await;
+    let second = fixture
+        .create(2, &beneficiary.pubkey(), VestingType::Linear)
+        .await;
+    let foreign_escrow = AccountMeta::new(escrow_address(&second), false);
+
+    fixture.env.set_time(fixture.start_time + 500).await;
+    let mut claim = claim_tokens_ix(
+        &first,
+        &beneficiary.pubkey(),
+        &beneficiary_token_account,
+        &fixture.mint,
+        &TOKEN_2022_PROGRAM_ID,
+        &[],
+    );
+    claim.accounts[1] = foreign_escrow.clone();
+    assert_anchor_error(
+        fixture.env.process(&[claim], &[]).await,
+        ErrorCode::ConstraintSeeds,
+    );
+
+    let mut cancel = cancel_vesting_ix(
+        &first,
+        &fixture.creator.pubkey(),
+        &fixture.creator_token_account,
+        &fixture.mint,
+        &TOKEN_2022_PROGRAM_ID,
+        &[],
+    );
+    cancel.accounts[2] = foreign_escrow;
+    assert_anchor_error(
+        fixture.env.process(&[cancel], &[]).await,
+        ErrorCode::ConstraintSeeds,
+    );
+
+    assert_eq!(
+        fixture.env.token_balance(&escrow_address(&first)).await,
+        TOTAL
+    );
+    assert_eq!(
+        fixture.env.token_balance(&escrow_address(&second)).await,
+        TOTAL
+    );
+}
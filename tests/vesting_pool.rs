@@ -0,0 +1,309 @@
+mod common;
+
+use common::*;
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+use verum_vesting::{PoolMemberInit, VestingError, VestingType};
+
+const TOTAL: u64 = 1_000_000;
+
+struct PoolFixture {
+    env: TestEnv,
+    creator: Keypair,
+    mint: Pubkey,
+    creator_token_account: Pubkey,
+    members: Vec<(Keypair, Pubkey)>,
+    start_time: i64,
+}
+
+async fn setup_pool(shares: &[u16]) -> PoolFixture {
+    let mut env = TestEnv::new().await;
+    let creator = env.payer();
+    let mint = env.create_mint(&TOKEN_PROGRAM_ID, 6, None).await;
+    let creator_token_account = env.create_token_account(&mint, &creator.pubkey()).await;
+    env.mint_to(&mint, &creator_token_account, TOTAL).await;
+
+    let mut members = Vec::new();
+    for _ in shares {
+        let member = Keypair::new();
+        let token_account = env.create_token_account(&mint, &member.pubkey()).await;
+        members.push((member, token_account));
+    }
+
+    let start_time = env.now().await;
+    PoolFixture {
+        env,
+        creator,
+        mint,
+        creator_token_account,
+        members,
+        start_time,
+    }
+}
+
+fn pool_args(fixture: &PoolFixture, shares: &[u16]) -> CreatePoolArgs {
+    CreatePoolArgs {
+        creator: fixture.creator.pubkey(),
+        mint: fixture.mint,
+        token_program: TOKEN_PROGRAM_ID,
+        sender_token_account: fixture.creator_token_account,
+        pool_id: 1,
+        total_amount: TOTAL,
+        start_time: fixture.start_time,
+        end_time: fixture.start_time + 1_000,
+        vesting_type: VestingType::Linear,
+        allowed_mint_flags: 0,
+        members: fixture
+            .members
+            .iter()
+            .zip(shares)
+            .map(|((member, _), share_bps)| PoolMemberInit {
+                beneficiary: member.pubkey(),
+                share_bps: *share_bps,
+            })
+            .collect(),
+    }
+}
+
+impl PoolFixture {
+    fn pool(&self) -> Pubkey {
+        pool_address(&self.creator.pubkey(), &self.mint, 1)
+    }
+
+    async fn claim(&mut self, member: usize) -> Result<(), solana_program_test::BanksClientError> {
+        let instruction = claim_pool_share_ix(
+            &self.pool(),
+            &self.members[member].0.pubkey(),
+            &self.members[member].1,
+            &self.mint,
+            &TOKEN_PROGRAM_ID,
+            &[],
+        );
+        self.env.process(&[instruction], &[]).await
+    }
+
+    async fn balance(&mut self, member: usize) -> u64 {
+        let token_account = self.members[member].1;
+        self.env.token_balance(&token_account).await
+    }
+}
+
+#[tokio::test]
+async fn members_claim_their_share_and_departed_share_is_reassigned() {
+    let shares = [5_000, 3_000, 2_000];
+    let mut fixture = setup_pool(&shares).await;
+    let args = pool_args(&fixture, &shares);
+    fixture
+        .env
+        .process(&[create_vesting_pool_ix(&args, &[])], &[])
+        .await
+        .unwrap();
+    assert_eq!(
+        fixture
+            .env
+            .token_balance(&escrow_address(&fixture.pool()))
+            .await,
+        TOTAL
+    );
+
+    fixture.env.set_time(fixture.start_time + 500).await;
+    fixture.claim(0).await.unwrap();
+    fixture.claim(1).await.unwrap();
+    assert_eq!(fixture.balance(0).await, 250_000);
+    assert_eq!(fixture.balance(1).await, 150_000);
+
+    // O terceiro membro sai na metade: mantem 100_000 ja adquiridos e o restante
+    // da sua participacao passa ao primeiro membro.
+    let departed = fixture.members[2].0.pubkey();
+    let recipient = fixture.members[0].0.pubkey();
+    let pool = fixture.pool();
+    fixture
+        .env
+        .process(
+            &[reassign_pool_share_ix(
+                &pool,
+                &fixture.creator.pubkey(),
+                &departed,
+                &recipient,
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let state = fixture.env.vesting_pool(&pool).await;
+    assert_eq!(state.members[0].share_bps, 7_000);
+    assert_eq!(state.members[2].share_bps, 0);
+    assert_eq!(state.members[2].settled_amount, 100_000);
+
+    fixture.claim(2).await.unwrap();
+    assert_eq!(fixture.balance(2).await, 100_000);
+
+    fixture.env.set_time(fixture.start_time + 1_000).await;
+    fixture.claim(0).await.unwrap();
+    fixture.claim(1).await.unwrap();
+    assert_eq!(fixture.balance(0).await, 600_000);
+    assert_eq!(fixture.balance(1).await, 300_000);
+    assert_eq!(fixture.env.token_balance(&escrow_address(&pool)).await, 0);
+
+    let result = fixture.claim(2).await;
+    assert_vesting_error(result, VestingError::NothingToRelease);
+}
+
+#[tokio::test]
+async fn departed_share_can_go_to_a_new_member() {
+    let shares = [6_000, 4_000];
+    let mut fixture = setup_pool(&shares).await;
+    let args = pool_args(&fixture, &shares);
+    fixture
+        .env
+        .process(&[create_vesting_pool_ix(&args, &[])], &[])
+        .await
+        .unwrap();
+
+    let newcomer = Keypair::new();
+    let newcomer_token_account = fixture
+        .env
+        .create_token_account(&fixture.mint, &newcomer.pubkey())
+        .await;
+
+    fixture.env.set_time(fixture.start_time + 250).await;
+    let pool = fixture.pool();
+    let departed = fixture.members[1].0.pubkey();
+    fixture
+        .env
+        .process(
+            &[reassign_pool_share_ix(
+                &pool,
+                &fixture.creator.pubkey(),
+                &departed,
+                &newcomer.pubkey(),
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+
+    fixture.env.set_time(fixture.start_time + 1_000).await;
+    fixture
+        .env
+        .process(
+            &[claim_pool_share_ix(
+                &pool,
+                &newcomer.pubkey(),
+                &newcomer_token_account,
+                &fixture.mint,
+                &TOKEN_PROGRAM_ID,
+                &[],
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+    fixture.claim(1).await.unwrap();
+    assert_eq!(
+        fixture.env.token_balance(&newcomer_token_account).await,
+        300_000
+    );
+    assert_eq!(fixture.balance(1).await, 100_000);
+
+    let outsider = Keypair::new();
+    let outsider_token_account = fixture
+        .env
+        .create_token_account(&fixture.mint, &outsider.pubkey())
+        .await;
+    let result = fixture
+        .env
+        .process(
+            &[claim_pool_share_ix(
+                &pool,
+                &outsider.pubkey(),
+                &outsider_token_account,
+                &fixture.mint,
+                &TOKEN_PROGRAM_ID,
+                &[],
+            )],
+            &[],
+        )
+        .await;
+    assert_vesting_error(result, VestingError::PoolMemberNotFound);
+}
+
+#[tokio::test]
+async fn pool_shares_must_sum_to_full_basis_points() {
+    let shares = [5_000, 4_000];
+    let mut fixture = setup_pool(&shares).await;
+    let args = pool_args(&fixture, &shares);
+
+    let result = fixture
+        .env
+        .process(&[create_vesting_pool_ix(&args, &[])], &[])
+        .await;
+    assert_vesting_error(result, VestingError::InvalidPoolShares);
+}
+
+#[tokio::test]
+async fn creator_sweeps_rounding_dust_after_every_share_is_claimed() {
+    let shares = [3_333, 3_333, 3_334];
+    let mut fixture = setup_pool(&shares).await;
+    // 999_999 nao divide exatamente: as participacoes somam 999_997
+    let mut args = pool_args(&fixture, &shares);
+    args.total_amount = TOTAL - 1;
+    fixture
+        .env
+        .process(&[create_vesting_pool_ix(&args, &[])], &[])
+        .await
+        .unwrap();
+
+    let pool = fixture.pool();
+    let (creator, creator_token_account, mint) = (
+        fixture.creator.pubkey(),
+        fixture.creator_token_account,
+        fixture.mint,
+    );
+    let sweep = || {
+        sweep_pool_dust_ix(
+            &pool,
+            &creator,
+            &creator_token_account,
+            &mint,
+            &TOKEN_PROGRAM_ID,
+        )
+    };
+
+    fixture.env.set_time(fixture.start_time + 1_000).await;
+    fixture.claim(0).await.unwrap();
+    fixture.claim(1).await.unwrap();
+    let result = fixture.env.process(&[sweep()], &[]).await;
+    assert_vesting_error(result, VestingError::PoolSharesOutstanding);
+
+    fixture.claim(2).await.unwrap();
+    assert_eq!(fixture.balance(0).await, 333_299);
+    assert_eq!(fixture.balance(2).await, 333_399);
+    assert_eq!(fixture.env.token_balance(&escrow_address(&pool)).await, 2);
+
+    let member = fixture.members[0].0.insecure_clone();
+    let result = fixture
+        .env
+        .process(
+            &[sweep_pool_dust_ix(
+                &pool,
+                &member.pubkey(),
+                &fixture.members[0].1,
+                &fixture.mint,
+                &TOKEN_PROGRAM_ID,
+            )],
+            &[&member],
+        )
+        .await;
+    assert_vesting_error(result, VestingError::Unauthorized);
+
+    fixture.env.process(&[sweep()], &[]).await.unwrap();
+    assert_eq!(fixture.env.token_balance(&escrow_address(&pool)).await, 0);
+    assert_eq!(fixture.env.token_balance(&creator_token_account).await, 3);
+
+    let result = fixture.env.process(&[sweep()], &[]).await;
+    assert_vesting_error(result, VestingError::NothingToRelease);
+}
@@ -0,0 +1,172 @@
+//! Propriedades de `VestingContract::vested_amount` para todos os `VestingType`,
+//! com pausas e valores extremos, sobre entradas pseudoaleatorias de semente fixa.
+
+use anchor_lang::prelude::Pubkey;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use verum_vesting::{VestingContract, VestingType};
+
+const CASES: usize = 2_000;
+const SAMPLES: usize = 64;
+
+fn any_time(rng: &mut StdRng) -> i64 {
+    match rng.gen_range(0..8) {
+        0 => i64::MIN + rng.gen_range(0..1_000),
+        1 => i64::MAX - rng.gen_range(0..1_000),
+        2 => rng.gen(),
+        _ => rng.gen_range(-10_000..10_000),
+    }
+}
+
+fn any_total(rng: &mut StdRng) -> u64 {
+    match rng.gen_range(0..6) {
+        0 => 0,
+        1 => u64::MAX - rng.gen_range(0..1_000),
+        2 => rng.gen(),
+        _ => rng.gen_range(1..1_000_000_000),
+    }
+}
+
+fn any_vesting_type(rng: &mut StdRng) -> VestingType {
+    match rng.gen_range(0..4) {
+        0 => VestingType::Linear,
+        1 => VestingType::Cliff(any_time(rng), rng.gen_range(0..=150)),
+        2 => VestingType::Milestones,
+        _ => VestingType::PriceTriggered,
+    }
+}
+
+fn any_contract(rng: &mut StdRng) -> VestingContract {
+    let total_amount = any_total(rng);
+    let (paused_at, paused_duration) = match rng.gen_range(0..4) {
+        0 => (any_time(rng), rng.gen_range(0..10_000)),
+        1 => (0, any_time(rng).max(0)),
+        _ => (0, 0),
+    };
+    VestingContract {
+        creator: Pubkey::new_unique(),
+        beneficiary: Pubkey::new_unique(),
+        mint: Pubkey::new_unique(),
+        total_amount,
+        released_amount: 0,
+        start_time: any_time(rng),
+        end_time: any_time(rng),
+        contract_id: 1,
+        vesting_type: any_vesting_type(rng),
+        bump: 255,
+        is_cancelled: false,
+        is_token_2022: false,
+        mint_flags: 0,
+        is_native: false,
+        pause_policy: 0,
+        paused_at,
+        paused_duration,
+        pause_count: 0,
+        unlocked_amount: rng.gen_range(0..=total_amount),
+        staking_program: Pubkey::default(),
+        staked_amount: 0,
+        compliance_authority: Pubkey::default(),
+        clawed_back_amount: 0,
+        timelock_delay: 0,
+        guardian: Pubkey::default(),
+        has_audit_log: false,
+        claim_count: 0,
+        beneficiary_updated_slot: 0,
+    }
+}
+
+fn describe(contract: &VestingContract) -> String {
+    let vesting_type = match contract.vesting_type {
+        VestingType::Linear => "Linear".to_string(),
+        VestingType::Cliff(cliff_time, percentage) => format!("Cliff({cliff_time}, {percentage})"),
+        VestingType::Milestones => "Milestones".to_string(),
+        VestingType::PriceTriggered => "PriceTriggered".to_string(),
+    };
+    format!(
+        "{vesting_type} {}..{} total {} pausa {}/{}",
+        contract.start_time,
+        contract.end_time,
+        contract.total_amount,
+        contract.paused_at,
+        contract.paused_duration
+    )
+}
+
+#[test]
+fn vested_amount_is_bounded_and_monotonic_for_every_type() {
+    let mut rng = StdRng::seed_from_u64(0x7479_7065);
+    for _ in 0..CASES {
+        let contract = any_contract(&mut rng);
+        let mut times: Vec<i64> = (0..SAMPLES).map(|_| any_time(&mut rng)).collect();
+        times.extend([
+            contract.start_time,
+            contract.end_time,
+            contract.paused_at,
+            i64::MIN,
+            i64::MAX,
+        ]);
+        if let VestingType::Cliff(cliff_time, _) = contract.vesting_type {
+            times.push(cliff_time);
+        }
+        times.sort_unstable();
+
+        let mut previous = 0;
+        for time in times {
+            let vested = contract.vested_amount(time);
+            assert!(
+                vested <= contract.total_amount,
+                "{vested} acima do total em {time}: {}",
+                describe(&contract)
+            );
+            assert!(
+                vested >= previous,
+                "valor caiu de {previous} para {vested} em {time}: {}",
+                describe(&contract)
+            );
+            previous = vested;
+        }
+    }
+}
+
+#[test]
+fn vested_amount_end_states_for_every_type() {
+    let mut rng = StdRng::seed_from_u64(0x656e_6473);
+    for _ in 0..CASES {
+        let mut contract = any_contract(&mut rng);
+        let time = any_time(&mut rng);
+
+        if contract.vesting_type.is_event_driven() {
+            // Liberado por evento: o tempo e as pausas nao mudam nada
+            assert_eq!(contract.vested_amount(time), contract.unlocked_amount);
+            continue;
+        }
+
+        contract.paused_at = 0;
+        contract.paused_duration = 0;
+        let vested = contract.vested_amount(time);
+        if time < contract.start_time {
+            assert_eq!(vested, 0, "antes do inicio: {}", describe(&contract));
+        } else if time >= contract.end_time {
+            assert_eq!(
+                vested,
+                contract.total_amount,
+                "apos o fim: {}",
+                describe(&contract)
+            );
+        }
+
+        // Uma pausa em andamento congela o valor a partir do instante pausado
+        let paused_at = match any_time(&mut rng) {
+            0 => 1,
+            time => time,
+        };
+        let frozen = contract.vested_amount(paused_at);
+        contract.paused_at = paused_at;
+        assert_eq!(
+            contract.vested_amount(paused_at.saturating_add(rng.gen_range(0..1_000_000))),
+            frozen,
+            "pausa em {paused_at}: {}",
+            describe(&contract)
+        );
+    }
+}
@@ -0,0 +1,246 @@
+mod common;
+
+use common::*;
+use solana_sdk::{
+    clock::Clock,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+use verum_vesting::{VestingError, VestingType};
+
+const TOTAL: u64 = 1_000_000;
+
+struct VoterFixture {
+    env: TestEnv,
+    creator: Keypair,
+    beneficiary: Keypair,
+    mint: Pubkey,
+    creator_token_account: Pubkey,
+    beneficiary_token_account: Pubkey,
+    governance_program: Pubkey,
+    realm: Pubkey,
+    start_time: i64,
+}
+
+async fn setup_realm() -> VoterFixture {
+    let mut env = TestEnv::new().await;
+    let creator = env.payer();
+    let beneficiary = Keypair::new();
+    let mint = env.create_mint(&TOKEN_PROGRAM_ID, 6, None).await;
+    let creator_token_account = env.create_token_account(&mint, &creator.pubkey()).await;
+    let beneficiary_token_account = env.create_token_account(&mint, &beneficiary.pubkey()).await;
+    env.mint_to(&mint, &creator_token_account, 2 * TOTAL).await;
+
+    let governance_program = Pubkey::new_unique();
+    let realm = env
+        .create_realm(&governance_program, &mint, &creator.pubkey())
+        .await;
+    let start_time = env.now().await;
+
+    VoterFixture {
+        env,
+        creator,
+        beneficiary,
+        mint,
+        creator_token_account,
+        beneficiary_token_account,
+        governance_program,
+        realm,
+        start_time,
+    }
+}
+
+impl VoterFixture {
+    async fn create_contract(
+        &mut self,
+        beneficiary: &Pubkey,
+        contract_id: u64,
+        amount: u64,
+        duration: i64,
+    ) -> Pubkey {
+        let args = CreateVestingArgs {
+            creator: self.creator.pubkey(),
+            beneficiary: *beneficiary,
+            mint: self.mint,
+            token_program: TOKEN_PROGRAM_ID,
+            sender_token_account: self.creator_token_account,
+            contract_id,
+            total_amount: amount,
+            start_time: self.start_time,
+            end_time: self.start_time + duration,
+            vesting_type: VestingType::Linear,
+            allowed_mint_flags: 0,
+        };
+        self.env
+            .process(&[create_vesting_ix(&args, &[])], &[])
+            .await
+            .unwrap();
+        vesting_address(&self.creator.pubkey(), &self.mint, contract_id)
+    }
+
+    async fn create_registrar(
+        &mut self,
+        lockup_bonus_bps: u16,
+        lockup_saturation_secs: i64,
+    ) -> Result<Pubkey, solana_program_test::BanksClientError> {
+        let instruction = create_registrar_ix(
+            &self.realm,
+            &self.governance_program,
+            &self.mint,
+            &self.creator.pubkey(),
+            &self.creator.pubkey(),
+            lockup_bonus_bps,
+            lockup_saturation_secs,
+        );
+        self.env.process(&[instruction], &[]).await?;
+
+        let registrar = registrar_address(&self.realm, &self.mint);
+        let instruction = create_voter_weight_record_ix(
+            &registrar,
+            &self.beneficiary.pubkey(),
+            &self.creator.pubkey(),
+        );
+        self.env.process(&[instruction], &[]).await?;
+        Ok(registrar)
+    }
+
+    async fn update(
+        &mut self,
+        registrar: &Pubkey,
+        vesting_contracts: &[Pubkey],
+    ) -> Result<(), solana_program_test::BanksClientError> {
+        let instruction =
+            update_voter_weight_record_ix(registrar, &self.beneficiary.pubkey(), vesting_contracts);
+        self.env.process(&[instruction], &[]).await
+    }
+
+    async fn voter_weight(&mut self, registrar: &Pubkey) -> u64 {
+        let record = voter_weight_record_address(registrar, &self.beneficiary.pubkey());
+        let record = self.env.voter_weight_record(&record).await;
+        let clock: Clock = self.env.context.banks_client.get_sysvar().await.unwrap();
+        assert_eq!(record.realm, self.realm);
+        assert_eq!(record.governing_token_mint, self.mint);
+        assert_eq!(record.voter_weight_expiry, Some(clock.slot));
+        record.voter_weight
+    }
+}
+
+#[tokio::test]
+async fn voter_weight_tracks_unreleased_balance() {
+    let mut fixture = setup_realm().await;
+    let beneficiary = fixture.beneficiary.pubkey();
+    let first = fixture.create_contract(&beneficiary, 1, TOTAL, 1_000).await;
+    let second = fixture
+        .create_contract(&beneficiary, 2, TOTAL / 2, 1_000)
+        .await;
+    let registrar = fixture.create_registrar(0, 0).await.unwrap();
+
+    fixture.update(&registrar, &[first, second]).await.unwrap();
+    assert_eq!(fixture.voter_weight(&registrar).await, TOTAL + TOTAL / 2);
+
+    fixture.env.set_time(fixture.start_time + 500).await;
+    let instruction = claim_tokens_ix(
+        &first,
+        &beneficiary,
+        &fixture.beneficiary_token_account,
+        &fixture.mint,
+        &TOKEN_PROGRAM_ID,
+        &[],
+    );
+    fixture.env.process(&[instruction], &[]).await.unwrap();
+
+    fixture.update(&registrar, &[first, second]).await.unwrap();
+    assert_eq!(
+        fixture.voter_weight(&registrar).await,
+        TOTAL / 2 + TOTAL / 2
+    );
+}
+
+#[tokio::test]
+async fn lockup_bonus_decays_with_remaining_time() {
+    let mut fixture = setup_realm().await;
+    let beneficiary = fixture.beneficiary.pubkey();
+    let contract = fixture.create_contract(&beneficiary, 1, TOTAL, 2_000).await;
+    let registrar = fixture.create_registrar(5_000, 1_000).await.unwrap();
+
+    // Bloqueio restante acima da saturacao: bonus integral de 50%
+    fixture.update(&registrar, &[contract]).await.unwrap();
+    assert_eq!(fixture.voter_weight(&registrar).await, TOTAL + TOTAL / 2);
+
+    fixture.env.set_time(fixture.start_time + 1_500).await;
+    fixture.update(&registrar, &[contract]).await.unwrap();
+    assert_eq!(fixture.voter_weight(&registrar).await, TOTAL + TOTAL / 4);
+}
+
+#[tokio::test]
+async fn foreign_or_duplicate_contracts_are_rejected() {
+    let mut fixture = setup_realm().await;
+    let beneficiary = fixture.beneficiary.pubkey();
+    let own = fixture.create_contract(&beneficiary, 1, TOTAL, 1_000).await;
+    let foreign = fixture
+        .create_contract(&Pubkey::new_unique(), 2, TOTAL, 1_000)
+        .await;
+    let registrar = fixture.create_registrar(0, 0).await.unwrap();
+
+    let result = fixture.update(&registrar, &[own, foreign]).await;
+    assert_vesting_error(result, VestingError::InvalidVoterWeightContract);
+
+    let result = fixture.update(&registrar, &[own, own]).await;
+    assert_vesting_error(result, VestingError::InvalidVoterWeightContract);
+}
+
+#[tokio::test]
+async fn beneficiary_change_does_not_double_count_weight() {
+    let mut fixture = setup_realm().await;
+    let beneficiary = fixture.beneficiary.pubkey();
+    let contract = fixture.create_contract(&beneficiary, 1, TOTAL, 1_000).await;
+    let registrar = fixture.create_registrar(0, 0).await.unwrap();
+    fixture.update(&registrar, &[contract]).await.unwrap();
+    assert_eq!(fixture.voter_weight(&registrar).await, TOTAL);
+
+    let new_beneficiary = Pubkey::new_unique();
+    let instruction =
+        create_voter_weight_record_ix(&registrar, &new_beneficiary, &fixture.creator.pubkey());
+    fixture.env.process(&[instruction], &[]).await.unwrap();
+
+    // No slot da troca o registro antigo ainda vale: o novo beneficiario nao conta o saldo
+    let instructions = [
+        update_beneficiary_ix(&contract, &fixture.creator.pubkey(), &new_beneficiary),
+        update_voter_weight_record_ix(&registrar, &new_beneficiary, &[contract]),
+    ];
+    let result = fixture.env.process(&instructions, &[]).await;
+    assert_vesting_error(result, VestingError::BeneficiaryChangedThisSlot);
+
+    fixture.env.process(&instructions[..1], &[]).await.unwrap();
+    let result = fixture.update(&registrar, &[contract]).await;
+    assert_vesting_error(result, VestingError::InvalidVoterWeightContract);
+
+    fixture.env.set_time(fixture.start_time + 1).await;
+    fixture.env.process(&instructions[1..], &[]).await.unwrap();
+    let record = voter_weight_record_address(&registrar, &new_beneficiary);
+    assert_eq!(
+        fixture.env.voter_weight_record(&record).await.voter_weight,
+        TOTAL
+    );
+}
+
+#[tokio::test]
+async fn registrar_requires_realm_authority_and_valid_config() {
+    let mut fixture = setup_realm().await;
+
+    let result = fixture.create_registrar(5_000, 0).await;
+    assert_vesting_error(result.map(|_| ()), VestingError::InvalidRegistrarConfig);
+
+    let impostor = Keypair::new();
+    let instruction = create_registrar_ix(
+        &fixture.realm,
+        &fixture.governance_program,
+        &fixture.mint,
+        &impostor.pubkey(),
+        &fixture.creator.pubkey(),
+        0,
+        0,
+    );
+    let result = fixture.env.process(&[instruction], &[&impostor]).await;
+    assert_vesting_error(result, VestingError::InvalidRealm);
+}